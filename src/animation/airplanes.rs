@@ -1,4 +1,6 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -15,6 +17,8 @@ pub struct AirplaneSystem {
     terminal_width: u16,
     terminal_height: u16,
     spawn_cooldown: u16,
+    // Body, windows, tail, underside and exhaust
+    colors: [Color; 5],
 }
 
 impl AirplaneSystem {
@@ -24,10 +28,24 @@ impl AirplaneSystem {
             terminal_width,
             terminal_height,
             spawn_cooldown: 0,
+            colors: DEFAULT_SCENERY.airplane,
         }
     }
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+    pub fn set_colors(&mut self, colors: [Color; 5]) {
+        self.colors = colors;
+    }
+
+    fn spawn_plane(&mut self, rng: &mut dyn Rng) {
+        let y = (rng.random::<u16>() % (self.terminal_height / 4).max(1)) as f32;
+        let speed = 0.3 + (rng.random::<f32>() * 0.2);
+
+        self.planes.push(Airplane { x: 0.0, y, speed });
+    }
+}
+
+impl AnimationSystem for AirplaneSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
@@ -44,14 +62,17 @@ impl AirplaneSystem {
         }
     }
 
-    fn spawn_plane(&mut self, rng: &mut impl Rng) {
-        let y = (rng.random::<u16>() % (self.terminal_height / 4)) as f32;
-        let speed = 0.3 + (rng.random::<f32>() * 0.2);
-
-        self.planes.push(Airplane { x: 0.0, y, speed });
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.planes
+                .iter_mut()
+                .map(|plane| (&mut plane.x, &mut plane.y)),
+        );
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         let airplane_art = [
             "           _",
             "         -=\\`\\",
@@ -79,16 +100,17 @@ impl AirplaneSystem {
                     }
 
                     if ch != ' ' {
+                        let [body, windows, tail, underside, exhaust] = self.colors;
                         let color = match ch {
-                            '"' => Color::Cyan,
+                            '"' => windows,
 
-                            '\\' => Color::Blue,
+                            '\\' => tail,
 
-                            '_' => Color::DarkGrey,
+                            '_' => underside,
 
-                            '~' => Color::Grey,
+                            '~' => exhaust,
 
-                            _ => Color::White,
+                            _ => body,
                         };
                         renderer.render_char(render_x, render_y, ch, color)?;
                     }
@@ -0,0 +1,83 @@
+use super::AnimationSystem;
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const TIME_STEP: f32 = 0.03;
+const CURTAIN_TOP: f32 = 3.0;
+
+// Northern (or southern) lights: a few overlapping sine waves shape slowly rippling
+// curtains of ▒ across the upper sky, purple at the top fading into green.
+pub struct AuroraSystem {
+    time: f32,
+    terminal_width: u16,
+    horizon_y: u16,
+}
+
+impl AuroraSystem {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            time: 0.0,
+            terminal_width,
+            horizon_y: terminal_height,
+        }
+    }
+
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
+
+    // Top row and height of the curtain in a column, or None where it thins out
+    fn curtain(&self, x: f32) -> Option<(f32, f32)> {
+        let t = self.time;
+        let strength = (x * 0.03 + t * 0.1).sin() + 0.5 * (x * 0.11 + t * 0.07).sin();
+        // Vertical rays give the curtain its folds
+        let rays = (x * 0.7 + t * 0.4).sin() + 0.6 * (x * 1.9 - t * 0.25).sin();
+        if strength < 0.3 || rays < 0.0 {
+            return None;
+        }
+
+        let ripple = (x * 0.08 + t * 0.5).sin() + 0.5 * (x * 0.21 - t * 0.3).sin();
+        let top = CURTAIN_TOP + ripple;
+        let height = 2.0 + ((x * 0.05 + t * 0.2).sin() + 1.0) * strength;
+        Some((top, height))
+    }
+}
+
+impl AnimationSystem for AuroraSystem {
+    fn update(&mut self, terminal_width: u16, _terminal_height: u16, _rng: &mut dyn Rng) {
+        self.terminal_width = terminal_width;
+        self.time += TIME_STEP;
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        // Keep to the upper half of the sky, well clear of the house
+        let lowest = self.horizon_y / 2;
+
+        for x in 0..self.terminal_width {
+            let Some((top, height)) = self.curtain(x as f32) else {
+                continue;
+            };
+
+            let top = top.round().max(0.0) as u16;
+            let height = height.round() as u16;
+            for row in 0..height {
+                let y = top + row;
+                if y >= lowest {
+                    break;
+                }
+
+                let color = if row == 0 {
+                    Color::Magenta
+                } else if row + 1 == height {
+                    Color::DarkGreen
+                } else {
+                    Color::Green
+                };
+                renderer.render_char(x, y, '▒', color)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
+use crate::weather::astronomy::Season;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
 
+const MAX_LONE_BIRDS: usize = 3;
+
 struct Bird {
     x: f32,
     y: f32,
@@ -10,58 +15,157 @@ struct Bird {
     character: char,
     flap_state: bool, // true = wings up, false = wings down/flat
     flap_timer: u8,
+    wobble_phase: f32,
 }
 
+impl Bird {
+    fn new(x: f32, y: f32, speed: f32, rng: &mut dyn Rng) -> Self {
+        Self {
+            x,
+            y,
+            speed,
+            character: 'v',
+            flap_state: true,
+            flap_timer: 0,
+            wobble_phase: rng.random::<f32>() * std::f32::consts::TAU,
+        }
+    }
+}
+
+// Birds by day, bats by night. Around the migration seasons flocks sometimes pass over
+// in a V.
 pub struct BirdSystem {
     birds: Vec<Bird>,
     terminal_width: u16,
     terminal_height: u16,
+    is_night: bool,
+    season: Season,
+    // By day, then by night
+    colors: (Color, Color),
 }
 
 impl BirdSystem {
     pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
         Self {
-            birds: Vec::with_capacity(3),
+            birds: Vec::with_capacity(MAX_LONE_BIRDS),
             terminal_width,
             terminal_height,
+            is_night: false,
+            season: Season::default(),
+            colors: DEFAULT_SCENERY.birds,
+        }
+    }
+
+    pub fn set_colors(&mut self, colors: (Color, Color)) {
+        self.colors = colors;
+    }
+
+    pub fn set_night(&mut self, is_night: bool) {
+        if is_night != self.is_night {
+            self.birds.clear();
         }
+        self.is_night = is_night;
     }
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+    pub fn set_season(&mut self, season: Season) {
+        self.season = season;
+    }
+
+    fn formation_chance(&self) -> f32 {
+        if self.is_night {
+            return 0.0;
+        }
+        match self.season {
+            Season::Spring | Season::Autumn => 0.4,
+            Season::Summer => 0.1,
+            Season::Winter => 0.0,
+        }
+    }
+
+    fn spawn_formation(&mut self, y: f32, speed: f32, rng: &mut dyn Rng) {
+        // Leader at the front, each pair one step further back and out
+        let pairs = rng.random_range(2..=3);
+        self.birds.push(Bird::new(0.0, y, speed, rng));
+        for i in 1..=pairs {
+            let back = -2.0 * i as f32;
+            self.birds.push(Bird::new(back, y - i as f32, speed, rng));
+            self.birds.push(Bird::new(back, y + i as f32, speed, rng));
+        }
+    }
+}
+
+impl AnimationSystem for BirdSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for bird in &mut self.birds {
             bird.x += bird.speed;
             bird.flap_timer += 1;
-            if bird.flap_timer > 5 {
-                bird.flap_state = !bird.flap_state;
-                bird.flap_timer = 0;
+            if self.is_night {
+                // Bats flit about rather than gliding
+                bird.wobble_phase += 0.4;
+                bird.y += bird.wobble_phase.sin() * 0.3;
+                if bird.flap_timer > 2 {
+                    bird.flap_state = !bird.flap_state;
+                    bird.flap_timer = 0;
+                }
+                bird.character = if bird.flap_state { '^' } else { 'w' };
+            } else {
+                if bird.flap_timer > 5 {
+                    bird.flap_state = !bird.flap_state;
+                    bird.flap_timer = 0;
+                }
+                bird.character = if bird.flap_state { 'v' } else { '-' };
             }
-            bird.character = if bird.flap_state { 'v' } else { '-' };
         }
 
         self.birds.retain(|b| b.x < terminal_width as f32);
-        if self.birds.len() < 3 && rng.random::<f32>() < 0.01 {
-            let y = (rng.random::<u16>() % (terminal_height / 3)) as f32;
-            let speed = 0.2 + (rng.random::<f32>() * 0.2);
-            self.birds.push(Bird {
-                x: 0.0,
-                y,
-                speed,
-                character: 'v',
-                flap_state: true,
-                flap_timer: 0,
-            });
+
+        let max_birds = if self.season == Season::Winter {
+            1
+        } else {
+            MAX_LONE_BIRDS
+        };
+        if self.birds.len() < max_birds && rng.random::<f32>() < 0.01 {
+            let y = (rng.random::<u16>() % (terminal_height / 3).max(1)) as f32;
+            let speed = if self.is_night {
+                0.4 + (rng.random::<f32>() * 0.3)
+            } else {
+                0.2 + (rng.random::<f32>() * 0.2)
+            };
+
+            if self.birds.is_empty() && rng.random::<f32>() < self.formation_chance() {
+                // Keep the whole V on screen
+                self.spawn_formation(y.max(3.0), speed, rng);
+            } else {
+                self.birds.push(Bird::new(0.0, y, speed, rng));
+            }
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.birds.iter_mut().map(|bird| (&mut bird.x, &mut bird.y)),
+        );
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let color = if self.is_night {
+            self.colors.1
+        } else {
+            self.colors.0
+        };
         for bird in &self.birds {
+            if bird.x < 0.0 || bird.y < 0.0 {
+                continue;
+            }
             let x = bird.x as u16;
             let y = bird.y as u16;
             if x < self.terminal_width && y < self.terminal_height {
-                renderer.render_char(x, y, bird.character, Color::Yellow)?;
+                renderer.render_char(x, y, bird.character, color)?;
             }
         }
         Ok(())
@@ -1,9 +1,12 @@
+use super::AnimationSystem;
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
 
 const MAX_PARTICLES: usize = 200;
+// Nobody lights the fire on a warm day
+const SMOKE_BELOW_CELSIUS: f64 = 10.0;
 
 struct SmokeParticle {
     x: f32,
@@ -14,7 +17,7 @@ struct SmokeParticle {
 }
 
 impl SmokeParticle {
-    fn new(chimney_x: u16, chimney_y: u16, rng: &mut impl Rng) -> Self {
+    fn new(chimney_x: u16, chimney_y: u16, rng: &mut dyn Rng) -> Self {
         let drift = (rng.random::<f32>() - 0.5) * 0.15;
         let max_age = 30 + (rng.random::<u32>() % 15);
 
@@ -27,10 +30,10 @@ impl SmokeParticle {
         }
     }
 
-    fn update(&mut self) {
+    fn update(&mut self, wind_x: f32) {
         self.age += 1;
         self.y -= 0.2;
-        self.x += self.drift;
+        self.x += self.drift + wind_x;
     }
 
     fn is_alive(&self) -> bool {
@@ -53,6 +56,10 @@ pub struct ChimneySmoke {
     particles: Vec<SmokeParticle>,
     spawn_counter: u32,
     spawn_rate: u32,
+    chimney_x: u16,
+    chimney_y: u16,
+    wind_x: f32,
+    lit: bool,
 }
 
 impl ChimneySmoke {
@@ -61,25 +68,50 @@ impl ChimneySmoke {
             particles: Vec::with_capacity(MAX_PARTICLES),
             spawn_counter: 0,
             spawn_rate: 8,
+            chimney_x: 0,
+            chimney_y: 0,
+            wind_x: 0.0,
+            lit: true,
         }
     }
 
-    pub fn update(&mut self, chimney_x: u16, chimney_y: u16, rng: &mut impl Rng) {
+    pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+        self.wind_x = speed_kmh / 60.0 * -direction_deg.to_radians().sin();
+    }
+
+    pub fn set_temperature(&mut self, celsius: f64) {
+        self.lit = celsius < SMOKE_BELOW_CELSIUS;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.lit || !self.particles.is_empty()
+    }
+
+    pub fn set_anchor(&mut self, chimney_x: u16, chimney_y: u16) {
+        self.chimney_x = chimney_x;
+        self.chimney_y = chimney_y;
+    }
+}
+
+impl AnimationSystem for ChimneySmoke {
+    fn update(&mut self, _terminal_width: u16, _terminal_height: u16, rng: &mut dyn Rng) {
+        let (chimney_x, chimney_y) = (self.chimney_x, self.chimney_y);
         for particle in &mut self.particles {
-            particle.update();
+            particle.update(self.wind_x);
         }
 
         self.particles.retain(|p| p.is_alive() && p.y >= 0.0);
 
         self.spawn_counter += 1;
-        if self.spawn_counter >= self.spawn_rate && self.particles.len() < MAX_PARTICLES {
+        if self.lit && self.spawn_counter >= self.spawn_rate && self.particles.len() < MAX_PARTICLES
+        {
             self.spawn_counter = 0;
             self.particles
                 .push(SmokeParticle::new(chimney_x, chimney_y, rng));
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for particle in &self.particles {
             let x = particle.x as i16;
             let y = particle.y as i16;
@@ -1,3 +1,4 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
 use rand::prelude::*;
@@ -5,6 +6,10 @@ use std::io;
 use std::sync::OnceLock;
 
 static CLOUD_SHAPES: OnceLock<Vec<Vec<String>>> = OnceLock::new();
+static LARGE_CLOUD_SHAPES: OnceLock<Vec<Vec<String>>> = OnceLock::new();
+
+// Columns of sky per cloud when the sky is fully covered
+const FULL_COVER_SPACING: f32 = 12.0;
 
 struct Cloud {
     x: f32,
@@ -18,56 +23,80 @@ pub struct CloudSystem {
     clouds: Vec<Cloud>,
     terminal_width: u16,
     terminal_height: u16,
+    cloud_cover: f32,
+    cloud_color: Color,
+    drift: f32,
 }
 
 impl CloudSystem {
-    pub fn set_cloud_color(&mut self, is_clear: bool) {
-        let color = if is_clear {
-            Color::White
-        } else {
-            Color::DarkGrey
-        };
-
+    // `cloud_cover` is the percentage of sky covered (0-100)
+    pub fn set_sky(&mut self, cloud_cover: f32, color: Color) {
+        let cloud_cover = cloud_cover.clamp(0.0, 100.0);
+        self.cloud_color = color;
         for cloud in &mut self.clouds {
             cloud.color = color;
         }
+
+        if (cloud_cover - self.cloud_cover).abs() < f32::EPSILON {
+            return;
+        }
+        self.cloud_cover = cloud_cover;
+
+        // Fill the sky straight away rather than waiting for clouds to drift in
+        let mut rng = rand::rng();
+        let target = self.max_clouds(self.terminal_width);
+        while self.clouds.len() < target {
+            let cloud = self.create_random_cloud(true, &mut rng);
+            self.clouds.push(cloud);
+        }
+        self.clouds.truncate(target);
+    }
+
+    // Scales each cloud's own speed, negative when the wind carries clouds to the left.
+    // Clouds never stop completely, even when it's calm or the wind blows along the view.
+    pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+        let strength = (speed_kmh / 15.0).clamp(0.3, 5.0);
+        let x_component = -direction_deg.to_radians().sin();
+        let magnitude = strength * (0.3 + 0.7 * x_component.abs());
+        self.drift = if x_component < 0.0 {
+            -magnitude
+        } else {
+            magnitude
+        };
+    }
+
+    fn max_clouds(&self, terminal_width: u16) -> usize {
+        let full = terminal_width as f32 / FULL_COVER_SPACING;
+        (full * self.cloud_cover / 100.0).round() as usize
     }
 }
 
 impl CloudSystem {
     pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
-        let mut rng = rand::rng();
-        // Add a few initial clouds
-        let count = std::cmp::max(1, terminal_width / 20);
-
-        let max_capacity = (terminal_width / 20) as usize;
-        let mut clouds = Vec::with_capacity(max_capacity);
-
-        for _ in 0..count {
-            clouds.push(Self::create_random_cloud(
-                terminal_width,
-                terminal_height,
-                true,
-                Color::White,
-                &mut rng,
-            ));
-        }
+        let max_capacity = (terminal_width as f32 / FULL_COVER_SPACING).ceil() as usize;
 
         Self {
-            clouds,
+            clouds: Vec::with_capacity(max_capacity),
             terminal_width,
             terminal_height,
+            cloud_cover: 0.0,
+            cloud_color: Color::White,
+            drift: 1.0,
         }
     }
 
-    fn create_random_cloud(
-        width: u16,
-        height: u16,
-        random_x: bool,
-        color: Color,
-        rng: &mut impl Rng,
-    ) -> Cloud {
-        let shapes = CLOUD_SHAPES.get_or_init(Self::create_cloud_shapes);
+    fn create_random_cloud(&self, random_x: bool, rng: &mut dyn Rng) -> Cloud {
+        let width = self.terminal_width;
+        let height = self.terminal_height;
+
+        // Heavier cover favours the big, slow cloud banks
+        let large_chance = ((self.cloud_cover - 50.0) / 50.0).max(0.0);
+        let is_large = rng.random::<f32>() < large_chance;
+        let shapes = if is_large {
+            LARGE_CLOUD_SHAPES.get_or_init(Self::create_large_cloud_shapes)
+        } else {
+            CLOUD_SHAPES.get_or_init(Self::create_cloud_shapes)
+        };
 
         let shape_idx = (rng.random::<u32>() as usize) % shapes.len();
         let shape = shapes[shape_idx].clone();
@@ -75,23 +104,51 @@ impl CloudSystem {
         let y_range = height / 3;
         let y = (rng.random::<u16>() % std::cmp::max(1, y_range)) as f32;
 
+        // New clouds blow in from the upwind edge
         let x = if random_x {
             (rng.random::<u16>() % width) as f32
+        } else if self.drift < 0.0 {
+            width as f32
         } else {
             -(shape[0].len() as f32)
         };
 
-        let speed = 0.05 + (rng.random::<f32>() * 0.1);
+        let speed = if is_large {
+            0.02 + (rng.random::<f32>() * 0.03)
+        } else {
+            0.05 + (rng.random::<f32>() * 0.1)
+        };
 
         Cloud {
             x,
             y,
             speed,
             shape,
-            color,
+            color: self.cloud_color,
         }
     }
 
+    fn create_large_cloud_shapes() -> Vec<Vec<String>> {
+        let shapes = [
+            vec![
+                "         .--.      .-.       ".to_string(),
+                "     .-(      ).-(    ).     ".to_string(),
+                "   (          (        )--.  ".to_string(),
+                "  (                        ) ".to_string(),
+                "   `-.__.-'`-.___.-'`-.__.-' ".to_string(),
+            ],
+            vec![
+                "        _  _     _         ".to_string(),
+                "   .-( `    )_-(  `)-.     ".to_string(),
+                " (                     `)  ".to_string(),
+                "(                        ) ".to_string(),
+                " `--.___.--'`--.____.--'   ".to_string(),
+            ],
+        ];
+
+        shapes.to_vec()
+    }
+
     fn create_cloud_shapes() -> Vec<Vec<String>> {
         let shapes = [
             vec![
@@ -120,54 +177,55 @@ impl CloudSystem {
 
         shapes.to_vec()
     }
+}
 
-    pub fn update(
-        &mut self,
-        terminal_width: u16,
-        terminal_height: u16,
-        is_clear: bool,
-        cloud_color: Color,
-        rng: &mut impl Rng,
-    ) {
+impl AnimationSystem for CloudSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for cloud in &mut self.clouds {
-            cloud.x += cloud.speed;
+            cloud.x += cloud.speed * self.drift;
         }
 
-        self.clouds.retain(|c| c.x < terminal_width as f32);
+        self.clouds.retain(|c| {
+            let cloud_width = c.shape[0].len() as f32;
+            c.x < terminal_width as f32 + 1.0 && c.x > -cloud_width - 1.0
+        });
 
-        let max_clouds = if is_clear {
-            (terminal_width / 40) as usize
-        } else {
-            (terminal_width / 20) as usize
-        };
-
-        let spawn_chance = if is_clear { 0.002 } else { 0.005 };
+        let max_clouds = self.max_clouds(terminal_width);
+        let spawn_chance = 0.002 + 0.006 * self.cloud_cover / 100.0;
 
         if self.clouds.len() < max_clouds && rng.random::<f32>() < spawn_chance {
-            self.clouds.push(Self::create_random_cloud(
-                terminal_width,
-                terminal_height,
-                false,
-                cloud_color,
-                rng,
-            ));
+            let cloud = self.create_random_cloud(false, rng);
+            self.clouds.push(cloud);
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.clouds
+                .iter_mut()
+                .map(|cloud| (&mut cloud.x, &mut cloud.y)),
+        );
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for cloud in &self.clouds {
             for (i, line) in cloud.shape.iter().enumerate() {
                 let y = cloud.y as i16 + i as i16;
                 let x = cloud.x as i16;
 
                 if y >= 0 && y < self.terminal_height as i16 {
+                    // Clip the part of a cloud still drifting in from the left edge
+                    let skip = (-x).max(0) as usize;
+                    let visible: String = line.chars().skip(skip).collect();
                     renderer.render_line_colored(
                         std::cmp::max(0, x) as u16,
                         y as u16,
-                        line,
+                        &visible,
                         cloud.color,
                     )?;
                 }
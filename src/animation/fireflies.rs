@@ -1,4 +1,6 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -14,7 +16,7 @@ struct Firefly {
 }
 
 impl Firefly {
-    fn new(terminal_width: u16, horizon_y: u16, rng: &mut impl Rng) -> Self {
+    fn new(terminal_width: u16, horizon_y: u16, rng: &mut dyn Rng) -> Self {
         let x = rng.random::<f32>() * terminal_width as f32;
         let min_y = (horizon_y.saturating_sub(8)) as f32;
         let max_y = (horizon_y.saturating_sub(1)) as f32;
@@ -37,7 +39,7 @@ impl Firefly {
         }
     }
 
-    fn update(&mut self, terminal_width: u16, horizon_y: u16, rng: &mut impl Rng) {
+    fn update(&mut self, terminal_width: u16, horizon_y: u16, rng: &mut dyn Rng) {
         self.x += self.vx;
         self.y += self.vy;
 
@@ -84,23 +86,14 @@ impl Firefly {
         }
     }
 
-    fn get_color(&self) -> Color {
+    // Brightest first
+    fn get_color(&self, colors: &[Color; 3]) -> Color {
         if self.brightness > 200 {
-            Color::Yellow
+            colors[0]
         } else if self.brightness > 128 {
-            Color::Rgb {
-                r: 200,
-                g: 255,
-                b: 100,
-            }
-        } else if self.brightness > 64 {
-            Color::Rgb {
-                r: 150,
-                g: 200,
-                b: 80,
-            }
+            colors[1]
         } else {
-            Color::DarkGrey
+            colors[2]
         }
     }
 
@@ -113,6 +106,8 @@ pub struct FireflySystem {
     fireflies: Vec<Firefly>,
     terminal_width: u16,
     terminal_height: u16,
+    horizon_y: u16,
+    colors: [Color; 3],
 }
 
 impl FireflySystem {
@@ -123,18 +118,25 @@ impl FireflySystem {
             fireflies: Vec::with_capacity(fireflies_capacity),
             terminal_width,
             terminal_height,
+            horizon_y: terminal_height,
+            colors: DEFAULT_SCENERY.fireflies,
         }
     }
 
-    pub fn update(
-        &mut self,
-        terminal_width: u16,
-        terminal_height: u16,
-        horizon_y: u16,
-        rng: &mut impl Rng,
-    ) {
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
+
+    pub fn set_colors(&mut self, colors: [Color; 3]) {
+        self.colors = colors;
+    }
+}
+
+impl AnimationSystem for FireflySystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
+        let horizon_y = self.horizon_y;
 
         for firefly in &mut self.fireflies {
             firefly.update(terminal_width, horizon_y, rng);
@@ -147,7 +149,17 @@ impl FireflySystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.fireflies
+                .iter_mut()
+                .map(|firefly| (&mut firefly.x, &mut firefly.y)),
+        );
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for firefly in &self.fireflies {
             if firefly.is_visible() {
                 let x = firefly.x as i16;
@@ -162,7 +174,7 @@ impl FireflySystem {
                         x as u16,
                         y as u16,
                         firefly.get_character(),
-                        firefly.get_color(),
+                        firefly.get_color(&self.colors),
                     )?;
                 }
             }
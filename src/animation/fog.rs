@@ -1,4 +1,6 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
 use crate::weather::types::FogIntensity;
 use crossterm::style::Color;
 use rand::prelude::*;
@@ -10,13 +12,13 @@ struct FogWisp {
     y: f32,
     speed_x: f32,
     character: char,
-    color: Color,
+    dim: bool,
     lifetime: u32,
     max_lifetime: u32,
 }
 
 impl FogWisp {
-    fn new(terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) -> Self {
+    fn new(terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) -> Self {
         let ground_level = terminal_height.saturating_sub(7);
         let fog_zone_top = ground_level.saturating_sub(15);
 
@@ -26,23 +28,15 @@ impl FogWisp {
         let chars = ['.', ',', '-', '~'];
         let char_idx = (rng.random::<u32>() as usize) % chars.len();
 
-        let colors = [
-            Color::Grey,
-            Color::DarkGrey,
-            Color::Rgb {
-                r: 120,
-                g: 120,
-                b: 120,
-            },
-        ];
-        let color_idx = (rng.random::<u32>() as usize) % colors.len();
+        // Two in three wisps are faint
+        let dim = rng.random::<u32>() % 3 != 0;
 
         Self {
             x,
             y,
             speed_x: (rng.random::<f32>() - 0.5) * 0.15,
             character: chars[char_idx],
-            color: colors[color_idx],
+            dim,
             lifetime: 0,
             max_lifetime: 100 + (rng.random::<u32>() % 200),
         }
@@ -60,12 +54,64 @@ impl FogWisp {
     }
 }
 
+struct FogBand {
+    x: f32,
+    y: u16,
+    length: u16,
+    speed_x: f32,
+    dense: bool,
+}
+
+impl FogBand {
+    fn new(terminal_width: u16, terminal_height: u16, dense: bool, rng: &mut dyn Rng) -> Self {
+        // Bands sit between the upper sky and the horizon so they drift across the house
+        let ground_level = terminal_height.saturating_sub(7);
+        let band_zone_top = terminal_height / 3;
+        let zone_height = ground_level.saturating_sub(band_zone_top).max(1);
+
+        let length =
+            (terminal_width / 4).max(8) + rng.random::<u16>() % (terminal_width / 3).max(1);
+
+        Self {
+            x: rng.random::<f32>() * terminal_width as f32 - length as f32 / 2.0,
+            y: band_zone_top + rng.random::<u16>() % zone_height,
+            length,
+            speed_x: 0.03 + rng.random::<f32>() * 0.05,
+            dense,
+        }
+    }
+
+    fn update(&mut self, terminal_width: u16) {
+        self.x += self.speed_x;
+        if self.x > terminal_width as f32 {
+            self.x = -(self.length as f32);
+        }
+    }
+
+    fn character_at(&self, offset: u16, col: u16) -> Option<char> {
+        // Leave a scattered pattern of gaps so the scene shows through
+        if (col as u32 * 7 + self.y as u32 * 13) % 5 == 0 {
+            return None;
+        }
+
+        let edge = self.length / 5;
+        if self.dense && offset >= edge && offset < self.length - edge {
+            Some('▒')
+        } else {
+            Some('░')
+        }
+    }
+}
+
 pub struct FogSystem {
     wisps: VecDeque<FogWisp>,
+    bands: Vec<FogBand>,
     terminal_width: u16,
     terminal_height: u16,
     intensity: FogIntensity,
     spawn_timer: u32,
+    color: Color,
+    dim_color: Color,
 }
 
 impl FogSystem {
@@ -78,10 +124,13 @@ impl FogSystem {
 
         Self {
             wisps: VecDeque::with_capacity(wisps_capacity),
+            bands: Vec::with_capacity(6),
             terminal_width,
             terminal_height,
             intensity,
             spawn_timer: 0,
+            color: DEFAULT_SCENERY.fog,
+            dim_color: DEFAULT_SCENERY.fog_dim,
         }
     }
 
@@ -89,7 +138,14 @@ impl FogSystem {
         self.intensity = intensity;
     }
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+    pub fn set_colors(&mut self, color: Color, dim_color: Color) {
+        self.color = color;
+        self.dim_color = dim_color;
+    }
+}
+
+impl AnimationSystem for FogSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
@@ -99,6 +155,23 @@ impl FogSystem {
 
         self.wisps.retain(|w| w.is_alive(terminal_width));
 
+        for band in &mut self.bands {
+            band.update(terminal_width);
+        }
+        self.bands.retain(|b| b.y < terminal_height);
+
+        let (target_bands, dense_bands) = match self.intensity {
+            FogIntensity::Light => (2, 0),
+            FogIntensity::Medium => (4, 1),
+            FogIntensity::Heavy => (6, 3),
+        };
+        self.bands.truncate(target_bands);
+        while self.bands.len() < target_bands {
+            let dense = self.bands.len() < dense_bands;
+            self.bands
+                .push(FogBand::new(terminal_width, terminal_height, dense, rng));
+        }
+
         let (target_multiplier, spawn_delay) = match self.intensity {
             FogIntensity::Light => (0.3, 4),
             FogIntensity::Medium => (0.6, 2),
@@ -118,14 +191,44 @@ impl FogSystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        let (sx, sy) = resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.wisps.iter_mut().map(|wisp| (&mut wisp.x, &mut wisp.y)),
+        );
+        for band in &mut self.bands {
+            band.x *= sx;
+            band.y = (band.y as f32 * sy) as u16;
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for band in &self.bands {
+            let color = if band.dense {
+                self.color
+            } else {
+                self.dim_color
+            };
+            for offset in 0..band.length {
+                let x = band.x as i32 + offset as i32;
+                if x < 0 || x >= self.terminal_width as i32 {
+                    continue;
+                }
+                if let Some(ch) = band.character_at(offset, x as u16) {
+                    renderer.render_char(x as u16, band.y, ch, color)?;
+                }
+            }
+        }
+
         for wisp in &self.wisps {
             let x = wisp.x as i16;
             let y = wisp.y as i16;
 
             if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
             {
-                renderer.render_char(x as u16, y as u16, wisp.character, wisp.color)?;
+                let color = if wisp.dim { self.dim_color } else { self.color };
+                renderer.render_char(x as u16, y as u16, wisp.character, color)?;
             }
         }
         Ok(())
@@ -0,0 +1,130 @@
+use super::{AnimationSystem, resize_positions};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const GRAVITY: f32 = 0.25;
+const BOUNCE_DAMPING: f32 = 0.45;
+const MAX_BOUNCES: u8 = 2;
+
+struct Hailstone {
+    x: f32,
+    y: f32,
+    speed_x: f32,
+    speed_y: f32,
+    // Row this stone lands on; spread over the ground rows for depth
+    ground_y: f32,
+    bounces: u8,
+    character: char,
+}
+
+pub struct HailSystem {
+    stones: Vec<Hailstone>,
+    terminal_width: u16,
+    terminal_height: u16,
+    horizon_y: u16,
+    wind_x: f32,
+}
+
+impl HailSystem {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            stones: Vec::with_capacity((terminal_width / 3) as usize),
+            terminal_width,
+            terminal_height,
+            horizon_y: terminal_height.saturating_sub(5),
+            wind_x: 0.0,
+        }
+    }
+
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
+
+    pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+        // Hailstones are heavy, so wind pushes them far less than rain
+        self.wind_x = speed_kmh / 120.0 * -direction_deg.to_radians().sin();
+    }
+
+    fn spawn_stone(&mut self, rng: &mut dyn Rng) {
+        let ground_rows = self.terminal_height.saturating_sub(self.horizon_y).max(1);
+        let ground_y = self.horizon_y + (rng.random::<u16>() % ground_rows);
+        let is_large = rng.random::<f32>() < 0.3;
+
+        self.stones.push(Hailstone {
+            x: (rng.random::<u16>() % self.terminal_width.max(1)) as f32,
+            y: 0.0,
+            speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
+            speed_y: 1.4 + rng.random::<f32>() * 0.8,
+            ground_y: ground_y as f32,
+            bounces: 0,
+            character: if is_large { 'O' } else { 'o' },
+        });
+    }
+}
+
+impl AnimationSystem for HailSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
+        self.terminal_width = terminal_width;
+        self.terminal_height = terminal_height;
+
+        let target_count = (terminal_width / 3) as usize;
+        if self.stones.len() < target_count {
+            for _ in 0..3 {
+                self.spawn_stone(rng);
+            }
+        }
+
+        self.stones.retain_mut(|stone| {
+            stone.x += stone.speed_x;
+            stone.y += stone.speed_y;
+
+            if stone.bounces > 0 {
+                stone.speed_y += GRAVITY;
+            }
+
+            if stone.y >= stone.ground_y && stone.speed_y > 0.0 {
+                if stone.bounces >= MAX_BOUNCES {
+                    return false;
+                }
+                stone.y = stone.ground_y;
+                stone.speed_y = -stone.speed_y * BOUNCE_DAMPING;
+                stone.speed_x += rng.random::<f32>() * 0.6 - 0.3;
+                stone.bounces += 1;
+            }
+
+            stone.x >= 0.0 && stone.x < terminal_width as f32
+        });
+    }
+
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        let (_, sy) = resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.stones
+                .iter_mut()
+                .map(|stone| (&mut stone.x, &mut stone.y)),
+        );
+        for stone in &mut self.stones {
+            stone.ground_y *= sy;
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for stone in &self.stones {
+            let x = stone.x as u16;
+            let y = stone.y as u16;
+            if x < self.terminal_width && y < self.terminal_height {
+                // Stones shatter smaller with each bounce
+                let ch = match stone.bounces {
+                    0 => stone.character,
+                    1 => 'o',
+                    _ => '.',
+                };
+                renderer.render_char(x, y, ch, Color::White)?;
+            }
+        }
+        Ok(())
+    }
+}
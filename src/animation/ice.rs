@@ -0,0 +1,99 @@
+use super::AnimationSystem;
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const GLAZE_RATE: f32 = 0.0015;
+const MELT_RATE: f32 = 0.004;
+const SHIMMER_CHANCE: f32 = 0.04;
+
+struct GlazeCell {
+    x: u16,
+    y: u16,
+    character: char,
+    shimmering: bool,
+}
+
+// Ice that builds up on the ground and roof during freezing rain, a cell at a time in a
+// random order, and thaws once the freezing rain stops.
+pub struct IceGlaze {
+    cells: Vec<GlazeCell>,
+    surfaces: Vec<(u16, u16)>,
+    coverage: f32,
+    freezing: bool,
+}
+
+impl IceGlaze {
+    pub fn new() -> Self {
+        Self {
+            cells: Vec::new(),
+            surfaces: Vec::new(),
+            coverage: 0.0,
+            freezing: false,
+        }
+    }
+
+    pub fn set_freezing(&mut self, freezing: bool) {
+        self.freezing = freezing;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.coverage > 0.0
+    }
+
+    pub fn set_surfaces(&mut self, surfaces: Vec<(u16, u16)>, rng: &mut dyn Rng) {
+        if surfaces == self.surfaces {
+            return;
+        }
+
+        let mut cells: Vec<GlazeCell> = surfaces
+            .iter()
+            .map(|&(x, y)| GlazeCell {
+                x,
+                y,
+                character: if rng.random::<bool>() { '=' } else { '~' },
+                shimmering: false,
+            })
+            .collect();
+        cells.shuffle(rng);
+
+        self.cells = cells;
+        self.surfaces = surfaces;
+    }
+}
+
+impl Default for IceGlaze {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationSystem for IceGlaze {
+    fn update(&mut self, _terminal_width: u16, _terminal_height: u16, rng: &mut dyn Rng) {
+        self.coverage = if self.freezing {
+            (self.coverage + GLAZE_RATE).min(1.0)
+        } else {
+            (self.coverage - MELT_RATE).max(0.0)
+        };
+
+        for cell in &mut self.cells {
+            if rng.random::<f32>() < SHIMMER_CHANCE {
+                cell.shimmering = !cell.shimmering;
+            }
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let visible = (self.cells.len() as f32 * self.coverage) as usize;
+        for cell in self.cells.iter().take(visible) {
+            let color = if cell.shimmering {
+                Color::White
+            } else {
+                Color::Cyan
+            };
+            renderer.render_char(cell.x, cell.y, cell.character, color)?;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -16,7 +18,13 @@ struct Leaf {
 }
 
 impl Leaf {
-    fn new(terminal_width: u16, spawn_at_top: bool, rng: &mut impl Rng) -> Self {
+    fn new(
+        terminal_width: u16,
+        spawn_at_top: bool,
+        blossom: bool,
+        palette: &Palette,
+        rng: &mut dyn Rng,
+    ) -> Self {
         let x = rng.random::<f32>() * terminal_width as f32;
         let y = if spawn_at_top {
             -(rng.random::<f32>() * 5.0)
@@ -29,41 +37,12 @@ impl Leaf {
         let sway_phase = rng.random::<f32>() * std::f32::consts::PI * 2.0;
         let sway_amplitude = 0.5 + (rng.random::<f32>() * 1.5);
 
-        let colors = [
-            Color::Rgb {
-                r: 255,
-                g: 165,
-                b: 0,
-            }, // Orange
-            Color::Rgb {
-                r: 218,
-                g: 165,
-                b: 32,
-            }, // Golden
-            Color::Rgb {
-                r: 184,
-                g: 134,
-                b: 11,
-            }, // Dark golden
-            Color::Rgb {
-                r: 205,
-                g: 92,
-                b: 92,
-            }, // Indian red
-            Color::Rgb {
-                r: 160,
-                g: 82,
-                b: 45,
-            }, // Sienna brown
-            Color::Rgb {
-                r: 139,
-                g: 69,
-                b: 19,
-            }, // Saddle brown
-        ];
+        let (colors, chars): (&[Color], &[char]) = if blossom {
+            (&palette.blossoms, &['*', '.', '\'', '°'])
+        } else {
+            (&palette.leaves, &['*', '+', ',', '.', '~'])
+        };
         let color = colors[(rng.random::<u32>() % colors.len() as u32) as usize];
-
-        let chars = ['*', '+', ',', '.', '~'];
         let character = chars[(rng.random::<u32>() % chars.len() as u32) as usize];
 
         Self {
@@ -119,12 +98,19 @@ impl Leaf {
     }
 }
 
+struct Palette {
+    leaves: [Color; 6],
+    blossoms: [Color; 3],
+}
+
 pub struct FallingLeaves {
     leaves: Vec<Leaf>,
     spawn_counter: u32,
     spawn_rate: u32,
     terminal_width: u16,
     terminal_height: u16,
+    blossoms: bool,
+    palette: Palette,
 }
 
 impl FallingLeaves {
@@ -134,9 +120,13 @@ impl FallingLeaves {
 
         let max_capacity = std::cmp::max(10, terminal_width / 8) as usize;
         let mut leaves = Vec::with_capacity(max_capacity);
+        let palette = Palette {
+            leaves: DEFAULT_SCENERY.leaves,
+            blossoms: DEFAULT_SCENERY.blossoms,
+        };
 
         for _ in 0..initial_count {
-            leaves.push(Leaf::new(terminal_width, false, &mut rng));
+            leaves.push(Leaf::new(terminal_width, false, false, &palette, &mut rng));
         }
 
         Self {
@@ -145,10 +135,24 @@ impl FallingLeaves {
             spawn_rate: 15,
             terminal_width,
             terminal_height,
+            blossoms: false,
+            palette,
         }
     }
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+    // For leaves that fall from now on
+    pub fn set_colors(&mut self, leaves: [Color; 6], blossoms: [Color; 3]) {
+        self.palette = Palette { leaves, blossoms };
+    }
+
+    // Spring petals instead of autumn leaves; already falling leaves are left to land
+    pub fn set_blossoms(&mut self, blossoms: bool) {
+        self.blossoms = blossoms;
+    }
+}
+
+impl AnimationSystem for FallingLeaves {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
@@ -162,7 +166,13 @@ impl FallingLeaves {
         if self.spawn_counter >= self.spawn_rate {
             self.spawn_counter = 0;
             if rng.random::<f32>() < 0.7 {
-                self.leaves.push(Leaf::new(terminal_width, true, rng));
+                self.leaves.push(Leaf::new(
+                    terminal_width,
+                    true,
+                    self.blossoms,
+                    &self.palette,
+                    rng,
+                ));
             }
         }
 
@@ -172,7 +182,17 @@ impl FallingLeaves {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.leaves
+                .iter_mut()
+                .map(|leaf| (&mut leaf.x, &mut leaf.y)),
+        );
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for leaf in &self.leaves {
             let x = leaf.x as i16;
             let y = leaf.y as i16;
@@ -1,21 +1,80 @@
 pub mod airplanes;
+pub mod aurora;
 pub mod birds;
 pub mod chimney;
 pub mod clouds;
 pub mod fireflies;
 pub mod fog;
+pub mod hail;
+pub mod ice;
 pub mod leaves;
 pub mod moon;
+pub mod rainbow;
 pub mod raindrops;
+pub mod shimmer;
 pub mod snow;
+pub mod snow_cover;
 pub mod stars;
 pub mod sunny;
 pub mod thunderstorm;
 
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
+use rand::Rng;
 use std::io;
 
+// Heavier stand-ins for the thin particle characters, for the high-contrast theme
+pub fn heavy_glyph(ch: char) -> char {
+    match ch {
+        '.' | ',' | '·' => '•',
+        '|' | ':' => '┃',
+        '/' => '╱',
+        '\\' => '╲',
+        '-' | '~' => '━',
+        '*' => '✱',
+        'o' => '●',
+        _ => ch,
+    }
+}
+
+pub trait AnimationSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng);
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()>;
+
+    // Called when the terminal changes size, so particles already on screen can
+    // move with it instead of the system starting over
+    fn resize(&mut self, _terminal_width: u16, _terminal_height: u16) {}
+}
+
+// How much positions stretch when the terminal goes from `from` to `to` cells
+pub fn resize_scale(from: (u16, u16), to: (u16, u16)) -> (f32, f32) {
+    let scale = |old: u16, new: u16| {
+        if old == 0 {
+            1.0
+        } else {
+            new as f32 / old as f32
+        }
+    };
+    (scale(from.0, to.0), scale(from.1, to.1))
+}
+
+// Moves (x, y) positions along with the terminal as it goes from `size`, the one a system
+// last saw, to `to`, and takes note of the new size. The scale is returned for anything
+// else the system places
+pub fn resize_positions<'a>(
+    size: (&mut u16, &mut u16),
+    to: (u16, u16),
+    positions: impl IntoIterator<Item = (&'a mut f32, &'a mut f32)>,
+) -> (f32, f32) {
+    let (sx, sy) = resize_scale((*size.0, *size.1), to);
+    for (x, y) in positions {
+        *x *= sx;
+        *y *= sy;
+    }
+    (*size.0, *size.1) = to;
+    (sx, sy)
+}
+
 pub trait Animation {
     fn get_frame(&self, frame_number: usize) -> &[String];
     fn frame_count(&self) -> usize;
@@ -50,6 +109,21 @@ impl AnimationController {
         renderer.render_centered_colored(frame, y_offset, color)
     }
 
+    pub fn render_frame_at<A: Animation>(
+        &self,
+        renderer: &mut TerminalRenderer,
+        animation: &A,
+        x: u16,
+        y: u16,
+    ) -> io::Result<()> {
+        let frame = animation.get_frame(self.current_frame);
+        let color = animation.get_color();
+        for (idx, line) in frame.iter().enumerate() {
+            renderer.render_line_colored(x, y + idx as u16, line, color)?;
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub fn reset(&mut self) {
         self.current_frame = 0;
@@ -61,3 +135,27 @@ impl Default for AnimationController {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_positions() {
+        let mut size = (80, 20);
+        let mut particles = [(40.0, 10.0), (8.0, 2.0)];
+        let scale = resize_positions(
+            (&mut size.0, &mut size.1),
+            (160, 10),
+            particles.iter_mut().map(|(x, y)| (x, y)),
+        );
+        assert_eq!(scale, (2.0, 0.5));
+        assert_eq!(particles, [(80.0, 5.0), (16.0, 1.0)]);
+        assert_eq!(size, (160, 10));
+
+        // Nothing to scale from before the first size is known
+        let mut size = (0, 0);
+        let scale = resize_positions((&mut size.0, &mut size.1), (80, 20), []);
+        assert_eq!(scale, (1.0, 1.0));
+    }
+}
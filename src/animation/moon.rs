@@ -1,5 +1,7 @@
+use super::AnimationSystem;
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
+use rand::Rng;
 use std::io;
 
 pub struct MoonSystem {
@@ -17,17 +19,31 @@ impl MoonSystem {
         }
     }
 
-    #[allow(dead_code)]
     pub fn set_phase(&mut self, phase: f64) {
         self.phase = phase;
     }
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16) {
-        self.x = (terminal_width / 4 * 3).min(terminal_width.saturating_sub(15));
-        self.y = (terminal_height / 4).max(2);
+    // Top-left corner of the moon art
+    fn position(terminal_width: u16, terminal_height: u16) -> (u16, u16) {
+        (
+            (terminal_width / 4 * 3).min(terminal_width.saturating_sub(15)),
+            (terminal_height / 4).max(2),
+        )
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    // Middle of the disc in the art, for drawing the moon some other way
+    pub fn center(terminal_width: u16, terminal_height: u16) -> (u16, u16) {
+        let (x, y) = Self::position(terminal_width, terminal_height);
+        (x + 13, y + 3)
+    }
+}
+
+impl AnimationSystem for MoonSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, _rng: &mut dyn Rng) {
+        (self.x, self.y) = Self::position(terminal_width, terminal_height);
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         let step = (self.phase * 8.0).round() as usize % 8;
 
         let art = match step {
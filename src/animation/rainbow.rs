@@ -0,0 +1,63 @@
+use super::AnimationSystem;
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const BANDS: [Color; 6] = [
+    Color::Red,
+    Color::DarkYellow,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Magenta,
+];
+
+// A half-ellipse arc resting on the horizon, twice as wide as it is tall so it looks
+// round in terminal cells.
+pub struct Rainbow {
+    terminal_width: u16,
+    horizon_y: u16,
+}
+
+impl Rainbow {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            terminal_width,
+            horizon_y: terminal_height,
+        }
+    }
+
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
+}
+
+impl AnimationSystem for Rainbow {
+    fn update(&mut self, terminal_width: u16, _terminal_height: u16, _rng: &mut dyn Rng) {
+        self.terminal_width = terminal_width;
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        // Off to the right of the house, and below the status line
+        let center_x = self.terminal_width as f32 * 0.8;
+        let base_y = self.horizon_y as f32;
+        let radius = (base_y - 4.0).min(self.terminal_width as f32 / 7.0);
+        if radius < BANDS.len() as f32 {
+            return Ok(());
+        }
+
+        let top = (base_y - radius).max(0.0) as u16;
+        for y in top..self.horizon_y {
+            let dy = base_y - y as f32;
+            for x in 0..self.terminal_width {
+                let dx = (x as f32 - center_x) / 2.0;
+                let band = radius - (dx * dx + dy * dy).sqrt();
+                if (0.0..BANDS.len() as f32).contains(&band) {
+                    renderer.render_char(x, y, '░', BANDS[band as usize])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use crate::render::TerminalRenderer;
+use super::{AnimationSystem, heavy_glyph, resize_positions};
+use crate::render::{BrailleCanvas, TerminalRenderer};
 use crate::weather::types::RainIntensity;
 use crossterm::style::Color;
 use rand::prelude::*;
@@ -6,6 +7,9 @@ use std::collections::VecDeque;
 use std::io;
 
 const MAX_SPLASHES: usize = 100;
+const SLANT_THRESHOLD: f32 = 0.5;
+// Dots per streak in braille mode, for far and near drops
+const BRAILLE_STREAK: [usize; 2] = [2, 3];
 
 struct Raindrop {
     x: f32,
@@ -33,16 +37,27 @@ pub struct RaindropSystem {
     terminal_height: u16,
     intensity: RainIntensity,
     wind_x: f32,
+    density_scale: f32,
+    speed_scale: f32,
+    // Nearer drops: tinted for drizzle and heavy rain, bright for light rain and storms
+    color: Color,
+    bright_color: Color,
+    heavy: bool,
+    braille: bool,
+}
+
+fn base_drop_count(intensity: RainIntensity, terminal_width: u16) -> usize {
+    match intensity {
+        RainIntensity::Drizzle => (terminal_width / 4) as usize,
+        RainIntensity::Light => (terminal_width / 2) as usize,
+        RainIntensity::Heavy => terminal_width as usize,
+        RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
+    }
 }
 
 impl RaindropSystem {
     pub fn new(terminal_width: u16, terminal_height: u16, intensity: RainIntensity) -> Self {
-        let drops_capacity = match intensity {
-            RainIntensity::Drizzle => (terminal_width / 4) as usize,
-            RainIntensity::Light => (terminal_width / 2) as usize,
-            RainIntensity::Heavy => terminal_width as usize,
-            RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
-        };
+        let drops_capacity = base_drop_count(intensity, terminal_width);
 
         let mut system = Self {
             drops: Vec::with_capacity(drops_capacity),
@@ -52,12 +67,53 @@ impl RaindropSystem {
             terminal_height,
             intensity,
             wind_x: 0.0,
+            density_scale: 1.0,
+            speed_scale: 1.0,
+            color: Color::Cyan,
+            bright_color: Color::White,
+            heavy: false,
+            braille: false,
         };
         let wind_dir = if rand::random::<bool>() { 1.0 } else { -1.0 };
         system.set_intensity_with_dir(intensity, wind_dir);
         system
     }
 
+    pub fn set_colors(&mut self, color: Color, bright_color: Color) {
+        self.color = color;
+        self.bright_color = bright_color;
+    }
+
+    pub fn set_heavy(&mut self, heavy: bool) {
+        self.heavy = heavy;
+    }
+
+    pub fn set_braille(&mut self, braille: bool) {
+        self.braille = braille;
+    }
+
+    // Each drop as a short streak of dots trailing back along its path
+    fn render_braille(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let mut canvas = BrailleCanvas::new(self.terminal_width, self.terminal_height);
+        for drop in &self.drops {
+            // One dot row apart, whatever the speed
+            let step = 0.25 / drop.speed_y.max(0.1);
+            for k in 0..BRAILLE_STREAK[drop.z_index as usize] {
+                let t = k as f32 * step;
+                canvas.plot(
+                    drop.x - drop.speed_x * t,
+                    drop.y - drop.speed_y * t,
+                    drop.color,
+                );
+            }
+        }
+        canvas.render(renderer)
+    }
+
+    fn glyph(&self, ch: char) -> char {
+        if self.heavy { heavy_glyph(ch) } else { ch }
+    }
+
     pub fn set_intensity(&mut self, intensity: RainIntensity) {
         let current_dir = if self.wind_x >= 0.0 { 1.0 } else { -1.0 };
         self.set_intensity_with_dir(intensity, current_dir);
@@ -81,7 +137,19 @@ impl RaindropSystem {
         self.wind_x = speed_factor * x_component;
     }
 
-    fn spawn_drop(&mut self, rng: &mut impl Rng) {
+    // Precipitation in mm over the last hour. Zero means the provider reported none for a
+    // raining condition, so the condition's own density is used unscaled.
+    pub fn set_precipitation(&mut self, mm: f32) {
+        if mm <= 0.0 {
+            self.density_scale = 1.0;
+            self.speed_scale = 1.0;
+        } else {
+            self.density_scale = (0.4 + mm * 0.3).clamp(0.4, 2.5);
+            self.speed_scale = 1.0 + mm.min(10.0) / 20.0;
+        }
+    }
+
+    fn spawn_drop(&mut self, rng: &mut dyn Rng) {
         let x = (rng.random::<u32>() % (self.terminal_width as u32 * 2)) as f32
             - (self.terminal_width as f32 * 0.5);
         let z_index = if rng.random::<bool>() { 1 } else { 0 };
@@ -91,7 +159,7 @@ impl RaindropSystem {
                 if z_index == 1 { 0.4 } else { 0.2 },
                 vec!['.', ','],
                 if z_index == 1 {
-                    Color::Cyan
+                    self.color
                 } else {
                     Color::DarkGrey
                 },
@@ -100,7 +168,7 @@ impl RaindropSystem {
                 if z_index == 1 { 0.7 } else { 0.4 },
                 vec!['|', ':', '.'],
                 if z_index == 1 {
-                    Color::White
+                    self.bright_color
                 } else {
                     Color::DarkGrey
                 },
@@ -109,7 +177,7 @@ impl RaindropSystem {
                 if z_index == 1 { 0.9 } else { 0.6 }, // Slightly faster than Light
                 vec!['|', ':'],                       // Vertical density
                 if z_index == 1 {
-                    Color::Cyan
+                    self.color
                 } else {
                     Color::DarkGrey // Blue-ish background
                 },
@@ -123,7 +191,7 @@ impl RaindropSystem {
                     vec!['/']
                 },
                 if z_index == 1 {
-                    Color::White
+                    self.bright_color
                 } else {
                     Color::DarkGrey
                 },
@@ -135,31 +203,30 @@ impl RaindropSystem {
         self.drops.push(Raindrop {
             x,
             y: 0.0,
-            speed_y: speed_y + (rng.random::<f32>() * 0.2),
+            speed_y: (speed_y + (rng.random::<f32>() * 0.2)) * self.speed_scale,
             speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
             character: chars[char_idx],
             color,
             z_index,
         });
     }
+}
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+impl AnimationSystem for RaindropSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
-        let target_count = match self.intensity {
-            RainIntensity::Drizzle => (terminal_width / 4) as usize,
-            RainIntensity::Light => (terminal_width / 2) as usize,
-            RainIntensity::Heavy => terminal_width as usize,
-            RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
-        };
+        let target_count =
+            (base_drop_count(self.intensity, terminal_width) as f32 * self.density_scale) as usize;
 
         if self.drops.len() < target_count {
-            let spawn_rate = match self.intensity {
-                RainIntensity::Drizzle => 1,
-                RainIntensity::Light => 2,
-                _ => 5,
+            let base_spawn_rate = match self.intensity {
+                RainIntensity::Drizzle => 1.0,
+                RainIntensity::Light => 2.0,
+                _ => 5.0,
             };
+            let spawn_rate = (base_spawn_rate * self.density_scale).ceil() as usize;
             for _ in 0..spawn_rate {
                 self.spawn_drop(rng);
             }
@@ -210,28 +277,41 @@ impl RaindropSystem {
         });
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.drops.iter_mut().map(|drop| (&mut drop.x, &mut drop.y)),
+        );
+        // Splashes only last a few frames
+        self.splashes.clear();
+        self.new_splashes.clear();
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         // Render drops
-        for drop in &self.drops {
-            let x = drop.x as i16;
-            let y = drop.y as i16;
+        if self.braille {
+            self.render_braille(renderer)?;
+        } else {
+            for drop in &self.drops {
+                let x = drop.x as i16;
+                let y = drop.y as i16;
 
-            if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
-            {
-                let ch = if self.intensity == RainIntensity::Storm
-                    || self.intensity == RainIntensity::Heavy
+                if x >= 0
+                    && x < self.terminal_width as i16
+                    && y >= 0
+                    && y < self.terminal_height as i16
                 {
-                    if drop.speed_x > 0.5 {
+                    // Strong wind streaks every drop along its direction of travel
+                    let ch = if drop.speed_x > SLANT_THRESHOLD {
                         '\\'
-                    } else if drop.speed_x < -0.5 {
+                    } else if drop.speed_x < -SLANT_THRESHOLD {
                         '/'
                     } else {
                         drop.character
-                    }
-                } else {
-                    drop.character
-                };
-                renderer.render_char(x as u16, y as u16, ch, drop.color)?;
+                    };
+                    renderer.render_char(x as u16, y as u16, self.glyph(ch), drop.color)?;
+                }
             }
         }
 
@@ -244,7 +324,7 @@ impl RaindropSystem {
                     2 => 'O',
                     _ => ' ',
                 };
-                renderer.render_char(splash.x, splash.y, ch, Color::White)?;
+                renderer.render_char(splash.x, splash.y, self.glyph(ch), Color::White)?;
             }
         }
 
@@ -0,0 +1,78 @@
+use super::AnimationSystem;
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const SHIMMER_ROWS: f32 = 3.0;
+const RISE_SPEED: f32 = 0.04;
+
+struct Wisp {
+    x: f32,
+    height: f32,
+    offset: i16,
+}
+
+// Hot air wavering just above the ground: short-lived `~` wisps that rise a few rows,
+// shifting sideways as they go.
+pub struct HeatShimmer {
+    wisps: Vec<Wisp>,
+    terminal_width: u16,
+    horizon_y: u16,
+}
+
+impl HeatShimmer {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            wisps: Vec::new(),
+            terminal_width,
+            horizon_y: terminal_height,
+        }
+    }
+
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
+}
+
+impl AnimationSystem for HeatShimmer {
+    fn update(&mut self, terminal_width: u16, _terminal_height: u16, rng: &mut dyn Rng) {
+        self.terminal_width = terminal_width;
+
+        for wisp in &mut self.wisps {
+            wisp.height += RISE_SPEED;
+            if rng.random::<f32>() < 0.2 {
+                wisp.offset = if wisp.offset == 0 { 1 } else { 0 };
+            }
+        }
+        self.wisps.retain(|w| w.height < SHIMMER_ROWS);
+
+        let target = (terminal_width / 6) as usize;
+        if self.wisps.len() < target && rng.random::<f32>() < 0.3 {
+            self.wisps.push(Wisp {
+                x: rng.random::<f32>() * terminal_width as f32,
+                height: 0.0,
+                offset: 0,
+            });
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for wisp in &self.wisps {
+            let y = self.horizon_y as i16 - 1 - wisp.height as i16;
+            let x = wisp.x as i16 + wisp.offset;
+            if y < 0 || x < 0 || x >= self.terminal_width as i16 {
+                continue;
+            }
+
+            // Fainter the higher it rises
+            let color = if wisp.height < 1.0 {
+                Color::Grey
+            } else {
+                Color::DarkGrey
+            };
+            renderer.render_char(x as u16, y as u16, '~', color)?;
+        }
+        Ok(())
+    }
+}
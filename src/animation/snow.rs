@@ -1,4 +1,5 @@
-use crate::render::TerminalRenderer;
+use super::{AnimationSystem, heavy_glyph, resize_positions};
+use crate::render::{BrailleCanvas, TerminalRenderer};
 use crate::weather::types::SnowIntensity;
 use crossterm::style::Color;
 use rand::prelude::*;
@@ -14,12 +15,25 @@ struct Snowflake {
     color: Color,
 }
 
+struct Gust {
+    x: f32,
+    y: u16,
+}
+
+const GUST_TEXT: &str = "~ whooOOOoooosh ~";
+
 pub struct SnowSystem {
     flakes: Vec<Snowflake>,
     terminal_width: u16,
     terminal_height: u16,
     intensity: SnowIntensity,
     wind_x: f32,
+    blizzard: bool,
+    gust: Option<Gust>,
+    // Nearer flakes; farther ones stay grey
+    color: Color,
+    heavy: bool,
+    braille: bool,
 }
 
 impl SnowSystem {
@@ -36,6 +50,11 @@ impl SnowSystem {
             terminal_height,
             intensity,
             wind_x: 0.0,
+            blizzard: false,
+            gust: None,
+            color: Color::White,
+            heavy: false,
+            braille: false,
         };
         // Initialize with some default wind
         let wind_dir = if rand::random::<bool>() { 0.2 } else { -0.2 };
@@ -43,6 +62,22 @@ impl SnowSystem {
         system
     }
 
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    pub fn set_heavy(&mut self, heavy: bool) {
+        self.heavy = heavy;
+    }
+
+    pub fn set_braille(&mut self, braille: bool) {
+        self.braille = braille;
+    }
+
+    fn glyph(&self, ch: char) -> char {
+        if self.heavy { heavy_glyph(ch) } else { ch }
+    }
+
     pub fn set_intensity(&mut self, intensity: SnowIntensity) {
         // Preserve direction but update magnitude based on intensity if needed
         let current_dir = if self.wind_x >= 0.0 { 1.0 } else { -1.0 };
@@ -66,17 +101,88 @@ impl SnowSystem {
         self.wind_x = speed_factor * x_component;
     }
 
-    fn spawn_flake(&mut self, rng: &mut impl Rng) {
+    // Driving snow: denser, near-horizontal streaks and the odd howl of wind
+    pub fn set_blizzard(&mut self, blizzard: bool) {
+        self.blizzard = blizzard;
+        if !blizzard {
+            self.gust = None;
+        }
+    }
+
+    fn blizzard_direction(&self) -> f32 {
+        if self.wind_x < 0.0 { -1.0 } else { 1.0 }
+    }
+
+    fn spawn_streak(&mut self, rng: &mut dyn Rng) {
+        let direction = self.blizzard_direction();
+        // Enter from the upwind edge at any height, since they barely fall
+        let x = if direction > 0.0 {
+            -(rng.random::<f32>() * 10.0)
+        } else {
+            self.terminal_width as f32 + rng.random::<f32>() * 10.0
+        };
+        let near = rng.random::<bool>();
+        let chars = ['-', '~', '·', '-'];
+
+        self.flakes.push(Snowflake {
+            x,
+            y: rng.random::<f32>() * self.terminal_height as f32,
+            speed_y: 0.1 + rng.random::<f32>() * 0.15,
+            speed_x: direction * (1.2 + rng.random::<f32>() * 0.8),
+            sway_offset: rng.random::<f32>() * 100.0,
+            character: chars[(rng.random::<u32>() as usize) % chars.len()],
+            color: if near { self.color } else { Color::Grey },
+        });
+    }
+
+    fn update_gust(&mut self, rng: &mut dyn Rng) {
+        let direction = self.blizzard_direction();
+        let text_width = GUST_TEXT.chars().count() as f32;
+
+        if let Some(ref mut gust) = self.gust {
+            gust.x += direction * 0.8;
+            if gust.x < -text_width || gust.x > self.terminal_width as f32 {
+                self.gust = None;
+            }
+        } else if rng.random::<f32>() < 0.008 {
+            let rows = (self.terminal_height / 3).max(1);
+            self.gust = Some(Gust {
+                x: if direction > 0.0 {
+                    -text_width
+                } else {
+                    self.terminal_width as f32
+                },
+                y: 3 + rng.random::<u16>() % rows,
+            });
+        }
+    }
+
+    fn spawn_flake(&mut self, rng: &mut dyn Rng) {
         // Spawn across a wider area to account for wind blowing them in
         let x = (rng.random::<u32>() % (self.terminal_width as u32 * 3)) as f32
             - (self.terminal_width as f32);
 
         let z_index = if rng.random::<bool>() { 1 } else { 0 };
 
+        // Near flakes are larger; the crystal glyph only shows up in the front layer
         let (base_speed_y, chars) = match self.intensity {
             SnowIntensity::Light => (if z_index == 1 { 0.15 } else { 0.08 }, vec!['.', '·']),
-            SnowIntensity::Medium => (if z_index == 1 { 0.2 } else { 0.1 }, vec!['.', '·', '*']),
-            SnowIntensity::Heavy => (if z_index == 1 { 0.3 } else { 0.15 }, vec!['*', '.', '·']),
+            SnowIntensity::Medium => (
+                if z_index == 1 { 0.2 } else { 0.1 },
+                if z_index == 1 {
+                    vec!['*', '·', '❄']
+                } else {
+                    vec!['.', '·', '*']
+                },
+            ),
+            SnowIntensity::Heavy => (
+                if z_index == 1 { 0.3 } else { 0.15 },
+                if z_index == 1 {
+                    vec!['*', '❄', '*', '·']
+                } else {
+                    vec!['*', '.', '·']
+                },
+            ),
         };
 
         let char_idx = (rng.random::<u32>() as usize) % chars.len();
@@ -89,14 +195,16 @@ impl SnowSystem {
             sway_offset: rng.random::<f32>() * 100.0, // Random phase for sway
             character: chars[char_idx],
             color: if z_index == 1 {
-                Color::White
+                self.color
             } else {
                 Color::DarkGrey
             },
         });
     }
+}
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+impl AnimationSystem for SnowSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
@@ -106,7 +214,15 @@ impl SnowSystem {
             SnowIntensity::Heavy => terminal_width as usize,
         };
 
-        if self.flakes.len() < target_count {
+        if self.blizzard {
+            let target_count = terminal_width as usize * 2;
+            for _ in 0..8 {
+                if self.flakes.len() < target_count {
+                    self.spawn_streak(rng);
+                }
+            }
+            self.update_gust(rng);
+        } else if self.flakes.len() < target_count {
             let spawn_rate = match self.intensity {
                 SnowIntensity::Light => 1,
                 SnowIntensity::Medium => 2,
@@ -138,14 +254,53 @@ impl SnowSystem {
         });
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
-        for flake in &self.flakes {
-            let x = flake.x as i16;
-            let y = flake.y as i16;
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        let (sx, sy) = resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            self.flakes
+                .iter_mut()
+                .map(|flake| (&mut flake.x, &mut flake.y)),
+        );
+        if let Some(gust) = &mut self.gust {
+            gust.x *= sx;
+            gust.y = (gust.y as f32 * sy) as u16;
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        if self.braille {
+            let mut canvas = BrailleCanvas::new(self.terminal_width, self.terminal_height);
+            for flake in &self.flakes {
+                canvas.plot(flake.x, flake.y, flake.color);
+            }
+            canvas.render(renderer)?;
+        } else {
+            for flake in &self.flakes {
+                let x = flake.x as i16;
+                let y = flake.y as i16;
+
+                if x >= 0
+                    && x < self.terminal_width as i16
+                    && y >= 0
+                    && y < self.terminal_height as i16
+                {
+                    renderer.render_char(
+                        x as u16,
+                        y as u16,
+                        self.glyph(flake.character),
+                        flake.color,
+                    )?;
+                }
+            }
+        }
 
-            if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
-            {
-                renderer.render_char(x as u16, y as u16, flake.character, flake.color)?;
+        if let Some(ref gust) = self.gust {
+            for (i, ch) in GUST_TEXT.chars().enumerate() {
+                let x = gust.x as i16 + i as i16;
+                if x >= 0 && x < self.terminal_width as i16 {
+                    renderer.render_char(x as u16, gust.y, self.glyph(ch), Color::Grey)?;
+                }
             }
         }
         Ok(())
@@ -0,0 +1,103 @@
+use super::AnimationSystem;
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+const LAYERS: [char; 3] = ['▁', '▂', '▃'];
+const MAX_DEPTH: f32 = LAYERS.len() as f32;
+const SETTLE_CHANCE: f32 = 0.02;
+const SETTLE_AMOUNT: f32 = 0.04;
+const MELT_RATE: f32 = 0.0005;
+const MIN_VISIBLE_DEPTH: f32 = 0.15;
+
+struct SnowPatch {
+    x: u16,
+    y: u16,
+    depth: f32,
+}
+
+// Snow that settles on the roof and along the bottom of the screen while it's snowing,
+// building up unevenly from ▁ to ▃ and melting slowly once it's above freezing.
+pub struct SnowCover {
+    patches: Vec<SnowPatch>,
+    surfaces: Vec<(u16, u16)>,
+    snowing: bool,
+    melting: bool,
+}
+
+impl SnowCover {
+    pub fn new() -> Self {
+        Self {
+            patches: Vec::new(),
+            surfaces: Vec::new(),
+            snowing: false,
+            melting: false,
+        }
+    }
+
+    pub fn set_snowing(&mut self, snowing: bool) {
+        self.snowing = snowing;
+    }
+
+    pub fn set_temperature(&mut self, celsius: f64) {
+        self.melting = celsius > 0.0;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.patches.iter().any(|p| p.depth > 0.0)
+    }
+
+    pub fn set_surfaces(&mut self, surfaces: Vec<(u16, u16)>) {
+        if surfaces == self.surfaces {
+            return;
+        }
+
+        // Keep the overall depth of the snow when the layout changes on resize
+        let average = if self.patches.is_empty() {
+            0.0
+        } else {
+            self.patches.iter().map(|p| p.depth).sum::<f32>() / self.patches.len() as f32
+        };
+
+        self.patches = surfaces
+            .iter()
+            .map(|&(x, y)| SnowPatch {
+                x,
+                y,
+                depth: average,
+            })
+            .collect();
+        self.surfaces = surfaces;
+    }
+}
+
+impl Default for SnowCover {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationSystem for SnowCover {
+    fn update(&mut self, _terminal_width: u16, _terminal_height: u16, rng: &mut dyn Rng) {
+        for patch in &mut self.patches {
+            if self.snowing && rng.random::<f32>() < SETTLE_CHANCE {
+                patch.depth = (patch.depth + SETTLE_AMOUNT).min(MAX_DEPTH);
+            }
+            if self.melting {
+                patch.depth = (patch.depth - MELT_RATE).max(0.0);
+            }
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for patch in &self.patches {
+            if patch.depth < MIN_VISIBLE_DEPTH {
+                continue;
+            }
+            let layer = (patch.depth as usize).min(LAYERS.len() - 1);
+            renderer.render_char(patch.x, patch.y, LAYERS[layer], Color::White)?;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use super::{AnimationSystem, resize_positions};
 use crate::render::TerminalRenderer;
+use crate::theme::DEFAULT_SCENERY;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -8,6 +10,7 @@ struct Star {
     y: u16,
     brightness: f32,
     phase: f32,
+    twinkle_speed: f32,
 }
 
 struct ShootingStar {
@@ -24,22 +27,43 @@ pub struct StarSystem {
     shooting_star: Option<ShootingStar>,
     terminal_width: u16,
     terminal_height: u16,
+    color: Color,
+    dim_color: Color,
 }
 
 impl StarSystem {
     pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
-        let mut rng = rand::rng();
+        Self {
+            stars: Self::scatter_stars(terminal_width, terminal_height, &mut rand::rng()),
+            shooting_star: None,
+            terminal_width,
+            terminal_height,
+            color: DEFAULT_SCENERY.star,
+            dim_color: DEFAULT_SCENERY.star_dim,
+        }
+    }
+
+    pub fn set_colors(&mut self, color: Color, dim_color: Color) {
+        self.color = color;
+        self.dim_color = dim_color;
+    }
+
+    fn scatter_stars(terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) -> Vec<Star> {
         let count = (terminal_width as usize * terminal_height as usize) / 80; // Density
         let mut stars = Vec::with_capacity(count);
         const MIN_DISTANCE: f32 = 3.0; // Minimum distance between stars
 
+        if terminal_width == 0 || terminal_height < 2 {
+            return stars;
+        }
+
         for _ in 0..count {
             let mut attempts = 0;
             let max_attempts = 50;
 
             loop {
                 let x = rng.random::<u16>() % terminal_width;
-                let y = rng.random::<u16>() % (terminal_height / 2); // Upper half
+                let y = rng.random::<u16>() % (terminal_height / 2).max(1); // Upper half
 
                 // Check if this position is far enough from existing stars
                 let too_close = stars.iter().any(|star: &Star| {
@@ -55,6 +79,7 @@ impl StarSystem {
                         y,
                         brightness: rng.random::<f32>(),
                         phase: rng.random::<f32>() * std::f32::consts::TAU,
+                        twinkle_speed: rng.random_range(0.02..0.08),
                     });
                     break;
                 }
@@ -63,21 +88,22 @@ impl StarSystem {
             }
         }
 
-        Self {
-            stars,
-            shooting_star: None,
-            terminal_width,
-            terminal_height,
-        }
+        stars
     }
+}
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+impl AnimationSystem for StarSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
+        if terminal_width != self.terminal_width || terminal_height != self.terminal_height {
+            self.stars = Self::scatter_stars(terminal_width, terminal_height, rng);
+            self.shooting_star = None;
+        }
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
-        // Twinkle
+        // Twinkle, each star at its own pace so the field never pulses in unison
         for star in &mut self.stars {
-            star.phase += 0.05;
+            star.phase += star.twinkle_speed;
             star.brightness = (star.phase.sin() + 1.0) / 2.0; // 0.0 to 1.0
         }
 
@@ -90,8 +116,9 @@ impl StarSystem {
                 self.shooting_star = None;
             }
         } else if rng.random::<f32>() < 0.005 {
-            let start_x = (rng.random::<u16>() % (terminal_width / 2)) + (terminal_width / 4);
-            let start_y = rng.random::<u16>() % (terminal_height / 4);
+            let start_x =
+                (rng.random::<u16>() % (terminal_width / 2).max(1)) + (terminal_width / 4);
+            let start_y = rng.random::<u16>() % (terminal_height / 4).max(1);
 
             self.shooting_star = Some(ShootingStar {
                 x: start_x as f32,
@@ -104,7 +131,21 @@ impl StarSystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    fn resize(&mut self, terminal_width: u16, terminal_height: u16) {
+        // Stars sit on whole cells
+        let (sx, sy) = resize_positions(
+            (&mut self.terminal_width, &mut self.terminal_height),
+            (terminal_width, terminal_height),
+            [],
+        );
+        for star in &mut self.stars {
+            star.x = ((star.x as f32 * sx) as u16).min(terminal_width.saturating_sub(1));
+            star.y = (star.y as f32 * sy) as u16;
+        }
+        self.shooting_star = None;
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for star in &self.stars {
             let ch = if star.brightness > 0.8 {
                 '*'
@@ -114,9 +155,9 @@ impl StarSystem {
                 '.'
             };
             let color = if star.brightness > 0.6 {
-                Color::White
+                self.color
             } else {
-                Color::DarkGrey
+                self.dim_color
             };
 
             renderer.render_char(star.x, star.y, ch, color)?;
@@ -133,7 +174,7 @@ impl StarSystem {
                 && head_y >= 0
                 && head_y < self.terminal_height as i16
             {
-                renderer.render_char(head_x as u16, head_y as u16, '*', Color::White)?;
+                renderer.render_char(head_x as u16, head_y as u16, '*', self.color)?;
             }
 
             for i in 1..star.length {
@@ -146,7 +187,7 @@ impl StarSystem {
                     && trail_y < self.terminal_height as i16
                 {
                     let ch = if i == 1 { '+' } else { '.' };
-                    renderer.render_char(trail_x as u16, trail_y as u16, ch, Color::White)?;
+                    renderer.render_char(trail_x as u16, trail_y as u16, ch, self.color)?;
                 }
             }
         }
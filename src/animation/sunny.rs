@@ -1,15 +1,91 @@
 use super::Animation;
 use crossterm::style::Color;
 
+// WHO bands: below 3 is low, 8 and up very high
+const WEAK_BELOW_UV: f64 = 3.0;
+const STRONG_FROM_UV: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Intensity {
+    Weak,
+    Normal,
+    Strong,
+}
+
 pub struct SunnyAnimation {
     frames: Vec<Vec<String>>,
+    intensity: Intensity,
+    color: Color,
+    dim_color: Color,
 }
 
 impl SunnyAnimation {
     pub fn new() -> Self {
-        let frames = vec![Self::create_frame_1(), Self::create_frame_2()];
+        Self {
+            frames: Self::frames(Intensity::Normal),
+            intensity: Intensity::Normal,
+            color: Color::Yellow,
+            dim_color: Color::DarkYellow,
+        }
+    }
+
+    pub fn set_colors(&mut self, color: Color, dim_color: Color) {
+        self.color = color;
+        self.dim_color = dim_color;
+    }
+
+    // A weak winter sun loses its outer rays; a scorching one grows longer ones
+    pub fn set_uv_index(&mut self, uv_index: Option<f64>) {
+        let intensity = match uv_index {
+            Some(uv) if uv < WEAK_BELOW_UV => Intensity::Weak,
+            Some(uv) if uv >= STRONG_FROM_UV => Intensity::Strong,
+            _ => Intensity::Normal,
+        };
+        if intensity != self.intensity {
+            self.intensity = intensity;
+            self.frames = Self::frames(intensity);
+        }
+    }
+
+    fn frames(intensity: Intensity) -> Vec<Vec<String>> {
+        [Self::create_frame_1(), Self::create_frame_2()]
+            .into_iter()
+            .map(|frame| match intensity {
+                Intensity::Normal => frame,
+                Intensity::Weak => Self::without_rays(frame),
+                Intensity::Strong => Self::with_long_rays(frame),
+            })
+            .collect()
+    }
+
+    // Blanks rather than removes, so the sun keeps its size on the arc
+    fn without_rays(mut frame: Vec<String>) -> Vec<String> {
+        let last = frame.len() - 1;
+        for row in [0, last] {
+            frame[row] = " ".repeat(frame[row].len());
+        }
+        let middle = frame.len() / 2;
+        frame[middle] = frame[middle]
+            .replace("~ --", "    ")
+            .replace("-- ~", "    ");
+        frame
+    }
 
-        Self { frames }
+    fn with_long_rays(frame: Vec<String>) -> Vec<String> {
+        let middle = frame.len() / 2;
+        frame
+            .into_iter()
+            .enumerate()
+            .map(|(row, line)| {
+                if row == middle {
+                    format!("~ {} ~", line)
+                } else if row == 0 || row == middle * 2 {
+                    format!("    .   {}   .", line.trim_start())
+                } else {
+                    format!("  {}", line)
+                }
+            })
+            .collect()
     }
 
     fn create_frame_1() -> Vec<String> {
@@ -51,7 +127,10 @@ impl Animation for SunnyAnimation {
     }
 
     fn get_color(&self) -> Color {
-        Color::Yellow
+        match self.intensity {
+            Intensity::Weak => self.dim_color,
+            _ => self.color,
+        }
     }
 }
 
@@ -1,32 +1,42 @@
+use super::AnimationSystem;
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
 use rand::prelude::*;
-use std::collections::VecDeque;
 use std::io;
 
-const MAX_BOLTS: usize = 10;
+const MAX_BRANCH_DEPTH: u8 = 3;
+const BRANCH_CHANCE: f32 = 0.18;
+// Share of strikes that are sheet lightning: a flash with no visible bolt
+const SHEET_LIGHTNING_CHANCE: f32 = 0.25;
+const FLICKER_CHANCE: f32 = 0.35;
 
 #[derive(Clone, Copy, PartialEq)]
 enum LightningState {
-    Forming,
     Strike,
-    Flash,
-    Fading,
+    Afterglow,
     Idle,
 }
 
+struct Segment {
+    x: u16,
+    y: u16,
+    character: char,
+    depth: u8,
+}
+
 struct LightningBolt {
-    segments: Vec<(u16, u16, char)>,
+    segments: Vec<Segment>,
     age: u8,
     max_age: u8,
 }
 
 pub struct ThunderstormSystem {
-    bolts: VecDeque<LightningBolt>,
+    bolt: Option<LightningBolt>,
     state: LightningState,
     timer: u16,
     terminal_width: u16,
     terminal_height: u16,
+    horizon_y: u16,
     flash_active: bool,
     next_strike_in: u16,
 }
@@ -34,76 +44,122 @@ pub struct ThunderstormSystem {
 impl ThunderstormSystem {
     pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
         Self {
-            bolts: VecDeque::with_capacity(MAX_BOLTS),
+            bolt: None,
             state: LightningState::Idle,
             timer: 0,
             terminal_width,
             terminal_height,
+            horizon_y: terminal_height.saturating_sub(5),
             flash_active: false,
             next_strike_in: 60 + (rand::random::<u16>() % 120), // Random start delay
         }
     }
 
-    fn generate_bolt(&mut self, rng: &mut impl Rng) {
-        let start_x = (rng.random::<u16>() % (self.terminal_width - 10)) + 5;
-        let mut segments = Vec::new();
-        let mut x = start_x as i16;
-        let mut y = 2; // Start below top bar
+    pub fn set_horizon(&mut self, horizon_y: u16) {
+        self.horizon_y = horizon_y;
+    }
 
-        segments.push((x as u16, y as u16, '+')); // Start point
+    fn generate_bolt(&mut self, rng: &mut dyn Rng) {
+        if self.terminal_width < 12 || self.horizon_y < 6 {
+            return;
+        }
 
-        while y < (self.terminal_height - 5) as i16 {
-            let direction = (rng.random::<i8>() % 3) - 1; // -1, 0, 1
-            x += direction as i16;
-            y += 1;
+        let start_x = (rng.random::<u16>() % (self.terminal_width - 10)) + 5;
+        let start_y = 2; // Start below top bar
+        let mut segments = vec![Segment {
+            x: start_x,
+            y: start_y,
+            character: '+',
+            depth: 0,
+        }];
+
+        self.grow_branch(
+            &mut segments,
+            (start_x as i16, start_y as i16),
+            self.horizon_y as i16,
+            0,
+            0,
+            rng,
+        );
+
+        self.bolt = Some(LightningBolt {
+            segments,
+            age: 0,
+            max_age: 2 + (rng.random::<u8>() % 2),
+        });
+    }
 
-            // Constrain x
-            if x < 2 {
-                x = 2;
-            }
-            if x >= (self.terminal_width - 2) as i16 {
-                x = (self.terminal_width - 3) as i16;
-            }
+    // Walks one channel of the bolt downwards, forking side branches that die out sooner
+    // the deeper they are. `bias` nudges a branch away from its parent.
+    fn grow_branch(
+        &self,
+        segments: &mut Vec<Segment>,
+        (mut x, mut y): (i16, i16),
+        bottom: i16,
+        depth: u8,
+        bias: i8,
+        rng: &mut dyn Rng,
+    ) {
+        let min_x = 1;
+        let max_x = self.terminal_width as i16 - 2;
+
+        while y < bottom {
+            let roll = rng.random::<f32>();
+            let direction: i8 = if bias != 0 && roll < 0.5 {
+                bias
+            } else if roll < 0.33 {
+                -1
+            } else if roll < 0.66 {
+                1
+            } else {
+                0
+            };
+            x = (x + direction as i16).clamp(min_x, max_x);
+            y += 1;
 
-            let char = match direction {
+            let character = match direction {
                 -1 => '/',
                 1 => '\\',
                 _ => '|',
             };
-
-            segments.push((x as u16, y as u16, char));
-
-            // Occasionally branch
-            if rng.random::<f32>() < 0.2 {
-                let branch_dir = -direction;
-                let mut bx = x + branch_dir as i16;
-                let mut by = y + 1;
-                for _ in 0..3 {
-                    if by < (self.terminal_height - 2) as i16 {
-                        segments.push((
-                            bx as u16,
-                            by as u16,
-                            if branch_dir < 0 { '/' } else { '\\' },
-                        ));
-                        bx += branch_dir as i16;
-                        by += 1;
-                    }
-                }
+            segments.push(Segment {
+                x: x as u16,
+                y: y as u16,
+                character,
+                depth,
+            });
+
+            if depth < MAX_BRANCH_DEPTH && rng.random::<f32>() < BRANCH_CHANCE / (depth + 1) as f32
+            {
+                let length = 3 + (rng.random::<u16>() % 6) as i16;
+                let branch_bias = if direction == 0 {
+                    if rng.random::<bool>() { 1 } else { -1 }
+                } else {
+                    -direction
+                };
+                self.grow_branch(
+                    segments,
+                    (x, y),
+                    (y + length).min(bottom),
+                    depth + 1,
+                    branch_bias,
+                    rng,
+                );
             }
         }
+    }
 
-        self.bolts.push_back(LightningBolt {
-            segments,
-            age: 0,
-            max_age: 10,
-        });
+    pub fn is_flashing(&self) -> bool {
+        self.flash_active
+    }
 
-        while self.bolts.len() > MAX_BOLTS {
-            self.bolts.pop_front();
-        }
+    pub fn horizon(&self) -> u16 {
+        self.horizon_y
     }
+}
 
-    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
+impl AnimationSystem for ThunderstormSystem {
+    fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut dyn Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
@@ -111,38 +167,33 @@ impl ThunderstormSystem {
             LightningState::Idle => {
                 self.flash_active = false;
                 if self.timer >= self.next_strike_in {
-                    self.state = LightningState::Forming;
+                    if rng.random::<f32>() >= SHEET_LIGHTNING_CHANCE {
+                        self.generate_bolt(rng);
+                    }
+                    self.state = LightningState::Strike;
                     self.timer = 0;
-                    self.generate_bolt(rng);
                 } else {
                     self.timer += 1;
                 }
             }
-            LightningState::Forming => {
-                self.state = LightningState::Strike;
-                self.timer = 0;
-            }
             LightningState::Strike => {
                 self.flash_active = true;
-                self.state = LightningState::Flash;
-                self.timer = 0;
-            }
-            LightningState::Flash => {
-                self.flash_active = false;
-                if self.timer > 2 {
-                    self.state = LightningState::Fading;
-                    self.timer = 0;
-                } else {
-                    self.timer += 1;
-                }
+                self.state = LightningState::Afterglow;
             }
-            LightningState::Fading => {
-                self.bolts.retain_mut(|bolt| {
+            LightningState::Afterglow => {
+                // A second, shorter flicker makes the strike feel less mechanical
+                self.flash_active = self.timer == 1 && rng.random::<f32>() < FLICKER_CHANCE;
+                self.timer += 1;
+
+                if let Some(bolt) = &mut self.bolt {
                     bolt.age += 1;
-                    bolt.age < bolt.max_age
-                });
+                    if bolt.age >= bolt.max_age {
+                        self.bolt = None;
+                    }
+                }
 
-                if self.bolts.is_empty() {
+                if self.bolt.is_none() && self.timer > 2 {
+                    self.flash_active = false;
                     self.state = LightningState::Idle;
                     self.timer = 0;
                     self.next_strike_in = 30 + (rng.random::<u16>() % 200);
@@ -151,20 +202,15 @@ impl ThunderstormSystem {
         }
     }
 
-    pub fn is_flashing(&self) -> bool {
-        self.flash_active
-    }
-
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
-        let color = if self.flash_active {
-            Color::White
-        } else {
-            Color::Yellow
-        };
-
-        for bolt in &self.bolts {
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        if let Some(bolt) = &self.bolt {
             for segment in &bolt.segments {
-                renderer.render_char(segment.0, segment.1, segment.2, color)?;
+                let color = if segment.depth == 0 || self.flash_active {
+                    Color::White
+                } else {
+                    Color::Yellow
+                };
+                renderer.render_char(segment.x, segment.y, segment.character, color)?;
             }
         }
         Ok(())
@@ -1,37 +1,67 @@
 use crate::animation::{
-    AnimationController, airplanes::AirplaneSystem, birds::BirdSystem, chimney::ChimneySmoke,
-    clouds::CloudSystem, fireflies::FireflySystem, fog::FogSystem, leaves::FallingLeaves,
-    moon::MoonSystem, raindrops::RaindropSystem, snow::SnowSystem, stars::StarSystem,
+    Animation, AnimationController, AnimationSystem, airplanes::AirplaneSystem,
+    aurora::AuroraSystem, birds::BirdSystem, chimney::ChimneySmoke, clouds::CloudSystem,
+    fireflies::FireflySystem, fog::FogSystem, hail::HailSystem, ice::IceGlaze,
+    leaves::FallingLeaves, moon::MoonSystem, rainbow::Rainbow, raindrops::RaindropSystem,
+    shimmer::HeatShimmer, snow::SnowSystem, snow_cover::SnowCover, stars::StarSystem,
     sunny::SunnyAnimation, thunderstorm::ThunderstormSystem,
 };
 use crate::app_state::AppState;
-use crate::render::TerminalRenderer;
-use crate::scene::WorldScene;
-use crate::scene::house::House;
-use crate::weather::{FogIntensity, RainIntensity, SnowIntensity, WeatherConditions};
+use crate::render::{SkyBody, SkyImage, TerminalRenderer, display_width};
+use crate::scene::{SceneAnchors, WorldScene, sky};
+use crate::theme::Theme;
+use crate::weather::astronomy::{self, Season};
+use crate::weather::units::ms_to_kmh;
+use crate::weather::{FogIntensity, RainIntensity, SnowIntensity, WeatherConditions, WeatherData};
 use crossterm::style::Color;
 use std::io;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-const FRAME_DELAY: Duration = Duration::from_millis(500);
+// The particle systems move a fixed amount per update, tuned for 30 updates a second;
+// they're stepped at that rate whatever the frame rate
+const SIMULATION_STEP: Duration = Duration::from_nanos(1_000_000_000 / 30);
+// At most a second of steps per frame, so a 1 FPS idle frame still keeps pace. After a
+// longer stall (e.g. a suspended laptop) the rest is skipped rather than replayed.
+const MAX_STEPS_PER_FRAME: u32 = 30;
+// Steps run before freezing a still frame, enough for rain and snow to fill the sky
+const SETTLE_STEPS: u32 = 150;
+pub const MIN_SPEED: f32 = 0.25;
+pub const MAX_SPEED: f32 = 4.0;
 
 pub struct AnimationManager {
     raindrop_system: RaindropSystem,
     snow_system: SnowSystem,
     fog_system: FogSystem,
     thunderstorm_system: ThunderstormSystem,
+    hail_system: HailSystem,
+    ice_glaze: IceGlaze,
+    snow_cover: SnowCover,
+    rainbow: Rainbow,
+    heat_shimmer: HeatShimmer,
     cloud_system: CloudSystem,
     bird_system: BirdSystem,
     airplane_system: AirplaneSystem,
     star_system: StarSystem,
+    aurora_system: AuroraSystem,
     moon_system: MoonSystem,
     chimney_smoke: ChimneySmoke,
     firefly_system: FireflySystem,
     falling_leaves: FallingLeaves,
     sunny_animation: SunnyAnimation,
     animation_controller: AnimationController,
-    last_frame_time: Instant,
+    // Animation time since the sun last changed frame
+    sun_clock: Duration,
+    frame_delay: Duration,
     show_leaves: bool,
+    theme: Theme,
+    paused: bool,
+    // Reduced motion: systems settle into one frame and stay there
+    still: bool,
+    settle_pending: bool,
+    speed: f32,
+    // Fractional updates carried over between frames, so slow motion still moves
+    update_budget: f32,
+    steps: u32,
 }
 
 impl AnimationManager {
@@ -41,21 +71,150 @@ impl AnimationManager {
             snow_system: SnowSystem::new(term_width, term_height, SnowIntensity::Light),
             fog_system: FogSystem::new(term_width, term_height, FogIntensity::Light),
             thunderstorm_system: ThunderstormSystem::new(term_width, term_height),
+            hail_system: HailSystem::new(term_width, term_height),
+            ice_glaze: IceGlaze::new(),
+            snow_cover: SnowCover::new(),
+            rainbow: Rainbow::new(term_width, term_height),
+            heat_shimmer: HeatShimmer::new(term_width, term_height),
             cloud_system: CloudSystem::new(term_width, term_height),
             bird_system: BirdSystem::new(term_width, term_height),
             airplane_system: AirplaneSystem::new(term_width, term_height),
             star_system: StarSystem::new(term_width, term_height),
+            aurora_system: AuroraSystem::new(term_width, term_height),
             moon_system: MoonSystem::new(term_width, term_height),
             chimney_smoke: ChimneySmoke::new(),
             firefly_system: FireflySystem::new(term_width, term_height),
             falling_leaves: FallingLeaves::new(term_width, term_height),
             sunny_animation: SunnyAnimation::new(),
             animation_controller: AnimationController::new(),
-            last_frame_time: Instant::now(),
+            sun_clock: Duration::ZERO,
+            frame_delay: Duration::from_millis(crate::config::DEFAULT_FRAME_DELAY_MS),
             show_leaves,
+            theme: Theme::default(),
+            paused: false,
+            still: false,
+            settle_pending: false,
+            speed: 1.0,
+            update_budget: 0.0,
+            steps: 1,
         }
     }
 
+    // Systems keep drawing their last state but stop moving
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.sunny_animation.set_colors(theme.sun, theme.sun_dim);
+        self.raindrop_system
+            .set_colors(theme.rain, theme.rain_bright);
+        self.snow_system.set_color(theme.snow);
+        self.raindrop_system.set_heavy(theme.high_contrast);
+        self.snow_system.set_heavy(theme.high_contrast);
+        let scenery = theme.scenery;
+        self.star_system.set_colors(scenery.star, scenery.star_dim);
+        self.fog_system.set_colors(scenery.fog, scenery.fog_dim);
+        self.firefly_system.set_colors(scenery.fireflies);
+        self.falling_leaves
+            .set_colors(scenery.leaves, scenery.blossoms);
+        self.bird_system.set_colors(scenery.birds);
+        self.airplane_system.set_colors(scenery.airplane);
+        self.theme = theme;
+    }
+
+    pub fn set_frame_delay(&mut self, delay: Duration) {
+        self.frame_delay = delay;
+    }
+
+    pub fn set_braille(&mut self, braille: bool) {
+        self.raindrop_system.set_braille(braille);
+        self.snow_system.set_braille(braille);
+    }
+
+    pub fn set_still(&mut self, still: bool) {
+        self.still = still;
+        self.settle_pending = still;
+    }
+
+    pub fn is_still(&self) -> bool {
+        self.still
+    }
+
+    // Rescales the particles already on screen to the new terminal size
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.raindrop_system.resize(width, height);
+        self.snow_system.resize(width, height);
+        self.fog_system.resize(width, height);
+        self.hail_system.resize(width, height);
+        self.cloud_system.resize(width, height);
+        self.bird_system.resize(width, height);
+        self.airplane_system.resize(width, height);
+        self.star_system.resize(width, height);
+        self.firefly_system.resize(width, height);
+        self.falling_leaves.resize(width, height);
+    }
+
+    // Recomposes the still frame, e.g. after the weather or the terminal size changed
+    pub fn settle(&mut self) {
+        self.settle_pending = self.still;
+    }
+
+    // Runs the settling steps in the next frame even while animating, for a screenshot
+    // that shouldn't show the sky still filling up
+    pub fn fast_forward(&mut self) {
+        self.settle_pending = true;
+    }
+
+    // Doubles or halves the speed, within MIN_SPEED..=MAX_SPEED
+    pub fn change_speed(&mut self, faster: bool) -> f32 {
+        let speed = if faster {
+            self.speed * 2.0
+        } else {
+            self.speed / 2.0
+        };
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self.speed
+    }
+
+    // Advances animation time by `dt` of real time, deciding how many fixed steps the
+    // systems take in the coming frame
+    pub fn tick(&mut self, dt: Duration) {
+        if std::mem::take(&mut self.settle_pending) {
+            self.steps = SETTLE_STEPS;
+            return;
+        }
+        if self.still {
+            self.steps = 0;
+            return;
+        }
+        if self.paused {
+            self.steps = 0;
+            return;
+        }
+        let scaled = dt.mul_f32(self.speed);
+        self.sun_clock += scaled;
+
+        self.update_budget += scaled.as_secs_f32() / SIMULATION_STEP.as_secs_f32();
+        self.steps = (self.update_budget.floor() as u32).min(MAX_STEPS_PER_FRAME);
+        self.update_budget = self.update_budget.fract();
+    }
+
+    // Everything the systems take from a reading
+    pub fn update_weather(&mut self, weather: &WeatherData) {
+        self.update_uv_index(weather.uv_index);
+        self.update_precipitation(weather.precipitation as f32);
+        self.update_temperature(weather.temperature);
+        self.update_rain_intensity(weather.condition.rain_intensity());
+        self.update_snow_intensity(weather.condition.snow_intensity());
+        self.update_fog_intensity(weather.condition.fog_intensity());
+        self.update_wind(weather.wind_speed as f32, weather.wind_direction as f32);
+    }
+
     pub fn update_rain_intensity(&mut self, intensity: RainIntensity) {
         self.raindrop_system.set_intensity(intensity);
     }
@@ -64,9 +223,27 @@ impl AnimationManager {
         self.snow_system.set_intensity(intensity);
     }
 
-    pub fn update_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+    // WeatherData stores wind in m/s; the particle systems are tuned in km/h
+    pub fn update_wind(&mut self, speed_ms: f32, direction_deg: f32) {
+        let speed_kmh = ms_to_kmh(speed_ms as f64) as f32;
         self.raindrop_system.set_wind(speed_kmh, direction_deg);
         self.snow_system.set_wind(speed_kmh, direction_deg);
+        self.hail_system.set_wind(speed_kmh, direction_deg);
+        self.chimney_smoke.set_wind(speed_kmh, direction_deg);
+        self.cloud_system.set_wind(speed_kmh, direction_deg);
+    }
+
+    pub fn update_precipitation(&mut self, precipitation_mm: f32) {
+        self.raindrop_system.set_precipitation(precipitation_mm);
+    }
+
+    pub fn update_uv_index(&mut self, uv_index: Option<f64>) {
+        self.sunny_animation.set_uv_index(uv_index);
+    }
+
+    pub fn update_temperature(&mut self, celsius: f64) {
+        self.snow_cover.set_temperature(celsius);
+        self.chimney_smoke.set_temperature(celsius);
     }
 
     pub fn update_fog_intensity(&mut self, intensity: FogIntensity) {
@@ -80,32 +257,28 @@ impl AnimationManager {
         state: &AppState,
         term_width: u16,
         term_height: u16,
-        mut rng: &mut impl rand::Rng,
+        rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
         // Calculate horizon_y early so it's available for all systems
         let ground_height = WorldScene::GROUND_HEIGHT;
         let horizon_y = term_height.saturating_sub(ground_height);
 
-        if !conditions.is_day {
-            self.star_system.update(term_width, term_height, &mut rng);
-            self.star_system.render(renderer)?;
-            self.moon_system.update(term_width, term_height);
-            self.moon_system.render(renderer)?;
-
-            if state.should_show_fireflies() {
-                self.firefly_system
-                    .update(term_width, term_height, horizon_y, &mut rng);
-                self.firefly_system.render(renderer)?;
-            }
-        }
-
-        if !conditions.is_raining
-            && !conditions.is_thunderstorm
-            && !conditions.is_snowing
-            && conditions.is_day
-        {
-            self.bird_system.update(term_width, term_height, &mut rng);
-            self.bird_system.render(renderer)?;
+        let progress = state
+            .current_weather
+            .as_ref()
+            .and_then(|w| astronomy::sun_progress(state.now(), w.sunrise?, w.sunset?));
+        let overcast = conditions.is_cloudy
+            || conditions.is_raining
+            || conditions.is_thunderstorm
+            || conditions.is_snowing
+            || conditions.is_foggy;
+        let (sky_top, sky_horizon) =
+            sky::sky_colors(&self.theme, progress, conditions.is_day, overcast);
+        // With graphics the sun and moon go into the sky image instead of the text layer
+        let graphics = renderer.has_graphics();
+        let mut sky_body = None;
+        if !renderer.has_sky_image() {
+            sky::render(renderer, (sky_top, sky_horizon), horizon_y)?;
         }
 
         if state.should_show_sun()
@@ -114,68 +287,231 @@ impl AnimationManager {
             && !conditions.is_snowing
         {
             let animation_y = if term_height > 20 { 3 } else { 2 };
-            self.animation_controller
-                .render_frame(renderer, &self.sunny_animation, animation_y)?;
+
+            match progress {
+                Some(progress) if graphics => {
+                    let (x, y) =
+                        self.sun_arc_position(progress, animation_y, term_width, horizon_y);
+                    sky_body = Some((x, y));
+                }
+                None if graphics => {
+                    let x = term_width.saturating_sub(self.sun_width()) / 2;
+                    sky_body = Some((x, animation_y));
+                }
+                Some(progress) => {
+                    let (x, y) =
+                        self.sun_arc_position(progress, animation_y, term_width, horizon_y);
+                    self.animation_controller.render_frame_at(
+                        renderer,
+                        &self.sunny_animation,
+                        x,
+                        y,
+                    )?;
+                }
+                None => {
+                    self.animation_controller.render_frame(
+                        renderer,
+                        &self.sunny_animation,
+                        animation_y,
+                    )?;
+                }
+            }
         }
 
-        if conditions.is_cloudy
-            || (!conditions.is_raining && !conditions.is_thunderstorm && !conditions.is_snowing)
-        {
-            let (is_clear, cloud_color) = if let Some(weather) = &state.current_weather {
-                match weather.condition {
-                    crate::weather::WeatherCondition::Clear => (true, Color::White),
-                    crate::weather::WeatherCondition::PartlyCloudy => (false, Color::Grey),
-                    _ => (false, Color::DarkGrey),
+        let moon_phase = state.current_weather.as_ref().and_then(|w| w.moon_phase);
+        if let Some(phase) = moon_phase {
+            self.moon_system.set_phase(phase);
+        }
+
+        if graphics && horizon_y > 0 {
+            let body = match sky_body {
+                Some((x, y)) => {
+                    let frame = self.sunny_animation.get_frame(0);
+                    let color = crate::theme::to_rgb(self.sunny_animation.get_color());
+                    Some((
+                        x + self.sun_width() / 2,
+                        y + frame.len() as u16 / 2,
+                        SkyBody::Sun { color },
+                    ))
+                }
+                None if !conditions.is_day => {
+                    let (x, y) = MoonSystem::center(term_width, term_height);
+                    let phase = moon_phase.unwrap_or(0.5);
+                    Some((x, y, SkyBody::Moon { phase }))
                 }
-            } else {
-                (false, Color::DarkGrey)
+                None => None,
+            };
+            renderer.set_sky_image(SkyImage {
+                top: sky_top,
+                horizon: sky_horizon,
+                columns: term_width,
+                rows: horizon_y,
+                body: body.filter(|&(_, y, _)| y < horizon_y),
+            });
+        }
+        self.firefly_system.set_horizon(horizon_y);
+
+        let (cloud_cover, cloud_color) = if let Some(weather) = &state.current_weather {
+            let color = match weather.condition {
+                crate::weather::WeatherCondition::Clear => self.theme.cloud,
+                crate::weather::WeatherCondition::PartlyCloudy => Color::Grey,
+                _ => Color::DarkGrey,
             };
+            (weather.cloud_cover as f32, color)
+        } else {
+            (0.0, Color::DarkGrey)
+        };
 
-            if conditions.is_cloudy || is_clear {
-                self.cloud_system.set_cloud_color(is_clear);
-                self.cloud_system
-                    .update(term_width, term_height, is_clear, cloud_color, &mut rng);
-                self.cloud_system.render(renderer)?;
+        // Moonlit clouds shouldn't glow brighter than the house windows
+        let cloud_color = if conditions.is_day {
+            cloud_color
+        } else if cloud_color == self.theme.cloud {
+            Color::Grey
+        } else {
+            Color::DarkGrey
+        };
+        self.cloud_system.set_sky(cloud_cover, cloud_color);
+
+        let precipitating =
+            conditions.is_raining || conditions.is_thunderstorm || conditions.is_snowing;
+        let clear_sky = !conditions.is_cloudy && !precipitating && !conditions.is_foggy;
+
+        let mut systems: Vec<&mut dyn AnimationSystem> = Vec::new();
+
+        if state.should_show_rainbow() {
+            self.rainbow.set_horizon(horizon_y);
+            systems.push(&mut self.rainbow);
+        }
+
+        if !conditions.is_day {
+            if clear_sky {
+                systems.push(&mut self.star_system);
+            }
+            if state.should_show_aurora() {
+                self.aurora_system.set_horizon(horizon_y);
+                systems.push(&mut self.aurora_system);
+            }
+            if !graphics {
+                systems.push(&mut self.moon_system);
+            }
+
+            if state.should_show_fireflies() {
+                systems.push(&mut self.firefly_system);
             }
         }
 
-        if !conditions.is_raining
-            && !conditions.is_thunderstorm
-            && !conditions.is_snowing
-            && !conditions.is_foggy
-        {
-            self.airplane_system
-                .update(term_width, term_height, &mut rng);
-            self.airplane_system.render(renderer)?;
+        // Bats only come out on calm, clear nights
+        if !precipitating && (conditions.is_day || clear_sky) {
+            self.bird_system.set_night(!conditions.is_day);
+            self.bird_system.set_season(conditions.season);
+            systems.push(&mut self.bird_system);
         }
 
-        Ok(())
+        if conditions.is_cloudy || !precipitating {
+            systems.push(&mut self.cloud_system);
+        }
+
+        if !precipitating && !conditions.is_foggy {
+            systems.push(&mut self.airplane_system);
+        }
+
+        if state.should_show_heat_shimmer() {
+            self.heat_shimmer.set_horizon(horizon_y);
+            systems.push(&mut self.heat_shimmer);
+        }
+
+        run_systems(systems, self.steps, renderer, term_width, term_height, rng)
+    }
+
+    fn sun_width(&self) -> u16 {
+        let frame = self.sunny_animation.get_frame(0);
+        frame.iter().map(|l| display_width(l)).max().unwrap_or(0) as u16
+    }
+
+    // Places the sun on a half-sine arc: rising at the left edge, peaking at `top` at solar
+    // noon and half-sunk into the horizon on the right at sunset.
+    fn sun_arc_position(
+        &self,
+        progress: f64,
+        top: u16,
+        term_width: u16,
+        horizon_y: u16,
+    ) -> (u16, u16) {
+        let frame_width = self.sun_width();
+        let frame_height = self.sunny_animation.get_frame(0).len() as u16;
+
+        let span_x = term_width.saturating_sub(frame_width) as f64;
+        let lowest = horizon_y.saturating_sub(frame_height / 2).max(top) as f64;
+        let height = (std::f64::consts::PI * progress).sin();
+
+        let x = (progress * span_x).round() as u16;
+        let y = (lowest - height * (lowest - top as f64)).round() as u16;
+        (x, y)
     }
 
     pub fn render_chimney_smoke(
         &mut self,
         renderer: &mut TerminalRenderer,
         conditions: &WeatherConditions,
+        anchors: &SceneAnchors,
         term_width: u16,
         term_height: u16,
-        mut rng: &mut impl rand::Rng,
+        rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
-        if conditions.is_raining || conditions.is_thunderstorm {
+        let Some((chimney_x, chimney_y)) = anchors.chimney else {
+            return Ok(());
+        };
+        if conditions.is_raining || conditions.is_thunderstorm || !self.chimney_smoke.is_visible() {
             return Ok(());
         }
-        let ground_height = WorldScene::GROUND_HEIGHT;
-        let horizon_y = term_height.saturating_sub(ground_height);
-        let house_width = House::WIDTH;
-        let house_height = House::HEIGHT;
-        let house_x = (term_width / 2).saturating_sub(house_width / 2);
-        let house_y = horizon_y.saturating_sub(house_height);
-        let chimney_x = house_x + House::CHIMNEY_X_OFFSET;
-        let chimney_y = house_y;
 
-        self.chimney_smoke.update(chimney_x, chimney_y, &mut rng);
-        self.chimney_smoke.render(renderer)?;
+        self.chimney_smoke.set_anchor(chimney_x, chimney_y);
+        run_systems(
+            vec![&mut self.chimney_smoke],
+            self.steps,
+            renderer,
+            term_width,
+            term_height,
+            rng,
+        )
+    }
 
-        Ok(())
+    // Effects that settle on the scene itself, drawn over the house and ground
+    pub fn render_surface_effects(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        conditions: &WeatherConditions,
+        anchors: &SceneAnchors,
+        term_width: u16,
+        term_height: u16,
+        rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        self.ice_glaze.set_freezing(conditions.is_freezing_rain);
+        self.snow_cover.set_snowing(conditions.is_snowing);
+
+        let mut systems: Vec<&mut dyn AnimationSystem> = Vec::new();
+
+        if conditions.is_freezing_rain || self.ice_glaze.is_visible() {
+            let mut surfaces = anchors.roofline.clone();
+            surfaces.extend((0..term_width).map(|x| (x, anchors.horizon_y)));
+            self.ice_glaze.set_surfaces(surfaces, rng);
+            systems.push(&mut self.ice_glaze);
+        }
+
+        if conditions.is_snowing || self.snow_cover.is_visible() {
+            // Roof snow sits on top of the tiles rather than replacing them
+            let bottom = term_height.saturating_sub(1);
+            let surfaces = anchors
+                .roofline
+                .iter()
+                .map(|&(x, y)| (x, y.saturating_sub(1)))
+                .chain((0..term_width).map(|x| (x, bottom)))
+                .collect();
+            self.snow_cover.set_surfaces(surfaces);
+            systems.push(&mut self.snow_cover);
+        }
+
+        run_systems(systems, self.steps, renderer, term_width, term_height, rng)
     }
 
     pub fn render_foreground(
@@ -184,55 +520,87 @@ impl AnimationManager {
         conditions: &WeatherConditions,
         term_width: u16,
         term_height: u16,
-        mut rng: &mut impl rand::Rng,
+        rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
-        if conditions.is_thunderstorm {
-            self.raindrop_system
-                .update(term_width, term_height, &mut rng);
-            self.raindrop_system.render(renderer)?;
+        let mut systems: Vec<&mut dyn AnimationSystem> = Vec::new();
 
-            self.thunderstorm_system
-                .update(term_width, term_height, &mut rng);
-            self.thunderstorm_system.render(renderer)?;
-
-            if self.thunderstorm_system.is_flashing() {
-                renderer.flash_screen()?;
+        if conditions.is_thunderstorm {
+            let horizon_y = term_height.saturating_sub(WorldScene::GROUND_HEIGHT);
+            self.thunderstorm_system.set_horizon(horizon_y);
+            systems.push(&mut self.raindrop_system);
+            if conditions.is_hailing {
+                self.hail_system.set_horizon(horizon_y);
+                systems.push(&mut self.hail_system);
             }
+            systems.push(&mut self.thunderstorm_system);
         } else if conditions.is_raining {
-            self.raindrop_system
-                .update(term_width, term_height, &mut rng);
-            self.raindrop_system.render(renderer)?;
+            systems.push(&mut self.raindrop_system);
         } else if conditions.is_snowing {
-            self.snow_system.update(term_width, term_height, &mut rng);
-            self.snow_system.render(renderer)?;
+            self.snow_system.set_blizzard(conditions.is_blizzard);
+            systems.push(&mut self.snow_system);
         }
 
-        if conditions.is_foggy {
-            self.fog_system.update(term_width, term_height, &mut rng);
-            self.fog_system.render(renderer)?;
+        // Blowing snow shrouds the house like thick fog
+        if conditions.is_blizzard {
+            self.fog_system.set_intensity(FogIntensity::Heavy);
+        }
+        if conditions.is_foggy || conditions.is_blizzard {
+            systems.push(&mut self.fog_system);
         }
 
-        if self.show_leaves
+        // Leaves fall in autumn (or always with --leaves), blossoms in spring
+        let falling =
+            self.show_leaves || matches!(conditions.season, Season::Autumn | Season::Spring);
+        self.falling_leaves
+            .set_blossoms(!self.show_leaves && conditions.season == Season::Spring);
+        if falling
             && !conditions.is_raining
             && !conditions.is_thunderstorm
             && !conditions.is_snowing
         {
-            self.falling_leaves
-                .update(term_width, term_height, &mut rng);
-            self.falling_leaves.render(renderer)?;
+            systems.push(&mut self.falling_leaves);
+        }
+
+        run_systems(systems, self.steps, renderer, term_width, term_height, rng)?;
+
+        if conditions.is_thunderstorm
+            && self.thunderstorm_system.is_flashing()
+            && !self.paused
+            && !self.still
+        {
+            renderer.flash_region(0, self.thunderstorm_system.horizon())?;
         }
 
         Ok(())
     }
 
     pub fn update_sunny_animation(&mut self, conditions: &WeatherConditions) {
-        if !conditions.is_raining
+        if !self.paused
+            && !self.still
+            && !conditions.is_raining
             && !conditions.is_thunderstorm
             && !conditions.is_snowing
-            && self.last_frame_time.elapsed() >= FRAME_DELAY
+            && self.sun_clock >= self.frame_delay
         {
             self.animation_controller.next_frame(&self.sunny_animation);
-            self.last_frame_time = Instant::now();
+            self.sun_clock = Duration::ZERO;
+        }
+    }
+}
+
+fn run_systems(
+    systems: Vec<&mut dyn AnimationSystem>,
+    steps: u32,
+    renderer: &mut TerminalRenderer,
+    term_width: u16,
+    term_height: u16,
+    rng: &mut dyn rand::Rng,
+) -> io::Result<()> {
+    for system in systems {
+        for _ in 0..steps {
+            system.update(term_width, term_height, rng);
         }
+        system.render(renderer)?;
     }
+    Ok(())
 }
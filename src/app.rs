@@ -1,21 +1,66 @@
 use crate::animation_manager::AnimationManager;
 use crate::app_state::AppState;
-use crate::config::Config;
+use crate::cache;
+use crate::config::{Config, ConfigWatcher};
 use crate::error::WeatherError;
-use crate::render::TerminalRenderer;
-use crate::scene::WorldScene;
+use crate::metrics::Metrics;
+use crate::notify::Notifier;
+use crate::publish::WeatherPublisher;
+use crate::render::{Layer, Region, TerminalRenderer, display_width};
+use crate::scene::{Midground, WorldScene, builtin};
+use crate::simulation::Simulation;
+use crate::theme::Theme;
+use crate::ui::{
+    AirQualityChip, AlertTicker, BigTemperature, DetailPanel, Gauges, HelpOverlay,
+    PrecipitationPanel, TemperatureSparkline, WindCompass,
+};
+use crate::weather::alerts::{NwsAlerts, WeatherAlert};
+use crate::weather::history::{self, HistoricalHour};
+use crate::weather::pressure::{self, PressureReading};
+use crate::weather::provider::{Capabilities, WeatherProvider, create_providers};
+use crate::weather::types::TemperatureUnit;
 use crate::weather::{
-    OpenMeteoProvider, WeatherClient, WeatherCondition, WeatherData, WeatherLocation,
+    OpenMeteoProvider, WeatherClient, WeatherCondition, WeatherData, WeatherLocation, WeatherUnits,
+    astronomy,
 };
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use std::fs;
 use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
-const INPUT_POLL_FPS: u64 = 30;
-const FRAME_DURATION: Duration = Duration::from_millis(1000 / INPUT_POLL_FPS);
+// A frozen scene only needs redrawing for the status line and key presses; the renderer
+// writes nothing for frames that didn't change
+const PAUSED_FRAME_DURATION: Duration = Duration::from_millis(250);
+
+const FETCH_REQUEST_BUFFER: usize = 8;
+
+// How long --demo shows each condition
+const DEMO_STEP: Duration = Duration::from_secs(10);
+
+// How long each hour of a replayed day lasts, so the whole day takes two minutes
+const REPLAY_HOUR: Duration = Duration::from_secs(5);
+
+enum FetchRequest {
+    Refresh,
+    SetLocation(WeatherLocation),
+}
+
+// The fetch task reports when a request goes out as well as when it completes, so the
+// render loop can show a spinner without ever awaiting the network itself.
+enum FetchEvent {
+    Started(WeatherLocation),
+    Finished(Box<FetchResult>),
+    Alerts(WeatherLocation, Vec<WeatherAlert>),
+}
+
+struct FetchResult {
+    location: WeatherLocation,
+    result: Result<WeatherData, WeatherError>,
+    retry_in: Option<Duration>,
+}
 
 fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
     use chrono::{Local, Timelike};
@@ -44,31 +89,191 @@ fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
         } else {
             0.0
         },
-        wind_speed: rng.random_range(5.0..15.0),
+        wind_speed: rng.random_range(1.5..4.0),
         wind_direction: rng.random_range(0.0..360.0),
         cloud_cover: rng.random_range(20.0..80.0),
         pressure: rng.random_range(1000.0..1020.0),
         visibility: Some(10000.0),
+        uv_index: None,
         is_day,
-        moon_phase: Some(0.5),
+        moon_phase: Some(astronomy::current_moon_phase()),
         timestamp: now.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        source: None,
+        fetched_at: None,
+        air_quality: None,
+    }
+}
+
+// Where the shown weather comes from
+pub enum WeatherMode {
+    Live,
+    // --simulate
+    Simulated(Simulation),
+    // --date, the past day's hours in order
+    Replay(Vec<HistoricalHour>),
+    // --demo, every condition by day and then by night
+    Demo,
+}
+
+// Walks through WeatherCondition::ALL by day, then again by night, then starts over
+#[derive(Default)]
+struct Demo {
+    step: usize,
+    elapsed: Duration,
+}
+
+impl Demo {
+    const STEPS: usize = WeatherCondition::ALL.len() * 2;
+
+    fn condition(&self) -> WeatherCondition {
+        WeatherCondition::ALL[self.step % WeatherCondition::ALL.len()]
+    }
+
+    fn is_day(&self) -> bool {
+        self.step < WeatherCondition::ALL.len()
+    }
+
+    // Moves on by `dt`; true when that reached the next condition
+    fn advance(&mut self, dt: Duration) -> bool {
+        self.elapsed += dt;
+        let mut changed = false;
+        while self.elapsed >= DEMO_STEP {
+            self.elapsed -= DEMO_STEP;
+            self.step = (self.step + 1) % Self::STEPS;
+            changed = true;
+        }
+        changed
+    }
+}
+
+// A past day played back as a timelapse, starting over after its last hour
+struct Replay {
+    hours: Vec<HistoricalHour>,
+    index: usize,
+    // How far into the current hour, in playback time
+    elapsed: Duration,
+}
+
+impl Replay {
+    fn new(hours: Vec<HistoricalHour>) -> Self {
+        Self {
+            hours,
+            index: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    fn weather(&self) -> &WeatherData {
+        &self.hours[self.index].weather
+    }
+
+    // Moves playback on by `dt`; true when that reached another hour
+    fn advance(&mut self, dt: Duration) -> bool {
+        self.elapsed += dt;
+        let mut changed = false;
+        while self.elapsed >= REPLAY_HOUR {
+            self.elapsed -= REPLAY_HOUR;
+            self.index = (self.index + 1) % self.hours.len();
+            changed = true;
+        }
+        changed
+    }
+
+    // The moment of the day being shown, moving smoothly through each hour
+    fn clock(&self) -> chrono::DateTime<chrono::Utc> {
+        let fraction = self.elapsed.as_secs_f64() / REPLAY_HOUR.as_secs_f64();
+        self.hours[self.index].time + chrono::TimeDelta::seconds((fraction * 3600.0) as i64)
     }
 }
 
+// Stands in for the scene until the terminal is big enough again
+fn render_too_small(renderer: &mut TerminalRenderer, message: &str) -> io::Result<()> {
+    let (width, height) = renderer.get_size();
+    let lines = crate::ui::text::wrap(message, (width as usize).saturating_sub(2).max(1));
+    let top = height.saturating_sub(lines.len() as u16) / 2;
+    renderer.set_layer(Layer::Overlay);
+    for (idx, line) in lines.iter().enumerate() {
+        renderer.render_centered_colored(
+            std::slice::from_ref(line),
+            top + idx as u16,
+            crossterm::style::Color::Yellow,
+        )?;
+    }
+    Ok(())
+}
+
+// --screenshot, taken once the weather is in and then ending the app
+struct PendingScreenshot {
+    path: PathBuf,
+    png: bool,
+    // The animations have been run forward, so the next frame is the one to save
+    settled: bool,
+}
+
 pub struct App {
     state: AppState,
     animations: AnimationManager,
     scene: WorldScene,
-    weather_receiver: mpsc::Receiver<Result<WeatherData, WeatherError>>,
+    weather_receiver: mpsc::Receiver<FetchEvent>,
+    fetch_requests: Option<mpsc::Sender<FetchRequest>>,
+    locations: Vec<(Option<String>, WeatherLocation)>,
+    location_index: usize,
+    is_simulated: bool,
+    // The first fetch has finished, for better or worse
+    weather_arrived: bool,
+    screenshot_colors: bool,
+    pending_screenshot: Option<PendingScreenshot>,
+    // --duration: how long to run before quitting on its own
+    run_for: Option<Duration>,
+    replay: Option<Replay>,
+    demo: Option<Demo>,
+    help: HelpOverlay,
+    big_temperature: BigTemperature,
+    gauges: Gauges,
+    compass: WindCompass,
+    sparkline: TemperatureSparkline,
+    alerts: AlertTicker,
+    air_quality_chip: AirQualityChip,
+    precipitation_panel: PrecipitationPanel,
+    details: DetailPanel,
+    // Where units chosen with 'u' are saved, if `remember_units` is on
+    units_file: Option<PathBuf>,
+    frame_duration: Duration,
+    // Low-power frame rate used once idle or, if enabled, while unfocused
+    idle_frame_duration: Duration,
+    idle_after: Option<Duration>,
+    throttle_unfocused: bool,
+    focused: bool,
+    last_input: Instant,
     hide_hud: bool,
+    hud_color: crossterm::style::Color,
+    // Readings for `pressure_location`, reloaded from the cache when the location changes
+    pressure_history: Vec<PressureReading>,
+    pressure_location: Option<WeatherLocation>,
+    scenes: Vec<String>,
+    scene_index: usize,
+    config_watcher: Option<ConfigWatcher>,
+    // Name, credit line and capabilities of each provider in the fallback chain, the
+    // selected one first
+    sources: Vec<(&'static str, &'static str, Capabilities)>,
+    // --metrics-port, told of every fetch for the shown location
+    metrics: Option<Arc<Metrics>>,
+    // [publish], sent the home location's weather after each fetch
+    publisher: Option<WeatherPublisher>,
+    // [notifications], about the home location like `publisher`
+    notifier: Option<Notifier>,
 }
 
 impl App {
     pub fn new(
         config: &Config,
-        simulate_condition: Option<String>,
-        simulate_night: bool,
+        mode: WeatherMode,
         show_leaves: bool,
+        midground: Midground,
         term_width: u16,
         term_height: u16,
     ) -> Self {
@@ -78,67 +283,154 @@ impl App {
             elevation: None,
         };
 
+        let mut locations = vec![(None, location)];
+        if !config.locations.is_empty() {
+            locations[0].0 = Some("Home".to_string());
+            locations.extend(config.locations.iter().map(|saved| {
+                (
+                    Some(saved.name.clone()),
+                    WeatherLocation {
+                        latitude: saved.latitude,
+                        longitude: saved.longitude,
+                        elevation: None,
+                    },
+                )
+            }));
+        }
+
         let mut state = AppState::new(location, config.location.hide, config.units);
+        state.set_location(location, locations[0].0.clone());
         let mut animations = AnimationManager::new(term_width, term_height, show_leaves);
-        let scene = WorldScene::new(term_width, term_height);
-
-        let (tx, rx) = mpsc::channel(1);
-
-        if let Some(ref condition_str) = simulate_condition {
-            let simulated_condition =
-                condition_str
-                    .parse::<WeatherCondition>()
-                    .unwrap_or_else(|e| {
-                        eprintln!("{}", e);
-                        WeatherCondition::Clear
-                    });
-
-            let weather = WeatherData {
-                condition: simulated_condition,
-                temperature: 20.0,
-                apparent_temperature: 19.0,
-                humidity: 65.0,
-                precipitation: if simulated_condition.is_raining() {
-                    2.5
-                } else {
-                    0.0
-                },
-                wind_speed: if simulated_condition.is_thunderstorm() {
-                    45.0
-                } else {
-                    10.0
-                },
-                wind_direction: 225.0,
-                cloud_cover: 50.0,
-                pressure: 1013.0,
-                visibility: Some(10000.0),
-                is_day: !simulate_night,
-                moon_phase: Some(0.5),
-                timestamp: "simulated".to_string(),
-            };
+        animations.set_still(config.animation.reduce_motion);
+        animations.set_braille(config.animation.braille);
+        animations.set_frame_delay(config.frame_delay());
+        // Already checked when the config was loaded
+        let theme = Theme::from_config(&config.theme).unwrap_or_default();
+        animations.set_theme(theme);
+        let mut scene = WorldScene::new(term_width, term_height, midground);
+        scene.set_scenery(theme.scenery);
 
-            let rain_intensity = weather.condition.rain_intensity();
-            let snow_intensity = weather.condition.snow_intensity();
+        let scene_name = config.scene.as_deref().unwrap_or("house");
+        let mut scenes: Vec<String> = builtin::SCENE_NAMES.iter().map(|s| s.to_string()).collect();
+        if !scenes.iter().any(|s| s == scene_name) {
+            scenes.push(scene_name.to_string());
+        }
+        let scene_index = scenes.iter().position(|s| s == scene_name).unwrap_or(0);
 
-            let wind_speed = weather.wind_speed;
-            let wind_direction = weather.wind_direction;
+        let mut alerts = AlertTicker::default();
+        alerts.set_theme(theme);
 
-            state.update_weather(weather);
-            animations.update_rain_intensity(rain_intensity);
-            animations.update_snow_intensity(snow_intensity);
-            animations.update_wind(wind_speed as f32, wind_direction as f32);
+        let (tx, rx) = mpsc::channel(FETCH_REQUEST_BUFFER);
+        let mut fetch_requests = None;
+        // Already checked when the config was loaded
+        let mut providers = create_providers(&config.provider);
+        if providers.is_empty() {
+            providers.push(Arc::new(OpenMeteoProvider::new()));
+        }
+        let mut sources: Vec<_> = providers
+            .iter()
+            .map(|provider| {
+                (
+                    provider.name(),
+                    provider.attribution(),
+                    provider.capabilities(),
+                )
+            })
+            .collect();
+        let demo = matches!(mode, WeatherMode::Demo).then(Demo::default);
+        let (simulation, replay) = match mode {
+            WeatherMode::Live | WeatherMode::Demo => (None, None),
+            WeatherMode::Simulated(simulation) => (Some(simulation), None),
+            WeatherMode::Replay(hours) => (None, Some(hours)),
+        };
+        let replay = replay.filter(|hours| !hours.is_empty()).map(Replay::new);
+        if replay.is_some() {
+            let open_meteo = OpenMeteoProvider::new();
+            sources = vec![(
+                history::SOURCE,
+                open_meteo.attribution(),
+                Capabilities::default(),
+            )];
+        }
+        let provider_name = if simulation.is_some() || demo.is_some() {
+            "Simulated"
         } else {
-            let provider = Arc::new(OpenMeteoProvider::new());
-            let weather_client = WeatherClient::new(provider, REFRESH_INTERVAL);
+            sources[0].0
+        };
+        let help = HelpOverlay::new(config, provider_name, config.refresh_interval());
+
+        if let Some(ref simulation) = simulation {
+            state.clock = simulation.clock();
+            alerts.set_alerts(simulation.alerts());
+            let weather = simulation.weather(&config.units);
+            animations.update_weather(&weather);
+            state.update_weather(weather);
+        } else if replay.is_some() {
+            // Replays and demos are shown from the first frame by `run`
+            state.is_replay = true;
+        } else if demo.is_none() {
+            let refresh_interval = config.refresh_interval();
+            let weather_client = WeatherClient::new(providers, refresh_interval);
+            let nws_alerts = NwsAlerts::new();
             let units = config.units;
+            let (request_tx, mut request_rx) = mpsc::channel(FETCH_REQUEST_BUFFER);
+            fetch_requests = Some(request_tx);
 
             tokio::spawn(async move {
+                let mut location = location;
+                let mut force_refresh = false;
+                let mut failures = 0;
                 loop {
-                    let result = weather_client.get_current_weather(&location, &units).await;
-                    if tx.send(result).await.is_err() {
+                    if tx.send(FetchEvent::Started(location)).await.is_err() {
+                        break;
+                    }
+
+                    let result = if force_refresh {
+                        weather_client.refresh_weather(&location, &units).await
+                    } else {
+                        weather_client.get_current_weather(&location, &units).await
+                    };
+
+                    let retry_in = if result.is_ok() {
+                        failures = 0;
+                        None
+                    } else {
+                        failures += 1;
+                        Some(weather_client.retry_delay(failures))
+                    };
+                    let fetch_result = FetchResult {
+                        location,
+                        result,
+                        retry_in,
+                    };
+                    if tx
+                        .send(FetchEvent::Finished(Box::new(fetch_result)))
+                        .await
+                        .is_err()
+                    {
                         break;
                     }
-                    tokio::time::sleep(REFRESH_INTERVAL).await;
+
+                    // A failed alerts fetch keeps showing whatever was last known
+                    if NwsAlerts::covers(&location)
+                        && let Ok(alerts) = nws_alerts.fetch(&location).await
+                        && tx.send(FetchEvent::Alerts(location, alerts)).await.is_err()
+                    {
+                        break;
+                    }
+
+                    force_refresh = false;
+                    tokio::select! {
+                        _ = tokio::time::sleep(retry_in.unwrap_or(refresh_interval)) => {}
+                        request = request_rx.recv() => match request {
+                            Some(FetchRequest::Refresh) => force_refresh = true,
+                            Some(FetchRequest::SetLocation(new_location)) => {
+                                location = new_location;
+                                failures = 0;
+                            }
+                            None => break,
+                        },
+                    }
                 }
             });
         }
@@ -148,124 +440,288 @@ impl App {
             animations,
             scene,
             weather_receiver: rx,
+            fetch_requests,
+            locations,
+            location_index: 0,
+            is_simulated: simulation.is_some() || replay.is_some() || demo.is_some(),
+            weather_arrived: false,
+            screenshot_colors: config.screenshot_colors,
+            pending_screenshot: None,
+            run_for: None,
+            replay,
+            demo,
+            help,
+            big_temperature: BigTemperature::new(config.units.temperature, config.big_temperature),
+            gauges: Gauges::new(config.units.temperature, config.gauges),
+            compass: WindCompass::new(config.units.wind_speed, config.compass),
+            sparkline: TemperatureSparkline::new(config.units.temperature, config.sparkline),
+            precipitation_panel: PrecipitationPanel::default(),
+            details: DetailPanel::new(config.units),
+            units_file: config.path.clone().filter(|_| config.remember_units),
+            frame_duration: Duration::from_secs(1) / config.animation.fps.max(1),
+            idle_frame_duration: Duration::from_secs(1) / config.animation.idle_fps.max(1),
+            idle_after: (config.animation.idle_minutes > 0)
+                .then(|| Duration::from_secs(config.animation.idle_minutes * 60)),
+            throttle_unfocused: config.animation.throttle_unfocused,
+            focused: true,
+            last_input: Instant::now(),
+            alerts,
+            air_quality_chip: AirQualityChip::new(config.aqi_scale),
             hide_hud: config.hide_hud,
+            hud_color: theme.text,
+            pressure_history: Vec::new(),
+            pressure_location: None,
+            scenes,
+            scene_index,
+            config_watcher: config.path.clone().map(ConfigWatcher::new),
+            sources,
+            metrics: None,
+            publisher: WeatherPublisher::start(&config.publish),
+            notifier: Notifier::new(&config.notifications),
         }
     }
 
+    // Saves the first frame with the weather in to `path`, as text or a PNG image, and
+    // then quits
+    pub fn exit_after_screenshot(&mut self, path: PathBuf, png: bool) {
+        self.pending_screenshot = Some(PendingScreenshot {
+            path,
+            png,
+            settled: false,
+        });
+    }
+
+    // Quits by itself once `duration` is up, as if 'q' was pressed
+    pub fn exit_after(&mut self, duration: Duration) {
+        self.run_for = Some(duration);
+    }
+
+    pub fn export_metrics(&mut self, metrics: Arc<Metrics>) {
+        // A simulation's weather is there from the start and never fetched
+        if let Some(ref weather) = self.state.current_weather {
+            metrics.set_weather(weather);
+        }
+        self.metrics = Some(metrics);
+    }
+
+    // Whichever provider the shown weather came from
+    fn source(&self) -> (&'static str, &'static str, Capabilities) {
+        let source = self
+            .state
+            .current_weather
+            .as_ref()
+            .and_then(|w| w.source.as_deref());
+        *self
+            .sources
+            .iter()
+            .find(|(name, _, _)| Some(*name) == source)
+            .unwrap_or(&self.sources[0])
+    }
+
+    // Credit for whichever provider the shown weather came from
+    fn attribution(&self) -> &'static str {
+        self.source().1
+    }
+
     pub async fn run(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         let mut rng = rand::rng();
+
+        if !self.is_simulated {
+            self.load_stale_weather().await;
+        }
+        if let Some(ref replay) = self.replay {
+            self.state.clock = Some(replay.clock());
+            self.apply_weather(replay.weather().clone());
+        }
+        if self.demo.is_some() {
+            self.show_demo_step();
+        }
+
+        let mut last_frame = Instant::now();
+        let deadline = self.run_for.map(|duration| last_frame + duration);
         loop {
-            if let Ok(result) = self.weather_receiver.try_recv() {
-                match result {
+            // Input is polled synchronously below, so give the caller a chance each frame
+            // to notice a shutdown signal
+            tokio::task::yield_now().await;
+
+            let frame_start = Instant::now();
+            if deadline.is_some_and(|deadline| frame_start >= deadline) {
+                break;
+            }
+            let dt = frame_start - last_frame;
+            last_frame = frame_start;
+
+            while let Ok(event) = self.weather_receiver.try_recv() {
+                let fetch = match event {
+                    FetchEvent::Started(location) => {
+                        if location == self.state.location {
+                            self.state.set_fetching(true);
+                        }
+                        continue;
+                    }
+                    FetchEvent::Finished(fetch) if fetch.location == self.state.location => *fetch,
+                    FetchEvent::Finished(_) => continue,
+                    FetchEvent::Alerts(location, alerts) => {
+                        if let Some(ref mut notifier) = self.notifier
+                            && location == self.locations[0].1
+                        {
+                            notifier.alerts(&alerts);
+                        }
+                        if location == self.state.location {
+                            self.alerts.set_alerts(alerts);
+                        }
+                        continue;
+                    }
+                };
+
+                self.state.set_fetching(false);
+                self.weather_arrived = true;
+                if let Some(ref metrics) = self.metrics {
+                    match fetch.result {
+                        Ok(ref weather) => metrics.record_success(weather),
+                        Err(_) => metrics.record_failure(),
+                    }
+                }
+                if let Ok(ref weather) = fetch.result
+                    && fetch.location == self.locations[0].1
+                {
+                    if let Some(ref publisher) = self.publisher {
+                        publisher.publish(weather);
+                    }
+                    if let Some(ref mut notifier) = self.notifier {
+                        notifier.weather(weather);
+                    }
+                }
+                match fetch.result {
                     Ok(weather) => {
-                        let rain_intensity = weather.condition.rain_intensity();
-                        let snow_intensity = weather.condition.snow_intensity();
-                        let fog_intensity = weather.condition.fog_intensity();
-                        let wind_speed = weather.wind_speed;
-                        let wind_direction = weather.wind_direction;
-
-                        self.state.update_weather(weather);
-                        self.animations.update_rain_intensity(rain_intensity);
-                        self.animations.update_snow_intensity(snow_intensity);
-                        self.animations.update_fog_intensity(fog_intensity);
-                        self.animations
-                            .update_wind(wind_speed as f32, wind_direction as f32);
+                        self.record_pressure(&weather).await;
+                        self.apply_weather(weather);
                     }
                     Err(error) => {
-                        let _error_msg = match &error {
-                            WeatherError::Network(net_err) => net_err.user_friendly_message(),
-                            _ => format!("Failed to fetch weather: {}", error),
-                        };
+                        let _error_msg = error.user_friendly_message();
+
+                        if self.state.current_weather.is_none() {
+                            self.load_stale_weather().await;
+                        }
 
                         if self.state.current_weather.is_none() {
                             let offline_weather = generate_offline_weather(&mut rng);
-                            let rain_intensity = offline_weather.condition.rain_intensity();
-                            let snow_intensity = offline_weather.condition.snow_intensity();
-                            let fog_intensity = offline_weather.condition.fog_intensity();
-                            let wind_speed = offline_weather.wind_speed;
-                            let wind_direction = offline_weather.wind_direction;
-
-                            self.state.update_weather(offline_weather);
-                            self.state.set_offline_mode(true);
-                            self.animations.update_rain_intensity(rain_intensity);
-                            self.animations.update_snow_intensity(snow_intensity);
-                            self.animations.update_fog_intensity(fog_intensity);
-                            self.animations
-                                .update_wind(wind_speed as f32, wind_direction as f32);
-                        } else {
-                            self.state.set_offline_mode(true);
+                            self.apply_weather(offline_weather);
                         }
+                        self.state.set_offline_mode(true);
+                        self.state.set_retry_in(fetch.retry_in);
                     }
                 }
             }
 
-            renderer.clear()?;
-
-            let (term_width, term_height) = renderer.get_size();
-
-            self.animations.render_background(
-                renderer,
-                &self.state.weather_conditions,
-                &self.state,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.scene
-                .render(renderer, &self.state.weather_conditions)?;
-
-            self.animations.render_chimney_smoke(
-                renderer,
-                &self.state.weather_conditions,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.animations.render_foreground(
-                renderer,
-                &self.state.weather_conditions,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.state.update_loading_animation();
-            self.state.update_cached_info();
-
-            if !self.hide_hud {
-                renderer.render_line_colored(
-                    2,
-                    1,
-                    &self.state.cached_weather_info,
-                    crossterm::style::Color::Cyan,
-                )?;
+            self.check_config(renderer);
+            self.advance_replay(dt);
+            self.advance_demo(dt);
+            if let Some(ref mut pending) = self.pending_screenshot
+                && !pending.settled
+                && (self.weather_arrived || self.is_simulated)
+            {
+                self.animations.fast_forward();
+                pending.settled = true;
             }
 
-            let attribution = "Weather data by Open-Meteo.com";
-            let attribution_x = if term_width > attribution.len() as u16 {
-                term_width - attribution.len() as u16 - 2
-            } else {
-                0
-            };
-            let attribution_y = if term_height > 0 { term_height - 1 } else { 0 };
-            renderer.render_line_colored(
-                attribution_x,
-                attribution_y,
-                attribution,
-                crossterm::style::Color::DarkGrey,
-            )?;
-
+            renderer.clear()?;
+            self.draw_frame(renderer, dt, &mut rng)?;
             renderer.flush()?;
 
-            if event::poll(FRAME_DURATION)? {
-                match event::read()? {
+            if let Some(pending) = self.pending_screenshot.take_if(|pending| pending.settled) {
+                let contents = if pending.png {
+                    renderer.screenshot_png()
+                } else {
+                    renderer.screenshot(self.screenshot_colors).into_bytes()
+                };
+                fs::write(&pending.path, contents)?;
+                break;
+            }
+
+            // Wait out the rest of the frame for input; a key press ends it early
+            let mut frame_duration = self.frame_duration;
+            if self.animations.is_paused() || self.animations.is_still() {
+                frame_duration = frame_duration.max(PAUSED_FRAME_DURATION);
+            }
+            if self.is_low_power() {
+                frame_duration = frame_duration.max(self.idle_frame_duration);
+            }
+            let remaining =
+                (frame_start + frame_duration).saturating_duration_since(Instant::now());
+            if event::poll(remaining)? {
+                let event = event::read()?;
+                if matches!(event, Event::Key(ref key) if key.kind == KeyEventKind::Press) {
+                    self.last_input = Instant::now();
+                    self.focused = true;
+                }
+                match event {
                     Event::Resize(width, height) => {
+                        self.last_input = Instant::now();
                         renderer.manual_resize(width, height)?;
+                        self.animations.resize(width, height);
+                        self.animations.settle();
+                    }
+                    Event::FocusGained => {
+                        self.focused = true;
+                        self.last_input = Instant::now();
+                    }
+                    Event::FocusLost => self.focused = false,
+                    Event::Key(key_event) if key_event.kind != KeyEventKind::Press => {}
+                    Event::Key(key_event)
+                        if self.help.is_visible()
+                            && !(key_event.code == KeyCode::Char('c')
+                                && key_event.modifiers.contains(KeyModifiers::CONTROL)) =>
+                    {
+                        self.help.hide();
+                    }
+                    Event::Key(key_event)
+                        if self.alerts.is_expanded()
+                            && !(key_event.code == KeyCode::Char('c')
+                                && key_event.modifiers.contains(KeyModifiers::CONTROL)) =>
+                    {
+                        self.alerts.hide();
+                    }
+                    Event::Key(key_event)
+                        if self.details.is_visible()
+                            && !(key_event.code == KeyCode::Char('c')
+                                && key_event.modifiers.contains(KeyModifiers::CONTROL)) =>
+                    {
+                        self.details.hide();
                     }
                     Event::Key(key_event) => match key_event.code {
                         KeyCode::Char('q') | KeyCode::Char('Q') => break,
+                        KeyCode::Char('?') => self.help.toggle(),
+                        KeyCode::Char('r') | KeyCode::Char('R') => self.request_refresh(),
+                        KeyCode::Char('n') | KeyCode::Char('N') => self.cycle_location(1),
+                        KeyCode::Char('s') | KeyCode::Char('S') => self.save_screenshot(renderer),
+                        KeyCode::Char('c') | KeyCode::Char('C')
+                            if !key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            self.cycle_scene()
+                        }
+                        KeyCode::Char('t') | KeyCode::Char('T') => self.big_temperature.toggle(),
+                        KeyCode::Char('a') | KeyCode::Char('A') => self.alerts.toggle_expanded(),
+                        KeyCode::Char('d') | KeyCode::Char('D') => self.details.toggle(),
+                        KeyCode::Char('u') | KeyCode::Char('U') => self.toggle_units(),
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            let speed = self.animations.change_speed(true);
+                            self.state.set_speed(speed);
+                        }
+                        KeyCode::Char('-') | KeyCode::Char('_') => {
+                            let speed = self.animations.change_speed(false);
+                            self.state.set_speed(speed);
+                        }
+                        KeyCode::Char(' ') => {
+                            self.animations.toggle_paused();
+                            self.state.set_paused(self.animations.is_paused());
+                        }
+                        KeyCode::Char('f') | KeyCode::Char('F') => {
+                            self.precipitation_panel.toggle()
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('P') => {
+                            self.cycle_location(self.locations.len() - 1)
+                        }
                         KeyCode::Char('c')
                             if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
                         {
@@ -286,4 +742,404 @@ impl App {
 
         Ok(())
     }
+
+    // Everything drawn in a frame, or only the message while the terminal is too small
+    fn draw_frame(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        dt: Duration,
+        rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        if let Some(too_small) = renderer.too_small() {
+            return render_too_small(renderer, &too_small.user_friendly_message());
+        }
+
+        self.animations.tick(dt);
+
+        let (term_width, term_height) = renderer.get_size();
+
+        self.state.update_loading_animation();
+        self.state.update_retry_countdown();
+        self.state.update_toast();
+        self.state.update_cached_info();
+
+        // Keep rain and lightning off the text lines, with a cell of margin
+        let attribution = self.attribution();
+        let attribution_width = display_width(attribution) as u16;
+        let attribution_x = if term_width > attribution_width {
+            term_width - attribution_width - 2
+        } else {
+            0
+        };
+        let attribution_y = if term_height > 0 { term_height - 1 } else { 0 };
+        renderer.protect(Region {
+            x: attribution_x.saturating_sub(1),
+            y: attribution_y,
+            width: attribution_width + 2,
+            height: 1,
+        });
+        if !self.hide_hud {
+            renderer.protect(Region {
+                x: 1,
+                y: 1,
+                width: display_width(&self.state.cached_weather_info) as u16 + 2,
+                height: 1,
+            });
+        }
+        self.alerts.drop_expired(chrono::Utc::now());
+        if self.alerts.has_alerts() {
+            renderer.protect(Region {
+                x: 0,
+                y: 0,
+                width: term_width,
+                height: 1,
+            });
+        }
+
+        renderer.set_layer(Layer::Sky);
+        self.animations.render_background(
+            renderer,
+            &self.state.weather_conditions,
+            &self.state,
+            term_width,
+            term_height,
+            rng,
+        )?;
+
+        renderer.set_layer(Layer::Scene);
+        self.scene
+            .render(renderer, &self.state.weather_conditions)?;
+
+        self.animations.render_surface_effects(
+            renderer,
+            &self.state.weather_conditions,
+            self.scene.anchors(),
+            term_width,
+            term_height,
+            rng,
+        )?;
+
+        self.animations.render_chimney_smoke(
+            renderer,
+            &self.state.weather_conditions,
+            self.scene.anchors(),
+            term_width,
+            term_height,
+            rng,
+        )?;
+
+        renderer.set_layer(Layer::Weather);
+        self.animations.render_foreground(
+            renderer,
+            &self.state.weather_conditions,
+            term_width,
+            term_height,
+            rng,
+        )?;
+
+        renderer.set_layer(Layer::Hud);
+        if !self.hide_hud {
+            renderer.render_line_colored(2, 1, &self.state.cached_weather_info, self.hud_color)?;
+            self.air_quality_chip.render(
+                renderer,
+                self.state
+                    .current_weather
+                    .as_ref()
+                    .and_then(|w| w.air_quality.as_ref()),
+            )?;
+        }
+
+        renderer.render_line_colored(
+            attribution_x,
+            attribution_y,
+            attribution,
+            crossterm::style::Color::DarkGrey,
+        )?;
+
+        self.big_temperature.render(
+            renderer,
+            self.state.current_weather.as_ref().map(|w| w.temperature),
+        )?;
+
+        self.gauges.render(
+            renderer,
+            self.state
+                .current_weather
+                .as_ref()
+                .map(|w| (w.temperature, w.humidity)),
+        )?;
+
+        self.compass.render(
+            renderer,
+            self.state
+                .current_weather
+                .as_ref()
+                .map(|w| (w.wind_speed, w.wind_direction)),
+        )?;
+
+        let (hourly, minutely) = self
+            .state
+            .current_weather
+            .as_ref()
+            .map_or((&[][..], &[][..]), |w| (&w.hourly[..], &w.minutely[..]));
+        self.sparkline
+            .render(renderer, hourly, attribution_x.saturating_sub(2))?;
+        // Named when it's the provider, not the wait, that leaves the chart empty
+        let (name, _, capabilities) = self.source();
+        let without_hourly =
+            (self.state.current_weather.is_some() && !capabilities.hourly).then_some(name);
+        self.precipitation_panel
+            .render(renderer, hourly, minutely, without_hourly)?;
+
+        if !self.animations.is_still() {
+            self.alerts.update();
+        }
+        renderer.set_layer(Layer::Overlay);
+        self.alerts.render(renderer)?;
+
+        self.details
+            .render(renderer, self.state.current_weather.as_ref())?;
+
+        self.help.render(renderer)?;
+        Ok(())
+    }
+
+    // Keeps pace with the animation, so pausing or slowing it holds the day back too
+    fn advance_replay(&mut self, dt: Duration) {
+        let Some(ref mut replay) = self.replay else {
+            return;
+        };
+        if self.animations.is_paused() {
+            return;
+        }
+        let next_hour = replay.advance(dt.mul_f32(self.state.speed));
+        self.state.clock = Some(replay.clock());
+        if next_hour {
+            let weather = replay.weather().clone();
+            self.apply_weather(weather);
+        }
+    }
+
+    fn advance_demo(&mut self, dt: Duration) {
+        let Some(ref mut demo) = self.demo else {
+            return;
+        };
+        if !self.animations.is_paused() && demo.advance(dt.mul_f32(self.state.speed)) {
+            self.show_demo_step();
+        }
+    }
+
+    fn show_demo_step(&mut self) {
+        let Some(ref demo) = self.demo else {
+            return;
+        };
+        let (condition, is_day, step) = (demo.condition(), demo.is_day(), demo.step);
+        let simulation = Simulation::new(condition, is_day);
+        self.alerts.set_alerts(simulation.alerts());
+        self.apply_weather(simulation.weather(&self.state.units));
+        self.state.show_toast(format!(
+            "Demo {}/{}: {} by {}",
+            step + 1,
+            Demo::STEPS,
+            condition.description(),
+            if is_day { "day" } else { "night" }
+        ));
+    }
+
+    async fn load_stale_weather(&mut self) {
+        let location = self.state.location;
+        if let Some((weather, fetched_at)) =
+            cache::load_stale_weather(location.latitude, location.longitude).await
+        {
+            self.apply_weather(weather);
+            self.state.set_stale(fetched_at);
+        }
+    }
+
+    // Stamped with when the weather was fetched, so the same weather out of a cache
+    // isn't recorded again as a reading of its own
+    async fn record_pressure(&mut self, weather: &WeatherData) {
+        let Some(fetched_at) = weather.fetched_at else {
+            return;
+        };
+        let location = self.state.location;
+        if self.pressure_location != Some(location) {
+            self.pressure_history =
+                cache::load_pressure_history(location.latitude, location.longitude).await;
+            self.pressure_location = Some(location);
+        }
+
+        let reading = PressureReading {
+            timestamp: fetched_at.timestamp().max(0) as u64,
+            hpa: weather.pressure,
+        };
+        if !self.pressure_history.contains(&reading) {
+            pressure::record(&mut self.pressure_history, reading);
+            cache::save_pressure_history(
+                &self.pressure_history,
+                location.latitude,
+                location.longitude,
+            );
+        }
+        self.state
+            .set_pressure_trend(pressure::trend(&self.pressure_history));
+    }
+
+    fn apply_weather(&mut self, weather: WeatherData) {
+        self.animations.settle();
+        self.animations.update_weather(&weather);
+        self.state.update_weather(weather);
+    }
+
+    fn cycle_location(&mut self, step: usize) {
+        if self.locations.len() < 2 {
+            return;
+        }
+
+        self.show_location((self.location_index + step) % self.locations.len());
+    }
+
+    fn show_location(&mut self, index: usize) {
+        let (name, location) = self.locations[index].clone();
+        if let Some(ref requests) = self.fetch_requests
+            && requests
+                .try_send(FetchRequest::SetLocation(location))
+                .is_ok()
+        {
+            self.location_index = index;
+            self.state.set_location(location, name);
+            self.alerts.set_alerts(Vec::new());
+            self.state.set_refreshing(true);
+        }
+    }
+
+    // A custom scene that fails to reload (e.g. its file was removed) is skipped
+    fn cycle_scene(&mut self) {
+        for step in 1..self.scenes.len() {
+            let index = (self.scene_index + step) % self.scenes.len();
+            if let Ok(midground) = Midground::from_name(&self.scenes[index]) {
+                self.scene_index = index;
+                self.scene.set_midground(midground);
+                self.animations.settle();
+                return;
+            }
+        }
+    }
+
+    // Into the working directory, named for the time it was taken
+    fn save_screenshot(&mut self, renderer: &TerminalRenderer) {
+        let path = chrono::Local::now()
+            .format("weathr-%Y%m%d-%H%M%S.txt")
+            .to_string();
+        let message = match fs::write(&path, renderer.screenshot(self.screenshot_colors)) {
+            Ok(()) => format!("Saved {}", path),
+            Err(e) => format!("Screenshot not saved: {}", e),
+        };
+        self.state.show_toast(message);
+    }
+
+    fn is_low_power(&self) -> bool {
+        self.idle_after
+            .is_some_and(|idle| self.last_input.elapsed() >= idle)
+            || (self.throttle_unfocused && !self.focused)
+    }
+
+    fn toggle_units(&mut self) {
+        let units = if self.state.units.temperature == TemperatureUnit::Celsius {
+            WeatherUnits::imperial()
+        } else {
+            WeatherUnits::metric()
+        };
+        self.set_units(units);
+
+        // Best effort, like the cache; the toggle still applies for this session
+        if let Some(ref path) = self.units_file
+            && Config::save_units(path, &units).is_ok()
+            && let Some(ref mut watcher) = self.config_watcher
+        {
+            watcher.saved();
+        }
+    }
+
+    // Readings are kept in metric, so only the display needs to change
+    fn set_units(&mut self, units: WeatherUnits) {
+        self.state.units = units;
+        self.state.weather_info_needs_update = true;
+        self.big_temperature.set_unit(units.temperature);
+        self.gauges.set_unit(units.temperature);
+        self.compass.set_unit(units.wind_speed);
+        self.sparkline.set_unit(units.temperature);
+        self.details.set_units(units);
+        self.help.set_units(&units);
+    }
+
+    fn set_theme(&mut self, theme: Theme, renderer: &mut TerminalRenderer) {
+        self.animations.set_theme(theme);
+        self.scene.set_scenery(theme.scenery);
+        self.alerts.set_theme(theme);
+        self.hud_color = theme.text;
+        renderer.set_bold(theme.high_contrast);
+        self.animations.settle();
+    }
+
+    fn check_config(&mut self, renderer: &mut TerminalRenderer) {
+        let Some(change) = self.config_watcher.as_mut().and_then(|w| w.poll()) else {
+            return;
+        };
+        match change {
+            Ok((previous, config)) => {
+                self.apply_config(&previous, &config, renderer);
+                self.state.show_toast("Config reloaded".to_string());
+            }
+            Err(error) => self
+                .state
+                .show_toast(format!("Config not reloaded: {}", error)),
+        }
+    }
+
+    // Only what changed in the file is applied, so command line overrides of everything
+    // else stay in place
+    fn apply_config(
+        &mut self,
+        previous: &Config,
+        config: &Config,
+        renderer: &mut TerminalRenderer,
+    ) {
+        if config.theme != previous.theme {
+            // The watcher only hands over configs that passed validation
+            let theme = Theme::from_config(&config.theme).unwrap_or_default();
+            self.set_theme(theme, renderer);
+        }
+
+        if config.units != previous.units {
+            self.set_units(config.units);
+        }
+
+        if config.location.hide != previous.location.hide {
+            self.state.hide_location = config.location.hide;
+            self.state.weather_info_needs_update = true;
+        }
+
+        // An auto-detected location came from the network, not the file
+        let moved = config.location.latitude != previous.location.latitude
+            || config.location.longitude != previous.location.longitude;
+        if moved && !config.location.auto {
+            self.locations[0].1 = WeatherLocation {
+                latitude: config.location.latitude,
+                longitude: config.location.longitude,
+                elevation: None,
+            };
+            if self.location_index == 0 {
+                self.show_location(0);
+            }
+        }
+    }
+
+    fn request_refresh(&mut self) {
+        if let Some(ref requests) = self.fetch_requests
+            && requests.try_send(FetchRequest::Refresh).is_ok()
+        {
+            self.state.set_refreshing(true);
+        }
+    }
 }
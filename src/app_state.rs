@@ -1,19 +1,45 @@
+use crate::weather::pressure::PressureTrend;
 use crate::weather::{
-    WeatherCondition, WeatherConditions, WeatherData, WeatherLocation, WeatherUnits,
-    format_precipitation, format_temperature, format_wind_speed,
+    WeatherCondition, WeatherConditions, WeatherData, WeatherLocation, WeatherUnits, astronomy,
+    format_precipitation, format_pressure, format_temperature, format_wind_speed,
 };
-use std::time::Instant;
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+const AURORA_MIN_LATITUDE: f64 = 60.0;
+const FIREFLY_MIN_CELSIUS: f64 = 18.0;
+const HEAT_SHIMMER_MIN_CELSIUS: f64 = 32.0;
+// Gale force, about 50 km/h
+const BLIZZARD_MIN_WIND_MS: f64 = 13.9;
+const RAINBOW_DURATION: Duration = Duration::from_secs(180);
+const TOAST_DURATION: Duration = Duration::from_secs(3);
 
 pub struct AppState {
     pub current_weather: Option<WeatherData>,
     pub is_offline: bool,
+    pub is_refreshing: bool,
+    pub is_fetching: bool,
+    pub stale_since: Option<u64>,
+    pub retry_at: Option<Instant>,
+    rainbow_until: Option<Instant>,
+    retry_secs_shown: Option<u64>,
+    // A short message at the start of the status line, until the Instant passes
+    toast: Option<(String, Instant)>,
     pub weather_conditions: WeatherConditions,
     pub loading_state: LoadingState,
     pub cached_weather_info: String,
     pub weather_info_needs_update: bool,
     pub location: WeatherLocation,
+    pub location_name: Option<String>,
     pub hide_location: bool,
     pub units: WeatherUnits,
+    pub pressure_trend: Option<PressureTrend>,
+    pub is_paused: bool,
+    pub speed: f32,
+    // Stands in for the system clock, for the sun's place and the season
+    pub clock: Option<DateTime<Utc>>,
+    // Replaying a past day, with the hour shown in the status line
+    pub is_replay: bool,
 }
 
 impl AppState {
@@ -21,32 +47,150 @@ impl AppState {
         Self {
             current_weather: None,
             is_offline: false,
+            is_refreshing: false,
+            is_fetching: false,
+            stale_since: None,
+            retry_at: None,
+            rainbow_until: None,
+            retry_secs_shown: None,
+            toast: None,
             weather_conditions: WeatherConditions::default(),
             loading_state: LoadingState::new(),
             cached_weather_info: String::new(),
             weather_info_needs_update: true,
             location,
+            location_name: None,
             hide_location,
             units,
+            pressure_trend: None,
+            is_paused: false,
+            speed: 1.0,
+            clock: None,
+            is_replay: false,
         }
     }
 
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.unwrap_or_else(Utc::now)
+    }
+
     pub fn update_weather(&mut self, weather: WeatherData) {
+        let clearing = matches!(
+            weather.condition,
+            WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+        );
+        let was_raining = self
+            .current_weather
+            .as_ref()
+            .is_some_and(|w| w.condition.is_raining());
+        if !clearing {
+            self.rainbow_until = None;
+        } else if was_raining && weather.is_day {
+            self.rainbow_until = Some(Instant::now() + RAINBOW_DURATION);
+        }
+
         self.weather_conditions.is_thunderstorm = weather.condition.is_thunderstorm();
         self.weather_conditions.is_snowing = weather.condition.is_snowing();
+        self.weather_conditions.is_hailing =
+            weather.condition == WeatherCondition::ThunderstormHail;
+        self.weather_conditions.is_freezing_rain =
+            weather.condition == WeatherCondition::FreezingRain;
+        self.weather_conditions.is_blizzard =
+            weather.condition.is_snowing() && weather.wind_speed >= BLIZZARD_MIN_WIND_MS;
         self.weather_conditions.is_raining =
             weather.condition.is_raining() && !self.weather_conditions.is_thunderstorm;
         self.weather_conditions.is_cloudy = weather.condition.is_cloudy();
         self.weather_conditions.is_foggy = weather.condition.is_foggy();
         self.weather_conditions.is_day = weather.is_day;
+        self.weather_conditions.season =
+            astronomy::season(
+            self.now().with_timezone(&chrono::Local).date_naive(),
+            self.location.latitude,
+        );
 
         self.current_weather = Some(weather);
         self.is_offline = false;
+        self.is_refreshing = false;
+        self.stale_since = None;
+        self.retry_at = None;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_pressure_trend(&mut self, trend: Option<PressureTrend>) {
+        self.pressure_trend = trend;
         self.weather_info_needs_update = true;
     }
 
     pub fn set_offline_mode(&mut self, offline: bool) {
         self.is_offline = offline;
+        self.is_refreshing = false;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_location(&mut self, location: WeatherLocation, name: Option<String>) {
+        self.location = location;
+        self.location_name = name;
+        self.pressure_trend = None;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_stale(&mut self, fetched_at: u64) {
+        self.stale_since = Some(fetched_at);
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_retry_in(&mut self, delay: Option<Duration>) {
+        self.retry_at = delay.map(|d| Instant::now() + d);
+        self.weather_info_needs_update = true;
+    }
+
+    fn retry_seconds_remaining(&self) -> Option<u64> {
+        self.retry_at
+            .map(|at| at.saturating_duration_since(Instant::now()).as_secs())
+    }
+
+    // Redraw the status line once per second while a retry countdown is showing
+    pub fn update_retry_countdown(&mut self) {
+        let remaining = self.retry_seconds_remaining();
+        if remaining != self.retry_secs_shown {
+            self.retry_secs_shown = remaining;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    pub fn show_toast(&mut self, message: String) {
+        self.toast = Some((message, Instant::now() + TOAST_DURATION));
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn update_toast(&mut self) {
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|(_, until)| Instant::now() >= *until)
+        {
+            self.toast = None;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    pub fn set_fetching(&mut self, fetching: bool) {
+        self.is_fetching = fetching;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.is_paused = paused;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_refreshing(&mut self, refreshing: bool) {
+        self.is_refreshing = refreshing;
         self.weather_info_needs_update = true;
     }
 
@@ -59,22 +203,7 @@ impl AppState {
 
     pub fn get_condition_text(&self) -> &str {
         if let Some(ref weather) = self.current_weather {
-            match weather.condition {
-                WeatherCondition::Clear => "Clear",
-                WeatherCondition::Cloudy => "Cloudy",
-                WeatherCondition::PartlyCloudy => "Partly Cloudy",
-                WeatherCondition::Overcast => "Overcast",
-                WeatherCondition::Fog => "Fog",
-                WeatherCondition::Drizzle => "Drizzle",
-                WeatherCondition::FreezingRain => "Freezing Rain",
-                WeatherCondition::Rain => "Rain",
-                WeatherCondition::Snow => "Snow",
-                WeatherCondition::SnowGrains => "Snow Grains",
-                WeatherCondition::RainShowers => "Rain Showers",
-                WeatherCondition::SnowShowers => "Snow Showers",
-                WeatherCondition::Thunderstorm => "Thunderstorm",
-                WeatherCondition::ThunderstormHail => "Thunderstorm with Hail",
-            }
+            weather.condition.description()
         } else {
             "Loading"
         }
@@ -86,7 +215,10 @@ impl AppState {
         }
 
         let location_str = if self.hide_location {
-            String::new()
+            match self.location_name {
+                Some(ref name) => format!(" | Location: {}", name),
+                None => String::new(),
+            }
         } else {
             let (lat_value, lat_dir) = if self.location.latitude >= 0.0 {
                 (self.location.latitude, "N")
@@ -98,10 +230,16 @@ impl AppState {
             } else {
                 (-self.location.longitude, "W")
             };
-            format!(
-                " | Location: {:.2}°{}, {:.2}°{}",
-                lat_value, lat_dir, lon_value, lon_dir
-            )
+            let coordinates = format!("{:.2}°{}, {:.2}°{}", lat_value, lat_dir, lon_value, lon_dir);
+            match self.location_name {
+                Some(ref name) => format!(" | Location: {} ({})", name, coordinates),
+                None => format!(" | Location: {}", coordinates),
+            }
+        };
+
+        let toast = match self.toast {
+            Some((ref message, _)) => format!("{} | ", message),
+            None => String::new(),
         };
 
         self.cached_weather_info = if let Some(ref weather) = self.current_weather {
@@ -109,12 +247,63 @@ impl AppState {
             let (wind, wind_unit) = format_wind_speed(weather.wind_speed, self.units.wind_speed);
             let (precip, precip_unit) =
                 format_precipitation(weather.precipitation, self.units.precipitation);
+            let (pressure, pressure_unit) = format_pressure(weather.pressure, self.units.pressure);
+
+            let offline_indicator = match (self.is_offline, self.retry_seconds_remaining()) {
+                (true, Some(secs)) if !self.is_refreshing => {
+                    format!("OFFLINE (retrying in {}s) | ", secs)
+                }
+                (true, _) => "OFFLINE | ".to_string(),
+                (false, _) => String::new(),
+            };
+            let stale_indicator = match self.stale_since {
+                Some(fetched_at) => format!(
+                    "STALE (fetched {}) | ",
+                    format_age(crate::cache::seconds_since(fetched_at))
+                ),
+                None => String::new(),
+            };
+            let refresh_indicator = match (self.is_refreshing, self.is_fetching) {
+                (true, true) => format!("{} Refreshing... | ", self.loading_state.current_char()),
+                (true, false) => "Refreshing... | ".to_string(),
+                (false, true) => format!("{} ", self.loading_state.current_char()),
+                (false, false) => String::new(),
+            };
+            let replay_indicator = if self.is_replay {
+                format!("REPLAY {} | ", weather.timestamp.replace('T', " "))
+            } else {
+                String::new()
+            };
+            let playback_indicator = if self.is_paused {
+                "PAUSED | ".to_string()
+            } else if self.speed != 1.0 {
+                format!("SPEED {}x | ", self.speed)
+            } else {
+                String::new()
+            };
 
-            let offline_indicator = if self.is_offline { "OFFLINE | " } else { "" };
+            let trend = match self.pressure_trend {
+                Some(trend) => format!(" {}", trend.arrow()),
+                None => String::new(),
+            };
+            let uv = match weather.uv_index {
+                Some(uv) if weather.is_day => format!(" | UV: {:.0}", uv),
+                _ => String::new(),
+            };
+            // Which provider in the fallback chain answered
+            let source = match weather.source {
+                Some(ref name) => format!(" | Source: {}", name),
+                None => String::new(),
+            };
 
             format!(
-                "{}Weather: {} | Temp: {:.1}{} | Wind: {:.1}{} | Precip: {:.1}{}{} | Press 'q' to quit",
+                "{}{}{}{}{}{}Weather: {} | Temp: {:.1}{} | Wind: {:.1}{} | Precip: {:.1}{} | Pressure: {:.*}{}{}{}{}{} | Press '?' for help, 'q' to quit",
+                toast,
+                replay_indicator,
+                playback_indicator,
+                refresh_indicator,
                 offline_indicator,
+                stale_indicator,
                 self.get_condition_text(),
                 temp,
                 temp_unit,
@@ -122,10 +311,20 @@ impl AppState {
                 wind_unit,
                 precip,
                 precip_unit,
-                location_str
+                self.units.pressure.decimals(),
+                pressure,
+                pressure_unit,
+                trend,
+                uv,
+                location_str,
+                source
             )
         } else {
-            format!("Weather: Loading... {}", self.loading_state.current_char())
+            format!(
+                "{}Weather: Loading... {}",
+                toast,
+                self.loading_state.current_char()
+            )
         };
 
         self.weather_info_needs_update = false;
@@ -146,13 +345,43 @@ impl AppState {
         }
     }
 
+    // Shown for a few minutes after rain gives way to sunshine
+    pub fn should_show_rainbow(&self) -> bool {
+        self.weather_conditions.is_day
+            && self
+                .rainbow_until
+                .is_some_and(|until| Instant::now() < until)
+    }
+
+    pub fn should_show_heat_shimmer(&self) -> bool {
+        self.current_weather.as_ref().is_some_and(|weather| {
+            weather.temperature > HEAT_SHIMMER_MIN_CELSIUS
+                && matches!(
+                    weather.condition,
+                    WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+                )
+        })
+    }
+
+    // Aurorae are only a regular sight near the polar circles
+    pub fn should_show_aurora(&self) -> bool {
+        if self.weather_conditions.is_day || self.location.latitude.abs() < AURORA_MIN_LATITUDE {
+            return false;
+        }
+
+        matches!(
+            self.current_weather.as_ref().map(|w| w.condition),
+            Some(WeatherCondition::Clear)
+        )
+    }
+
     pub fn should_show_fireflies(&self) -> bool {
         if self.weather_conditions.is_day {
             return false;
         }
 
         if let Some(ref weather) = self.current_weather {
-            let is_warm = weather.temperature > 15.0;
+            let is_warm = weather.temperature > FIREFLY_MIN_CELSIUS;
             let is_clear_night = matches!(
                 weather.condition,
                 WeatherCondition::Clear | WeatherCondition::PartlyCloudy
@@ -168,6 +397,15 @@ impl AppState {
     }
 }
 
+pub fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
 pub struct LoadingState {
     pub frame: usize,
     pub last_update: Instant,
@@ -175,6 +413,7 @@ pub struct LoadingState {
 }
 
 impl LoadingState {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             frame: 0,
@@ -200,7 +439,7 @@ impl LoadingState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::weather::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+    use crate::weather::types::{PrecipitationUnit, PressureUnit, TemperatureUnit, WindSpeedUnit};
 
     fn create_app_state(lat: f64, lon: f64) -> AppState {
         let location = WeatherLocation {
@@ -212,6 +451,7 @@ mod tests {
             temperature: TemperatureUnit::Celsius,
             wind_speed: WindSpeedUnit::Kmh,
             precipitation: PrecipitationUnit::Mm,
+            pressure: PressureUnit::Hpa,
         };
         let mut app = AppState::new(location, false, units);
 
@@ -226,15 +466,284 @@ mod tests {
             cloud_cover: 0.0,
             pressure: 1013.0,
             visibility: Some(10.0),
+            uv_index: None,
             is_day: true,
             moon_phase: Some(0.5),
             timestamp: "2024-01-01T12:00:00Z".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            source: None,
+            fetched_at: None,
+            air_quality: None,
         };
         app.update_weather(weather);
 
         app
     }
 
+    #[test]
+    fn test_refreshing_indicator_cleared_by_update() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_refreshing(true);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("Refreshing..."));
+
+        let weather = app.current_weather.clone().unwrap();
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Refreshing..."));
+    }
+
+    #[test]
+    fn test_location_name_in_hud() {
+        let mut app = create_app_state(35.6762, 139.6503);
+        app.set_location(app.location, Some("Tokyo".to_string()));
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("Location: Tokyo (35.68°N, 139.65°E)")
+        );
+
+        app.hide_location = true;
+        app.weather_info_needs_update = true;
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Location: Tokyo"));
+        assert!(!app.cached_weather_info.contains("35.68"));
+    }
+
+    #[test]
+    fn test_pressure_trend_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Pressure: 1013hPa |"));
+
+        app.set_pressure_trend(Some(PressureTrend::Falling));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Pressure: 1013hPa ↓"));
+
+        app.set_location(app.location, None);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains('↓'));
+
+        app.units = WeatherUnits::imperial();
+        app.weather_info_needs_update = true;
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Pressure: 29.91inHg |"));
+    }
+
+    #[test]
+    fn test_source_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Source:"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.source = Some("wttr.in".to_string());
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("E | Source: wttr.in | Press '?'")
+        );
+    }
+
+    #[test]
+    fn test_replay_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.timestamp = "2021-07-14T14:00".to_string();
+        app.update_weather(weather);
+        app.is_replay = true;
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .starts_with("REPLAY 2021-07-14 14:00 | Weather:")
+        );
+    }
+
+    #[test]
+    fn test_paused_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_paused(true);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("PAUSED | Weather:"));
+
+        app.set_paused(false);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("PAUSED"));
+
+        app.set_speed(0.25);
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .starts_with("SPEED 0.25x | Weather:")
+        );
+    }
+
+    #[test]
+    fn test_toast_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.show_toast("Config reloaded".to_string());
+        app.update_toast();
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .starts_with("Config reloaded | Weather:")
+        );
+
+        app.toast = Some(("Config reloaded".to_string(), Instant::now()));
+        app.update_toast();
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("Weather:"));
+    }
+
+    #[test]
+    fn test_retry_countdown_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_offline_mode(true);
+        app.set_retry_in(Some(Duration::from_secs(30)));
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("OFFLINE (retrying in 29s)")
+                || app
+                    .cached_weather_info
+                    .contains("OFFLINE (retrying in 30s)")
+        );
+
+        let weather = app.current_weather.clone().unwrap();
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("retrying"));
+    }
+
+    #[test]
+    fn test_fetch_spinner_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_fetching(true);
+        app.update_cached_info();
+        let spinner = app.loading_state.current_char();
+        assert!(
+            app.cached_weather_info
+                .starts_with(&format!("{} Weather:", spinner))
+        );
+
+        app.set_fetching(false);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("Weather:"));
+    }
+
+    #[test]
+    fn test_rainbow_after_rain_clears() {
+        let mut app = create_app_state(0.0, 0.0);
+        assert!(!app.should_show_rainbow());
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.condition = WeatherCondition::Rain;
+        app.update_weather(weather.clone());
+        assert!(!app.should_show_rainbow());
+
+        weather.condition = WeatherCondition::PartlyCloudy;
+        app.update_weather(weather.clone());
+        assert!(app.should_show_rainbow());
+
+        weather.condition = WeatherCondition::Overcast;
+        app.update_weather(weather);
+        assert!(!app.should_show_rainbow());
+    }
+
+    #[test]
+    fn test_fireflies_only_on_warm_clear_nights() {
+        let mut app = create_app_state(0.0, 0.0);
+        assert!(!app.should_show_fireflies());
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.is_day = false;
+        app.update_weather(weather.clone());
+        assert!(app.should_show_fireflies());
+
+        weather.temperature = 16.0;
+        app.update_weather(weather.clone());
+        assert!(!app.should_show_fireflies());
+
+        weather.temperature = 22.0;
+        weather.condition = WeatherCondition::Overcast;
+        app.update_weather(weather);
+        assert!(!app.should_show_fireflies());
+    }
+
+    #[test]
+    fn test_heat_shimmer_on_hot_clear_days() {
+        let mut app = create_app_state(0.0, 0.0);
+        assert!(!app.should_show_heat_shimmer());
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.temperature = 35.0;
+        app.update_weather(weather.clone());
+        assert!(app.should_show_heat_shimmer());
+
+        weather.condition = WeatherCondition::Rain;
+        app.update_weather(weather);
+        assert!(!app.should_show_heat_shimmer());
+    }
+
+    #[test]
+    fn test_blizzard_needs_snow_and_gale() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.condition = WeatherCondition::SnowShowers;
+        weather.wind_speed = 5.0;
+        app.update_weather(weather.clone());
+        assert!(!app.weather_conditions.is_blizzard);
+
+        weather.wind_speed = 18.0;
+        app.update_weather(weather.clone());
+        assert!(app.weather_conditions.is_blizzard);
+
+        weather.condition = WeatherCondition::Rain;
+        app.update_weather(weather);
+        assert!(!app.weather_conditions.is_blizzard);
+    }
+
+    #[test]
+    fn test_aurora_only_on_high_latitude_clear_nights() {
+        let mut tromso = create_app_state(69.65, 18.96);
+        assert!(!tromso.should_show_aurora());
+        tromso.weather_conditions.is_day = false;
+        assert!(tromso.should_show_aurora());
+
+        let mut south = create_app_state(-77.85, 166.67);
+        south.weather_conditions.is_day = false;
+        assert!(south.should_show_aurora());
+
+        let mut paris = create_app_state(48.86, 2.35);
+        paris.weather_conditions.is_day = false;
+        assert!(!paris.should_show_aurora());
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(5), "just now");
+        assert_eq!(format_age(125), "2m ago");
+        assert_eq!(format_age(2 * 3600 + 59), "2h ago");
+        assert_eq!(format_age(3 * 86400), "3d ago");
+    }
+
+    #[test]
+    fn test_stale_marker_cleared_by_fresh_weather() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_stale(0);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("STALE (fetched"));
+
+        let weather = app.current_weather.clone().unwrap();
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("STALE"));
+    }
+
     #[test]
     fn test_new_york_coordinates() {
         // New York: 40.7128°N, 74.0060°W (positive lat, negative lon)
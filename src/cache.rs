@@ -1,5 +1,7 @@
+use crate::geography::Geography;
 use crate::geolocation::GeoLocation;
 use crate::weather::WeatherData;
+use crate::weather::pressure::PressureReading;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tokio::fs;
@@ -29,14 +31,14 @@ fn get_cache_dir() -> Option<PathBuf> {
     Some(cache_dir.join("weathr"))
 }
 
-fn current_timestamp() -> u64 {
+pub fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0)
 }
 
-fn make_location_key(latitude: f64, longitude: f64) -> String {
+pub fn make_location_key(latitude: f64, longitude: f64) -> String {
     format!("{:.2},{:.2}", latitude, longitude)
 }
 
@@ -71,24 +73,46 @@ pub fn save_location_cache(location: &GeoLocation) {
     });
 }
 
-pub async fn load_cached_weather(latitude: f64, longitude: f64) -> Option<WeatherData> {
-    let cache_path = get_cache_dir()?.join("weather.json");
+fn weather_cache_file_name(latitude: f64, longitude: f64) -> String {
+    format!(
+        "weather_{}.json",
+        make_location_key(latitude, longitude).replace(',', "_")
+    )
+}
+
+async fn read_weather_cache(latitude: f64, longitude: f64) -> Option<WeatherCache> {
+    let cache_path = get_cache_dir()?.join(weather_cache_file_name(latitude, longitude));
     let contents = fs::read_to_string(&cache_path).await.ok()?;
     let cache: WeatherCache = serde_json::from_str(&contents).ok()?;
 
-    let location_key = make_location_key(latitude, longitude);
-    if cache.location_key != location_key {
-        return None;
+    if cache.location_key == make_location_key(latitude, longitude) {
+        Some(cache)
+    } else {
+        None
     }
+}
+
+pub async fn load_cached_weather(latitude: f64, longitude: f64) -> Option<WeatherData> {
+    let cache = read_weather_cache(latitude, longitude).await?;
 
     let now = current_timestamp();
-    if now - cache.cached_at < WEATHER_CACHE_DURATION_SECS {
+    if now.saturating_sub(cache.cached_at) < WEATHER_CACHE_DURATION_SECS {
         Some(cache.data)
     } else {
         None
     }
 }
 
+// Last successful fetch regardless of age, with its unix timestamp
+pub async fn load_stale_weather(latitude: f64, longitude: f64) -> Option<(WeatherData, u64)> {
+    let cache = read_weather_cache(latitude, longitude).await?;
+    Some((cache.data, cache.cached_at))
+}
+
+pub fn seconds_since(timestamp: u64) -> u64 {
+    current_timestamp().saturating_sub(timestamp)
+}
+
 pub fn save_weather_cache(weather: &WeatherData, latitude: f64, longitude: f64) {
     let weather = weather.clone();
     tokio::spawn(async move {
@@ -102,8 +126,99 @@ pub fn save_weather_cache(weather: &WeatherData, latitude: f64, longitude: f64)
             };
 
             if let Ok(json) = serde_json::to_string(&cache) {
-                let _ = fs::write(cache_dir.join("weather.json"), json).await;
+                let _ = fs::write(
+                    cache_dir.join(weather_cache_file_name(latitude, longitude)),
+                    json,
+                )
+                .await;
+            }
+        }
+    });
+}
+
+fn geography_cache_file_name(latitude: f64, longitude: f64) -> String {
+    format!(
+        "geography_{}.json",
+        make_location_key(latitude, longitude).replace(',', "_")
+    )
+}
+
+// The land doesn't move, so this never expires
+pub async fn load_cached_geography(latitude: f64, longitude: f64) -> Option<Geography> {
+    let cache_path = get_cache_dir()?.join(geography_cache_file_name(latitude, longitude));
+    let contents = fs::read_to_string(&cache_path).await.ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn save_geography_cache(geography: &Geography, latitude: f64, longitude: f64) {
+    let geography = geography.clone();
+    tokio::spawn(async move {
+        if let Some(cache_dir) = get_cache_dir() {
+            let _ = fs::create_dir_all(&cache_dir).await;
+
+            if let Ok(json) = serde_json::to_string(&geography) {
+                let _ = fs::write(
+                    cache_dir.join(geography_cache_file_name(latitude, longitude)),
+                    json,
+                )
+                .await;
+            }
+        }
+    });
+}
+
+fn pressure_cache_file_name(latitude: f64, longitude: f64) -> String {
+    format!(
+        "pressure_{}.json",
+        make_location_key(latitude, longitude).replace(',', "_")
+    )
+}
+
+pub async fn load_pressure_history(latitude: f64, longitude: f64) -> Vec<PressureReading> {
+    let Some(cache_dir) = get_cache_dir() else {
+        return Vec::new();
+    };
+    let path = cache_dir.join(pressure_cache_file_name(latitude, longitude));
+    match fs::read_to_string(&path).await {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn save_pressure_history(history: &[PressureReading], latitude: f64, longitude: f64) {
+    let history = history.to_vec();
+    tokio::spawn(async move {
+        if let Some(cache_dir) = get_cache_dir() {
+            let _ = fs::create_dir_all(&cache_dir).await;
+
+            if let Ok(json) = serde_json::to_string(&history) {
+                let _ = fs::write(
+                    cache_dir.join(pressure_cache_file_name(latitude, longitude)),
+                    json,
+                )
+                .await;
             }
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_cache_file_is_per_location() {
+        assert_eq!(
+            weather_cache_file_name(52.5200, 13.4100),
+            "weather_52.52_13.41.json"
+        );
+        assert_eq!(
+            weather_cache_file_name(-33.8688, 151.2093),
+            "weather_-33.87_151.21.json"
+        );
+        assert_ne!(
+            weather_cache_file_name(52.52, 13.41),
+            weather_cache_file_name(35.68, 139.65)
+        );
+    }
+}
@@ -1,23 +1,471 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::error::ConfigError;
+use crate::theme::Theme;
+use crate::weather::air_quality::AqiScale;
+use crate::weather::alerts::AlertSeverity;
+use crate::mqtt;
+use crate::weather::json_path;
+use crate::weather::provider::{DEFAULT_FALLBACK, DEFAULT_PROVIDER, PROVIDERS};
+use crate::weather::providers::metar;
 use crate::weather::types::WeatherUnits;
 
-#[derive(Deserialize, Debug, Default, Clone)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct Config {
     #[serde(default)]
     pub location: Location,
     #[serde(default)]
     pub hide_hud: bool,
     #[serde(default)]
+    pub monochrome: bool,
+    // Sky, sun and moon as an image on terminals with kitty graphics, or the sun and moon
+    // as a sixel
+    #[serde(default)]
+    pub graphics: bool,
+    #[serde(default)]
     pub units: WeatherUnits,
     #[serde(default)]
+    pub animation: AnimationConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
     pub silent: bool,
+    #[serde(default)]
+    pub locations: Vec<SavedLocation>,
+    #[serde(default)]
+    pub scene: Option<String>,
+    #[serde(default)]
+    pub big_temperature: bool,
+    #[serde(default)]
+    pub gauges: bool,
+    #[serde(default)]
+    pub compass: bool,
+    #[serde(default)]
+    pub sparkline: bool,
+    #[serde(default)]
+    pub aqi_scale: AqiScale,
+    #[serde(default)]
+    pub remember_units: bool,
+    // Keep the colors in screenshots, as ANSI escapes
+    #[serde(default)]
+    pub screenshot_colors: bool,
+    // Seconds between weather fetches, DEFAULT_REFRESH_SECONDS if unset
+    #[serde(default)]
+    pub refresh_seconds: Option<u64>,
+    #[serde(default)]
+    pub provider: ProviderConfig,
+    #[serde(default)]
+    pub publish: PublishConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    // The file this was read from, or would be written to if it doesn't exist yet
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+pub const DEFAULT_FPS: u32 = 30;
+pub const MAX_FPS: u32 = 120;
+
+// Open-Meteo updates its models every 15 minutes at best; a minute is plenty often
+pub const DEFAULT_REFRESH_SECONDS: u64 = 300;
+pub const MIN_REFRESH_SECONDS: u64 = 60;
+
+// How long each frame of the sun animation stays up
+pub const DEFAULT_FRAME_DELAY_MS: u64 = 500;
+pub const MIN_FRAME_DELAY_MS: u64 = 50;
+
+pub const DEFAULT_IDLE_MINUTES: u64 = 10;
+pub const DEFAULT_IDLE_FPS: u32 = 2;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AnimationConfig {
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+    // Minutes without input before dropping to `idle_fps`; 0 never throttles
+    #[serde(default = "default_idle_minutes")]
+    pub idle_minutes: u64,
+    #[serde(default = "default_idle_fps")]
+    pub idle_fps: u32,
+    #[serde(default)]
+    pub throttle_unfocused: bool,
+    // One still frame, redrawn only when something changes
+    #[serde(default)]
+    pub reduce_motion: bool,
+    // Rain and snow as braille dots, four rows of them per cell
+    #[serde(default)]
+    pub braille: bool,
+    #[serde(default = "default_frame_delay_ms")]
+    pub frame_delay_ms: u64,
+}
+
+fn default_fps() -> u32 {
+    DEFAULT_FPS
+}
+
+fn default_frame_delay_ms() -> u64 {
+    DEFAULT_FRAME_DELAY_MS
+}
+
+fn default_idle_minutes() -> u64 {
+    DEFAULT_IDLE_MINUTES
 }
 
-#[derive(Deserialize, Debug, Clone)]
+fn default_idle_fps() -> u32 {
+    DEFAULT_IDLE_FPS
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            fps: DEFAULT_FPS,
+            idle_minutes: DEFAULT_IDLE_MINUTES,
+            idle_fps: DEFAULT_IDLE_FPS,
+            throttle_unfocused: false,
+            reduce_motion: false,
+            braille: false,
+            frame_delay_ms: DEFAULT_FRAME_DELAY_MS,
+        }
+    }
+}
+
+// Where weather comes from, either `provider = "nws"` or a [provider] table whose `name`
+// picks the provider, alongside the settings of those that need an account
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(from = "RawProviderConfig")]
+pub struct ProviderConfig {
+    // DEFAULT_PROVIDER if unset
+    pub name: Option<String>,
+    // Tried in order when the one before fails; DEFAULT_FALLBACK if unset
+    pub fallback: Option<Vec<String>>,
+    pub openweathermap: ApiKeyConfig,
+    pub pirateweather: ApiKeyConfig,
+    pub custom: CustomProviderConfig,
+    pub metar: MetarConfig,
+    pub mqtt: MqttSensorConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ApiKeyConfig {
+    pub api_key: Option<String>,
+}
+
+// The airport whose reports the metar provider shows, such as "KSFO"
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct MetarConfig {
+    pub station: Option<String>,
+}
+
+// A home weather station publishing to an MQTT broker. Its readings replace the
+// provider's for the current conditions
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct MqttSensorConfig {
+    // Like "mqtt://192.168.1.20:1883"; sensors are off while unset
+    pub broker: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // What the readings are measured in, like [units]
+    pub units: WeatherUnits,
+    pub topics: SensorTopics,
+}
+
+// Each topic's messages are a plain number: humidity in percent, pressure in hPa and
+// rain as the last hour's amount
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SensorTopics {
+    pub temperature: Option<String>,
+    pub humidity: Option<String>,
+    pub pressure: Option<String>,
+    pub rain: Option<String>,
+}
+
+impl SensorTopics {
+    pub fn all(&self) -> impl Iterator<Item = &str> {
+        [&self.temperature, &self.humidity, &self.pressure, &self.rain]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+}
+
+impl MqttSensorConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: String| Err(ConfigError::InvalidMqtt(reason));
+        let Some(ref broker) = self.broker else {
+            if self.topics.all().next().is_some() {
+                return invalid("broker is missing".to_string());
+            }
+            return Ok(());
+        };
+        if let Err(reason) = mqtt::parse_broker(broker) {
+            return invalid(format!("broker = \"{}\": {}", broker, reason));
+        }
+        if self.topics.all().next().is_none() {
+            return invalid("no topics to subscribe to".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub const DEFAULT_PUBLISH_TOPIC: &str = "weathr/weather";
+
+// Each fetched weather sent as JSON to an MQTT broker, for home automation to pick up
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PublishConfig {
+    // Like "mqtt://192.168.1.20:1883"; publishing is off while unset
+    pub broker: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // DEFAULT_PUBLISH_TOPIC if unset
+    pub topic: Option<String>,
+    // Announce the readings as sensors to Home Assistant's MQTT discovery
+    pub home_assistant: bool,
+    // Home Assistant's discovery prefix, "homeassistant" unless changed there
+    pub discovery_prefix: Option<String>,
+}
+
+impl PublishConfig {
+    pub fn topic(&self) -> &str {
+        self.topic.as_deref().unwrap_or(DEFAULT_PUBLISH_TOPIC)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: String| Err(ConfigError::InvalidPublish(reason));
+        let Some(ref broker) = self.broker else {
+            if self.topic.is_some() || self.home_assistant {
+                return invalid("broker is missing".to_string());
+            }
+            return Ok(());
+        };
+        if let Err(reason) = mqtt::parse_broker(broker) {
+            return invalid(format!("broker = \"{}\": {}", broker, reason));
+        }
+        for (key, topic) in [
+            ("topic", self.topic.as_deref()),
+            ("discovery_prefix", self.discovery_prefix.as_deref()),
+        ] {
+            if let Some(topic) = topic
+                && (topic.is_empty() || topic.contains(['+', '#']))
+            {
+                return invalid(format!(
+                    "{} = \"{}\" isn't a topic to publish to",
+                    key, topic
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Desktop notifications about the first location's weather, sent with notify-send, or
+// osascript on macOS
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    // When the weather turns from one kind to another, like clear to thunderstorm
+    pub condition_change: bool,
+    // When the nowcast has precipitation starting within this many minutes; 0 for never
+    pub precipitation_minutes: u64,
+    // Alerts at least this severe: minor, moderate, severe or extreme
+    pub alert_severity: AlertSeverity,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            condition_change: true,
+            precipitation_minutes: 15,
+            alert_severity: AlertSeverity::Severe,
+        }
+    }
+}
+
+// Any JSON API: `url` with {latitude} and {longitude} filled in, and where in the
+// response each reading is, as JSON paths like "$.current.temp_c"
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CustomProviderConfig {
+    pub url: Option<String>,
+    // What the readings are measured in, like [units]
+    pub units: WeatherUnits,
+    pub fields: CustomFields,
+}
+
+// Percentages for humidity and cloud cover, hPa, meters of visibility, degrees the wind
+// comes from, and a WMO code for the condition (guessed from clouds and rain if unset)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomFields {
+    pub temperature: Option<String>,
+    pub apparent_temperature: Option<String>,
+    pub humidity: Option<String>,
+    pub precipitation: Option<String>,
+    pub wind_speed: Option<String>,
+    pub wind_direction: Option<String>,
+    pub cloud_cover: Option<String>,
+    pub pressure: Option<String>,
+    pub visibility: Option<String>,
+    pub uv_index: Option<String>,
+    pub weather_code: Option<String>,
+    pub is_day: Option<String>,
+}
+
+impl CustomFields {
+    pub fn all(&self) -> [(&'static str, Option<&str>); 12] {
+        [
+            ("temperature", self.temperature.as_deref()),
+            ("apparent_temperature", self.apparent_temperature.as_deref()),
+            ("humidity", self.humidity.as_deref()),
+            ("precipitation", self.precipitation.as_deref()),
+            ("wind_speed", self.wind_speed.as_deref()),
+            ("wind_direction", self.wind_direction.as_deref()),
+            ("cloud_cover", self.cloud_cover.as_deref()),
+            ("pressure", self.pressure.as_deref()),
+            ("visibility", self.visibility.as_deref()),
+            ("uv_index", self.uv_index.as_deref()),
+            ("weather_code", self.weather_code.as_deref()),
+            ("is_day", self.is_day.as_deref()),
+        ]
+    }
+}
+
+impl CustomProviderConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: String| Err(ConfigError::InvalidCustomProvider(reason));
+        if self.url.as_deref().is_none_or(|url| url.trim().is_empty()) {
+            return invalid("url is missing".to_string());
+        }
+        if self.fields.temperature.is_none() {
+            return invalid("fields.temperature is missing".to_string());
+        }
+        for (field, path) in self.fields.all() {
+            if let Some(path) = path
+                && let Err(reason) = json_path::parse(path)
+            {
+                return invalid(format!("fields.{} = \"{}\": {}", field, path, reason));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Only lives while the config is read
+#[allow(clippy::large_enum_variant)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawProviderConfig {
+    Name(String),
+    Table {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        fallback: Option<Vec<String>>,
+        #[serde(default)]
+        openweathermap: ApiKeyConfig,
+        #[serde(default)]
+        pirateweather: ApiKeyConfig,
+        #[serde(default)]
+        custom: CustomProviderConfig,
+        #[serde(default)]
+        metar: MetarConfig,
+        #[serde(default)]
+        mqtt: MqttSensorConfig,
+    },
+}
+
+impl From<RawProviderConfig> for ProviderConfig {
+    fn from(raw: RawProviderConfig) -> Self {
+        match raw {
+            RawProviderConfig::Name(name) => Self {
+                name: Some(name),
+                ..Self::default()
+            },
+            RawProviderConfig::Table {
+                name,
+                fallback,
+                openweathermap,
+                pirateweather,
+                custom,
+                metar,
+                mqtt,
+            } => Self {
+                name,
+                fallback,
+                openweathermap,
+                pirateweather,
+                custom,
+                metar,
+                mqtt,
+            },
+        }
+    }
+}
+
+impl ProviderConfig {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(DEFAULT_PROVIDER)
+    }
+
+    // The selected provider followed by its fallbacks, each named once
+    pub fn chain(&self) -> Vec<&str> {
+        let fallback = match self.fallback {
+            Some(ref names) => names.iter().map(String::as_str).collect(),
+            None => DEFAULT_FALLBACK.to_vec(),
+        };
+        let mut chain = vec![self.name()];
+        for name in fallback {
+            if !chain.contains(&name) {
+                chain.push(name);
+            }
+        }
+        chain
+    }
+
+    // Whether the named provider needs an API key that isn't set
+    fn missing_api_key(&self, name: &str) -> bool {
+        let key = match name {
+            "openweathermap" => &self.openweathermap.api_key,
+            "pirateweather" => &self.pirateweather.api_key,
+            _ => return false,
+        };
+        key.as_deref().is_none_or(|key| key.trim().is_empty())
+    }
+}
+
+// Colors are "#rrggbb" or a terminal color name; the sky only takes "#rrggbb"
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ThemeConfig {
+    pub preset: Option<String>,
+    pub sun: Option<String>,
+    pub sun_dim: Option<String>,
+    pub rain: Option<String>,
+    pub rain_bright: Option<String>,
+    pub snow: Option<String>,
+    pub cloud: Option<String>,
+    pub text: Option<String>,
+    pub alert_extreme: Option<String>,
+    pub alert_severe: Option<String>,
+    pub alert_moderate: Option<String>,
+    pub alert_minor: Option<String>,
+    pub sky_top: Option<String>,
+    pub sky_horizon: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SavedLocation {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Location {
     #[serde(default = "default_latitude")]
     pub latitude: f64,
@@ -49,39 +497,162 @@ impl Default for Location {
 }
 
 impl Config {
-    pub fn load() -> Result<Self, ConfigError> {
-        // try local config.toml
-        if let Ok(cwd) = std::env::current_dir() {
-            let local_config = cwd.join("config.toml");
-            if local_config.exists() {
-                let config = Self::load_from_path(&local_config)?;
-                config.validate()?;
-                return Ok(config);
-            }
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_seconds.unwrap_or(DEFAULT_REFRESH_SECONDS))
+    }
+
+    pub fn frame_delay(&self) -> Duration {
+        Duration::from_millis(self.animation.frame_delay_ms)
+    }
+
+    // Settings that environment variables override, applied between the file and the
+    // command line. Returns the variables that were skipped for not parsing; whether
+    // they make a valid config together is for validate() to say once they're all in
+    pub fn apply_env(&mut self) -> Vec<ConfigError> {
+        if std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
+            self.monochrome = true;
+        }
+        let mut vars: Vec<(String, String)> = std::env::vars()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        vars.sort();
+
+        let before = self.location.clone();
+        let errors = vars
+            .iter()
+            .filter_map(|(name, value)| self.apply_env_var(name, value).err())
+            .collect();
+        // Coordinates from the environment are meant to be used, unless auto was set too
+        let moved = self.location.latitude != before.latitude
+            || self.location.longitude != before.longitude;
+        if moved && self.location.auto == before.auto {
+            self.location.auto = false;
+        }
+        errors
+    }
+
+    // `WEATHR_<KEY>` for top-level keys and `WEATHR_<TABLE>_<KEY>` for the rest, e.g.
+    // WEATHR_ANIMATION_FPS, plus the shorthands in ENV_ALIASES and WEATHR_UNITS
+    fn apply_env_var(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        let invalid = || ConfigError::InvalidEnv {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        let key = name[ENV_PREFIX.len()..].to_lowercase();
+
+        if key == "units" {
+            self.units = match value.to_lowercase().as_str() {
+                "metric" => WeatherUnits::metric(),
+                "imperial" => WeatherUnits::imperial(),
+                _ => return Err(invalid()),
+            };
+            return Ok(());
+        }
+
+        let mut tree = to_toml(self);
+        let path: Vec<String> = match ENV_ALIASES.iter().find(|(alias, _)| *alias == key) {
+            Some((_, path)) => path.split('.').map(str::to_string).collect(),
+            None => match key.split_once('_') {
+                Some((table, rest)) if tree.get(table).is_some_and(toml::Value::is_table) => {
+                    vec![table.to_string(), rest.to_string()]
+                }
+                _ => vec![key.clone()],
+            },
+        };
+
+        let parsed = env_value(value, lookup(&tree, &path)).ok_or_else(invalid)?;
+        let (leaf, tables) = path.split_last().ok_or_else(invalid)?;
+        let table = tables
+            .iter()
+            .try_fold(&mut tree, |node, key| node.get_mut(key))
+            .and_then(toml::Value::as_table_mut)
+            .ok_or_else(invalid)?;
+        table.insert(leaf.clone(), parsed);
+
+        let mut config: Config = tree.clone().try_into().map_err(|_| invalid())?;
+        // Unknown keys are dropped by the round trip, which is how a typo shows up
+        if lookup(&to_toml(&config), &path).is_none() {
+            return Err(ConfigError::UnknownEnv(name.to_string()));
         }
+        // Not part of the tree, but still where the config came from
+        config.path = self.path.take();
+        *self = config;
+        Ok(())
+    }
 
-        // try XDG config
-        let config_path = Self::get_config_path()?;
+    // `explicit` is a --config path, which has to exist; otherwise the usual places
+    // are tried in turn
+    pub fn load(explicit: Option<PathBuf>) -> Result<Self, ConfigError> {
+        let is_explicit = explicit.is_some();
+        let config_path = match explicit {
+            Some(path) => path,
+            None => Self::active_path()?,
+        };
 
-        if !config_path.exists() {
+        if !is_explicit && !config_path.exists() {
             eprintln!("Config file not found at {:?}", config_path);
             eprintln!("Auto-detecting location via IP...");
             eprintln!("(Set auto = false in config to use Berlin as default)");
-            return Ok(Self::default());
+            return Ok(Self {
+                path: Some(config_path),
+                ..Self::default()
+            });
         }
 
-        let config = Self::load_from_path(&config_path)?;
+        let mut config = Self::load_from_path(&config_path)?;
         config.validate()?;
+        config.path = Some(config_path);
         Ok(config)
     }
 
-    fn validate(&self) -> Result<(), ConfigError> {
-        if self.location.latitude < -90.0 || self.location.latitude > 90.0 {
-            return Err(ConfigError::InvalidLatitude(self.location.latitude));
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_coordinates(self.location.latitude, self.location.longitude)?;
+
+        for saved in &self.locations {
+            validate_coordinates(saved.latitude, saved.longitude)?;
         }
 
-        if self.location.longitude < -180.0 || self.location.longitude > 180.0 {
-            return Err(ConfigError::InvalidLongitude(self.location.longitude));
+        Theme::from_config(&self.theme)?;
+
+        for name in self.provider.chain() {
+            if !PROVIDERS.contains(&name) {
+                return Err(ConfigError::UnknownProvider(name.to_string()));
+            }
+            if self.provider.missing_api_key(name) {
+                return Err(ConfigError::MissingApiKey(name.to_string()));
+            }
+            if name == "custom" {
+                self.provider.custom.validate()?;
+            }
+            if name == "metar" {
+                match self.provider.metar.station {
+                    None => return Err(ConfigError::MissingStation),
+                    Some(ref station) if !metar::is_icao_code(station) => {
+                        return Err(ConfigError::InvalidStation(station.clone()));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        self.provider.mqtt.validate()?;
+        self.publish.validate()?;
+
+        for fps in [self.animation.fps, self.animation.idle_fps] {
+            if !(1..=MAX_FPS).contains(&fps) {
+                return Err(ConfigError::InvalidFps(fps));
+            }
+        }
+
+        if let Some(seconds) = self.refresh_seconds
+            && seconds < MIN_REFRESH_SECONDS
+        {
+            return Err(ConfigError::InvalidRefresh(seconds));
+        }
+        if self.animation.frame_delay_ms < MIN_FRAME_DELAY_MS {
+            return Err(ConfigError::InvalidFrameDelay(
+                self.animation.frame_delay_ms,
+            ));
         }
 
         Ok(())
@@ -96,17 +667,322 @@ impl Config {
         toml::from_str(&content).map_err(ConfigError::ParseError)
     }
 
-    fn get_config_path() -> Result<PathBuf, ConfigError> {
+    // The file `load` reads: config.toml in the working directory if there is one,
+    // otherwise the one in the config directory, whether or not it exists yet
+    pub fn active_path() -> Result<PathBuf, ConfigError> {
+        let local_config = std::env::current_dir()
+            .map(|cwd| cwd.join("config.toml"))
+            .ok()
+            .filter(|path| path.exists());
+        match local_config {
+            Some(path) => Ok(path),
+            None => Self::get_config_path(),
+        }
+    }
+
+    // Writes the units into the config file `load` reads, leaving the rest of it as is
+    pub fn save_units(path: &PathBuf, units: &WeatherUnits) -> Result<(), ConfigError> {
+        let write_error = |source| ConfigError::WriteError {
+            path: path.display().to_string(),
+            source,
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(ConfigError::ReadError {
+                    path: path.display().to_string(),
+                    source: e,
+                });
+            }
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        fs::write(path, with_units(&content, units)).map_err(write_error)
+    }
+
+    pub fn get_config_path() -> Result<PathBuf, ConfigError> {
+        Ok(Self::weathr_config_dir()?.join("config.toml"))
+    }
+
+    pub fn scenes_dir() -> Result<PathBuf, ConfigError> {
+        Ok(Self::weathr_config_dir()?.join("scenes"))
+    }
+
+    fn weathr_config_dir() -> Result<PathBuf, ConfigError> {
         let config_dir = if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME") {
             PathBuf::from(xdg_config)
         } else {
             dirs::config_dir().ok_or(ConfigError::NoConfigDir)?
         };
 
-        Ok(config_dir.join("weathr").join("config.toml"))
+        Ok(config_dir.join("weathr"))
+    }
+}
+
+const ENV_PREFIX: &str = "WEATHR_";
+// Shorter names for the settings most often set from the environment
+const ENV_ALIASES: &[(&str, &str)] = &[
+    ("latitude", "location.latitude"),
+    ("longitude", "location.longitude"),
+    ("auto_location", "location.auto"),
+    ("theme", "theme.preset"),
+    ("provider", "provider.name"),
+    ("station", "provider.metar.station"),
+    (
+        "provider_openweathermap_api_key",
+        "provider.openweathermap.api_key",
+    ),
+    (
+        "provider_pirateweather_api_key",
+        "provider.pirateweather.api_key",
+    ),
+];
+
+// An environment string as the type the setting already has, or as a TOML literal
+// (falling back to a plain string) for settings that are unset
+fn env_value(raw: &str, current: Option<&toml::Value>) -> Option<toml::Value> {
+    use toml::Value;
+    match current {
+        Some(Value::Boolean(_)) => match raw.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(Value::Boolean(true)),
+            "0" | "false" | "no" | "off" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        Some(Value::Integer(_)) => raw.trim().parse().ok().map(Value::Integer),
+        Some(Value::Float(_)) => raw.trim().parse().ok().map(Value::Float),
+        Some(Value::String(_)) => Some(Value::String(raw.to_string())),
+        _ => Some(
+            toml::from_str::<toml::Table>(&format!("value = {}", raw))
+                .ok()
+                .and_then(|mut table| table.remove("value"))
+                .unwrap_or_else(|| Value::String(raw.to_string())),
+        ),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Default,
+    File,
+    Environment,
+    CommandLine,
+}
+
+impl Source {
+    fn label(&self) -> &'static str {
+        match self {
+            Source::Default => "default",
+            Source::File => "config file",
+            Source::Environment => "environment",
+            Source::CommandLine => "command line",
+        }
+    }
+}
+
+// Leaf settings by their key path; arrays such as [[locations]] count as one setting
+fn flatten(value: &toml::Value, path: &mut Vec<String>, out: &mut Vec<(Vec<String>, toml::Value)>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                path.push(key.clone());
+                flatten(value, path, out);
+                path.pop();
+            }
+        }
+        _ => out.push((path.clone(), value.clone())),
     }
 }
 
+fn lookup<'a>(value: &'a toml::Value, path: &[String]) -> Option<&'a toml::Value> {
+    path.iter().try_fold(value, |value, key| value.get(key))
+}
+
+fn to_toml(config: &Config) -> toml::Value {
+    toml::Value::try_from(config).unwrap_or(toml::Value::Table(Default::default()))
+}
+
+// The effective settings, TOML style, each marked with where its value came from.
+// `from_file`, `from_env` and `effective` are the config after each layer is applied,
+// and `file` holds what the file itself spelled out
+pub fn describe(
+    file: Option<&toml::Value>,
+    from_file: &Config,
+    from_env: &Config,
+    effective: &Config,
+) -> String {
+    let (from_file, from_env, values) = (to_toml(from_file), to_toml(from_env), to_toml(effective));
+    let mut leaves = Vec::new();
+    flatten(&values, &mut Vec::new(), &mut leaves);
+    // Top-level keys have to come before the first table header
+    leaves.sort_by_key(|(path, _)| path.len() > 1);
+
+    let mut out = String::new();
+    let mut section = String::new();
+    for (path, value) in leaves {
+        let source = if lookup(&values, &path) != lookup(&from_env, &path) {
+            Source::CommandLine
+        } else if lookup(&from_env, &path) != lookup(&from_file, &path) {
+            Source::Environment
+        } else if file.is_some_and(|file| lookup(file, &path).is_some()) {
+            Source::File
+        } else {
+            Source::Default
+        };
+
+        let (key, table) = path
+            .split_last()
+            .map_or(("", &[][..]), |(k, t)| (k.as_str(), t));
+        let table = table.join(".");
+        if table != section {
+            out.push_str(&format!("\n[{}]\n", table));
+            section = table;
+        }
+        let mut note = source.label().to_string();
+        if effective.location.auto
+            && section == "location"
+            && matches!(key, "latitude" | "longitude")
+        {
+            note.push_str("; replaced by IP detection at startup");
+        }
+        // Keys and passwords are secrets; print only that one is set
+        let value = if matches!(key, "api_key" | "password") {
+            toml::Value::String("********".to_string())
+        } else {
+            value
+        };
+        out.push_str(&format!("{} = {}  # {}\n", key, value, note));
+    }
+    out
+}
+
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Notices edits to the config file by its modification time, checked once a second.
+// Holds the file's own settings, without command line overrides, so a reload can tell
+// which of them actually changed
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    config: Config,
+    last_check: Instant,
+}
+
+fn modified_time(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn read_valid(path: &PathBuf) -> Result<Config, ConfigError> {
+    let config = Config::load_from_path(path)?;
+    config.validate()?;
+    Ok(config)
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        let config = read_valid(&path).unwrap_or_default();
+        Self {
+            path,
+            modified,
+            config,
+            last_check: Instant::now(),
+        }
+    }
+
+    // The previous and the new settings once the file has changed. A file that is
+    // removed (or mid-way through an editor's atomic save) keeps the last settings
+    pub fn poll(&mut self) -> Option<Result<(Config, Config), ConfigError>> {
+        if self.last_check.elapsed() < CONFIG_POLL_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+        self.check()
+    }
+
+    // After weathr writes the file itself, as when saving the units: the new settings
+    // are taken in without the write coming back from `poll` as an edit
+    pub fn saved(&mut self) {
+        self.modified = modified_time(&self.path);
+        if let Ok(config) = read_valid(&self.path) {
+            self.config = config;
+        }
+    }
+
+    fn check(&mut self) -> Option<Result<(Config, Config), ConfigError>> {
+        let modified = modified_time(&self.path)?;
+        if self.modified == Some(modified) {
+            return None;
+        }
+        // Also for a broken file, so its error is reported once rather than every second
+        self.modified = Some(modified);
+
+        Some(read_valid(&self.path).map(|config| {
+            let previous = std::mem::replace(&mut self.config, config.clone());
+            (previous, config)
+        }))
+    }
+}
+
+const UNIT_KEYS: [&str; 4] = ["temperature", "wind_speed", "precipitation", "pressure"];
+
+pub(crate) fn toml_value<T: serde::Serialize>(value: T) -> String {
+    toml::Value::try_from(value)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+// Replaces the unit keys in the [units] table, adding the table if there isn't one.
+// Line based so that comments and layout elsewhere survive.
+fn with_units(content: &str, units: &WeatherUnits) -> String {
+    let unit_lines = [
+        format!("temperature = {}", toml_value(units.temperature)),
+        format!("wind_speed = {}", toml_value(units.wind_speed)),
+        format!("precipitation = {}", toml_value(units.precipitation)),
+        format!("pressure = {}", toml_value(units.pressure)),
+    ];
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let Some(header) = lines.iter().position(|l| l.trim() == "[units]") else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push("[units]".to_string());
+        lines.extend(unit_lines);
+        return lines.join("\n") + "\n";
+    };
+
+    let end = lines[header + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| header + 1 + i);
+    let mut table: Vec<String> = lines[header + 1..end]
+        .iter()
+        .filter(|l| {
+            let key = l.split('=').next().unwrap_or("").trim();
+            !(l.contains('=') && UNIT_KEYS.contains(&key))
+        })
+        .cloned()
+        .collect();
+    table.splice(0..0, unit_lines);
+    lines.splice(header + 1..end, table);
+    lines.join("\n") + "\n"
+}
+
+fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), ConfigError> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(ConfigError::InvalidLatitude(latitude));
+    }
+
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(ConfigError::InvalidLongitude(longitude));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,9 +1107,7 @@ longitude = 0.0
                 auto: false,
                 hide: false,
             },
-            hide_hud: false,
-            units: WeatherUnits::default(),
-            silent: false,
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -249,9 +1123,7 @@ longitude = 0.0
                 auto: false,
                 hide: false,
             },
-            hide_hud: false,
-            units: WeatherUnits::default(),
-            silent: false,
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -267,9 +1139,7 @@ longitude = 0.0
                 auto: false,
                 hide: false,
             },
-            hide_hud: false,
-            units: WeatherUnits::default(),
-            silent: false,
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -285,9 +1155,7 @@ longitude = 0.0
                 auto: false,
                 hide: false,
             },
-            hide_hud: false,
-            units: WeatherUnits::default(),
-            silent: false,
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -303,9 +1171,7 @@ longitude = 0.0
                 auto: false,
                 hide: false,
             },
-            hide_hud: false,
-            units: WeatherUnits::default(),
-            silent: false,
+            ..Default::default()
         };
         let result = config.validate();
         assert!(result.is_ok());
@@ -359,4 +1225,462 @@ precipitation = "inch"
             crate::weather::types::PrecipitationUnit::Inch
         );
     }
+
+    #[test]
+    fn test_config_saved_locations() {
+        let toml_content = r#"
+[location]
+latitude = 52.52
+longitude = 13.41
+
+[[locations]]
+name = "Tokyo"
+latitude = 35.68
+longitude = 139.65
+
+[[locations]]
+name = "Sydney"
+latitude = -33.87
+longitude = 151.21
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.locations.len(), 2);
+        assert_eq!(config.locations[0].name, "Tokyo");
+        assert_eq!(config.locations[1].latitude, -33.87);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validation_invalid_saved_location() {
+        let config = Config {
+            locations: vec![SavedLocation {
+                name: "Nowhere".to_string(),
+                latitude: 0.0,
+                longitude: 200.0,
+            }],
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), "InvalidLongitude");
+    }
+
+    #[test]
+    fn test_with_units_rewrites_table() {
+        let content = "# mine\nhide_hud = true\n\n[units]\ntemperature = \"celsius\"\n# keep\nprecipitation=\"mm\"\n\n[location]\nauto = true\n";
+        let updated = with_units(content, &WeatherUnits::imperial());
+
+        assert_eq!(
+            updated,
+            "# mine\nhide_hud = true\n\n[units]\ntemperature = \"fahrenheit\"\nwind_speed = \"mph\"\nprecipitation = \"inch\"\npressure = \"inhg\"\n# keep\n\n[location]\nauto = true\n"
+        );
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert_eq!(
+            config.units.wind_speed,
+            crate::weather::types::WindSpeedUnit::Mph
+        );
+        assert!(config.hide_hud);
+    }
+
+    #[test]
+    fn test_with_units_adds_table() {
+        let updated = with_units("hide_hud = true", &WeatherUnits::metric());
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert!(updated.starts_with("hide_hud = true\n\n[units]\n"));
+        assert_eq!(
+            config.units.temperature,
+            crate::weather::types::TemperatureUnit::Celsius
+        );
+    }
+
+    #[test]
+    fn test_animation_fps() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.animation.fps, DEFAULT_FPS);
+
+        let config: Config = toml::from_str("[animation]\nfps = 0").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidFps");
+
+        let config: Config = toml::from_str("[animation]\nfps = 12").unwrap();
+        assert_eq!(config.animation.fps, 12);
+        assert_eq!(config.animation.idle_fps, DEFAULT_IDLE_FPS);
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str("[animation]\nidle_fps = 500").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidFps");
+    }
+
+    #[test]
+    fn test_config_watcher_reload() {
+        let path = std::env::temp_dir().join("weathr_test_watch.toml");
+        fs::write(&path, "[theme]\npreset = \"nord\"\n").unwrap();
+        let mut watcher = ConfigWatcher::new(path.clone());
+        assert!(watcher.check().is_none());
+
+        let touch = |secs| {
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+
+        fs::write(&path, "[theme]\npreset = \"gruvbox\"\n").unwrap();
+        touch(1_000);
+        let (previous, current) = watcher.check().unwrap().unwrap();
+        assert_eq!(previous.theme.preset.as_deref(), Some("nord"));
+        assert_eq!(current.theme.preset.as_deref(), Some("gruvbox"));
+        assert!(watcher.check().is_none());
+
+        fs::write(&path, "[location]\nlatitude = 500.0\n").unwrap();
+        touch(2_000);
+        assert_eq!(
+            watcher.check().unwrap().unwrap_err().kind(),
+            "InvalidLatitude"
+        );
+        assert!(watcher.check().is_none());
+
+        fs::remove_file(&path).ok();
+        assert!(watcher.check().is_none());
+    }
+
+    #[test]
+    fn test_config_watcher_ignores_own_writes() {
+        let path = std::env::temp_dir().join("weathr_test_watch_units.toml");
+        fs::write(&path, "[theme]\npreset = \"nord\"\n").unwrap();
+        let mut watcher = ConfigWatcher::new(path.clone());
+
+        Config::save_units(&path, &WeatherUnits::imperial()).unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000))
+            .unwrap();
+        watcher.saved();
+        assert!(watcher.check().is_none());
+
+        // An edit after that is compared with the saved units, not the ones before
+        let edited = fs::read_to_string(&path)
+            .unwrap()
+            .replace("nord", "gruvbox");
+        fs::write(&path, edited).unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(2_000))
+            .unwrap();
+        let (previous, current) = watcher.check().unwrap().unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(previous.units, WeatherUnits::imperial());
+        assert_eq!(current.units, previous.units);
+        assert_eq!(current.theme.preset.as_deref(), Some("gruvbox"));
+    }
+
+    #[test]
+    fn test_describe_sources() {
+        let content = "hide_hud = true\n[location]\nlatitude = 40.0\nauto = false\n";
+        let file: toml::Value = toml::from_str(content).unwrap();
+        let from_file: Config = toml::from_str(content).unwrap();
+        let mut from_env = from_file.clone();
+        from_env.monochrome = true;
+        let mut effective = from_env.clone();
+        effective.animation.fps = 10;
+
+        let text = describe(Some(&file), &from_file, &from_env, &effective);
+        for line in [
+            "hide_hud = true  # config file",
+            "monochrome = true  # environment",
+            "compass = false  # default",
+            "\n[animation]\n",
+            "fps = 10  # command line",
+            "latitude = 40.0  # config file",
+            "longitude = 13.41  # default",
+        ] {
+            assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+        }
+        assert!(text.find("monochrome").unwrap() < text.find("[animation]").unwrap());
+        assert!(!text.contains("IP detection"));
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config: Config = toml::from_str("[location]\nlatitude = 10.0").unwrap();
+        config.apply_env_var("WEATHR_LATITUDE", "40").unwrap();
+        config
+            .apply_env_var("WEATHR_LOCATION_LONGITUDE", "-74.5")
+            .unwrap();
+        config.apply_env_var("WEATHR_THEME", "nord").unwrap();
+        config.apply_env_var("WEATHR_UNITS", "imperial").unwrap();
+        config.apply_env_var("WEATHR_ANIMATION_FPS", "12").unwrap();
+        config.apply_env_var("WEATHR_HIDE_HUD", "yes").unwrap();
+        config
+            .apply_env_var("WEATHR_REFRESH_SECONDS", "600")
+            .unwrap();
+        config.apply_env_var("WEATHR_SCENE", "city").unwrap();
+        config
+            .apply_env_var("WEATHR_THEME_SKY_TOP", "#000000")
+            .unwrap();
+
+        assert_eq!(config.location.latitude, 40.0);
+        assert_eq!(config.location.longitude, -74.5);
+        assert_eq!(config.theme.preset.as_deref(), Some("nord"));
+        assert_eq!(config.theme.sky_top.as_deref(), Some("#000000"));
+        assert_eq!(config.units, WeatherUnits::imperial());
+        assert_eq!(config.animation.fps, 12);
+        assert!(config.hide_hud);
+        assert_eq!(config.refresh_seconds, Some(600));
+        assert_eq!(config.scene.as_deref(), Some("city"));
+
+        let kind = |name, value| {
+            let mut config = Config::default();
+            config
+                .apply_env_var(name, value)
+                .unwrap_err()
+                .kind()
+                .to_string()
+        };
+        assert_eq!(kind("WEATHR_LATITUDE", "north"), "InvalidEnv");
+        assert_eq!(kind("WEATHR_HIDE_HUDD", "true"), "UnknownEnv");
+
+        // Values that parse are checked together, once all of them are in
+        let mut config = Config::default();
+        config.apply_env_var("WEATHR_LATITUDE", "100").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidLatitude");
+        let mut config = Config::default();
+        config.apply_env_var("WEATHR_THEME", "neon").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "UnknownTheme");
+    }
+
+    #[test]
+    fn test_env_override_keeps_path() {
+        let path = std::env::temp_dir().join("weathr_test_env_path.toml");
+        fs::write(&path, "[theme]\npreset = \"gruvbox\"\n").unwrap();
+        let mut config = Config::load(Some(path.clone())).unwrap();
+        config.apply_env_var("WEATHR_THEME", "nord").unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(config.theme.preset.as_deref(), Some("nord"));
+        assert_eq!(config.path, Some(path));
+    }
+
+    #[test]
+    fn test_refresh_and_frame_delay() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(
+            config.refresh_interval(),
+            Duration::from_secs(DEFAULT_REFRESH_SECONDS)
+        );
+        assert_eq!(
+            config.frame_delay(),
+            Duration::from_millis(DEFAULT_FRAME_DELAY_MS)
+        );
+
+        let config: Config =
+            toml::from_str("refresh_seconds = 900\n[animation]\nframe_delay_ms = 250").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.refresh_interval(), Duration::from_secs(900));
+        assert_eq!(config.frame_delay(), Duration::from_millis(250));
+
+        let config: Config = toml::from_str("refresh_seconds = 5").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidRefresh");
+
+        let config: Config = toml::from_str("[animation]\nframe_delay_ms = 0").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidFrameDelay");
+    }
+
+    #[test]
+    fn test_provider() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.provider.name(), DEFAULT_PROVIDER);
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str("provider = \"open-meteo\"").unwrap();
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str("provider = \"dark-sky\"").unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.kind(), "UnknownProvider");
+        assert!(err.to_string().contains("open-meteo"));
+    }
+
+    #[test]
+    fn test_provider_fallback() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.provider.chain(), ["open-meteo", "wttr.in"]);
+
+        let config: Config = toml::from_str("provider = \"wttr.in\"").unwrap();
+        assert_eq!(config.provider.chain(), ["wttr.in"]);
+
+        let content = "[provider]\nname = \"nws\"\nfallback = [\"met-no\", \"nws\"]\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert_eq!(config.provider.chain(), ["nws", "met-no"]);
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str("[provider]\nfallback = []").unwrap();
+        assert_eq!(config.provider.chain(), ["open-meteo"]);
+
+        let config: Config = toml::from_str("[provider]\nfallback = [\"dark-sky\"]").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "UnknownProvider");
+
+        let config: Config = toml::from_str("[provider]\nfallback = [\"pirateweather\"]").unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.kind(), "MissingApiKey");
+        assert!(err.to_string().contains("[provider.pirateweather]"));
+    }
+
+    #[test]
+    fn test_provider_table_with_api_key() {
+        let content = "[provider]\nname = \"openweathermap\"\n\n\
+                       [provider.openweathermap]\napi_key = \"0123abcd\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert_eq!(config.provider.name(), "openweathermap");
+        assert_eq!(
+            config.provider.openweathermap.api_key.as_deref(),
+            Some("0123abcd")
+        );
+        assert!(config.validate().is_ok());
+
+        let text = describe(None, &config, &config, &config);
+        assert!(text.contains("api_key = \"********\""), "{}", text);
+        assert!(!text.contains("0123abcd"));
+
+        let config: Config = toml::from_str("provider = \"openweathermap\"").unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.kind(), "MissingApiKey");
+        assert!(err.to_string().contains("[provider.openweathermap]"));
+    }
+
+    #[test]
+    fn test_custom_provider() {
+        let content = "[provider]\nname = \"custom\"\n\n\
+                       [provider.custom]\nurl = \"http://station.local/now\"\n\n\
+                       [provider.custom.units]\ntemperature = \"fahrenheit\"\n\n\
+                       [provider.custom.fields]\ntemperature = \"$.temp_f\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.provider.custom.units.temperature,
+            crate::weather::types::TemperatureUnit::Fahrenheit
+        );
+
+        let config: Config = toml::from_str("provider = \"custom\"").unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.kind(), "InvalidCustomProvider");
+        assert!(err.to_string().contains("url"));
+
+        let content = content.replace("$.temp_f", "temp_f");
+        let config: Config = toml::from_str(&content).unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("fields.temperature"), "{}", err);
+
+        let content = "[provider.custom.fields]\ntemprature = \"$.t\"\n";
+        assert!(toml::from_str::<Config>(content).is_err());
+    }
+
+    #[test]
+    fn test_mqtt_sensors() {
+        let content = "[provider.mqtt]\nbroker = \"mqtt://192.168.1.20\"\npassword = \"hunter2\"\n\n\
+                       [provider.mqtt.topics]\ntemperature = \"home/weather/temperature\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+        let text = describe(None, &config, &config, &config);
+        assert!(text.contains("password = \"********\""), "{}", text);
+
+        let config: Config =
+            toml::from_str(&content.replace("mqtt://192.168.1.20", "mqtts://broker")).unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidMqtt");
+
+        let config: Config =
+            toml::from_str("[provider.mqtt.topics]\nrain = \"home/rain\"").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidMqtt");
+
+        let config: Config = toml::from_str("[provider.mqtt]\nbroker = \"broker\"").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidMqtt");
+    }
+
+    #[test]
+    fn test_publish() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.publish.topic(), DEFAULT_PUBLISH_TOPIC);
+
+        let content = "[publish]\nbroker = \"mqtt://192.168.1.20\"\ntopic = \"home/weathr\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.publish.topic(), "home/weathr");
+
+        let config: Config = toml::from_str(&content.replace("home/weathr", "home/#")).unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidPublish");
+
+        let config: Config = toml::from_str("[publish]\nhome_assistant = true").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidPublish");
+    }
+
+    #[test]
+    fn test_notifications() {
+        let content = "[notifications]\nenabled = true\nalert_severity = \"moderate\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.notifications.enabled);
+        assert!(config.notifications.condition_change);
+        assert_eq!(config.notifications.alert_severity, AlertSeverity::Moderate);
+
+        assert!(toml::from_str::<Config>("[notifications]\nalert_severity = \"dire\"").is_err());
+    }
+
+    #[test]
+    fn test_metar_station() {
+        let content = "[provider]\nname = \"metar\"\n\n[provider.metar]\nstation = \"KSFO\"\n";
+        let config: Config = toml::from_str(content).unwrap();
+        assert!(config.validate().is_ok());
+
+        let config: Config = toml::from_str("provider = \"metar\"").unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "MissingStation");
+
+        let config: Config = toml::from_str(&content.replace("KSFO", "SFO")).unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidStation");
+
+        // From the environment, in the sorted order apply_env goes in
+        let mut config = Config::default();
+        config.apply_env_var("WEATHR_PROVIDER", "metar").unwrap();
+        config.apply_env_var("WEATHR_STATION", "KSFO").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.provider.name(), "metar");
+        assert_eq!(config.provider.metar.station.as_deref(), Some("KSFO"));
+    }
+
+    #[test]
+    fn test_provider_env_keeps_api_key() {
+        let mut config: Config =
+            toml::from_str("[provider.openweathermap]\napi_key = \"0123abcd\"").unwrap();
+        config
+            .apply_env_var("WEATHR_PROVIDER", "openweathermap")
+            .unwrap();
+        assert_eq!(config.provider.name(), "openweathermap");
+        assert_eq!(
+            config.provider.openweathermap.api_key.as_deref(),
+            Some("0123abcd")
+        );
+
+        let mut config: Config = toml::from_str("provider = \"openweathermap\"").unwrap();
+        assert!(
+            config
+                .apply_env_var("WEATHR_PROVIDER_OPENWEATHERMAP_API_KEY", "beef42")
+                .is_ok()
+        );
+        assert_eq!(
+            config.provider.openweathermap.api_key.as_deref(),
+            Some("beef42")
+        );
+    }
+
+    #[test]
+    fn test_provider_and_api_key_from_env() {
+        // Applied in sorted order, the provider comes before its key
+        let mut config = Config::default();
+        config
+            .apply_env_var("WEATHR_PROVIDER", "openweathermap")
+            .unwrap();
+        config
+            .apply_env_var("WEATHR_PROVIDER_OPENWEATHERMAP_API_KEY", "abc")
+            .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.provider.name(), "openweathermap");
+        assert_eq!(
+            config.provider.openweathermap.api_key.as_deref(),
+            Some("abc")
+        );
+    }
 }
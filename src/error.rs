@@ -16,6 +16,17 @@ pub enum WeatherError {
     Geolocation(#[from] GeolocationError),
 }
 
+impl WeatherError {
+    pub fn user_friendly_message(&self) -> String {
+        match self {
+            WeatherError::Network(net_err) => net_err.user_friendly_message(),
+            WeatherError::Terminal(term_err) => term_err.user_friendly_message(),
+            WeatherError::Geolocation(geo_err) => geo_err.user_friendly_message(),
+            _ => format!("Failed to fetch weather: {}", self),
+        }
+    }
+}
+
 #[derive(ThisError, Debug)]
 pub enum NetworkError {
     #[error("failed to create HTTP client: {0}")]
@@ -49,6 +60,9 @@ pub enum NetworkError {
         source: reqwest::Error,
     },
 
+    #[error("no weather data from {url}: {reason}")]
+    Unavailable { url: String, reason: String },
+
     #[error("network error: {0}")]
     Other(#[from] reqwest::Error),
 }
@@ -121,6 +135,9 @@ impl NetworkError {
             NetworkError::JsonParse { url, .. } => {
                 format!("Received invalid data from {url}")
             }
+            NetworkError::Unavailable { url, reason } => {
+                format!("No weather data from {url}: {reason}")
+            }
             NetworkError::ClientCreation(_) => "Failed to initialize HTTP client".to_string(),
             NetworkError::Other(e) => format!("Network error: {e}"),
         }
@@ -136,6 +153,13 @@ pub enum ConfigError {
         source: io::Error,
     },
 
+    #[error("failed to write config file at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
     #[error("invalid TOML syntax in config file")]
     ParseError(#[from] toml::de::Error),
 
@@ -147,6 +171,48 @@ pub enum ConfigError {
 
     #[error("invalid longitude: {0} (must be between -180 and 180)")]
     InvalidLongitude(f64),
+
+    #[error("invalid frame rate: {0} fps (must be between 1 and 120)")]
+    InvalidFps(u32),
+
+    #[error("refresh interval too short: {0}s (must be at least 60)")]
+    InvalidRefresh(u64),
+
+    #[error("frame delay too short: {0}ms (must be at least 50)")]
+    InvalidFrameDelay(u64),
+
+    #[error("unknown theme '{0}' (available: {list})", list = crate::theme::PRESETS.join(", "))]
+    UnknownTheme(String),
+
+    #[error("unknown weather provider '{0}' (available: {list})", list = crate::weather::provider::PROVIDERS.join(", "))]
+    UnknownProvider(String),
+
+    #[error("the {0} provider needs an api_key under [provider.{0}]")]
+    MissingApiKey(String),
+
+    #[error("invalid [provider.custom]: {0}")]
+    InvalidCustomProvider(String),
+
+    #[error("invalid [provider.mqtt]: {0}")]
+    InvalidMqtt(String),
+
+    #[error("invalid [publish]: {0}")]
+    InvalidPublish(String),
+
+    #[error("the metar provider needs a station under [provider.metar], like station = \"KSFO\"")]
+    MissingStation,
+
+    #[error("invalid station '{0}' (an ICAO code of four letters or digits, like KSFO)")]
+    InvalidStation(String),
+
+    #[error("invalid color for theme.{key}: '{value}' (use \"#rrggbb\" or a color name)")]
+    InvalidColor { key: String, value: String },
+
+    #[error("invalid value for {name}: '{value}'")]
+    InvalidEnv { name: String, value: String },
+
+    #[error("{0} doesn't match any setting")]
+    UnknownEnv(String),
 }
 
 impl ConfigError {
@@ -154,14 +220,51 @@ impl ConfigError {
     pub fn kind(&self) -> &str {
         match self {
             ConfigError::ReadError { .. } => "ReadError",
+            ConfigError::WriteError { .. } => "WriteError",
             ConfigError::ParseError(_) => "ParseError",
             ConfigError::NoConfigDir => "NoConfigDir",
             ConfigError::InvalidLatitude(_) => "InvalidLatitude",
             ConfigError::InvalidLongitude(_) => "InvalidLongitude",
+            ConfigError::InvalidFps(_) => "InvalidFps",
+            ConfigError::InvalidRefresh(_) => "InvalidRefresh",
+            ConfigError::InvalidFrameDelay(_) => "InvalidFrameDelay",
+            ConfigError::UnknownTheme(_) => "UnknownTheme",
+            ConfigError::UnknownProvider(_) => "UnknownProvider",
+            ConfigError::MissingApiKey(_) => "MissingApiKey",
+            ConfigError::InvalidCustomProvider(_) => "InvalidCustomProvider",
+            ConfigError::InvalidMqtt(_) => "InvalidMqtt",
+            ConfigError::InvalidPublish(_) => "InvalidPublish",
+            ConfigError::MissingStation => "MissingStation",
+            ConfigError::InvalidStation(_) => "InvalidStation",
+            ConfigError::InvalidColor { .. } => "InvalidColor",
+            ConfigError::InvalidEnv { .. } => "InvalidEnv",
+            ConfigError::UnknownEnv(_) => "UnknownEnv",
         }
     }
 }
 
+#[derive(ThisError, Debug)]
+pub enum SceneError {
+    #[error("invalid scene name '{0}' (use the file name without .txt)")]
+    InvalidName(String),
+
+    #[error("scene '{name}' not found (expected {path})")]
+    NotFound { name: String, path: String },
+
+    #[error("failed to read scene file at {path}")]
+    ReadError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("scene '{0}' has no art in it")]
+    Empty(String),
+
+    #[error("{0}")]
+    Config(#[from] ConfigError),
+}
+
 #[derive(ThisError, Debug)]
 pub enum TerminalError {
     #[error("terminal is too small (minimum: {min_width}x{min_height}, current: {width}x{height})")]
@@ -260,7 +363,7 @@ impl GeolocationError {
                 NetworkError::ClientCreation(_) => "Failed to initialize network client.\n\
                      Using configured/default location."
                     .to_string(),
-                NetworkError::Other(_) => {
+                NetworkError::Unavailable { .. } | NetworkError::Other(_) => {
                     "Cannot auto-detect location. Check your internet connection.\n\
                      Using configured/default location."
                         .to_string()
@@ -0,0 +1,280 @@
+use crate::cache;
+use crate::error::NetworkError;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const ELEVATION_URL: &str = "https://api.open-meteo.com/v1/elevation";
+const MARINE_URL: &str = "https://marine-api.open-meteo.com/v1/marine";
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+const LOOKUP_TIMEOUT_SECS: u64 = 5;
+
+pub const MOUNTAIN_MIN_ELEVATION_M: f64 = 1500.0;
+pub const CITY_MIN_POPULATION: u64 = 500_000;
+// How far the geocoded place may be from the coordinates and still count as the same one
+const GEOCODE_MAX_DISTANCE_DEG: f64 = 0.5;
+
+// GeoNames feature codes for capitals and regional seats
+const CITY_FEATURE_CODES: &[&str] = &["PPLC", "PPLA", "PPLG"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Geography {
+    pub elevation: Option<f64>,
+    pub coastal: bool,
+    pub feature_code: Option<String>,
+    pub population: Option<u64>,
+}
+
+impl Geography {
+    fn is_city(&self) -> bool {
+        self.population.is_some_and(|p| p >= CITY_MIN_POPULATION)
+            || self
+                .feature_code
+                .as_deref()
+                .is_some_and(|code| CITY_FEATURE_CODES.contains(&code))
+    }
+}
+
+// Mountains win over a skyline, and a skyline over the sea, since a coastal capital
+// looks more like a city than a beach
+pub fn pick_scene(geography: &Geography) -> &'static str {
+    if geography
+        .elevation
+        .is_some_and(|e| e >= MOUNTAIN_MIN_ELEVATION_M)
+    {
+        "mountain"
+    } else if geography.is_city() {
+        "city"
+    } else if geography.coastal {
+        "beach"
+    } else {
+        "house"
+    }
+}
+
+#[derive(Deserialize)]
+struct ElevationResponse {
+    elevation: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+struct MarineCurrent {
+    wave_height: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct MarineResponse {
+    current: Option<MarineCurrent>,
+}
+
+#[derive(Deserialize)]
+struct GeocodingResult {
+    latitude: f64,
+    longitude: f64,
+    feature_code: Option<String>,
+    population: Option<u64>,
+    #[serde(default)]
+    name: String,
+    admin1: Option<String>,
+    country: Option<String>,
+}
+
+// A search result for a place name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub name: String,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Place {
+    // "Portland, Oregon, United States"; the region is left out when it repeats the name
+    pub fn label(&self) -> String {
+        let mut parts = vec![self.name.as_str()];
+        if let Some(region) = self.region.as_deref().filter(|r| *r != self.name) {
+            parts.push(region);
+        }
+        if let Some(ref country) = self.country {
+            parts.push(country);
+        }
+        parts.join(", ")
+    }
+}
+
+// "48.85,2.35" (spaces allowed), within the valid latitude and longitude ranges
+pub fn parse_coordinates(text: &str) -> Option<(f64, f64)> {
+    let (latitude, longitude) = text.split_once(',')?;
+    let latitude: f64 = latitude.trim().parse().ok()?;
+    let longitude: f64 = longitude.trim().parse().ok()?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+const SEARCH_RESULTS: usize = 5;
+
+// Places matching a name, most populous first as the geocoding API orders them
+pub async fn search_places(name: &str) -> Result<Vec<Place>, NetworkError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(LOOKUP_TIMEOUT_SECS))
+        .build()
+        .map_err(NetworkError::ClientCreation)?;
+    let network_error = |e| NetworkError::from_reqwest(e, GEOCODING_URL, LOOKUP_TIMEOUT_SECS);
+    // Same as fetch_place: the base URL is a constant, so only the name could be off
+    let count = SEARCH_RESULTS.to_string();
+    let Ok(url) =
+        reqwest::Url::parse_with_params(GEOCODING_URL, &[("name", name), ("count", &count)])
+    else {
+        return Ok(Vec::new());
+    };
+
+    let body: GeocodingResponse = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(network_error)?
+        .json()
+        .await
+        .map_err(network_error)?;
+
+    Ok(body
+        .results
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| Place {
+            name: r.name,
+            region: r.admin1,
+            country: r.country,
+            latitude: r.latitude,
+            longitude: r.longitude,
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct GeocodingResponse {
+    results: Option<Vec<GeocodingResult>>,
+}
+
+// Best effort: any lookup that fails just leaves its field unset
+pub async fn lookup(latitude: f64, longitude: f64, place: Option<&str>) -> Geography {
+    if let Some(cached) = cache::load_cached_geography(latitude, longitude).await {
+        return cached;
+    }
+
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(Duration::from_secs(LOOKUP_TIMEOUT_SECS))
+        .build()
+    else {
+        return Geography::default();
+    };
+
+    let (elevation, coastal, place) = tokio::join!(
+        fetch_elevation(&client, latitude, longitude),
+        fetch_coastal(&client, latitude, longitude),
+        fetch_place(&client, latitude, longitude, place),
+    );
+
+    let (feature_code, population) = match place {
+        Some(place) => (place.feature_code, place.population),
+        None => (None, None),
+    };
+    let geography = Geography {
+        elevation,
+        coastal: coastal.unwrap_or(false),
+        feature_code,
+        population,
+    };
+
+    // Only cache once the core lookups have answered, so an offline start tries again
+    if elevation.is_some() && coastal.is_some() {
+        cache::save_geography_cache(&geography, latitude, longitude);
+    }
+    geography
+}
+
+async fn fetch_elevation(client: &reqwest::Client, latitude: f64, longitude: f64) -> Option<f64> {
+    let url = format!(
+        "{}?latitude={}&longitude={}",
+        ELEVATION_URL, latitude, longitude
+    );
+    let response: ElevationResponse = client.get(&url).send().await.ok()?.json().await.ok()?;
+    response.elevation.first().copied()
+}
+
+// The marine API only has wave data for points on or near the sea
+async fn fetch_coastal(client: &reqwest::Client, latitude: f64, longitude: f64) -> Option<bool> {
+    let url = format!(
+        "{}?latitude={}&longitude={}&current=wave_height",
+        MARINE_URL, latitude, longitude
+    );
+    let response = client.get(&url).send().await.ok()?;
+    if response.status().is_client_error() {
+        return Some(false);
+    }
+    let marine: MarineResponse = response.error_for_status().ok()?.json().await.ok()?;
+    Some(marine.current.is_some_and(|c| c.wave_height.is_some()))
+}
+
+async fn fetch_place(
+    client: &reqwest::Client,
+    latitude: f64,
+    longitude: f64,
+    place: Option<&str>,
+) -> Option<GeocodingResult> {
+    let url =
+        reqwest::Url::parse_with_params(GEOCODING_URL, &[("name", place?), ("count", "1")]).ok()?;
+    let response: GeocodingResponse = client.get(url).send().await.ok()?.json().await.ok()?;
+
+    response.results?.into_iter().find(|r| {
+        (r.latitude - latitude).abs() <= GEOCODE_MAX_DISTANCE_DEG
+            && (r.longitude - longitude).abs() <= GEOCODE_MAX_DISTANCE_DEG
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coordinates() {
+        assert_eq!(parse_coordinates("48.85,2.35"), Some((48.85, 2.35)));
+        assert_eq!(parse_coordinates(" -33.9 , 151.2 "), Some((-33.9, 151.2)));
+        assert_eq!(parse_coordinates("95,2"), None);
+        assert_eq!(parse_coordinates("Paris"), None);
+        assert_eq!(parse_coordinates("Paris, France"), None);
+    }
+
+    #[test]
+    fn test_pick_scene() {
+        let flat = Geography::default();
+        assert_eq!(pick_scene(&flat), "house");
+
+        let coast = Geography {
+            coastal: true,
+            ..Default::default()
+        };
+        assert_eq!(pick_scene(&coast), "beach");
+
+        let capital = Geography {
+            coastal: true,
+            feature_code: Some("PPLC".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(pick_scene(&capital), "city");
+
+        let big_town = Geography {
+            population: Some(2_000_000),
+            ..Default::default()
+        };
+        assert_eq!(pick_scene(&big_town), "city");
+
+        let alps = Geography {
+            elevation: Some(1800.0),
+            population: Some(600_000),
+            ..Default::default()
+        };
+        assert_eq!(pick_scene(&alps), "mountain");
+    }
+}
@@ -1,10 +1,25 @@
+// The weather clients, scene and renderer behind the weathr binary, which is a thin
+// command line front end to `app::App`
 pub mod animation;
 pub mod animation_manager;
+pub mod app;
 pub mod app_state;
 pub mod cache;
 pub mod config;
 pub mod error;
+pub mod geography;
 pub mod geolocation;
+pub mod metrics;
+pub mod mqtt;
+pub mod notify;
+pub mod output;
+pub mod publish;
 pub mod render;
 pub mod scene;
+pub mod serve;
+pub mod setup;
+pub mod simulation;
+pub mod theme;
+pub mod ui;
 pub mod weather;
+pub mod widget;
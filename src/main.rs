@@ -1,24 +1,15 @@
-mod animation;
-mod animation_manager;
-mod app;
-mod app_state;
-mod cache;
-mod config;
-mod error;
-mod geolocation;
-mod render;
-mod scene;
-mod weather;
-
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use config::Config;
-use crossterm::{
-    cursor, execute,
-    style::ResetColor,
-    terminal::{LeaveAlternateScreen, disable_raw_mode},
-};
 use render::TerminalRenderer;
+use simulation::Simulation;
+use std::path::PathBuf;
 use std::{io, panic};
+use weather::provider::{PROVIDERS, create_providers};
+use weather::{WeatherClient, WeatherLocation};
+use weathr::{
+    app, config, geography, geolocation, metrics, output, render, scene, serve, setup, simulation,
+    theme, weather,
+};
 
 const LONG_VERSION: &str = concat!(
     env!("CARGO_PKG_VERSION"),
@@ -32,16 +23,79 @@ fn info(silent: bool, msg: &str) {
     }
 }
 
+// "10s", "2m" or "1h"; a bare number is seconds
+fn parse_duration(value: &str) -> Result<std::time::Duration, String> {
+    let (number, seconds_per) = match value.trim().char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 3600),
+        _ => (value, 1),
+    };
+    match number.trim().parse::<f64>() {
+        Ok(n) if n > 0.0 && n.is_finite() => {
+            Ok(std::time::Duration::from_secs_f64(n * seconds_per as f64))
+        }
+        _ => Err("must be a length of time like 10s, 2m or 1h".to_string()),
+    }
+}
+
+fn at_least(value: &str, min: u64) -> Result<u64, String> {
+    match value.parse::<u64>() {
+        Ok(n) if n >= min => Ok(n),
+        Ok(_) => Err(format!("must be at least {}", min)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_station(value: &str) -> Result<String, String> {
+    if weather::providers::metar::is_icao_code(value) {
+        Ok(value.to_uppercase())
+    } else {
+        Err("must be a four-character ICAO code like KSFO".to_string())
+    }
+}
+
+// A day in Open-Meteo's archive, which starts in 1940 and ends before today
+fn parse_date(value: &str) -> Result<chrono::NaiveDate, String> {
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| "must be a date like 2021-07-14".to_string())?;
+    let today = chrono::Local::now().date_naive();
+    if date < weather::history::earliest_date() {
+        Err("the archive starts in 1940".to_string())
+    } else if date >= today {
+        Err("must be a day before today".to_string())
+    } else {
+        Ok(date)
+    }
+}
+
+// A local time of day for --time
+fn parse_time(value: &str) -> Result<chrono::NaiveTime, String> {
+    chrono::NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| "must be a time like 19:30".to_string())
+}
+
 #[derive(Parser)]
 #[command(version, long_version = LONG_VERSION, about = "Terminal-based ASCII weather application", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Use this config file instead of the default one (e.g. one per place)"
+    )]
+    config: Option<PathBuf>,
+
     #[arg(
         short,
         long,
-        value_name = "CONDITION",
-        help = "Simulate weather condition (clear, rain, drizzle, snow, etc.)"
+        value_name = "CONDITION[,KEY=VALUE...]",
+        help = "Simulate weather condition (clear, rain, drizzle, snow, etc.), optionally with readings such as rain,temp=2,wind=45,night"
     )]
-    simulate: Option<String>,
+    simulate: Option<Simulation>,
 
     #[arg(
         short,
@@ -50,46 +104,304 @@ struct Cli {
     )]
     night: bool,
 
-    #[arg(short, long, help = "Enable falling autumn leaves")]
+    #[arg(
+        long,
+        value_name = "HH:MM",
+        requires = "simulate",
+        value_parser = parse_time,
+        help = "With --simulate, show the sun and sky at this local time (the day runs 06:00 to 20:00)"
+    )]
+    time: Option<chrono::NaiveTime>,
+
+    #[arg(short, long, help = "Show falling autumn leaves in any season")]
     leaves: bool,
 
-    #[arg(long, help = "Auto-detect location via IP (uses ipinfo.io)")]
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = parse_date,
+        conflicts_with = "simulate",
+        help = "Replay a past day's weather as a timelapse, an hour every 5 seconds"
+    )]
+    date: Option<chrono::NaiveDate>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["simulate", "date"],
+        help = "Cycle through every weather condition, by day and by night, 10 seconds each"
+    )]
+    demo: bool,
+
+    #[arg(
+        long,
+        value_name = "TIME",
+        value_parser = parse_duration,
+        help = "Quit after this long, e.g. 10s or 2m, as a greeting in a shell's startup file"
+    )]
+    duration: Option<std::time::Duration>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Record the session to FILE: an asciinema recording for .cast, the raw terminal output otherwise"
+    )]
+    record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Save the scene to FILE as text once the weather is in, then exit"
+    )]
+    screenshot: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "screenshot",
+        help = "Save the scene to FILE as a PNG image once the weather is in, then exit"
+    )]
+    screenshot_png: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Scene to draw: house, city, beach, mountain, forest or a custom scene file"
+    )]
+    scene: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FPS",
+        value_parser = clap::value_parser!(u32).range(1..=config::MAX_FPS as i64),
+        help = "Target frame rate of the animation (default 30)"
+    )]
+    fps: Option<u32>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        value_parser = |v: &str| at_least(v, config::MIN_REFRESH_SECONDS),
+        help = "Seconds between weather updates (default 300, at least 60)"
+    )]
+    refresh: Option<u64>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "MS",
+        value_parser = |v: &str| at_least(v, config::MIN_FRAME_DELAY_MS),
+        help = "Milliseconds each frame of the sun animation is shown (default 500)"
+    )]
+    frame_delay: Option<u64>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Draw a still scene that only changes with the weather (reduced motion)"
+    )]
+    no_animation: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Draw rain and snow with braille dots for smoother motion (needs a font with braille)"
+    )]
+    braille: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Color theme: default, gruvbox, solarized, nord, colorblind-{deuteranopia,protanopia,tritanopia} or high-contrast"
+    )]
+    theme: Option<String>,
+
+    #[arg(long, global = true, help = "Draw without colors (like NO_COLOR)")]
+    monochrome: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Draw the sky, sun and moon as an image (kitty graphics protocol: kitty, Ghostty, WezTerm; the sun and moon as a sixel elsewhere)"
+    )]
+    graphics: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PLACE",
+        conflicts_with = "auto_location",
+        help = "Show this place for this run: \"48.85,2.35\" or a name like \"Paris\""
+    )]
+    location: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        value_parser = clap::builder::PossibleValuesParser::new(PROVIDERS),
+        help = "Where weather data comes from (default open-meteo)"
+    )]
+    provider: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "ICAO",
+        value_parser = parse_station,
+        help = "Show the METAR reports of an airport's weather station, e.g. KSFO"
+    )]
+    station: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Auto-detect location via IP (uses ipinfo.io)"
+    )]
     auto_location: bool,
 
-    #[arg(long, help = "Hide location coordinates in UI")]
+    #[arg(long, global = true, help = "Hide location coordinates in UI")]
     hide_location: bool,
 
-    #[arg(long, help = "Hide HUD (status line)")]
+    #[arg(long, global = true, help = "Hide HUD (status line)")]
     hide_hud: bool,
 
     #[arg(
         long,
+        global = true,
         conflicts_with = "metric",
-        help = "Use imperial units (°F, mph, inch)"
+        help = "Use imperial units (°F, mph, inch, inHg)"
     )]
     imperial: bool,
 
     #[arg(
         long,
+        global = true,
         conflicts_with = "imperial",
-        help = "Use metric units (°C, km/h, mm)"
+        help = "Use metric units (°C, km/h, mm, hPa)"
     )]
     metric: bool,
 
     #[arg(long, help = "Run silently (suppress non-error output)")]
     silent: bool,
+
+    #[arg(long, help = "Print current weather as JSON and exit")]
+    json: bool,
+
+    #[arg(
+        long,
+        value_name = "PORT",
+        help = "Export the weather as Prometheus metrics on http://0.0.0.0:PORT/metrics"
+    )]
+    metrics_port: Option<u16>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Print a one-line summary of the current weather and exit")]
+    Now,
+    #[command(about = "Print a compact status line for status bars (waybar, polybar) and exit")]
+    Bar {
+        #[arg(long, value_enum, default_value_t = BarFormat::Plain, help = "Output format")]
+        format: BarFormat,
+    },
+    #[command(
+        about = "Serve the weather and the scene over HTTP: /weather.json, /frame.txt and /frame.ansi"
+    )]
+    Serve {
+        #[arg(long, default_value_t = 8080, help = "Port to listen on")]
+        port: u16,
+        #[arg(long, default_value = "0.0.0.0", help = "Address to listen on")]
+        bind: String,
+    },
+    #[command(about = "Manage the config file")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    #[command(about = "Answer a few questions and write a commented config file")]
+    Init,
+    #[command(about = "Print the effective configuration and where each value comes from")]
+    Show,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BarFormat {
+    Plain,
+    Waybar,
+}
+
+// Coordinates as given, or the best match for a place name with its full name
+async fn resolve_location(query: &str) -> Result<(f64, f64, Option<String>), String> {
+    if let Some((latitude, longitude)) = geography::parse_coordinates(query) {
+        return Ok((latitude, longitude, None));
+    }
+    let places = geography::search_places(query)
+        .await
+        .map_err(|e| e.user_friendly_message())?;
+    match places.into_iter().next() {
+        Some(place) => Ok((place.latitude, place.longitude, Some(place.label()))),
+        None => Err(format!("No place called '{}' found", query)),
+    }
+}
+
+async fn fetch_weather_once(config: &Config) -> weather::WeatherData {
+    let location = WeatherLocation {
+        latitude: config.location.latitude,
+        longitude: config.location.longitude,
+        elevation: None,
+    };
+    let client = WeatherClient::new(
+        create_providers(&config.provider),
+        config.refresh_interval(),
+    );
+
+    match client.get_current_weather(&location, &config.units).await {
+        Ok(weather) => weather,
+        Err(e) => {
+            eprintln!("{}", e.user_friendly_message());
+            std::process::exit(1);
+        }
+    }
+}
+
+// Ctrl+C normally arrives as a key press in raw mode; this covers `kill`, a closed
+// terminal window and SIGINT sent from elsewhere
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let (Ok(mut terminate), Ok(mut hangup)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::hangup()),
+        ) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+                _ = hangup.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
 }
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
     let default_hook = panic::take_hook();
     panic::set_hook(Box::new(move |info| {
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show, ResetColor);
+        let _ = render::restore_terminal();
         default_hook(info);
     }));
 
-    let cli = match Cli::try_parse() {
+    let mut cli = match Cli::try_parse() {
         Ok(cli) => cli,
         Err(err) => {
             let err_str = err.to_string();
@@ -124,6 +436,12 @@ async fn main() -> io::Result<()> {
                 eprintln!("  weathr --simulate rain");
                 eprintln!("  weathr --simulate snow --night");
                 eprintln!("  weathr -s thunderstorm -n");
+                eprintln!();
+                eprintln!("Readings can follow the condition, in your display units:");
+                eprintln!(
+                    "  temp, wind, precip, dir, humidity, clouds, pressure, uv, and night or day"
+                );
+                eprintln!("  weathr --simulate 'rain,temp=2,wind=45,night,precip=8'");
                 std::process::exit(1);
             } else {
                 err.exit();
@@ -131,8 +449,29 @@ async fn main() -> io::Result<()> {
         }
     };
 
-    let mut config = match Config::load() {
+    // Runs before the config is loaded, since there may not be one yet
+    if let Some(Command::Config {
+        action: ConfigAction::Init,
+    }) = cli.command
+    {
+        match setup::run(cli.config).await {
+            Ok(Some(path)) => println!("Wrote {}", path.display()),
+            Ok(None) => println!("Kept the existing config file"),
+            Err(e) => {
+                eprintln!("Setup failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let mut config = match Config::load(cli.config.clone()) {
         Ok(config) => config,
+        // Settings asked for by name aren't silently swapped for the defaults
+        Err(e) if cli.config.is_some() => {
+            eprintln!("Error loading config: {}", e);
+            std::process::exit(1);
+        }
         Err(e) => {
             eprintln!("Error loading config: {}", e);
             eprintln!("\nAuto-detecting location via IP...");
@@ -150,6 +489,15 @@ async fn main() -> io::Result<()> {
             Config::default()
         }
     };
+    let from_file = config.clone();
+    for e in config.apply_env() {
+        eprintln!("Ignoring environment override: {}", e);
+    }
+    if let Err(e) = config.validate() {
+        eprintln!("Error in the WEATHR_ environment variables: {}", e);
+        std::process::exit(1);
+    }
+    let from_env = config.clone();
 
     // CLI Overrides
     if cli.auto_location {
@@ -161,15 +509,98 @@ async fn main() -> io::Result<()> {
     if cli.hide_hud {
         config.hide_hud = true;
     }
+    if cli.scene.is_some() {
+        config.scene = cli.scene;
+    }
+    if cli.theme.is_some() {
+        config.theme.preset = cli.theme;
+    }
+    if cli.monochrome {
+        config.monochrome = true;
+    }
+    if cli.graphics {
+        config.graphics = true;
+    }
+    if cli.no_animation {
+        config.animation.reduce_motion = true;
+    }
+    if cli.braille {
+        config.animation.braille = true;
+    }
+    if let Some(fps) = cli.fps {
+        config.animation.fps = fps;
+    }
+    if cli.refresh.is_some() {
+        config.refresh_seconds = cli.refresh;
+    }
+    if cli.provider.is_some() {
+        config.provider.name = cli.provider;
+    }
+    if let Some(station) = cli.station {
+        config.provider.name = Some("metar".to_string());
+        config.provider.metar.station = Some(station);
+    }
+    if let Some(delay) = cli.frame_delay {
+        config.animation.frame_delay_ms = delay;
+    }
     if cli.imperial {
         config.units = weather::WeatherUnits::imperial();
     }
     if cli.metric {
         config.units = weather::WeatherUnits::metric();
     }
-    if cli.silent {
+    if cli.silent || cli.json || cli.command.is_some() {
         config.silent = true;
     }
+    // Flags are held to the same rules as the config file, e.g. --provider needs its key
+    if let Err(e) = config.validate() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    let mut detected_city = None;
+    if let Some(ref query) = cli.location {
+        let (latitude, longitude, name) = match resolve_location(query).await {
+            Ok(place) => place,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        };
+        if let Some(ref name) = name {
+            info(
+                config.silent,
+                &format!("Location: {} ({:.4}, {:.4})", name, latitude, longitude),
+            );
+        }
+        config.location.latitude = latitude;
+        config.location.longitude = longitude;
+        config.location.auto = false;
+        detected_city = name;
+    }
+
+    if let Some(Command::Config {
+        action: ConfigAction::Show,
+    }) = cli.command
+    {
+        let path = config.path.clone();
+        let file = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok());
+        match (&path, &file) {
+            (Some(path), Some(_)) => println!("# Config file: {}", path.display()),
+            (Some(path), None) => {
+                println!("# Config file: {} (not found or invalid)", path.display())
+            }
+            (None, _) => println!("# No config directory"),
+        }
+        print!(
+            "{}",
+            config::describe(file.as_ref(), &from_file, &from_env, &config)
+        );
+        return Ok(());
+    }
 
     // Auto-detect location if enabled
     if config.location.auto {
@@ -195,6 +626,7 @@ async fn main() -> io::Result<()> {
                 }
                 config.location.latitude = geo_loc.latitude;
                 config.location.longitude = geo_loc.longitude;
+                detected_city = geo_loc.city;
             }
             Err(e) => {
                 eprintln!("{}", e.user_friendly_message());
@@ -202,6 +634,135 @@ async fn main() -> io::Result<()> {
         }
     }
 
+    if cli.json {
+        let weather = fetch_weather_once(&config).await;
+        match output::format_json(&weather) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize weather data: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(command) = cli
+        .command
+        .take_if(|command| !matches!(command, Command::Serve { .. }))
+    {
+        let weather = fetch_weather_once(&config).await;
+        let line = match command {
+            Command::Now => output::format_summary(&weather, &config.units),
+            Command::Bar {
+                format: BarFormat::Plain,
+            } => output::format_bar(&weather, &config.units),
+            Command::Bar {
+                format: BarFormat::Waybar,
+            } => output::format_waybar(&weather, &config.units),
+            // Handled before the config was loaded, and below
+            Command::Config { .. } | Command::Serve { .. } => return Ok(()),
+        };
+        println!("{}", line);
+        return Ok(());
+    }
+
+    let mode = match (cli.simulate, cli.date) {
+        _ if cli.demo => app::WeatherMode::Demo,
+        (Some(mut simulation), _) => {
+            if cli.night {
+                simulation.is_day = false;
+            }
+            simulation.time = cli.time;
+            app::WeatherMode::Simulated(simulation)
+        }
+        (None, Some(date)) => {
+            info(
+                config.silent,
+                &format!("Fetching the weather of {}...", date),
+            );
+            let location = WeatherLocation {
+                latitude: config.location.latitude,
+                longitude: config.location.longitude,
+                elevation: None,
+            };
+            match weather::history::fetch_day(&location, date).await {
+                Ok(hours) => app::WeatherMode::Replay(hours),
+                Err(e) => {
+                    eprintln!("{}", e.user_friendly_message());
+                    std::process::exit(1);
+                }
+            }
+        }
+        (None, None) => app::WeatherMode::Live,
+    };
+
+    // Demo, simulated and replayed weather keep the house rather than wait on a lookup
+    if config.scene.is_none() && matches!(mode, app::WeatherMode::Live) {
+        let geography = geography::lookup(
+            config.location.latitude,
+            config.location.longitude,
+            detected_city.as_deref(),
+        )
+        .await;
+        let name = geography::pick_scene(&geography);
+        info(config.silent, &format!("Scene: {}", name));
+        config.scene = Some(name.to_string());
+    }
+
+    let midground = match config.scene.as_deref() {
+        Some(name) => scene::Midground::from_name(name).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            eprintln!("Falling back to the default scene");
+            scene::Midground::default()
+        }),
+        None => scene::Midground::default(),
+    };
+    // A bad theme was already turned away by validate, wherever it came from
+    let theme = theme::Theme::from_config(&config.theme).unwrap_or_default();
+
+    if matches!(midground, scene::Midground::House(_)) {
+        config.scene = None;
+    }
+
+    let metrics = match cli.metrics_port {
+        Some(port) => {
+            let listener = match tokio::net::TcpListener::bind(("0.0.0.0", port)).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("Can't listen on port {}: {}", port, e);
+                    std::process::exit(1);
+                }
+            };
+            let metrics = std::sync::Arc::new(metrics::Metrics::default());
+            tokio::spawn(std::sync::Arc::clone(&metrics).serve(listener));
+            Some(metrics)
+        }
+        None => None,
+    };
+
+    if let Some(Command::Serve { port, bind }) = cli.command {
+        let listener = match tokio::net::TcpListener::bind((bind.as_str(), port)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("Can't listen on {}:{}: {}", bind, port, e);
+                std::process::exit(1);
+            }
+        };
+        info(
+            cli.silent,
+            &format!("Serving the weather on http://{}:{}/", bind, port),
+        );
+        let simulation = match mode {
+            app::WeatherMode::Simulated(simulation) => Some(simulation),
+            _ => None,
+        };
+        tokio::select! {
+            result = serve::run(&config, simulation, midground, theme, listener, metrics) => result?,
+            _ = shutdown_signal() => {}
+        }
+        return Ok(());
+    }
+
     let mut renderer = match TerminalRenderer::new() {
         Ok(r) => r,
         Err(e) => {
@@ -210,25 +771,59 @@ async fn main() -> io::Result<()> {
         }
     };
 
+    if config.monochrome {
+        renderer.set_monochrome();
+    }
+    renderer.set_bold(theme.high_contrast);
+    // Images need colors to be worth drawing
+    if config.graphics && !config.monochrome && !renderer.enable_graphics() {
+        eprintln!(
+            "This terminal draws neither kitty graphics nor sixels; drawing the sky with text"
+        );
+    }
+
+    if let Some(path) = &cli.record
+        && let Err(e) = renderer.record(path)
+    {
+        eprintln!("Can't record to {}: {}", path.display(), e);
+        std::process::exit(1);
+    }
+
     if let Err(e) = renderer.init() {
         eprintln!("\n{}\n", e.user_friendly_message());
         std::process::exit(1);
     };
 
+    // Focus reports are only needed to throttle while unfocused
+    if config.animation.throttle_unfocused {
+        let _ = renderer.enable_focus_events();
+    }
+
     let (term_width, term_height) = renderer.get_size();
 
     let mut app = app::App::new(
         &config,
-        cli.simulate,
-        cli.night,
+        mode,
         cli.leaves,
+        midground,
         term_width,
         term_height,
     );
+    if let Some(duration) = cli.duration {
+        app.exit_after(duration);
+    }
+    if let Some(metrics) = metrics {
+        app.export_metrics(metrics);
+    }
+    if let Some(path) = cli.screenshot {
+        app.exit_after_screenshot(path, false);
+    } else if let Some(path) = cli.screenshot_png {
+        app.exit_after_screenshot(path, true);
+    }
 
     let result = tokio::select! {
         res = app.run(&mut renderer) => res,
-        _ = tokio::signal::ctrl_c() => {
+        _ = shutdown_signal() => {
             Ok(())
         }
     };
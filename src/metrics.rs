@@ -0,0 +1,194 @@
+use crate::serve::{self, Response};
+use crate::weather::WeatherData;
+use std::fmt::Write;
+use std::io;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+
+#[derive(Default)]
+struct Readings {
+    weather: Option<WeatherData>,
+    successes: u64,
+    failures: u64,
+    last_success: Option<u64>,
+}
+
+// What --metrics-port exports: the latest weather and how the fetches have gone, in
+// Prometheus' text format
+#[derive(Default)]
+pub struct Metrics {
+    readings: Mutex<Readings>,
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: Option<f64>) {
+    if let Some(value) = value {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+}
+
+impl Metrics {
+    fn readings(&self) -> std::sync::MutexGuard<'_, Readings> {
+        self.readings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Weather that wasn't fetched, like --simulate's, without counting a fetch
+    pub fn set_weather(&self, weather: &WeatherData) {
+        self.readings().weather = Some(weather.clone());
+    }
+
+    pub fn record_success(&self, weather: &WeatherData) {
+        let mut readings = self.readings();
+        readings.weather = Some(weather.clone());
+        readings.successes += 1;
+        readings.last_success = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|since| since.as_secs());
+    }
+
+    pub fn record_failure(&self) {
+        self.readings().failures += 1;
+    }
+
+    // Readings in SI units whatever the display units, as Prometheus expects
+    pub fn render(&self) -> String {
+        let readings = self.readings();
+        let mut out = String::new();
+        if let Some(ref weather) = readings.weather {
+            let condition = serde_json::to_value(weather.condition)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "# HELP weathr_condition_info The current weather condition"
+            );
+            let _ = writeln!(out, "# TYPE weathr_condition_info gauge");
+            let _ = writeln!(
+                out,
+                "weathr_condition_info{{condition=\"{}\"}} 1",
+                condition
+            );
+            gauge(
+                &mut out,
+                "weathr_temperature_celsius",
+                "Air temperature",
+                Some(weather.temperature),
+            );
+            gauge(
+                &mut out,
+                "weathr_apparent_temperature_celsius",
+                "How warm it feels",
+                Some(weather.apparent_temperature),
+            );
+            gauge(
+                &mut out,
+                "weathr_humidity_percent",
+                "Relative humidity",
+                Some(weather.humidity),
+            );
+            gauge(
+                &mut out,
+                "weathr_pressure_hpa",
+                "Air pressure",
+                Some(weather.pressure),
+            );
+            gauge(
+                &mut out,
+                "weathr_wind_speed_meters_per_second",
+                "Wind speed",
+                Some(weather.wind_speed),
+            );
+            gauge(
+                &mut out,
+                "weathr_wind_direction_degrees",
+                "Direction the wind blows from",
+                Some(weather.wind_direction),
+            );
+            gauge(
+                &mut out,
+                "weathr_precipitation_millimeters",
+                "Precipitation in the last hour",
+                Some(weather.precipitation),
+            );
+            gauge(
+                &mut out,
+                "weathr_cloud_cover_percent",
+                "Sky covered by cloud",
+                Some(weather.cloud_cover),
+            );
+            gauge(&mut out, "weathr_uv_index", "UV index", weather.uv_index);
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP weathr_fetches_total Weather fetches, by whether they succeeded"
+        );
+        let _ = writeln!(out, "# TYPE weathr_fetches_total counter");
+        let _ = writeln!(
+            out,
+            "weathr_fetches_total{{result=\"success\"}} {}",
+            readings.successes
+        );
+        let _ = writeln!(
+            out,
+            "weathr_fetches_total{{result=\"failure\"}} {}",
+            readings.failures
+        );
+        gauge(
+            &mut out,
+            "weathr_last_success_timestamp_seconds",
+            "When the weather was last fetched, in Unix time",
+            readings.last_success.map(|time| time as f64),
+        );
+        out
+    }
+
+    // Answers every request with the metrics, whatever its path
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let metrics = Arc::clone(&self);
+            tokio::spawn(async move {
+                let _ = serve::answer(stream, |_| {
+                    Response::ok("text/plain; version=0.0.4; charset=utf-8", metrics.render())
+                })
+                .await;
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+    use crate::weather::{WeatherCondition, WeatherUnits};
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::default();
+        let text = metrics.render();
+        assert!(!text.contains("weathr_temperature_celsius"));
+        assert!(text.contains("weathr_fetches_total{result=\"success\"} 0\n"));
+
+        let weather =
+            Simulation::new(WeatherCondition::Rain, true).weather(&WeatherUnits::imperial());
+        metrics.record_failure();
+        metrics.record_success(&weather);
+        let text = metrics.render();
+        assert!(text.contains("weathr_condition_info{condition=\"rain\"} 1\n"));
+        assert!(
+            text.contains(
+                "# TYPE weathr_temperature_celsius gauge\nweathr_temperature_celsius 20\n"
+            )
+        );
+        assert!(text.contains("weathr_wind_speed_meters_per_second 2.8\n"));
+        assert!(text.contains("weathr_fetches_total{result=\"success\"} 1\n"));
+        assert!(text.contains("weathr_fetches_total{result=\"failure\"} 1\n"));
+        assert!(text.contains("weathr_last_success_timestamp_seconds "));
+    }
+}
@@ -0,0 +1,402 @@
+// Just enough MQTT 3.1.1 to talk to a home broker: connect, subscribe and publish at QoS 0
+// over plain TCP
+use std::io::{self, ErrorKind};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+pub const DEFAULT_PORT: u16 = 1883;
+const KEEP_ALIVE: Duration = Duration::from_secs(60);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+// Keep the message for whoever subscribes later
+const RETAIN: u8 = 0x01;
+const SUBSCRIBE: u8 = 0x82;
+const PINGREQ: u8 = 0xc0;
+const DISCONNECT: u8 = 0xe0;
+
+// "mqtt://host:port", "host:port" or just "host"; mqtts isn't supported
+pub fn parse_broker(url: &str) -> Result<(String, u16), String> {
+    let address = match url.trim().split_once("://") {
+        Some(("mqtt" | "tcp", address)) => address,
+        Some((scheme, _)) => return Err(format!("{}:// isn't supported, use mqtt://", scheme)),
+        None => url.trim(),
+    };
+    let address = address.trim_end_matches('/');
+    let (host, port) = match address.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| format!("'{}' isn't a port number", port))?,
+        ),
+        None => (address, DEFAULT_PORT),
+    };
+    if host.is_empty() || host.contains('/') {
+        return Err(format!("'{}' isn't a broker address", url));
+    }
+    Ok((host.to_string(), port))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectOptions {
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+// MQTT's variable-length "remaining length": seven bits a byte, high bit set when more follow
+fn encode_length(mut length: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+}
+
+fn encode_string(text: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn packet(kind: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![kind];
+    encode_length(body.len(), &mut out);
+    out.extend_from_slice(body);
+    out
+}
+
+fn connect_packet(options: &ConnectOptions) -> Vec<u8> {
+    let mut body = Vec::new();
+    encode_string("MQTT", &mut body);
+    body.push(4);
+    // Clean session, plus whether a username and password follow
+    let mut flags = 0x02;
+    if options.username.is_some() {
+        flags |= 0x80;
+    }
+    if options.password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    body.extend_from_slice(&(KEEP_ALIVE.as_secs() as u16).to_be_bytes());
+    encode_string(&options.client_id, &mut body);
+    for field in [&options.username, &options.password].into_iter().flatten() {
+        encode_string(field, &mut body);
+    }
+    packet(CONNECT, &body)
+}
+
+fn subscribe_packet(packet_id: u16, topics: &[&str]) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    for topic in topics {
+        encode_string(topic, &mut body);
+        body.push(0);
+    }
+    packet(SUBSCRIBE, &body)
+}
+
+// At QoS 0, so without a packet id
+fn publish_packet(topic: &str, payload: &[u8], retain: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    encode_string(topic, &mut body);
+    body.extend_from_slice(payload);
+    packet(if retain { PUBLISH | RETAIN } else { PUBLISH }, &body)
+}
+
+// The first whole packet at the start of `buffer`: its first byte, its body, and how
+// many bytes it took. None until all of it has arrived
+fn split_packet(buffer: &[u8]) -> io::Result<Option<(u8, &[u8], usize)>> {
+    let Some(&kind) = buffer.first() else {
+        return Ok(None);
+    };
+    let mut length = 0usize;
+    for (i, &byte) in buffer.iter().enumerate().skip(1).take(4) {
+        length |= ((byte & 0x7f) as usize) << (7 * (i - 1));
+        if byte & 0x80 == 0 {
+            let start = i + 1;
+            return Ok(buffer
+                .get(start..start + length)
+                .map(|body| (kind, body, start + length)));
+        }
+    }
+    if buffer.len() > 5 {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "malformed packet length",
+        ));
+    }
+    Ok(None)
+}
+
+fn parse_publish(kind: u8, body: &[u8]) -> Option<Message> {
+    let topic_length = u16::from_be_bytes([*body.first()?, *body.get(1)?]) as usize;
+    let topic = std::str::from_utf8(body.get(2..2 + topic_length)?).ok()?;
+    // QoS 1 and 2 carry a packet id before the payload
+    let qos = (kind >> 1) & 0x03;
+    let start = 2 + topic_length + if qos > 0 { 2 } else { 0 };
+    Some(Message {
+        topic: topic.to_string(),
+        payload: body.get(start..)?.to_vec(),
+    })
+}
+
+pub struct MqttClient {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    next_packet_id: u16,
+    // The broker drops clients that send nothing for longer than the keep-alive
+    last_sent: Instant,
+}
+
+impl MqttClient {
+    pub async fn connect(options: &ConnectOptions) -> io::Result<Self> {
+        let stream = tokio::time::timeout(
+            CONNECT_TIMEOUT,
+            TcpStream::connect((options.host.as_str(), options.port)),
+        )
+        .await
+        .map_err(|_| io::Error::new(ErrorKind::TimedOut, "broker didn't answer"))??;
+        let mut client = Self {
+            stream,
+            buffer: Vec::new(),
+            next_packet_id: 1,
+            last_sent: Instant::now(),
+        };
+        client.send(&connect_packet(options)).await?;
+
+        let (kind, body) = tokio::time::timeout(CONNECT_TIMEOUT, client.read_packet())
+            .await
+            .map_err(|_| io::Error::new(ErrorKind::TimedOut, "broker didn't accept"))??;
+        match (kind & 0xf0, body.get(1)) {
+            (CONNACK, Some(0)) => Ok(client),
+            (CONNACK, Some(4 | 5)) => Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                "broker refused the username or password",
+            )),
+            (CONNACK, code) => Err(io::Error::new(
+                ErrorKind::ConnectionRefused,
+                format!("broker refused the connection (code {:?})", code),
+            )),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "expected CONNACK from broker",
+            )),
+        }
+    }
+
+    pub async fn subscribe(&mut self, topics: &[&str]) -> io::Result<()> {
+        let id = self.next_packet_id;
+        self.next_packet_id = self.next_packet_id.wrapping_add(1).max(1);
+        self.send(&subscribe_packet(id, topics)).await
+    }
+
+    pub async fn publish(&mut self, topic: &str, payload: &[u8], retain: bool) -> io::Result<()> {
+        self.send(&publish_packet(topic, payload, retain)).await
+    }
+
+    pub async fn disconnect(mut self) -> io::Result<()> {
+        self.stream.write_all(&[DISCONNECT, 0]).await
+    }
+
+    // Waits for the next message on a subscribed topic, pinging the broker in between
+    pub async fn next_message(&mut self) -> io::Result<Message> {
+        loop {
+            let until_ping = (KEEP_ALIVE / 2).saturating_sub(self.last_sent.elapsed());
+            match tokio::time::timeout(until_ping, self.read_packet()).await {
+                Ok(packet) => {
+                    let (kind, body) = packet?;
+                    if kind & 0xf0 == PUBLISH
+                        && let Some(message) = parse_publish(kind, &body)
+                    {
+                        return Ok(message);
+                    }
+                }
+                Err(_) => self.send(&[PINGREQ, 0]).await?,
+            }
+        }
+    }
+
+    async fn send(&mut self, packet: &[u8]) -> io::Result<()> {
+        self.stream.write_all(packet).await?;
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    // Cancel safe: bytes read before a timeout stay in the buffer for the next call
+    async fn read_packet(&mut self) -> io::Result<(u8, Vec<u8>)> {
+        loop {
+            if let Some((kind, body, used)) = split_packet(&self.buffer)? {
+                let packet = (kind, body.to_vec());
+                self.buffer.drain(..used);
+                return Ok(packet);
+            }
+            let mut chunk = [0u8; 1024];
+            let read = self.stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(io::Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "broker closed the connection",
+                ));
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_broker() {
+        assert_eq!(
+            parse_broker("mqtt://192.168.1.20:1884"),
+            Ok(("192.168.1.20".to_string(), 1884))
+        );
+        assert_eq!(
+            parse_broker("broker.local"),
+            Ok(("broker.local".to_string(), DEFAULT_PORT))
+        );
+        assert!(parse_broker("mqtts://broker.local").is_err());
+        assert!(parse_broker("broker.local:port").is_err());
+        assert!(parse_broker("").is_err());
+    }
+
+    #[test]
+    fn test_encode_length() {
+        for (length, expected) in [
+            (0, vec![0x00]),
+            (127, vec![0x7f]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xff, 0x7f]),
+            (2_097_152, vec![0x80, 0x80, 0x80, 0x01]),
+        ] {
+            let mut out = Vec::new();
+            encode_length(length, &mut out);
+            assert_eq!(out, expected, "{}", length);
+        }
+    }
+
+    #[test]
+    fn test_connect_packet() {
+        let options = ConnectOptions {
+            client_id: "w".to_string(),
+            username: Some("u".to_string()),
+            password: Some("p".to_string()),
+            ..ConnectOptions::default()
+        };
+        assert_eq!(
+            connect_packet(&options),
+            [
+                0x10, 19, 0, 4, b'M', b'Q', b'T', b'T', 4, 0xc2, 0, 60, 0, 1, b'w', 0, 1, b'u', 0,
+                1, b'p'
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_publish() {
+        let mut body = Vec::new();
+        encode_string("home/temp", &mut body);
+        body.extend_from_slice(b"21.5");
+        let bytes = packet(PUBLISH | 0x01, &body);
+        let (kind, body, used) = split_packet(&bytes).unwrap().unwrap();
+        assert_eq!(used, bytes.len());
+        assert_eq!(
+            parse_publish(kind, body),
+            Some(Message {
+                topic: "home/temp".to_string(),
+                payload: b"21.5".to_vec(),
+            })
+        );
+
+        // Not all there yet
+        assert_eq!(split_packet(&bytes[..5]).unwrap(), None);
+        assert_eq!(split_packet(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_publish_packet() {
+        assert_eq!(
+            publish_packet("w/t", b"21", true),
+            [PUBLISH | RETAIN, 7, 0, 3, b'w', b'/', b't', b'2', b'1']
+        );
+        let bytes = publish_packet("w/t", b"21", false);
+        let (kind, body, _) = split_packet(&bytes).unwrap().unwrap();
+        assert_eq!(parse_publish(kind, body).unwrap().payload, b"21");
+    }
+
+    #[test]
+    fn test_qos1_publish_skips_packet_id() {
+        let body = [0, 1, b't', 0, 7, b'4', b'2'];
+        let message = parse_publish(PUBLISH | 0x02, &body).unwrap();
+        assert_eq!(message.payload, b"42");
+    }
+
+    #[tokio::test]
+    async fn test_client_against_broker() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            // Both fit in a one-byte length
+            let mut header = [0u8; 2];
+            let mut body = [0u8; 128];
+            socket.read_exact(&mut header).await.unwrap();
+            socket
+                .read_exact(&mut body[..header[1] as usize])
+                .await
+                .unwrap();
+            assert_eq!(header[0], CONNECT);
+            socket.write_all(&[CONNACK, 2, 0, 0]).await.unwrap();
+            socket.read_exact(&mut header).await.unwrap();
+            socket
+                .read_exact(&mut body[..header[1] as usize])
+                .await
+                .unwrap();
+            assert_eq!(header[0], SUBSCRIBE);
+
+            let mut body = Vec::new();
+            encode_string("home/temp", &mut body);
+            body.extend_from_slice(b"21.5");
+            // SUBACK, then the retained reading split across two writes
+            let mut bytes = vec![0x90, 3, 0, 1, 0];
+            bytes.extend(packet(PUBLISH | 0x01, &body));
+            socket.write_all(&bytes[..8]).await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            socket.write_all(&bytes[8..]).await.unwrap();
+            socket
+        });
+
+        let options = ConnectOptions {
+            host: "127.0.0.1".to_string(),
+            port,
+            client_id: "weathr-test".to_string(),
+            ..ConnectOptions::default()
+        };
+        let mut client = MqttClient::connect(&options).await.unwrap();
+        client.subscribe(&["home/temp"]).await.unwrap();
+        let message = client.next_message().await.unwrap();
+        assert_eq!(message.topic, "home/temp");
+        assert_eq!(message.payload, b"21.5");
+        drop(broker.await.unwrap());
+    }
+}
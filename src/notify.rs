@@ -0,0 +1,284 @@
+use crate::config::NotificationConfig;
+use crate::weather::WeatherCondition;
+use crate::weather::alerts::WeatherAlert;
+use crate::weather::types::{MinutelyPrecipitation, WeatherData};
+use std::process::{Command, Stdio};
+
+// Lighter than this is a trace, as in the precipitation panel's nowcast
+const WET_MM_PER_HOUR: f64 = 0.1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+}
+
+// Kinds of weather worth hearing about a change between; clouds coming and going aren't
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Dry,
+    Fog,
+    Rain,
+    Snow,
+    Thunderstorm,
+}
+
+fn kind(condition: WeatherCondition) -> Kind {
+    if condition.is_thunderstorm() {
+        Kind::Thunderstorm
+    } else if condition.is_snowing() {
+        Kind::Snow
+    } else if condition.is_raining() {
+        Kind::Rain
+    } else if condition.is_foggy() {
+        Kind::Fog
+    } else {
+        Kind::Dry
+    }
+}
+
+// Minutes until precipitation starts, if it's dry now and the nowcast has some coming
+fn minutes_until_wet(minutely: &[MinutelyPrecipitation]) -> Option<i64> {
+    let first = minutely.first()?;
+    if first.intensity >= WET_MM_PER_HOUR {
+        return None;
+    }
+    minutely
+        .iter()
+        .find(|m| m.intensity >= WET_MM_PER_HOUR)
+        .map(|m| (m.time - first.time).num_minutes())
+}
+
+// [notifications]: tells the desktop when the first location's weather changes kind,
+// when the nowcast has precipitation on its way, and of severe alerts, each once
+pub struct Notifier {
+    settings: NotificationConfig,
+    // None until the first weather, which is only taken note of
+    last_kind: Option<(Kind, WeatherCondition)>,
+    precipitation_warned: bool,
+    // Events and headlines of the alerts already told of
+    alerted: Vec<(String, Option<String>)>,
+}
+
+impl Notifier {
+    // None unless notifications are enabled
+    pub fn new(settings: &NotificationConfig) -> Option<Self> {
+        settings.enabled.then(|| Self {
+            settings: settings.clone(),
+            last_kind: None,
+            precipitation_warned: false,
+            alerted: Vec::new(),
+        })
+    }
+
+    pub fn weather(&mut self, weather: &WeatherData) {
+        for notification in self.weather_changes(weather) {
+            send(notification);
+        }
+    }
+
+    pub fn alerts(&mut self, alerts: &[WeatherAlert]) {
+        for notification in self.new_alerts(alerts) {
+            send(notification);
+        }
+    }
+
+    fn weather_changes(&mut self, weather: &WeatherData) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        let now = (kind(weather.condition), weather.condition);
+        if let Some((last_kind, last_condition)) = self.last_kind.replace(now)
+            && last_kind != now.0
+            && self.settings.condition_change
+        {
+            notifications.push(Notification {
+                summary: weather.condition.description().to_string(),
+                body: format!(
+                    "{} → {}",
+                    last_condition.description(),
+                    weather.condition.description()
+                ),
+            });
+        }
+
+        let window = self.settings.precipitation_minutes as i64;
+        let starts_in = minutes_until_wet(&weather.minutely).filter(|&m| m <= window);
+        if let Some(minutes) = starts_in
+            && !self.precipitation_warned
+        {
+            notifications.push(Notification {
+                summary: "Precipitation on its way".to_string(),
+                body: format!("Starting in {} min", minutes),
+            });
+        }
+        // Until the nowcast has none coming again, or it has started
+        self.precipitation_warned = starts_in.is_some();
+        notifications
+    }
+
+    fn new_alerts(&mut self, alerts: &[WeatherAlert]) -> Vec<Notification> {
+        let alerts: Vec<_> = alerts
+            .iter()
+            .filter(|alert| alert.severity >= self.settings.alert_severity)
+            .collect();
+        let mut notifications = Vec::new();
+        for alert in &alerts {
+            let key = (alert.event.clone(), alert.headline.clone());
+            if !self.alerted.contains(&key) {
+                notifications.push(Notification {
+                    summary: alert.event.clone(),
+                    body: alert
+                        .headline
+                        .clone()
+                        .unwrap_or_else(|| alert.description.clone()),
+                });
+                self.alerted.push(key);
+            }
+        }
+        // An alert that ends and is issued again is told of again
+        self.alerted.retain(|(event, headline)| {
+            alerts
+                .iter()
+                .any(|alert| &alert.event == event && &alert.headline == headline)
+        });
+        notifications
+    }
+}
+
+// AppleScript string literal contents
+fn applescript_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Through the platform's own command, off the runtime's threads. Nothing happens where
+// there isn't one, and its output is kept off the scene
+fn send(notification: Notification) {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"weathr\" subtitle \"{}\"",
+            applescript_escape(&notification.body),
+            applescript_escape(&notification.summary)
+        ));
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command
+            .arg("--app-name=weathr")
+            .arg(&notification.summary)
+            .arg(&notification.body);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    tokio::task::spawn_blocking(move || command.status());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+    use crate::weather::WeatherUnits;
+    use crate::weather::alerts::AlertSeverity;
+    use chrono::{Duration, Utc};
+
+    fn notifier() -> Notifier {
+        let settings = NotificationConfig {
+            enabled: true,
+            ..NotificationConfig::default()
+        };
+        Notifier::new(&settings).unwrap()
+    }
+
+    fn weather(condition: WeatherCondition) -> WeatherData {
+        Simulation::new(condition, true).weather(&WeatherUnits::metric())
+    }
+
+    #[test]
+    fn test_condition_changes() {
+        assert!(Notifier::new(&NotificationConfig::default()).is_none());
+
+        let mut notifier = notifier();
+        assert!(
+            notifier
+                .weather_changes(&weather(WeatherCondition::Clear))
+                .is_empty()
+        );
+        // Clouds are still dry
+        assert!(
+            notifier
+                .weather_changes(&weather(WeatherCondition::Overcast))
+                .is_empty()
+        );
+        assert_eq!(
+            notifier.weather_changes(&weather(WeatherCondition::Thunderstorm)),
+            vec![Notification {
+                summary: "Thunderstorm".to_string(),
+                body: "Overcast → Thunderstorm".to_string(),
+            }]
+        );
+        assert!(
+            notifier
+                .weather_changes(&weather(WeatherCondition::ThunderstormHail))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_precipitation_coming() {
+        let mut notifier = notifier();
+        let start = Utc::now();
+        let nowcast = |wet_from: i64| -> WeatherData {
+            let mut weather = weather(WeatherCondition::Cloudy);
+            weather.minutely = (0..60)
+                .map(|minute| MinutelyPrecipitation {
+                    time: start + Duration::minutes(minute),
+                    intensity: if minute >= wet_from { 1.0 } else { 0.0 },
+                    probability: None,
+                })
+                .collect();
+            weather
+        };
+
+        // Beyond the default 15 minutes
+        assert!(notifier.weather_changes(&nowcast(40)).is_empty());
+        let notifications = notifier.weather_changes(&nowcast(10));
+        assert_eq!(notifications[0].body, "Starting in 10 min");
+        // Once, however close it gets
+        assert!(notifier.weather_changes(&nowcast(5)).is_empty());
+        assert!(notifier.weather_changes(&nowcast(0)).is_empty());
+        assert_eq!(notifier.weather_changes(&nowcast(12)).len(), 1);
+    }
+
+    #[test]
+    fn test_alerts_at_the_severity() {
+        let alert = |event: &str, severity| WeatherAlert {
+            event: event.to_string(),
+            severity,
+            headline: None,
+            description: "Take shelter".to_string(),
+            instruction: None,
+            expires: None,
+        };
+        let mut notifier = notifier();
+        let alerts = vec![
+            alert("Wind Advisory", AlertSeverity::Moderate),
+            alert("Tornado Warning", AlertSeverity::Extreme),
+        ];
+        let notifications = notifier.new_alerts(&alerts);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].summary, "Tornado Warning");
+        assert_eq!(notifications[0].body, "Take shelter");
+        assert!(notifier.new_alerts(&alerts).is_empty());
+
+        // Issued again after it ended
+        assert!(notifier.new_alerts(&[]).is_empty());
+        assert_eq!(notifier.new_alerts(&alerts).len(), 1);
+    }
+
+    #[test]
+    fn test_applescript_escape() {
+        assert_eq!(applescript_escape(r#"a "b" \c"#), r#"a \"b\" \\c"#);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::weather::{
+    WeatherData, WeatherUnits, compass_direction, format_precipitation, format_temperature,
+    format_wind_speed,
+};
+
+pub fn format_summary(weather: &WeatherData, units: &WeatherUnits) -> String {
+    let (temp, temp_unit) = format_temperature(weather.temperature, units.temperature);
+    let (feels, _) = format_temperature(weather.apparent_temperature, units.temperature);
+    let (wind, wind_unit) = format_wind_speed(weather.wind_speed, units.wind_speed);
+    let (precip, precip_unit) = format_precipitation(weather.precipitation, units.precipitation);
+
+    format!(
+        "{} | {:.1}{} (feels like {:.1}{}) | Wind: {:.1}{} {} | Humidity: {:.0}% | Precip: {:.1}{}",
+        weather.condition.description(),
+        temp,
+        temp_unit,
+        feels,
+        temp_unit,
+        wind,
+        wind_unit,
+        compass_direction(weather.wind_direction),
+        weather.humidity,
+        precip,
+        precip_unit
+    )
+}
+
+pub fn format_bar(weather: &WeatherData, units: &WeatherUnits) -> String {
+    let (temp, temp_unit) = format_temperature(weather.temperature, units.temperature);
+    format!(
+        "{} {:.0}{} {}",
+        weather.condition.icon(weather.is_day),
+        temp,
+        temp_unit,
+        weather.condition.description()
+    )
+}
+
+pub fn format_waybar(weather: &WeatherData, units: &WeatherUnits) -> String {
+    let class = serde_json::to_value(weather.condition)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    serde_json::json!({
+        "text": format_bar(weather, units),
+        "tooltip": format_summary(weather, units),
+        "class": class,
+    })
+    .to_string()
+}
+
+pub fn format_json(weather: &WeatherData) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(weather)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn sample_weather() -> WeatherData {
+        WeatherData {
+            condition: WeatherCondition::Rain,
+            temperature: 12.0,
+            apparent_temperature: 10.5,
+            humidity: 81.0,
+            precipitation: 1.2,
+            wind_speed: 5.0,
+            wind_direction: 225.0,
+            cloud_cover: 90.0,
+            pressure: 1008.0,
+            visibility: Some(8000.0),
+            uv_index: None,
+            is_day: true,
+            moon_phase: Some(0.5),
+            timestamp: "2024-01-01T12:00".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            source: None,
+            fetched_at: None,
+            air_quality: None,
+        }
+    }
+
+    #[test]
+    fn test_format_summary_metric() {
+        let summary = format_summary(&sample_weather(), &WeatherUnits::metric());
+        assert_eq!(
+            summary,
+            "Rain | 12.0°C (feels like 10.5°C) | Wind: 18.0km/h SW | Humidity: 81% | Precip: 1.2mm"
+        );
+    }
+
+    #[test]
+    fn test_format_bar() {
+        let bar = format_bar(&sample_weather(), &WeatherUnits::metric());
+        assert_eq!(bar, "☔ 12°C Rain");
+    }
+
+    #[test]
+    fn test_format_waybar() {
+        let json = format_waybar(&sample_weather(), &WeatherUnits::metric());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["text"], "☔ 12°C Rain");
+        assert_eq!(value["class"], "rain");
+        assert!(value["tooltip"].as_str().unwrap().contains("Humidity: 81%"));
+    }
+
+    #[test]
+    fn test_format_json_round_trip() {
+        let json = format_json(&sample_weather()).unwrap();
+        assert!(json.contains("\"condition\": \"rain\""));
+
+        let parsed: WeatherData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.condition, WeatherCondition::Rain);
+        assert_eq!(parsed.temperature, 12.0);
+    }
+
+    #[test]
+    fn test_format_summary_imperial() {
+        let summary = format_summary(&sample_weather(), &WeatherUnits::imperial());
+        assert!(summary.contains("53.6°F"));
+        assert!(summary.contains("mph"));
+        assert!(summary.contains("in"));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::config::PublishConfig;
+use crate::mqtt::{self, ConnectOptions, MqttClient};
+use crate::weather::WeatherData;
+use serde_json::json;
+use std::io;
+use tokio::sync::watch;
+
+const DEFAULT_DISCOVERY_PREFIX: &str = "homeassistant";
+
+// The readings Home Assistant is told about: key in the JSON, name, unit and device class
+const SENSORS: [(&str, &str, Option<&str>, Option<&str>); 10] = [
+    ("condition", "Condition", None, None),
+    (
+        "temperature",
+        "Temperature",
+        Some("°C"),
+        Some("temperature"),
+    ),
+    (
+        "apparent_temperature",
+        "Apparent temperature",
+        Some("°C"),
+        Some("temperature"),
+    ),
+    ("humidity", "Humidity", Some("%"), Some("humidity")),
+    (
+        "pressure",
+        "Pressure",
+        Some("hPa"),
+        Some("atmospheric_pressure"),
+    ),
+    ("wind_speed", "Wind speed", Some("m/s"), Some("wind_speed")),
+    ("wind_direction", "Wind direction", Some("°"), None),
+    (
+        "precipitation",
+        "Precipitation",
+        Some("mm"),
+        Some("precipitation"),
+    ),
+    ("cloud_cover", "Cloud cover", Some("%"), None),
+    ("uv_index", "UV index", None, None),
+];
+
+// Home Assistant's discovery messages for a weathr publishing to `topic`: a config topic
+// and payload for each of SENSORS, all part of one device named after the topic
+fn discovery(topic: &str, prefix: &str) -> Vec<(String, String)> {
+    let object_id: String = topic
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    SENSORS
+        .iter()
+        .map(|&(key, name, unit, device_class)| {
+            let mut config = json!({
+                "name": name,
+                "unique_id": format!("{}_{}", object_id, key),
+                "state_topic": topic,
+                "value_template": format!("{{{{ value_json.{} }}}}", key),
+                "device": {
+                    "identifiers": [object_id],
+                    "name": "weathr",
+                    "manufacturer": "weathr",
+                    "sw_version": env!("CARGO_PKG_VERSION"),
+                },
+            });
+            if let Some(unit) = unit {
+                config["unit_of_measurement"] = json!(unit);
+                config["state_class"] = json!("measurement");
+            }
+            if let Some(device_class) = device_class {
+                config["device_class"] = json!(device_class);
+            }
+            (
+                format!("{}/sensor/{}/{}/config", prefix, object_id, key),
+                config.to_string(),
+            )
+        })
+        .collect()
+}
+
+// [publish]: each weather handed to it sent to the broker as JSON, in the background so
+// a slow broker never holds up a frame
+pub struct WeatherPublisher {
+    updates: watch::Sender<Option<WeatherData>>,
+}
+
+impl WeatherPublisher {
+    // None while [publish] has no broker
+    pub fn start(settings: &PublishConfig) -> Option<Self> {
+        let (host, port) = mqtt::parse_broker(settings.broker.as_deref()?).ok()?;
+        let options = ConnectOptions {
+            host,
+            port,
+            // Apart from the sensor feed's, which a broker would otherwise disconnect
+            client_id: format!("weathr-{}-publish", std::process::id()),
+            username: settings.username.clone(),
+            password: settings.password.clone(),
+        };
+        let topic = settings.topic().to_string();
+        let discovery = settings.home_assistant.then(|| {
+            let prefix = settings
+                .discovery_prefix
+                .as_deref()
+                .unwrap_or(DEFAULT_DISCOVERY_PREFIX);
+            discovery(&topic, prefix)
+        });
+        let (updates, receiver) = watch::channel(None);
+        tokio::spawn(run(options, topic, discovery, receiver));
+        Some(Self { updates })
+    }
+
+    // Only the latest weather is sent if the one before is still on its way
+    pub fn publish(&self, weather: &WeatherData) {
+        self.updates.send_replace(Some(weather.clone()));
+    }
+}
+
+// Until the publisher is dropped. Updates come minutes apart, longer than a broker keeps
+// an idle client, so each is sent over a connection of its own
+async fn run(
+    options: ConnectOptions,
+    topic: String,
+    mut discovery: Option<Vec<(String, String)>>,
+    mut updates: watch::Receiver<Option<WeatherData>>,
+) {
+    while updates.changed().await.is_ok() {
+        let Some(weather) = updates.borrow_and_update().clone() else {
+            continue;
+        };
+        let Ok(payload) = serde_json::to_vec(&weather) else {
+            continue;
+        };
+        // A broker that's down misses this update; the next one tries again
+        if send(&options, &topic, &payload, discovery.as_deref())
+            .await
+            .is_ok()
+        {
+            // Retained by the broker, so once is enough
+            discovery = None;
+        }
+    }
+}
+
+async fn send(
+    options: &ConnectOptions,
+    topic: &str,
+    payload: &[u8],
+    discovery: Option<&[(String, String)]>,
+) -> io::Result<()> {
+    let mut client = MqttClient::connect(options).await?;
+    for (config_topic, config) in discovery.unwrap_or_default() {
+        client
+            .publish(config_topic, config.as_bytes(), true)
+            .await?;
+    }
+    client.publish(topic, payload, true).await?;
+    client.disconnect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+    use crate::weather::{WeatherCondition, WeatherUnits};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_discovery() {
+        let messages = discovery("home/weathr", "homeassistant");
+        assert_eq!(messages.len(), SENSORS.len());
+        let (topic, config) = &messages[1];
+        assert_eq!(topic, "homeassistant/sensor/home_weathr/temperature/config");
+        let config: serde_json::Value = serde_json::from_str(config).unwrap();
+        assert_eq!(config["state_topic"], "home/weathr");
+        assert_eq!(config["value_template"], "{{ value_json.temperature }}");
+        assert_eq!(config["unique_id"], "home_weathr_temperature");
+        assert_eq!(config["unit_of_measurement"], "°C");
+        assert_eq!(config["device"]["identifiers"][0], "home_weathr");
+
+        // The condition is text, not a measurement
+        let config: serde_json::Value = serde_json::from_str(&messages[0].1).unwrap();
+        assert!(config.get("state_class").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_publishes_to_broker() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut header = [0u8; 2];
+            let mut body = [0u8; 128];
+            socket.read_exact(&mut header).await.unwrap();
+            socket
+                .read_exact(&mut body[..header[1] as usize])
+                .await
+                .unwrap();
+            socket.write_all(&[0x20, 2, 0, 0]).await.unwrap();
+            // Everything up to the disconnect
+            let mut received = Vec::new();
+            socket.read_to_end(&mut received).await.unwrap();
+            String::from_utf8_lossy(&received).into_owned()
+        });
+
+        let settings = PublishConfig {
+            broker: Some(format!("mqtt://127.0.0.1:{}", port)),
+            home_assistant: true,
+            ..PublishConfig::default()
+        };
+        let publisher = WeatherPublisher::start(&settings).unwrap();
+        publisher.publish(
+            &Simulation::new(WeatherCondition::Rain, true).weather(&WeatherUnits::metric()),
+        );
+
+        let received = broker.await.unwrap();
+        assert!(received.contains("homeassistant/sensor/weathr_weather/humidity/config"));
+        assert!(received.contains("weathr/weather{\"condition\":\"rain\""));
+    }
+}
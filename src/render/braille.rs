@@ -0,0 +1,72 @@
+use super::TerminalRenderer;
+use crossterm::style::Color;
+use std::io;
+
+const BRAILLE_BASE: u32 = 0x2800;
+// Bit for the dot at (column, row) inside one braille cell
+const DOT_BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+// A layer of 2x4 dots per terminal cell, drawn as braille characters. Points are
+// in cell coordinates, so 0.5 of a column or 0.25 of a row apart lands on the next dot
+pub struct BrailleCanvas {
+    width: u16,
+    height: u16,
+    dots: Vec<u8>,
+    colors: Vec<Color>,
+}
+
+impl BrailleCanvas {
+    pub fn new(width: u16, height: u16) -> Self {
+        let size = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            dots: vec![0; size],
+            colors: vec![Color::Reset; size],
+        }
+    }
+
+    // The last color set in a cell wins, since a cell can only show one
+    pub fn plot(&mut self, x: f32, y: f32, color: Color) {
+        if x < 0.0 || y < 0.0 {
+            return;
+        }
+        let (dot_x, dot_y) = ((x * 2.0) as usize, (y * 4.0) as usize);
+        let (col, row) = (dot_x / 2, dot_y / 4);
+        if col >= self.width as usize || row >= self.height as usize {
+            return;
+        }
+        let idx = row * self.width as usize + col;
+        self.dots[idx] |= DOT_BITS[dot_x % 2][dot_y % 4];
+        self.colors[idx] = color;
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for (idx, &dots) in self.dots.iter().enumerate() {
+            if dots == 0 {
+                continue;
+            }
+            let ch = char::from_u32(BRAILLE_BASE + dots as u32).unwrap_or(' ');
+            let (x, y) = (idx % self.width as usize, idx / self.width as usize);
+            renderer.render_char(x as u16, y as u16, ch, self.colors[idx])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plot_sets_sub_cell_dots() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.plot(0.0, 0.0, Color::White);
+        canvas.plot(0.5, 0.75, Color::Cyan);
+        canvas.plot(1.2, 0.3, Color::White);
+        canvas.plot(5.0, 0.0, Color::White);
+
+        assert_eq!(canvas.dots, vec![0x01 | 0x80, 0x02]);
+        assert_eq!(canvas.colors[0], Color::Cyan);
+    }
+}
@@ -21,7 +21,8 @@ impl TerminalCapabilities {
     pub fn detect() -> Self {
         let is_tty = std::io::stdout().is_terminal();
 
-        if env::var("NO_COLOR").is_ok() {
+        // https://no-color.org: any non-empty value
+        if env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
             return Self {
                 color_support: ColorSupport::None,
                 is_tty,
@@ -58,6 +59,10 @@ impl TerminalCapabilities {
         }
     }
 
+    pub fn disable_color(&mut self) {
+        self.color_support = ColorSupport::None;
+    }
+
     pub fn adjust_color(&self, color: Color) -> Color {
         if self.color_support == ColorSupport::None {
             return Color::Reset;
@@ -100,6 +105,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_disable_color() {
+        let mut caps = TerminalCapabilities {
+            color_support: ColorSupport::TrueColor,
+            is_tty: true,
+        };
+        caps.disable_color();
+        assert_eq!(caps.adjust_color(Color::Cyan), Color::Reset);
+    }
+
     #[test]
     fn test_adjust_color_basic() {
         let caps = TerminalCapabilities {
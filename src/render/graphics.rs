@@ -0,0 +1,554 @@
+use super::Region;
+use crate::theme::Rgb;
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, IsTerminal};
+
+// Image pixels per terminal cell. Kitty scales the image to the cells it covers, so
+// this only sets how smooth the gradient and the sun and moon edges are
+const PIXELS_PER_COLUMN: usize = 4;
+const PIXELS_PER_ROW: usize = 8;
+// Largest base64 payload kitty takes in one escape sequence
+const CHUNK_SIZE: usize = 4096;
+const IMAGE_ID: u32 = 1;
+// Cell size to draw sixels at when the terminal doesn't report its own
+const DEFAULT_CELL_PIXELS: (u16, u16) = (10, 20);
+const SIXEL_COLORS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    // The whole sky is an image behind the text
+    Kitty,
+    // Only the sun or moon is an image. Text printed over a sixel replaces its pixels
+    // instead of sitting on top, so the gradient stays in the cell backgrounds and the
+    // image is sent again whenever a cell over it changes
+    Sixel,
+}
+
+// A kitty graphics query for a 1x1 image and a primary device attributes request. Every
+// terminal answers the second; one with kitty graphics answers the first before it
+const QUERY: &str = "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c";
+#[cfg(unix)]
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(300);
+
+// Asks the terminal which images it draws, or goes by what it calls itself when it
+// can't be asked or doesn't answer
+pub fn detect() -> Option<Protocol> {
+    match query_terminal() {
+        Some(reply) => parse_reply(&reply),
+        None => detect_kitty_graphics().then_some(Protocol::Kitty),
+    }
+}
+
+// Terminals known to speak the kitty graphics protocol
+fn detect_kitty_graphics() -> bool {
+    // tmux doesn't pass the image escapes through
+    if env::var_os("TMUX").is_some() {
+        return false;
+    }
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || matches!(program.as_str(), "WezTerm" | "ghostty")
+}
+
+// Kitty if it acknowledged the image, sixel if its device attributes include 4
+fn parse_reply(reply: &[u8]) -> Option<Protocol> {
+    let text = String::from_utf8_lossy(reply);
+    if text.contains("\x1b_Gi=31;OK") {
+        return Some(Protocol::Kitty);
+    }
+    let start = text.find("\x1b[?")? + 3;
+    let end = start + text[start..].find('c')?;
+    text[start..end]
+        .split(';')
+        .any(|attribute| attribute == "4")
+        .then_some(Protocol::Sixel)
+}
+
+// The device attributes answer, which comes last, is complete
+fn reply_complete(reply: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(reply);
+    text.find("\x1b[?")
+        .is_some_and(|start| text[start..].contains('c'))
+}
+
+#[cfg(unix)]
+fn query_terminal() -> Option<Vec<u8>> {
+    use crossterm::terminal;
+    use std::io::Write;
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return None;
+    }
+    // Without raw mode the answer would wait for Enter and be echoed
+    let was_raw = terminal::is_raw_mode_enabled().unwrap_or(false);
+    if !was_raw {
+        terminal::enable_raw_mode().ok()?;
+    }
+    let mut stdout = io::stdout();
+    let reply = stdout
+        .write_all(QUERY.as_bytes())
+        .and_then(|_| stdout.flush())
+        .ok()
+        .and_then(|_| read_reply());
+    if !was_raw {
+        let _ = terminal::disable_raw_mode();
+    }
+    reply
+}
+
+#[cfg(not(unix))]
+fn query_terminal() -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(unix)]
+fn read_reply() -> Option<Vec<u8>> {
+    use rustix::event::{PollFd, PollFlags, Timespec, poll};
+    use std::os::fd::AsFd;
+    use std::time::Instant;
+
+    let stdin = io::stdin();
+    let fd = stdin.as_fd();
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+    while !reply_complete(&reply) {
+        let left = deadline.checked_duration_since(Instant::now())?;
+        let timeout = Timespec {
+            tv_sec: left.as_secs() as _,
+            tv_nsec: left.subsec_nanos() as _,
+        };
+        let mut fds = [PollFd::new(&fd, PollFlags::IN)];
+        if poll(&mut fds, Some(&timeout)).ok()? == 0 {
+            return None;
+        }
+        let mut buf = [0u8; 256];
+        let read = rustix::io::read(fd, &mut buf).ok()?;
+        if read == 0 {
+            return None;
+        }
+        reply.extend_from_slice(&buf[..read]);
+    }
+    Some(reply)
+}
+
+// Pixels per cell, from the terminal's size in pixels
+pub fn cell_pixels() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            (size.width / size.columns, size.height / size.rows)
+        }
+        _ => DEFAULT_CELL_PIXELS,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkyBody {
+    Sun { color: Rgb },
+    // 0.0 new, 0.5 full, as in `WeatherData::moon_phase`
+    Moon { phase: f64 },
+}
+
+// Everything drawn into the sky image; a new image is only sent when this changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyImage {
+    pub top: Rgb,
+    pub horizon: Rgb,
+    pub columns: u16,
+    pub rows: u16,
+    // Center cell of the sun or moon
+    pub body: Option<(u16, u16, SkyBody)>,
+}
+
+const MOON_COLOR: Rgb = (235, 232, 215);
+const SUN_RADIUS_ROWS: f64 = 1.4;
+const MOON_RADIUS_ROWS: f64 = 1.2;
+
+fn mix(a: Rgb, b: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    (channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2))
+}
+
+impl SkyImage {
+    fn pixel_size(&self) -> (usize, usize) {
+        (
+            self.columns as usize * PIXELS_PER_COLUMN,
+            self.rows as usize * PIXELS_PER_ROW,
+        )
+    }
+
+    fn full_area(&self) -> Region {
+        Region {
+            x: 0,
+            y: 0,
+            width: self.columns,
+            height: self.rows,
+        }
+    }
+
+    // Row-major RGB pixels of the cells in `area`, `cell` pixels each. With `banded` the
+    // sky is one color per row of cells, as the text layer paints it
+    fn pixels(&self, area: Region, cell: (usize, usize), banded: bool) -> Vec<u8> {
+        let (cell_width, cell_height) = cell;
+        let (width, height) = (
+            area.width as usize * cell_width,
+            area.height as usize * cell_height,
+        );
+        let (left, top) = (area.x as usize * cell_width, area.y as usize * cell_height);
+        let sky_height = self.rows as usize * cell_height;
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        let body = self.body.map(|(col, row, body)| {
+            let x = (col as f64 + 0.5) * cell_width as f64;
+            let y = (row as f64 + 0.5) * cell_height as f64;
+            (x, y, body)
+        });
+
+        for py in top..top + height {
+            let t = if banded {
+                (py / cell_height) as f64 / self.rows.saturating_sub(1).max(1) as f64
+            } else {
+                py as f64 / sky_height.saturating_sub(1).max(1) as f64
+            };
+            let sky = mix(self.top, self.horizon, t);
+            for px in left..left + width {
+                let color = match body {
+                    Some((x, y, body)) => body_pixel(
+                        px as f64 + 0.5 - x,
+                        py as f64 + 0.5 - y,
+                        cell_height as f64,
+                        body,
+                    )
+                    .map_or(sky, |(color, alpha)| mix(sky, color, alpha)),
+                    None => sky,
+                };
+                pixels.extend_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+        pixels
+    }
+
+    // Cells the sun or moon and its glow cover, for a sixel image
+    pub fn body_area(&self, cell: (u16, u16)) -> Option<Region> {
+        let (col, row, body) = self.body?;
+        let (cell_width, cell_height) = (cell.0.max(1) as f64, cell.1.max(1) as f64);
+        let reach = match body {
+            SkyBody::Sun { .. } => SUN_RADIUS_ROWS * 2.0,
+            SkyBody::Moon { .. } => MOON_RADIUS_ROWS,
+        } * cell_height;
+        let columns = (reach / cell_width).ceil() as u16;
+        let rows = (reach / cell_height).ceil() as u16;
+        let (x, y) = (col.saturating_sub(columns), row.saturating_sub(rows));
+        let right = col.saturating_add(columns + 1).min(self.columns);
+        let bottom = row.saturating_add(rows + 1).min(self.rows);
+        (right > x && bottom > y).then_some(Region {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        })
+    }
+
+    // The cells in `area` as a sixel image, drawn from the cursor
+    pub fn sixel_escape(&self, area: Region, cell: (u16, u16)) -> String {
+        let cell = (cell.0.max(1) as usize, cell.1.max(1) as usize);
+        let pixels = self.pixels(area, cell, true);
+        sixel(
+            &pixels,
+            area.width as usize * cell.0,
+            area.height as usize * cell.1,
+        )
+    }
+
+    // Escape sequences that replace the previous sky image with this one, placed from
+    // the top-left cell, under the text and without moving the cursor
+    pub fn kitty_escape(&self) -> String {
+        let (width, height) = self.pixel_size();
+        let data =
+            base64(&self.pixels(self.full_area(), (PIXELS_PER_COLUMN, PIXELS_PER_ROW), false));
+        let mut out = delete_escape();
+
+        let chunks: Vec<&str> = data
+            .as_bytes()
+            .chunks(CHUNK_SIZE)
+            .map(|c| std::str::from_utf8(c).unwrap_or_default())
+            .collect();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let more = (idx + 1 < chunks.len()) as u8;
+            if idx == 0 {
+                out.push_str(&format!(
+                    "\x1b_Ga=T,f=24,s={},v={},i={},c={},r={},C=1,z=-1,q=2,m={};{}\x1b\\",
+                    width, height, IMAGE_ID, self.columns, self.rows, more, chunk
+                ));
+            } else {
+                out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+            }
+        }
+        out
+    }
+}
+
+pub fn delete_escape() -> String {
+    format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", IMAGE_ID)
+}
+
+// Color and coverage of the sun or moon at an offset in pixels from its center, with
+// rows of cells `row_height` pixels tall
+fn body_pixel(dx: f64, dy: f64, row_height: f64, body: SkyBody) -> Option<(Rgb, f64)> {
+    match body {
+        SkyBody::Sun { color } => {
+            let radius = SUN_RADIUS_ROWS * row_height;
+            let distance = (dx * dx + dy * dy).sqrt() / radius;
+            // Solid disc with a glow fading out to twice its radius
+            if distance <= 1.0 {
+                Some((color, 1.0))
+            } else if distance < 2.0 {
+                Some((color, (2.0 - distance).powi(2) * 0.35))
+            } else {
+                None
+            }
+        }
+        SkyBody::Moon { phase } => {
+            let radius = MOON_RADIUS_ROWS * row_height;
+            let (x, y) = (dx / radius, dy / radius);
+            if x * x + y * y > 1.0 {
+                return None;
+            }
+            // The terminator is an ellipse across the disc; waxing lights the right side
+            let half_width = (1.0 - y * y).sqrt();
+            let k = (phase * std::f64::consts::TAU).cos();
+            let lit = if phase.rem_euclid(1.0) < 0.5 {
+                x > k * half_width
+            } else {
+                x < -k * half_width
+            };
+            Some((MOON_COLOR, if lit { 1.0 } else { 0.12 }))
+        }
+    }
+}
+
+// Row-major RGB pixels as a sixel image, in at most SIXEL_COLORS colors
+fn sixel(pixels: &[u8], width: usize, height: usize) -> String {
+    let (palette, indices) = quantize(pixels);
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    let percent = |channel: u8| (channel as u32 * 100 + 127) / 255;
+    for (idx, &(r, g, b)) in palette.iter().enumerate() {
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            idx,
+            percent(r),
+            percent(g),
+            percent(b)
+        ));
+    }
+
+    // Six rows of pixels at a time, a pass per color in them
+    for band in 0..height.div_ceil(6) {
+        let rows = band * 6..(band * 6 + 6).min(height);
+        let mut colors: Vec<u8> = rows
+            .clone()
+            .flat_map(|y| indices[y * width..(y + 1) * width].iter().copied())
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for (pass, &color) in colors.iter().enumerate() {
+            if pass > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", color));
+            let column = |x: usize| {
+                let bits = rows
+                    .clone()
+                    .enumerate()
+                    .filter(|&(_, y)| indices[y * width + x] == color)
+                    .fold(0u8, |bits, (bit, _)| bits | 1 << bit);
+                (63 + bits) as char
+            };
+            let mut x = 0;
+            while x < width {
+                let ch = column(x);
+                let mut run = 1;
+                while x + run < width && column(x + run) == ch {
+                    run += 1;
+                }
+                if run > 3 {
+                    out.push_str(&format!("!{}{}", run, ch));
+                } else {
+                    (0..run).for_each(|_| out.push(ch));
+                }
+                x += run;
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+// A palette of at most SIXEL_COLORS colors and each pixel's index into it, dropping low
+// bits from every channel until the colors fit (one bit each always does)
+fn quantize(pixels: &[u8]) -> (Vec<Rgb>, Vec<u8>) {
+    (0..8)
+        .find_map(|shift| quantize_with_shift(pixels, shift))
+        .unwrap_or_default()
+}
+
+fn quantize_with_shift(pixels: &[u8], shift: u32) -> Option<(Vec<Rgb>, Vec<u8>)> {
+    let reduce = |channel: u8| channel >> shift << shift;
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut index: HashMap<Rgb, u8> = HashMap::new();
+    let mut indices = Vec::with_capacity(pixels.len() / 3);
+    for pixel in pixels.chunks_exact(3) {
+        let color = (reduce(pixel[0]), reduce(pixel[1]), reduce(pixel[2]));
+        let idx = match index.get(&color) {
+            Some(&idx) => idx,
+            None if palette.len() < SIXEL_COLORS => {
+                let idx = palette.len() as u8;
+                palette.push(color);
+                index.insert(color, idx);
+                idx
+            }
+            None => return None,
+        };
+        indices.push(idx);
+    }
+    Some((palette, indices))
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> shift) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_sky_image_pixels() {
+        let image = SkyImage {
+            top: (0, 0, 0),
+            horizon: (255, 255, 255),
+            columns: 10,
+            rows: 4,
+            body: Some((5, 2, SkyBody::Sun { color: (255, 0, 0) })),
+        };
+        let pixels = image.pixels(
+            image.full_area(),
+            (PIXELS_PER_COLUMN, PIXELS_PER_ROW),
+            false,
+        );
+        let (width, height) = image.pixel_size();
+        assert_eq!(pixels.len(), width * height * 3);
+        // Top-left corner is the top of the gradient, the sun's center is solid
+        assert_eq!(&pixels[..3], &[0, 0, 0]);
+        let center = ((2 * PIXELS_PER_ROW + 4) * width + 5 * PIXELS_PER_COLUMN + 2) * 3;
+        assert_eq!(&pixels[center..center + 3], &[255, 0, 0]);
+
+        let escape = image.kitty_escape();
+        assert!(escape.starts_with(&delete_escape()));
+        assert!(escape.contains("a=T,f=24,s=40,v=32"));
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let kitty = b"\x1b_Gi=31;OK\x1b\\\x1b[?62;22c";
+        assert_eq!(parse_reply(kitty), Some(Protocol::Kitty));
+        assert_eq!(parse_reply(b"\x1b[?62;4;22c"), Some(Protocol::Sixel));
+        assert_eq!(parse_reply(b"\x1b[?62;22;42c"), None);
+        assert_eq!(parse_reply(b"\x1b[?1;2c"), None);
+        assert!(reply_complete(b"\x1b[?6c"));
+        assert!(!reply_complete(b"\x1b_Gi=31;OK\x1b\\\x1b[?62;4"));
+    }
+
+    #[test]
+    fn test_sixel() {
+        // Two columns by seven rows: red over a green bottom row, which starts a band
+        let mut pixels = [255, 0, 0].repeat(12);
+        pixels.extend([0, 255, 0].repeat(2));
+        let sixel = sixel(&pixels, 2, 7);
+        assert_eq!(
+            sixel,
+            "\x1bP0;1;0q\"1;1;2;7#0;2;100;0;0#1;2;0;100;0#0~~-#1@@-\x1b\\"
+        );
+
+        let wide = [7, 7, 7].repeat(5);
+        assert!(super::sixel(&wide, 5, 1).contains("#0!5@"));
+    }
+
+    #[test]
+    fn test_quantize_fits_palette() {
+        let pixels: Vec<u8> = (0..=255u8).flat_map(|v| [v, 255 - v, v / 2]).collect();
+        let (palette, indices) = quantize(&pixels);
+        assert!(palette.len() <= SIXEL_COLORS);
+        assert_eq!(indices.len(), 256);
+        assert_eq!(palette[indices[0] as usize], (0, 255, 0));
+        let (palette, _) = quantize(&[0, 0, 0, 255, 255, 255]);
+        assert_eq!(palette, vec![(0, 0, 0), (255, 255, 255)]);
+    }
+
+    #[test]
+    fn test_sixel_area_around_body() {
+        let image = SkyImage {
+            top: (0, 0, 0),
+            horizon: (0, 0, 0),
+            columns: 80,
+            rows: 20,
+            body: Some((40, 2, SkyBody::Moon { phase: 0.5 })),
+        };
+        // A moon 1.2 rows across on 10x20 cells reaches 3 columns and 2 rows out
+        let area = image.body_area((10, 20)).unwrap();
+        assert_eq!(
+            area,
+            Region {
+                x: 37,
+                y: 0,
+                width: 7,
+                height: 5,
+            }
+        );
+        let escape = image.sixel_escape(area, (10, 20));
+        assert!(escape.starts_with("\x1bP0;1;0q\"1;1;70;100"));
+    }
+
+    #[test]
+    fn test_moon_phase_shading() {
+        let lit = |dx, phase| {
+            body_pixel(dx, 0.0, PIXELS_PER_ROW as f64, SkyBody::Moon { phase })
+                .unwrap()
+                .1
+                == 1.0
+        };
+        // First quarter: right half lit, last quarter: left half
+        assert!(lit(4.0, 0.25) && !lit(-4.0, 0.25));
+        assert!(lit(-4.0, 0.75) && !lit(4.0, 0.75));
+        assert!(lit(-4.0, 0.5) && lit(4.0, 0.5));
+    }
+}
@@ -1,21 +1,103 @@
+mod braille;
 mod capabilities;
+mod font;
+mod graphics;
+mod png;
+mod raster;
+mod record;
+mod target;
+mod width;
+mod width_table;
 
 use crate::error::TerminalError;
-use capabilities::TerminalCapabilities;
+pub use braille::BrailleCanvas;
+use capabilities::{ColorSupport, TerminalCapabilities};
 use crossterm::{
-    cursor, execute, queue,
-    style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor,
+    event::{DisableFocusChange, EnableFocusChange},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::io::{self, BufWriter, IsTerminal, Stdout, Write};
+use graphics::Protocol;
+pub use graphics::{SkyBody, SkyImage};
+use record::Recorder;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+pub use target::{CrosstermTarget, MemoryTarget, RecordingTarget, RenderTarget};
+pub use width::{char_width, display_width};
 
 const MIN_TERMINAL_WIDTH: u16 = 70;
 const MIN_TERMINAL_HEIGHT: u16 = 20;
+// Whether a kitty sky image may be on screen, for `restore_terminal` to remove
+static GRAPHICS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Puts the terminal back the way the shell expects it. Safe to call more than once,
+// and from a panic hook or signal handler that can't reach the renderer
+pub fn restore_terminal() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    if GRAPHICS_ENABLED.load(Ordering::Relaxed) {
+        execute!(stdout, Print(graphics::delete_escape()))?;
+    }
+    execute!(
+        stdout,
+        DisableFocusChange,
+        LeaveAlternateScreen,
+        cursor::Show,
+        ResetColor
+    )?;
+    terminal::disable_raw_mode()
+}
+
+// Right half of a wide character, which the terminal fills in itself
+const WIDE_TAIL: char = '\0';
+// Turns on bold, which lasts until the reset at the end of the frame
+const BOLD: &str = "\x1b[1m";
+// Never drawn, so a last frame made of it redraws every cell
+const REDRAW: char = '\u{FFFF}';
+
+// Draw order of the frame, back to front. A cell only takes characters from its own
+// layer or one in front of it, so systems can draw in any order. In the layers below
+// the HUD spaces in text are transparent; single characters always overwrite
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    #[default]
+    Sky,
+    Scene,
+    Weather,
+    Hud,
+    Overlay,
+}
+
+impl Layer {
+    fn is_transparent(self) -> bool {
+        self < Layer::Hud
+    }
+}
+
+// A rectangle of cells kept clear of everything below the HUD for one frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Region {
+    fn contains(&self, x: u16, y: u16) -> bool {
+        (self.x..self.x.saturating_add(self.width)).contains(&x)
+            && (self.y..self.y.saturating_add(self.height)).contains(&y)
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct Cell {
     character: char,
     color: Color,
+    background: Color,
+    layer: Layer,
 }
 
 impl Default for Cell {
@@ -23,63 +105,101 @@ impl Default for Cell {
         Self {
             character: ' ',
             color: Color::Reset,
+            background: Color::Reset,
+            layer: Layer::Sky,
         }
     }
 }
 
+impl Cell {
+    // Drawing a glyph keeps whatever background has been painted underneath it
+    fn draw(&mut self, character: char, color: Color, layer: Layer) {
+        self.character = character;
+        self.color = color;
+        self.layer = layer;
+    }
+}
+
 pub struct TerminalRenderer {
-    stdout: BufWriter<Stdout>,
+    target: Box<dyn RenderTarget>,
     width: u16,
     height: u16,
     buffer: Vec<Cell>,
     last_buffer: Vec<Cell>,
     capabilities: TerminalCapabilities,
+    layer: Layer,
+    protected: Vec<Region>,
+    bold: bool,
+    // Sky drawn as an image, and the one currently on screen
+    graphics: Option<Protocol>,
+    sky_image: Option<SkyImage>,
+    shown_sky_image: Option<SkyImage>,
+    // Size of a cell in pixels and the cells under the sixel on screen
+    cell_pixels: (u16, u16),
+    sixel_area: Option<Region>,
 }
 
 impl TerminalRenderer {
     pub fn new() -> Result<Self, TerminalError> {
-        if !io::stdout().is_terminal() {
-            return Err(TerminalError::NotATty);
-        }
-
-        let (width, height) = terminal::size().map_err(TerminalError::SizeError)?;
-
-        if width < MIN_TERMINAL_WIDTH || height < MIN_TERMINAL_HEIGHT {
-            return Err(TerminalError::TooSmall {
-                width,
-                height,
-                min_width: MIN_TERMINAL_WIDTH,
-                min_height: MIN_TERMINAL_HEIGHT,
-            });
-        }
+        let mut renderer = Self::with_target(Box::new(CrosstermTarget::new()?));
+        renderer.capabilities = TerminalCapabilities::detect();
+        Ok(renderer)
+    }
 
-        let stdout = BufWriter::new(io::stdout());
+    // Frames go to `target` instead of the terminal, in full color
+    pub fn with_target(target: Box<dyn RenderTarget>) -> Self {
+        let (width, height) = target.size();
         let buffer_size = (width as usize) * (height as usize);
-        let capabilities = TerminalCapabilities::detect();
-
-        Ok(Self {
-            stdout,
+        Self {
+            target,
             width,
             height,
             buffer: vec![Cell::default(); buffer_size],
             last_buffer: vec![Cell::default(); buffer_size],
-            capabilities,
-        })
+            capabilities: TerminalCapabilities {
+                color_support: ColorSupport::TrueColor,
+                is_tty: false,
+            },
+            layer: Layer::Sky,
+            protected: Vec::new(),
+            bold: false,
+            graphics: None,
+            sky_image: None,
+            shown_sky_image: None,
+            cell_pixels: (0, 0),
+            sixel_area: None,
+        }
+    }
+
+    // Composes frames in memory only, for a scene embedded in another program
+    pub fn headless(width: u16, height: u16) -> Self {
+        Self::with_target(Box::new(MemoryTarget::new(width, height)))
     }
 
     pub fn init(&mut self) -> Result<(), TerminalError> {
         terminal::enable_raw_mode().map_err(TerminalError::RawModeError)?;
-        execute!(self.stdout, EnterAlternateScreen, cursor::Hide)
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)
             .map_err(TerminalError::InitError)?;
         Ok(())
     }
 
-    pub fn cleanup(&mut self) -> io::Result<()> {
-        execute!(self.stdout, LeaveAlternateScreen, cursor::Show, ResetColor)?;
-        terminal::disable_raw_mode()?;
+    // Asks the terminal to report when it gains and loses focus
+    pub fn enable_focus_events(&mut self) -> io::Result<()> {
+        execute!(io::stdout(), EnableFocusChange)
+    }
+
+    // Writes the frames from here on to `path` as well, as asciicast for a .cast file
+    pub fn record(&mut self, path: &Path) -> io::Result<()> {
+        let recorder = Recorder::create(path, self.width, self.height)?;
+        let target = std::mem::replace(&mut self.target, Box::new(MemoryTarget::new(0, 0)));
+        self.target = Box::new(RecordingTarget::new(target, recorder));
         Ok(())
     }
 
+    pub fn cleanup(&mut self) -> io::Result<()> {
+        self.target.finish()
+    }
+
     pub fn manual_resize(&mut self, width: u16, height: u16) -> io::Result<()> {
         if width != self.width || height != self.height {
             self.width = width;
@@ -87,50 +207,70 @@ impl TerminalRenderer {
             let buffer_size = (width as usize) * (height as usize);
             self.buffer = vec![Cell::default(); buffer_size];
             self.last_buffer = vec![Cell::default(); buffer_size];
-            execute!(self.stdout, Clear(ClearType::All))?;
+            self.target.resize(width, height)?;
+            // The screen was cleared, and a sixel is drawn at the font's size
+            if self.graphics == Some(Protocol::Sixel) {
+                self.cell_pixels = graphics::cell_pixels();
+                self.shown_sky_image = None;
+                self.sixel_area = None;
+            }
         }
         Ok(())
     }
 
+    // Below this size the scene overlaps itself; the app shows a notice until resized
+    pub fn too_small(&self) -> Option<TerminalError> {
+        (self.width < MIN_TERMINAL_WIDTH || self.height < MIN_TERMINAL_HEIGHT).then_some(
+            TerminalError::TooSmall {
+                width: self.width,
+                height: self.height,
+                min_width: MIN_TERMINAL_WIDTH,
+                min_height: MIN_TERMINAL_HEIGHT,
+            },
+        )
+    }
+
     pub fn get_size(&self) -> (u16, u16) {
         (self.width, self.height)
     }
 
     pub fn clear(&mut self) -> io::Result<()> {
         self.buffer.fill(Cell::default());
+        self.layer = Layer::Sky;
+        self.protected.clear();
         Ok(())
     }
 
+    // Reserves cells for UI drawn later in the frame: until the next clear, the layers
+    // below the HUD skip them and lightning doesn't light them up
+    pub fn protect(&mut self, region: Region) {
+        self.protected.push(region);
+    }
+
+    fn is_protected(&self, x: u16, y: u16) -> bool {
+        self.protected.iter().any(|r| r.contains(x, y))
+    }
+
+    // Layer everything drawn from now on goes to
+    pub fn set_layer(&mut self, layer: Layer) {
+        self.layer = layer;
+    }
+
     pub fn render_centered_colored(
         &mut self,
         lines: &[String],
         start_row: u16,
         color: Color,
     ) -> io::Result<()> {
-        let max_width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let max_width = lines.iter().map(|l| display_width(l)).max().unwrap_or(0);
         let start_col = if self.width as usize > max_width {
             (self.width as usize - max_width) / 2
         } else {
             0
         };
-        let adjusted_color = self.capabilities.adjust_color(color);
 
         for (idx, line) in lines.iter().enumerate() {
-            let row = start_row + idx as u16;
-            if row < self.height {
-                for (char_idx, ch) in line.chars().enumerate() {
-                    let col = start_col as u16 + char_idx as u16;
-                    if col < self.width {
-                        let buffer_idx = (row as usize) * (self.width as usize) + (col as usize);
-                        if buffer_idx < self.buffer.len() {
-                            self.buffer[buffer_idx] = Cell {
-                                character: ch,
-                                color: adjusted_color,
-                            };
-                        }
-                    }
-                }
-            }
+            self.render_line_colored(start_col as u16, start_row + idx as u16, line, color)?;
         }
 
         Ok(())
@@ -148,45 +288,250 @@ impl TerminalRenderer {
         }
         let adjusted_color = self.capabilities.adjust_color(color);
 
-        for (idx, ch) in text.chars().enumerate() {
-            let col = x + idx as u16;
-            if col < self.width {
-                let buffer_idx = (y as usize) * (self.width as usize) + (col as usize);
-                if buffer_idx < self.buffer.len() {
-                    self.buffer[buffer_idx] = Cell {
-                        character: ch,
-                        color: adjusted_color,
-                    };
-                }
+        let mut col = x as usize;
+        for ch in text.chars() {
+            if col >= self.width as usize {
+                break;
             }
+            if ch == ' ' && self.layer.is_transparent() {
+                col += 1;
+                continue;
+            }
+            col += self.put(col as u16, y, ch, adjusted_color);
         }
         Ok(())
     }
 
     pub fn render_char(&mut self, x: u16, y: u16, ch: char, color: Color) -> io::Result<()> {
         if x < self.width && y < self.height {
-            let buffer_idx = (y as usize) * (self.width as usize) + (x as usize);
-            if buffer_idx < self.buffer.len() {
-                self.buffer[buffer_idx] = Cell {
-                    character: ch,
-                    color: self.capabilities.adjust_color(color),
-                };
+            let color = self.capabilities.adjust_color(color);
+            self.put(x, y, ch, color);
+        }
+        Ok(())
+    }
+
+    // Draws one character at an in-bounds cell and returns the columns it advances.
+    // A wide character that would cross the right edge is dropped rather than wrapped,
+    // and a wide character partly overdrawn is blanked so no half of it is left behind
+    fn put(&mut self, x: u16, y: u16, ch: char, color: Color) -> usize {
+        let char_width = char_width(ch);
+        if char_width == 0 {
+            return 0;
+        }
+        let width = self.width as usize;
+        let (x, row) = (x as usize, y as usize * width);
+        if x + char_width > width || self.buffer[row + x].layer > self.layer {
+            return char_width;
+        }
+        if self.layer < Layer::Hud && self.is_protected(x as u16, y) {
+            return char_width;
+        }
+
+        for col in x..x + char_width {
+            let cell = self.buffer[row + col];
+            if cell.character == WIDE_TAIL && col > 0 {
+                self.buffer[row + col - 1].character = ' ';
             }
+            if col + 1 < width && self.buffer[row + col + 1].character == WIDE_TAIL {
+                self.buffer[row + col + 1].character = ' ';
+            }
+        }
+        self.buffer[row + x].draw(ch, color, self.layer);
+        if char_width == 2 {
+            self.buffer[row + x + 1].draw(WIDE_TAIL, color, self.layer);
+        }
+        char_width
+    }
+
+    // Every color is dropped on the way into the buffer, like under NO_COLOR
+    pub fn set_monochrome(&mut self) {
+        self.capabilities.disable_color();
+    }
+
+    // Returns false, leaving the sky to background colors, when the terminal draws
+    // neither kitty graphics nor sixels
+    pub fn enable_graphics(&mut self) -> bool {
+        self.graphics = graphics::detect();
+        if self.graphics == Some(Protocol::Sixel) {
+            self.cell_pixels = graphics::cell_pixels();
+        }
+        GRAPHICS_ENABLED.store(self.graphics == Some(Protocol::Kitty), Ordering::Relaxed);
+        self.graphics.is_some()
+    }
+
+    // The sun and moon are drawn into the sky image
+    pub fn has_graphics(&self) -> bool {
+        self.graphics.is_some()
+    }
+
+    // So is the sky gradient, which otherwise stays in the cell backgrounds
+    pub fn has_sky_image(&self) -> bool {
+        self.graphics == Some(Protocol::Kitty)
+    }
+
+    // Sent with the next flush, if it differs from the image already shown
+    pub fn set_sky_image(&mut self, image: SkyImage) {
+        self.sky_image = Some(image);
+    }
+
+    // Everything is drawn bold, which most terminals also show brighter
+    pub fn set_bold(&mut self, bold: bool) {
+        if bold != self.bold {
+            self.bold = bold;
+            // Cells that didn't change would keep their old weight
+            self.last_buffer.fill(Cell {
+                character: REDRAW,
+                ..Cell::default()
+            });
+        }
+    }
+
+    pub fn supports_truecolor(&self) -> bool {
+        self.capabilities.color_support == ColorSupport::TrueColor
+    }
+
+    pub fn fill_row_background(&mut self, y: u16, color: Color) -> io::Result<()> {
+        if y >= self.height {
+            return Ok(());
+        }
+        let background = self.capabilities.adjust_color(color);
+        let start = (y as usize) * (self.width as usize);
+        for cell in &mut self.buffer[start..start + self.width as usize] {
+            cell.background = background;
         }
         Ok(())
     }
 
-    pub fn flash_screen(&mut self) -> io::Result<()> {
+    // Brightens rows `top..bottom`, e.g. only the sky during a lightning strike
+    pub fn flash_region(&mut self, top: u16, bottom: u16) -> io::Result<()> {
         let flash_color = self.capabilities.adjust_color(Color::White);
-        for cell in &mut self.buffer {
-            cell.color = flash_color;
+        let width = self.width as usize;
+        let start = (top.min(self.height) as usize) * width;
+        let end = (bottom.min(self.height) as usize) * width;
+        for idx in start..end.max(start) {
+            let (x, y) = ((idx % width) as u16, (idx / width) as u16);
+            if self.buffer[idx].layer < Layer::Hud && !self.is_protected(x, y) {
+                self.buffer[idx].color = flash_color;
+            }
         }
         Ok(())
     }
 
+    // The last composed frame, a line per row: plain text with trailing spaces trimmed,
+    // or with `colors` the text with the colors as ANSI escapes
+    pub fn screenshot(&self, colors: bool) -> String {
+        let mut text = Vec::new();
+        for row in self.buffer.chunks(self.width.max(1) as usize) {
+            let mut line = Vec::new();
+            let (mut color, mut background) = (Color::Reset, Color::Reset);
+            for cell in row.iter().filter(|cell| cell.character != WIDE_TAIL) {
+                if colors && cell.color != color {
+                    let _ = queue!(line, SetForegroundColor(cell.color));
+                    color = cell.color;
+                }
+                if colors && cell.background != background {
+                    let _ = queue!(line, SetBackgroundColor(cell.background));
+                    background = cell.background;
+                }
+                let _ = write!(line, "{}", cell.character);
+            }
+            if colors {
+                let _ = queue!(line, ResetColor);
+            } else {
+                line.truncate(line.trim_ascii_end().len());
+            }
+            text.extend(line);
+            text.push(b'\n');
+        }
+        String::from_utf8_lossy(&text).into_owned()
+    }
+
+    // The composed frame as (x, y, character, foreground, background), row by row. The
+    // cell to the right of a wide character is left out
+    pub fn cells(&self) -> impl Iterator<Item = (u16, u16, char, Color, Color)> + '_ {
+        let width = self.width.max(1) as usize;
+        self.buffer
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.character != WIDE_TAIL)
+            .map(move |(i, cell)| {
+                let (x, y) = ((i % width) as u16, (i / width) as u16);
+                (x, y, cell.character, cell.color, cell.background)
+            })
+    }
+
+    // The last composed frame as a PNG image, drawn with a built-in bitmap font
+    pub fn screenshot_png(&self) -> Vec<u8> {
+        raster::png(&self.buffer, self.width, self.height)
+    }
+
+    // The sun or moon as a sixel and the cells it covers, when it moved or a cell over it
+    // changed. The cells under the last one are drawn again so it doesn't linger
+    fn sixel_update(&mut self) -> Option<(Region, String)> {
+        let area = self
+            .sky_image
+            .and_then(|image| image.body_area(self.cell_pixels));
+        let moved = self.sky_image != self.shown_sky_image;
+        if moved {
+            if let Some(old) = self.sixel_area {
+                self.redraw_region(old);
+            }
+            self.shown_sky_image = self.sky_image;
+            self.sixel_area = area;
+        }
+        let area = area?;
+        let image = self.sky_image?;
+        (moved || self.region_changed(area))
+            .then(|| (area, image.sixel_escape(area, self.cell_pixels)))
+    }
+
+    // Buffer indices of the on-screen cells of `region`
+    fn region_cells(&self, region: Region) -> Vec<usize> {
+        let width = self.width as usize;
+        let columns = region.x..region.x.saturating_add(region.width).min(self.width);
+        (region.y..region.y.saturating_add(region.height).min(self.height))
+            .flat_map(|y| {
+                columns
+                    .clone()
+                    .map(move |x| y as usize * width + x as usize)
+            })
+            .collect()
+    }
+
+    fn redraw_region(&mut self, region: Region) {
+        for idx in self.region_cells(region) {
+            self.last_buffer[idx].character = REDRAW;
+        }
+    }
+
+    fn region_changed(&self, region: Region) -> bool {
+        self.region_cells(region)
+            .into_iter()
+            .any(|idx| self.buffer[idx] != self.last_buffer[idx])
+    }
+
     pub fn flush(&mut self) -> io::Result<()> {
         let mut current_color = Color::Reset;
+        let mut current_background = Color::Reset;
         let mut last_pos: Option<(u16, u16)> = None;
+        let sixel = match self.graphics {
+            Some(Protocol::Sixel) => self.sixel_update(),
+            _ => None,
+        };
+        let target = &mut self.target;
+
+        target.begin_frame()?;
+        // The reset at the end of the last frame cleared it
+        if self.bold {
+            target.escape(BOLD)?;
+        }
+        if self.graphics == Some(Protocol::Kitty) && self.sky_image != self.shown_sky_image {
+            if let Some(image) = self.sky_image {
+                target.move_to(0, 0)?;
+                target.escape(&image.kitty_escape())?;
+            }
+            self.shown_sky_image = self.sky_image;
+        }
 
         for y in 0..self.height {
             for x in 0..self.width {
@@ -199,28 +544,67 @@ impl TerminalRenderer {
                 let cell = self.buffer[idx];
                 let last_cell = self.last_buffer[idx];
 
+                // Printed along with the character to its left, or after the sixel
+                if cell.character == WIDE_TAIL
+                    || sixel.as_ref().is_some_and(|(area, _)| area.contains(x, y))
+                {
+                    continue;
+                }
+
                 if cell != last_cell {
                     let expected_pos = last_pos.map(|(lx, ly)| (lx + 1, ly));
                     if expected_pos != Some((x, y)) {
-                        queue!(self.stdout, cursor::MoveTo(x, y))?;
+                        target.move_to(x, y)?;
                     }
 
                     if cell.color != current_color {
-                        queue!(self.stdout, SetForegroundColor(cell.color))?;
+                        target.set_color(cell.color)?;
                         current_color = cell.color;
                     }
 
-                    queue!(self.stdout, Print(cell.character))?;
-                    last_pos = Some((x, y));
+                    if cell.background != current_background {
+                        target.set_background(cell.background)?;
+                        current_background = cell.background;
+                    }
+
+                    target.print(cell.character)?;
+                    last_pos = Some((x + char_width(cell.character) as u16 - 1, y));
+                }
+            }
+        }
+
+        if let Some((area, escape)) = &sixel {
+            target.move_to(area.x, area.y)?;
+            target.escape(escape)?;
+            // Everything but empty sky, in front of the image
+            let width = self.width as usize;
+            for y in area.y..area.y.saturating_add(area.height).min(self.height) {
+                for x in area.x..area.x.saturating_add(area.width).min(self.width) {
+                    let cell = self.buffer[y as usize * width + x as usize];
+                    if cell.character == WIDE_TAIL
+                        || (cell.character == ' ' && cell.layer == Layer::Sky)
+                    {
+                        continue;
+                    }
+                    target.move_to(x, y)?;
+                    if cell.color != current_color {
+                        target.set_color(cell.color)?;
+                        current_color = cell.color;
+                    }
+                    if cell.background != current_background {
+                        target.set_background(cell.background)?;
+                        current_background = cell.background;
+                    }
+                    target.print(cell.character)?;
                 }
             }
         }
 
-        if current_color != Color::Reset {
-            queue!(self.stdout, ResetColor)?;
+        if self.bold || current_color != Color::Reset || current_background != Color::Reset {
+            target.reset()?;
         }
 
-        self.stdout.flush()?;
+        target.end_frame()?;
         self.last_buffer.copy_from_slice(&self.buffer);
         Ok(())
     }
@@ -231,3 +615,128 @@ impl Drop for TerminalRenderer {
         let _ = self.cleanup();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renderer(width: u16, height: u16) -> TerminalRenderer {
+        TerminalRenderer::headless(width, height)
+    }
+
+    fn row(renderer: &TerminalRenderer) -> String {
+        renderer.buffer[..renderer.width as usize]
+            .iter()
+            .filter(|c| c.character != WIDE_TAIL)
+            .map(|c| c.character)
+            .collect()
+    }
+
+    #[test]
+    fn test_sixel_sent_when_cells_over_it_change() {
+        let mut r = renderer(80, 24);
+        r.graphics = Some(Protocol::Sixel);
+        r.cell_pixels = (10, 20);
+        let image = SkyImage {
+            top: (0, 0, 0),
+            horizon: (0, 0, 0),
+            columns: 80,
+            rows: 20,
+            body: Some((40, 2, SkyBody::Moon { phase: 0.5 })),
+        };
+        r.set_sky_image(image);
+        let (area, _) = r.sixel_update().unwrap();
+        r.flush().unwrap();
+        assert!(r.sixel_update().is_none());
+
+        // A cloud drifting over the moon
+        r.render_char(area.x, area.y, '~', Color::White).unwrap();
+        assert!(r.sixel_update().is_some());
+        r.flush().unwrap();
+
+        // Moving on leaves the old cells to be drawn again
+        r.set_sky_image(SkyImage {
+            body: Some((10, 2, SkyBody::Moon { phase: 0.5 })),
+            ..image
+        });
+        r.sixel_update().unwrap();
+        let idx = area.y as usize * 80 + area.x as usize + 1;
+        assert_eq!(r.last_buffer[idx].character, REDRAW);
+    }
+
+    #[test]
+    fn test_wide_characters_take_two_cells_and_clip() {
+        let mut r = renderer(6, 1);
+        r.render_line_colored(0, 0, "東京ab", Color::White).unwrap();
+        assert_eq!(row(&r), "東京ab");
+
+        // No room for the second half at the right edge
+        r.clear().unwrap();
+        r.render_line_colored(2, 0, "a東京", Color::White).unwrap();
+        assert_eq!(row(&r), "  a東 ");
+
+        // Overdrawing half of a wide character blanks the rest of it
+        r.render_char(4, 0, 'x', Color::White).unwrap();
+        assert_eq!(row(&r), "  a x ");
+    }
+
+    #[test]
+    fn test_screenshot() {
+        let mut r = renderer(5, 2);
+        r.render_line_colored(0, 0, "東 a", Color::White).unwrap();
+        assert_eq!(r.screenshot(false), "東 a\n\n");
+
+        // Colors as the terminal would get them, without the capability adjustments
+        r.buffer[0].color = Color::Red;
+        r.buffer[1].color = Color::Red;
+        r.buffer[3].color = Color::Blue;
+        assert_eq!(
+            r.screenshot(true).lines().next().unwrap(),
+            "\x1b[38;5;9m東\x1b[39m \x1b[38;5;12ma\x1b[39m \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_layers_compose_in_z_order() {
+        let mut r = renderer(6, 1);
+        r.set_layer(Layer::Weather);
+        r.render_char(1, 0, '|', Color::White).unwrap();
+        // A sky sprite drawn later stays behind, and its spaces don't punch holes
+        r.set_layer(Layer::Sky);
+        r.render_line_colored(0, 0, "( ) ~", Color::White).unwrap();
+        assert_eq!(row(&r), "(|) ~ ");
+
+        // HUD text is opaque, spaces included
+        r.set_layer(Layer::Hud);
+        r.render_line_colored(0, 0, "a  b", Color::White).unwrap();
+        assert_eq!(row(&r), "a  b~ ");
+    }
+
+    #[test]
+    fn test_protected_region_keeps_particles_out() {
+        let mut r = renderer(6, 2);
+        r.protect(Region {
+            x: 1,
+            y: 0,
+            width: 3,
+            height: 1,
+        });
+        r.set_layer(Layer::Weather);
+        r.render_line_colored(0, 0, "||||||", Color::White).unwrap();
+        r.render_char(2, 1, '|', Color::White).unwrap();
+        assert_eq!(row(&r), "|   ||");
+        assert_eq!(r.buffer[8].character, '|');
+
+        r.set_layer(Layer::Hud);
+        r.render_line_colored(1, 0, "ok", Color::White).unwrap();
+        assert_eq!(row(&r), "|ok ||");
+    }
+
+    #[test]
+    fn test_centering_uses_display_width() {
+        let mut r = renderer(8, 1);
+        r.render_centered_colored(&["❄東京".to_string()], 0, Color::White)
+            .unwrap();
+        assert_eq!(row(&r), " ❄東京  ");
+    }
+}
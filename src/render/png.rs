@@ -0,0 +1,237 @@
+// Just enough PNG to save a screenshot: 8-bit RGB, compressed with fixed-Huffman
+// deflate and a single-entry LZ77 hash, which does well on the long runs of one color
+// a terminal frame is made of
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+const WINDOW: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+// Deflate's bit order: values from the lowest bit up, Huffman codes from the highest
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn bits(&mut self, value: u32, count: u32) {
+        self.buffer |= (value as u64) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn code(&mut self, code: u32, count: u32) {
+        self.bits(code.reverse_bits() >> (32 - count), count);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn symbol(out: &mut BitWriter, symbol: u16) {
+    match symbol {
+        0..=143 => out.code(0x30 + symbol as u32, 8),
+        144..=255 => out.code(0x190 + (symbol as u32 - 144), 9),
+        256..=279 => out.code(symbol as u32 - 256, 7),
+        _ => out.code(0xc0 + (symbol as u32 - 280), 8),
+    }
+}
+
+// Index of the last base that's no more than `value`
+fn bucket(bases: &[u16], value: usize) -> usize {
+    bases.partition_point(|&base| base as usize <= value) - 1
+}
+
+fn copy(out: &mut BitWriter, length: usize, distance: usize) {
+    let code = bucket(&LENGTH_BASES, length);
+    symbol(out, 257 + code as u16);
+    out.bits(
+        (length - LENGTH_BASES[code] as usize) as u32,
+        LENGTH_EXTRA_BITS[code] as u32,
+    );
+
+    let code = bucket(&DISTANCE_BASES, distance);
+    out.code(code as u32, 5);
+    out.bits(
+        (distance - DISTANCE_BASES[code] as usize) as u32,
+        DISTANCE_EXTRA_BITS[code] as u32,
+    );
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let key = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+    (key.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+}
+
+// A zlib stream of `data` as one fixed-Huffman block
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::new();
+    // Final block, fixed codes
+    out.bits(1, 1);
+    out.bits(1, 2);
+
+    let mut last_seen = vec![usize::MAX; 1 << HASH_BITS];
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if i + MIN_MATCH <= data.len() {
+            let slot = hash(&data[i..]);
+            let candidate = last_seen[slot];
+            last_seen[slot] = i;
+            if candidate != usize::MAX && i - candidate <= WINDOW {
+                let limit = MAX_MATCH.min(data.len() - i);
+                length = (0..limit)
+                    .take_while(|&k| data[candidate + k] == data[i + k])
+                    .count();
+                distance = i - candidate;
+            }
+        }
+        if length >= MIN_MATCH {
+            copy(&mut out, length, distance);
+            i += length;
+        } else {
+            symbol(&mut out, data[i] as u16);
+            i += 1;
+        }
+    }
+    symbol(&mut out, 256);
+
+    let mut stream = vec![0x78, 0x01];
+    stream.extend(out.finish());
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// `rgb` holds the pixels row by row, three bytes each
+pub fn encode(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 3;
+    let mut scanlines = Vec::with_capacity((stride + 1) * height as usize);
+    for row in rgb.chunks(stride.max(1)).take(height as usize) {
+        // No filter
+        scanlines.push(0);
+        scanlines.extend(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGB, deflate, adaptive filtering, not interlaced
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib(&scanlines));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_encode() {
+        let (width, height) = (40, 30);
+        let rgb: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                if i % 40 < 20 {
+                    [10, 20, 30]
+                } else {
+                    [200, 0, 0]
+                }
+            })
+            .collect();
+        let png = encode(width, height, &rgb);
+
+        assert_eq!(png[..8], SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(png[16..20], 40u32.to_be_bytes());
+        assert_eq!(png[20..24], 30u32.to_be_bytes());
+        assert_eq!(
+            png[png.len() - 8..],
+            [b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+        // Runs of one color squeeze down to a fraction of the raw pixels
+        assert!(png.len() < rgb.len() / 10);
+    }
+}
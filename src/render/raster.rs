@@ -0,0 +1,208 @@
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{Cell, WIDE_TAIL, png};
+use crossterm::style::Color;
+
+type Rgb = (u8, u8, u8);
+
+// A terminal cell's usual 1:2 shape, with the font drawn at twice its size
+const CELL_WIDTH: usize = 12;
+const CELL_HEIGHT: usize = 24;
+const GLYPH_SCALE: usize = 2;
+const GLYPH_LEFT: usize = (CELL_WIDTH - GLYPH_WIDTH * GLYPH_SCALE) / 2;
+const GLYPH_TOP: usize = 4;
+
+// What the terminal shows for the default colors
+const DEFAULT_FOREGROUND: Rgb = (204, 204, 204);
+const DEFAULT_BACKGROUND: Rgb = (12, 12, 16);
+
+// xterm's 16 colors
+const ANSI: [Rgb; 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn ansi_256(index: u8) -> Rgb {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match index {
+        0..=15 => ANSI[index as usize],
+        16..=231 => {
+            let i = index as usize - 16;
+            (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn to_rgb(color: Color, default: Rgb) -> Rgb {
+    match color {
+        Color::Reset => default,
+        Color::Black => ANSI[0],
+        Color::DarkRed => ANSI[1],
+        Color::DarkGreen => ANSI[2],
+        Color::DarkYellow => ANSI[3],
+        Color::DarkBlue => ANSI[4],
+        Color::DarkMagenta => ANSI[5],
+        Color::DarkCyan => ANSI[6],
+        Color::Grey => ANSI[7],
+        Color::DarkGrey => ANSI[8],
+        Color::Red => ANSI[9],
+        Color::Green => ANSI[10],
+        Color::Yellow => ANSI[11],
+        Color::Blue => ANSI[12],
+        Color::Magenta => ANSI[13],
+        Color::Cyan => ANSI[14],
+        Color::White => ANSI[15],
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(index) => ansi_256(index),
+    }
+}
+
+// Arms of a box-drawing character (left, right, up, down) and whether it's heavy
+fn box_lines(character: char) -> Option<([bool; 4], bool)> {
+    let (arms, heavy) = match character {
+        '─' => ([true, true, false, false], false),
+        '━' => ([true, true, false, false], true),
+        '│' => ([false, false, true, true], false),
+        '┃' => ([false, false, true, true], true),
+        '┌' => ([false, true, false, true], false),
+        '┐' => ([true, false, false, true], false),
+        '└' => ([false, true, true, false], false),
+        '┘' => ([true, false, true, false], false),
+        _ => return None,
+    };
+    Some((arms, heavy))
+}
+
+// Block, shade, box-drawing and braille characters, drawn to fill the cell as they
+// would in a terminal. None for characters from the font
+fn covers(character: char, x: usize, y: usize) -> Option<bool> {
+    let code = character as u32;
+    match character {
+        '█' => Some(true),
+        '▀' => Some(y < CELL_HEIGHT / 2),
+        // Lower eighths
+        '▁'..='▇' => Some(y >= CELL_HEIGHT - (code - 0x2580) as usize * CELL_HEIGHT / 8),
+        '░' => Some((x / 2 + y / 2) % 4 == 0),
+        '▒' => Some((x / 2 + y / 2) % 2 == 0),
+        '▓' => Some((x / 2 + y / 2) % 4 != 0),
+        '\u{2800}'..='\u{28ff}' => {
+            let (column, row) = (x / (CELL_WIDTH / 2), y / (CELL_HEIGHT / 4));
+            let bit = if row < 3 {
+                column * 3 + row
+            } else {
+                6 + column
+            };
+            let (dx, dy) = (x % (CELL_WIDTH / 2), y % (CELL_HEIGHT / 4));
+            Some(code >> bit & 1 == 1 && (2..5).contains(&dx) && (2..5).contains(&dy))
+        }
+        _ => {
+            let ([left, right, up, down], heavy) = box_lines(character)?;
+            let half = if heavy { 2 } else { 1 };
+            let (cx, cy) = (CELL_WIDTH / 2, CELL_HEIGHT / 2);
+            let across =
+                y + half > cy && y < cy + half && ((left && x <= cx) || (right && x >= cx));
+            let along = x + half > cx && x < cx + half && ((up && y <= cy) || (down && y >= cy));
+            Some(across || along)
+        }
+    }
+}
+
+fn glyph_covers(glyph: &font::Glyph, x: usize, y: usize) -> bool {
+    let (Some(x), Some(y)) = (x.checked_sub(GLYPH_LEFT), y.checked_sub(GLYPH_TOP)) else {
+        return false;
+    };
+    let (gx, gy) = (x / GLYPH_SCALE, y / GLYPH_SCALE);
+    gx < GLYPH_WIDTH && gy < GLYPH_HEIGHT && font::is_set(glyph, gx, gy)
+}
+
+// The frame as a PNG, with every cell drawn in the built-in font
+pub(super) fn png(buffer: &[Cell], columns: u16, rows: u16) -> Vec<u8> {
+    let (columns, rows) = (columns as usize, rows as usize);
+    let width = columns * CELL_WIDTH;
+    let mut pixels = vec![0u8; width * rows * CELL_HEIGHT * 3];
+
+    for (index, cell) in buffer.iter().enumerate().take(columns * rows) {
+        let (column, row) = (index % columns, index / columns);
+        let foreground = to_rgb(cell.color, DEFAULT_FOREGROUND);
+        let background = to_rgb(cell.background, DEFAULT_BACKGROUND);
+        let character = if cell.character == WIDE_TAIL {
+            ' '
+        } else {
+            cell.character
+        };
+        let glyph = font::glyph(character);
+
+        for y in 0..CELL_HEIGHT {
+            for x in 0..CELL_WIDTH {
+                let on = covers(character, x, y).unwrap_or_else(|| glyph_covers(&glyph, x, y));
+                let (r, g, b) = if on { foreground } else { background };
+                let offset = ((row * CELL_HEIGHT + y) * width + column * CELL_WIDTH + x) * 3;
+                pixels[offset..offset + 3].copy_from_slice(&[r, g, b]);
+            }
+        }
+    }
+
+    png::encode(width as u32, (rows * CELL_HEIGHT) as u32, &pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colors() {
+        assert_eq!(to_rgb(Color::Reset, DEFAULT_BACKGROUND), DEFAULT_BACKGROUND);
+        assert_eq!(
+            to_rgb(Color::Rgb { r: 1, g: 2, b: 3 }, DEFAULT_FOREGROUND),
+            (1, 2, 3)
+        );
+        assert_eq!(to_rgb(Color::AnsiValue(9), DEFAULT_FOREGROUND), ANSI[9]);
+        assert_eq!(ansi_256(196), (255, 0, 0));
+        assert_eq!(ansi_256(244), (128, 128, 128));
+    }
+
+    #[test]
+    fn test_drawn_characters() {
+        assert_eq!(covers('█', 0, 0), Some(true));
+        assert_eq!(covers('▄', 0, 0), Some(false));
+        assert_eq!(covers('▄', 0, CELL_HEIGHT - 1), Some(true));
+        assert_eq!(covers('─', 0, CELL_HEIGHT / 2), Some(true));
+        assert_eq!(covers('┌', 0, CELL_HEIGHT / 2), Some(false));
+        // Braille dot 1 sits top left, dot 8 bottom right
+        assert_eq!(covers('\u{2801}', 3, 3), Some(true));
+        assert_eq!(
+            covers('\u{2801}', CELL_WIDTH - 3, CELL_HEIGHT - 3),
+            Some(false)
+        );
+        assert_eq!(
+            covers('\u{2880}', CELL_WIDTH - 3, CELL_HEIGHT - 3),
+            Some(true)
+        );
+        assert_eq!(covers('A', 0, 0), None);
+    }
+
+    #[test]
+    fn test_png_size() {
+        let cells = vec![Cell::default(); 6];
+        let png = png(&cells, 3, 2);
+        assert_eq!(png[16..20], ((3 * CELL_WIDTH) as u32).to_be_bytes());
+        assert_eq!(png[20..24], ((2 * CELL_HEIGHT) as u32).to_be_bytes());
+    }
+}
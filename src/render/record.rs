@@ -0,0 +1,122 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Clears the screen and hides the cursor, so playback starts from what the app saw
+const START: &str = "\x1b[?25l\x1b[2J\x1b[H";
+
+enum Format {
+    // asciinema's asciicast v2: a JSON header, then one JSON array per chunk of output
+    Asciicast,
+    // The bytes sent to the terminal, for `cat` to play back at full speed
+    Raw,
+}
+
+// Writes everything the renderer sends to the terminal into a file as well
+pub struct Recorder {
+    file: BufWriter<File>,
+    format: Format,
+    started: Instant,
+}
+
+impl Recorder {
+    // asciicast for a .cast file, the raw output for anything else
+    pub fn create(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("cast") => Format::Asciicast,
+            _ => Format::Raw,
+        };
+        let mut recorder = Self {
+            file: BufWriter::new(File::create(path)?),
+            format,
+            started: Instant::now(),
+        };
+        if let Format::Asciicast = recorder.format {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+            let header = serde_json::json!({
+                "version": 2,
+                "width": width,
+                "height": height,
+                "timestamp": timestamp,
+                "env": { "TERM": std::env::var("TERM").unwrap_or_default() },
+            });
+            writeln!(recorder.file, "{}", header)?;
+        }
+        recorder.output(START.as_bytes())?;
+        Ok(recorder)
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> io::Result<()> {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        writeln!(
+            self.file,
+            "{}",
+            serde_json::json!([(elapsed * 1e6).round() / 1e6, kind, data])
+        )
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match self.format {
+            Format::Asciicast => self.event("o", &String::from_utf8_lossy(bytes)),
+            Format::Raw => self.file.write_all(bytes),
+        }
+    }
+
+    // The screen is cleared along with the resize, as the renderer does
+    pub fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        if let Format::Asciicast = self.format {
+            self.event("r", &format!("{}x{}", width, height))?;
+        }
+        self.output(START.as_bytes())
+    }
+
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast() {
+        let path = std::env::temp_dir().join("weathr_test_record.cast");
+        let mut recorder = Recorder::create(&path, 80, 24).unwrap();
+        recorder.output("\x1b[1;1H☀".as_bytes()).unwrap();
+        recorder.resize(100, 30).unwrap();
+        recorder.finish().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 80);
+        assert_eq!(lines[0]["height"], 24);
+        assert_eq!(lines[2][1], "o");
+        assert_eq!(lines[2][2], "\x1b[1;1H☀");
+        assert_eq!(lines[3][1], "r");
+        assert_eq!(lines[3][2], "100x30");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_raw() {
+        let path = std::env::temp_dir().join("weathr_test_record.txt");
+        let mut recorder = Recorder::create(&path, 80, 24).unwrap();
+        recorder.output(b"\x1b[31mrain").unwrap();
+        recorder.finish().unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        assert_eq!(contents, format!("{}\x1b[31mrain", START).into_bytes());
+
+        std::fs::remove_file(path).ok();
+    }
+}
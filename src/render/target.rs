@@ -0,0 +1,408 @@
+use super::record::Recorder;
+use super::{WIDE_TAIL, char_width, restore_terminal};
+use crate::error::TerminalError;
+use crossterm::{
+    cursor, execute, queue,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate},
+};
+use std::io::{self, BufWriter, IsTerminal, Stdout, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+// Where the renderer sends its frames: between begin_frame and end_frame, each changed
+// cell as a move (skipped when the cursor is already there), the colors when they
+// change, and the character
+pub trait RenderTarget: Send {
+    fn size(&self) -> (u16, u16);
+
+    // The screen is cleared along with the resize
+    fn resize(&mut self, width: u16, height: u16) -> io::Result<()>;
+
+    fn begin_frame(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()>;
+
+    fn set_color(&mut self, color: Color) -> io::Result<()>;
+
+    fn set_background(&mut self, color: Color) -> io::Result<()>;
+
+    fn print(&mut self, character: char) -> io::Result<()>;
+
+    // Back to the default colors and attributes
+    fn reset(&mut self) -> io::Result<()>;
+
+    // Bold text and kitty graphics, which only a terminal understands
+    fn escape(&mut self, _escape: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn end_frame(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    // Once the renderer is done with it
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// A frame as the bytes a terminal gets, for the terminal and recordings alike
+#[derive(Default)]
+struct AnsiFrame {
+    bytes: Vec<u8>,
+}
+
+impl AnsiFrame {
+    fn begin(&mut self) -> io::Result<()> {
+        // Ask the terminal to present the diff atomically so partial frames never show
+        queue!(self.bytes, BeginSynchronizedUpdate)
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()> {
+        queue!(self.bytes, cursor::MoveTo(x, y))
+    }
+
+    fn set_color(&mut self, color: Color) -> io::Result<()> {
+        queue!(self.bytes, SetForegroundColor(color))
+    }
+
+    fn set_background(&mut self, color: Color) -> io::Result<()> {
+        queue!(self.bytes, SetBackgroundColor(color))
+    }
+
+    fn print(&mut self, character: char) -> io::Result<()> {
+        queue!(self.bytes, Print(character))
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        queue!(self.bytes, ResetColor)
+    }
+
+    fn escape(&mut self, escape: &str) -> io::Result<()> {
+        self.bytes.write_all(escape.as_bytes())
+    }
+
+    // The finished frame, leaving this one empty for the next
+    fn end(&mut self) -> io::Result<Vec<u8>> {
+        queue!(self.bytes, EndSynchronizedUpdate)?;
+        Ok(std::mem::take(&mut self.bytes))
+    }
+}
+
+// The terminal weathr runs in, through crossterm
+pub struct CrosstermTarget {
+    stdout: BufWriter<Stdout>,
+    frame: AnsiFrame,
+    width: u16,
+    height: u16,
+}
+
+impl CrosstermTarget {
+    pub fn new() -> Result<Self, TerminalError> {
+        if !io::stdout().is_terminal() {
+            return Err(TerminalError::NotATty);
+        }
+        let (width, height) = terminal::size().map_err(TerminalError::SizeError)?;
+        Ok(Self {
+            stdout: BufWriter::new(io::stdout()),
+            frame: AnsiFrame::default(),
+            width,
+            height,
+        })
+    }
+}
+
+impl RenderTarget for CrosstermTarget {
+    fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.width = width;
+        self.height = height;
+        execute!(self.stdout, Clear(ClearType::All))
+    }
+
+    fn begin_frame(&mut self) -> io::Result<()> {
+        self.frame.begin()
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.frame.move_to(x, y)
+    }
+
+    fn set_color(&mut self, color: Color) -> io::Result<()> {
+        self.frame.set_color(color)
+    }
+
+    fn set_background(&mut self, color: Color) -> io::Result<()> {
+        self.frame.set_background(color)
+    }
+
+    fn print(&mut self, character: char) -> io::Result<()> {
+        self.frame.print(character)
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.frame.reset()
+    }
+
+    fn escape(&mut self, escape: &str) -> io::Result<()> {
+        self.frame.escape(escape)
+    }
+
+    fn end_frame(&mut self) -> io::Result<()> {
+        let frame = self.frame.end()?;
+        self.stdout.write_all(&frame)?;
+        self.stdout.flush()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        // Anything still buffered belongs to the alternate screen
+        self.stdout.flush()?;
+        restore_terminal()
+    }
+}
+
+struct Screen {
+    width: u16,
+    height: u16,
+    cells: Vec<(char, Color, Color)>,
+    cursor: (u16, u16),
+    color: Color,
+    background: Color,
+}
+
+impl Screen {
+    fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(' ', Color::Reset, Color::Reset); width as usize * height as usize],
+            cursor: (0, 0),
+            color: Color::Reset,
+            background: Color::Reset,
+        }
+    }
+}
+
+// A screen in memory, for tests and frontends that read the frame rather than show it.
+// Clones share the screen, so one can be kept to look at what the renderer drew
+#[derive(Clone)]
+pub struct MemoryTarget {
+    screen: Arc<Mutex<Screen>>,
+}
+
+impl MemoryTarget {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            screen: Arc::new(Mutex::new(Screen::new(width, height))),
+        }
+    }
+
+    fn screen(&self) -> MutexGuard<'_, Screen> {
+        self.screen.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // The character and colors shown at (x, y)
+    pub fn cell(&self, x: u16, y: u16) -> Option<(char, Color, Color)> {
+        let screen = self.screen();
+        (x < screen.width && y < screen.height)
+            .then(|| screen.cells[y as usize * screen.width as usize + x as usize])
+    }
+
+    // The screen's text, a line per row with trailing spaces trimmed
+    pub fn text(&self) -> String {
+        let screen = self.screen();
+        let mut text = String::new();
+        for row in screen.cells.chunks(screen.width.max(1) as usize) {
+            let line: String = row
+                .iter()
+                .map(|&(character, _, _)| character)
+                .filter(|&character| character != WIDE_TAIL)
+                .collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl RenderTarget for MemoryTarget {
+    fn size(&self) -> (u16, u16) {
+        let screen = self.screen();
+        (screen.width, screen.height)
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        *self.screen() = Screen::new(width, height);
+        Ok(())
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.screen().cursor = (x, y);
+        Ok(())
+    }
+
+    fn set_color(&mut self, color: Color) -> io::Result<()> {
+        self.screen().color = color;
+        Ok(())
+    }
+
+    fn set_background(&mut self, color: Color) -> io::Result<()> {
+        self.screen().background = color;
+        Ok(())
+    }
+
+    fn print(&mut self, character: char) -> io::Result<()> {
+        let mut screen = self.screen();
+        let (x, y) = screen.cursor;
+        let width = char_width(character) as u16;
+        if x + width <= screen.width && y < screen.height {
+            let index = y as usize * screen.width as usize + x as usize;
+            let (color, background) = (screen.color, screen.background);
+            screen.cells[index] = (character, color, background);
+            if width == 2 {
+                screen.cells[index + 1] = (WIDE_TAIL, color, background);
+            }
+        }
+        screen.cursor = (x + width, y);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.color = Color::Reset;
+        screen.background = Color::Reset;
+        Ok(())
+    }
+}
+
+// Passes every frame on to `inner` and writes it to a recording (--record) as well
+pub struct RecordingTarget {
+    inner: Box<dyn RenderTarget>,
+    recorder: Recorder,
+    frame: AnsiFrame,
+}
+
+impl RecordingTarget {
+    pub fn new(inner: Box<dyn RenderTarget>, recorder: Recorder) -> Self {
+        Self {
+            inner,
+            recorder,
+            frame: AnsiFrame::default(),
+        }
+    }
+}
+
+impl RenderTarget for RecordingTarget {
+    fn size(&self) -> (u16, u16) {
+        self.inner.size()
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.recorder.resize(width, height)?;
+        self.inner.resize(width, height)
+    }
+
+    fn begin_frame(&mut self) -> io::Result<()> {
+        self.frame.begin()?;
+        self.inner.begin_frame()
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.frame.move_to(x, y)?;
+        self.inner.move_to(x, y)
+    }
+
+    fn set_color(&mut self, color: Color) -> io::Result<()> {
+        self.frame.set_color(color)?;
+        self.inner.set_color(color)
+    }
+
+    fn set_background(&mut self, color: Color) -> io::Result<()> {
+        self.frame.set_background(color)?;
+        self.inner.set_background(color)
+    }
+
+    fn print(&mut self, character: char) -> io::Result<()> {
+        self.frame.print(character)?;
+        self.inner.print(character)
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.frame.reset()?;
+        self.inner.reset()
+    }
+
+    fn escape(&mut self, escape: &str) -> io::Result<()> {
+        self.frame.escape(escape)?;
+        self.inner.escape(escape)
+    }
+
+    fn end_frame(&mut self) -> io::Result<()> {
+        self.inner.end_frame()?;
+        let frame = self.frame.end()?;
+        self.recorder.output(&frame)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.recorder.finish()?;
+        self.inner.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TerminalRenderer;
+    use super::*;
+
+    #[test]
+    fn test_memory_target_shows_the_flushed_frames() {
+        let screen = MemoryTarget::new(6, 2);
+        let mut renderer = TerminalRenderer::with_target(Box::new(screen.clone()));
+        renderer
+            .render_line_colored(0, 0, "rain", Color::Blue)
+            .unwrap();
+        renderer
+            .render_line_colored(1, 1, "東", Color::White)
+            .unwrap();
+        renderer.flush().unwrap();
+        assert_eq!(screen.text(), "rain\n 東\n");
+        assert_eq!(screen.cell(0, 0), Some(('r', Color::Blue, Color::Reset)));
+
+        // Only the changed cells are sent; the rest stay as they were
+        renderer.clear().unwrap();
+        renderer
+            .render_line_colored(0, 0, "ruin", Color::Blue)
+            .unwrap();
+        renderer.flush().unwrap();
+        assert_eq!(screen.text(), "ruin\n\n");
+
+        renderer.manual_resize(3, 1).unwrap();
+        assert_eq!(screen.size(), (3, 1));
+        assert_eq!(screen.text(), "\n");
+    }
+
+    #[test]
+    fn test_recording_target_passes_frames_on() {
+        let path = std::env::temp_dir().join("weathr_test_recording_target.txt");
+        let screen = MemoryTarget::new(4, 1);
+        let mut renderer = TerminalRenderer::with_target(Box::new(screen.clone()));
+        renderer.record(&path).unwrap();
+        renderer
+            .render_line_colored(0, 0, "hail", Color::Reset)
+            .unwrap();
+        renderer.flush().unwrap();
+        renderer.cleanup().unwrap();
+
+        assert_eq!(screen.text(), "hail\n");
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        assert!(recorded.contains("hail"));
+        assert!(recorded.ends_with("\x1b[?2026l"));
+
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,116 @@
+use super::width_table::{WIDE, ZERO};
+use std::cmp::Ordering;
+
+// Columns a character takes up in the terminal: 0 for combining marks and other
+// invisible code points, 2 for East Asian wide characters and most emoji, 1 otherwise
+pub fn char_width(ch: char) -> usize {
+    let cp = ch as u32;
+    if cp < 0x20 || (0x7F..0xA0).contains(&cp) {
+        return 0;
+    }
+    if within(ZERO, cp) {
+        0
+    } else if within(WIDE, cp) {
+        2
+    } else {
+        1
+    }
+}
+
+fn within(ranges: &[(u32, u32)], cp: u32) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < cp {
+                Ordering::Less
+            } else if lo > cp {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("Berlin"), 6);
+        assert_eq!(display_width("❄ snow"), 6);
+        assert_eq!(display_width("東京"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        // U+1F327 is East_Asian_Width N, so it takes one column without U+FE0F
+        assert_eq!(display_width("⛅🌧"), 3);
+        assert_eq!(display_width("🌈🌪"), 3);
+    }
+
+    // The wide code points EastAsianWidth.txt lists in the blocks weather symbols come from
+    fn wide_in(lo: u32, hi: u32) -> Vec<u32> {
+        (lo..=hi)
+            .filter(|&cp| char::from_u32(cp).is_some_and(|ch| char_width(ch) == 2))
+            .collect()
+    }
+
+    fn expand(ranges: &[(u32, u32)]) -> Vec<u32> {
+        ranges.iter().flat_map(|&(lo, hi)| lo..=hi).collect()
+    }
+
+    #[test]
+    fn test_misc_symbols_width() {
+        let expected = expand(&[
+            (0x2614, 0x2615),
+            (0x2648, 0x2653),
+            (0x267F, 0x267F),
+            (0x2693, 0x2693),
+            (0x26A1, 0x26A1),
+            (0x26AA, 0x26AB),
+            (0x26BD, 0x26BE),
+            (0x26C4, 0x26C5),
+            (0x26CE, 0x26CE),
+            (0x26D4, 0x26D4),
+            (0x26EA, 0x26EA),
+            (0x26F2, 0x26F3),
+            (0x26F5, 0x26F5),
+            (0x26FA, 0x26FA),
+            (0x26FD, 0x26FD),
+        ]);
+        assert_eq!(wide_in(0x2600, 0x26FF), expected);
+        assert_eq!(char_width('☀'), 1);
+        assert_eq!(char_width('☁'), 1);
+        assert_eq!(char_width('⛈'), 1);
+        assert_eq!(char_width('☔'), 2);
+    }
+
+    #[test]
+    fn test_enclosed_and_game_symbols_width() {
+        let expected = expand(&[
+            (0x1F004, 0x1F004),
+            (0x1F0CF, 0x1F0CF),
+            (0x1F18E, 0x1F18E),
+            (0x1F191, 0x1F19A),
+            (0x1F200, 0x1F202),
+            (0x1F210, 0x1F23B),
+            (0x1F240, 0x1F248),
+            (0x1F250, 0x1F251),
+            (0x1F260, 0x1F265),
+        ]);
+        assert_eq!(wide_in(0x1F000, 0x1F2FF), expected);
+        // Regional indicators stay narrow on their own
+        assert_eq!(char_width('\u{1F1E9}'), 1);
+    }
+
+    #[test]
+    fn test_width_table_is_sorted_and_disjoint() {
+        for table in [WIDE, ZERO] {
+            assert!(table.iter().all(|&(lo, hi)| lo <= hi));
+            assert!(table.windows(2).all(|pair| pair[0].1 < pair[1].0));
+        }
+        assert!(expand(ZERO).iter().all(|&cp| !within(WIDE, cp)));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::config::Config;
+use crate::error::SceneError;
+use crate::render::{TerminalRenderer, char_width, display_width};
+use crate::theme::Scenery;
+use crossterm::style::Color;
+use std::fs;
+use std::io;
+
+// A line holding only this marker splits the art: rows above it stand on the horizon,
+// rows below it are drawn into the ground.
+pub const GROUND_MARKER: &str = "@ground";
+// Where chimney smoke rises from, drawn as a blank
+pub const SMOKE_MARKER: char = '@';
+
+// Midground art loaded from a plain text scene file
+pub struct SceneArt {
+    lines: Vec<String>,
+    above_ground: u16,
+    smoke: Option<(u16, u16)>,
+    color: Color,
+}
+
+impl SceneArt {
+    pub fn parse(name: &str, text: &str) -> Result<Self, SceneError> {
+        let mut lines = Vec::new();
+        let mut above_ground = None;
+        let mut smoke = None;
+
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.trim() == GROUND_MARKER {
+                above_ground.get_or_insert(lines.len() as u16);
+                continue;
+            }
+            // Blank lines at the top would only push the art up into the sky
+            if lines.is_empty() && line.is_empty() {
+                continue;
+            }
+
+            if let Some(col) = line.chars().position(|ch| ch == SMOKE_MARKER) {
+                smoke.get_or_insert((col as u16, lines.len() as u16));
+            }
+            lines.push(line.replace(SMOKE_MARKER, " "));
+        }
+
+        if lines.iter().all(|l| l.trim().is_empty()) {
+            return Err(SceneError::Empty(name.to_string()));
+        }
+        let above_ground = above_ground.unwrap_or(lines.len() as u16);
+
+        Ok(Self {
+            lines,
+            above_ground,
+            smoke,
+            color: Color::White,
+        })
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Loads `<config dir>/weathr/scenes/<name>.txt`
+    pub fn load(name: &str) -> Result<Self, SceneError> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(SceneError::InvalidName(name.to_string()));
+        }
+
+        let path = Config::scenes_dir()?.join(format!("{}.txt", name));
+        if !path.exists() {
+            return Err(SceneError::NotFound {
+                name: name.to_string(),
+                path: path.display().to_string(),
+            });
+        }
+
+        let text = fs::read_to_string(&path).map_err(|e| SceneError::ReadError {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        Self::parse(name, &text)
+    }
+
+    pub fn width(&self) -> u16 {
+        self.lines
+            .iter()
+            .map(|l| display_width(l))
+            .max()
+            .unwrap_or(0) as u16
+    }
+
+    // Rows standing above the horizon
+    pub fn height(&self) -> u16 {
+        self.above_ground
+    }
+
+    pub fn smoke_anchor(&self) -> Option<(u16, u16)> {
+        self.smoke
+    }
+
+    // Topmost cell of every column that stands above the ground
+    pub fn roofline(&self) -> Vec<(u16, u16)> {
+        let mut cells: Vec<(u16, u16)> = Vec::new();
+
+        for (row, line) in self
+            .lines
+            .iter()
+            .enumerate()
+            .take(self.above_ground as usize)
+        {
+            for (col, ch) in line.chars().enumerate() {
+                let col = col as u16;
+                if ch != ' ' && !cells.iter().any(|&(c, _)| c == col) {
+                    cells.push((col, row as u16));
+                }
+            }
+        }
+
+        cells.sort_unstable();
+        cells
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        is_day: bool,
+        scenery: &Scenery,
+    ) -> io::Result<()> {
+        let color = match (is_day, self.color) {
+            (true, color) => color,
+            (false, Color::White) => Color::Grey,
+            (false, _) => Color::DarkGrey,
+        };
+        let window_color = Scenery::pick(scenery.window, is_day);
+        let water_color = Scenery::pick(scenery.water, is_day);
+
+        for (i, line) in self.lines.iter().enumerate() {
+            let mut col = x;
+            for ch in line.chars() {
+                if ch != ' ' {
+                    let ch_color = match ch {
+                        '[' | ']' => window_color,
+                        '~' => water_color,
+                        _ => color,
+                    };
+                    renderer.render_char(col, y + i as u16, ch, ch_color)?;
+                }
+                col += char_width(ch) as u16;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASTLE: &str = r#"
+  @
+ |^|   |^|
+ | |___| |
+ |  [ ]  |
+@ground
+ ~~~~~~~~~
+"#;
+
+    #[test]
+    fn test_parse_markers() {
+        let art = SceneArt::parse("castle", CASTLE).unwrap();
+        assert_eq!(art.height(), 4);
+        assert_eq!(art.width(), 10);
+        assert_eq!(art.smoke_anchor(), Some((2, 0)));
+        assert!(art.lines.iter().all(|l| !l.contains(SMOKE_MARKER)));
+    }
+
+    #[test]
+    fn test_parse_without_ground_marker() {
+        let art = SceneArt::parse("hut", " /\\\n/__\\\n").unwrap();
+        assert_eq!(art.height(), 2);
+        assert_eq!(art.roofline(), vec![(0, 1), (1, 0), (2, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn test_parse_empty_scene() {
+        assert!(matches!(
+            SceneArt::parse("blank", "\n  \n@ground\n"),
+            Err(SceneError::Empty(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_paths() {
+        assert!(matches!(
+            SceneArt::load("../secrets"),
+            Err(SceneError::InvalidName(_))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+// Built-in alternatives to the house, in the same format as user scene files
+use crossterm::style::Color;
+
+pub const SCENE_NAMES: &[&str] = &["house", "city", "beach", "mountain", "forest"];
+
+const CITY: &str = r#"
+                 @
+            _   |=|            ____
+    ____   | |  |_|     ___   |    |   _____
+   |[][]|  | |__|  |___|   |  |[][]|  |     |
+   |[][]|__|  [][] |  [] []|__|[][]|__| [][]|
+   |[][]|[]|  [][] |  [] []|[]|[][]|[]| [][]|
+   |[][]|[]|  [][] |  [] []|[]|[][]|[]| [][]|
+   |[][]|[]|  [][] |  [] []|[]|[][]|[]| [][]|
+ __|____|__|_______|_______|__|____|__|_____|__
+@ground
+ ==============================================
+"#;
+
+const BEACH: &str = r#"
+       __ _.--..--._ _
+    .-' _/   _/\_   \_'-.
+   |__ /   _/\__/\_   \__|
+      |___/\_\__/  \___|
+             \ \
+              \ \               ____
+               \ \             /    \
+                \ \           /______\
+                 | |           | [] |
+ ~   ~  ~   ~    | |     ~   ~ |____|  ~   ~
+@ground
+ ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+  ~~~  ~~~~~   ~~~~  ~~~~~~   ~~~~~  ~~~~  ~~~
+"#;
+
+const MOUNTAIN: &str = r#"
+                  /\
+                 /  \          /\
+           /\   / /\ \        /  \
+          /  \ / /  \ \      /    \       @
+         /    \ /    \ \    /      \     _|_|_______
+        /  /\  \      \ \  /        \   /           \
+       /  /  \  \      \ \/          \ /_____________\
+      /  /    \  \      \ \           \ | []  _  [] |
+ ____/__/______\__\______\_\___________\|____| |____|__
+@ground
+"#;
+
+const FOREST: &str = r#"
+        /\              /\                 /\
+       /  \     /\     /  \       /\      /  \
+      /    \   /  \   /    \     /  \    /    \
+     /      \ /    \ /      \   /    \  /      \
+    /        /      \        \ /      \/        \
+   /________/________\________/________\_________\
+       ||       ||       ||       ||        ||
+@ground
+"#;
+
+pub fn art(name: &str) -> Option<(&'static str, Color)> {
+    match name {
+        "city" => Some((CITY, Color::Grey)),
+        "beach" => Some((BEACH, Color::DarkYellow)),
+        "mountain" => Some((MOUNTAIN, Color::Grey)),
+        "forest" => Some((FOREST, Color::DarkGreen)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::art::SceneArt;
+
+    #[test]
+    fn test_builtin_scenes_parse() {
+        for name in SCENE_NAMES.iter().filter(|&&n| n != "house") {
+            let (text, _) = art(name).unwrap();
+            let scene = SceneArt::parse(name, text).unwrap();
+            assert!(scene.height() > 0, "{} has nothing above ground", name);
+        }
+    }
+}
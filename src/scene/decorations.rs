@@ -1,4 +1,6 @@
 use crate::render::TerminalRenderer;
+use crate::theme::Scenery;
+use crate::weather::astronomy::Season;
 use crossterm::style::Color;
 use std::io;
 
@@ -11,6 +13,8 @@ pub struct DecorationRenderConfig {
     pub house_width: u16,
     pub width: u16,
     pub is_day: bool,
+    pub season: Season,
+    pub scenery: Scenery,
 }
 
 impl Decorations {
@@ -24,7 +28,7 @@ impl Decorations {
         config: &DecorationRenderConfig,
     ) -> io::Result<()> {
         // Render Tree (Left of house)
-        let (tree_lines, tree_color) = self.get_tree(config.is_day);
+        let (tree_lines, tree_color) = self.get_tree(config);
         let tree_height = tree_lines.len() as u16;
         let tree_y = config.horizon_y.saturating_sub(tree_height);
         let tree_x = config.house_x.saturating_sub(20);
@@ -45,7 +49,7 @@ impl Decorations {
         }
 
         // Render Fence (Right of house)
-        let (fence_lines, fence_color) = self.get_fence(config.is_day);
+        let (fence_lines, fence_color) = self.get_fence(config);
         let fence_height = fence_lines.len() as u16;
         let fence_y = config.horizon_y.saturating_sub(fence_height); // Sitting on ground
         let fence_x = config.house_x + config.house_width + 2; // Slight gap
@@ -66,7 +70,7 @@ impl Decorations {
         }
 
         // Render Mailbox (On ground top level, left of tree)
-        let (mailbox_lines, mailbox_color) = self.get_mailbox(config.is_day);
+        let (mailbox_lines, mailbox_color) = self.get_mailbox(config);
         let mailbox_height = mailbox_lines.len() as u16;
         let mailbox_x = tree_x.saturating_sub(10); // Left of tree
         let mailbox_y = config.horizon_y.saturating_sub(mailbox_height); // On ground top
@@ -86,9 +90,34 @@ impl Decorations {
             }
         }
 
+        // Render Snowman (In front of the fence, winter only)
+        if config.season == Season::Winter {
+            let (snowman_lines, snowman_color) = self.get_snowman(config);
+            let snowman_x = config.house_x + config.house_width + 4;
+            let snowman_y = config.horizon_y + 1;
+
+            if snowman_x + 7 < config.width {
+                for (i, line) in snowman_lines.iter().enumerate() {
+                    // Paint the inner blanks too so the ground doesn't show through it
+                    let start = line.len() - line.trim_start().len();
+                    let end = line.trim_end().len();
+                    for (j, ch) in line.chars().enumerate() {
+                        if (start..end).contains(&j) {
+                            renderer.render_char(
+                                snowman_x + j as u16,
+                                snowman_y + i as u16,
+                                ch,
+                                snowman_color,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+
         // Render Second Tree (Right of house, only if terminal is wide enough)
         if config.width > 120 {
-            let (pine_lines, pine_color) = self.get_pine_tree(config.is_day);
+            let (pine_lines, pine_color) = self.get_pine_tree(config);
             let pine_height = pine_lines.len() as u16;
             let pine_x = config.house_x + config.house_width + 18; // 18 chars right of house
             let pine_y = config.horizon_y.saturating_sub(pine_height);
@@ -113,7 +142,7 @@ impl Decorations {
         Ok(())
     }
 
-    fn get_tree(&self, is_day: bool) -> (Vec<&'static str>, Color) {
+    fn get_tree(&self, config: &DecorationRenderConfig) -> (Vec<&'static str>, Color) {
         (
             vec![
                 "      ####      ",
@@ -122,29 +151,32 @@ impl Decorations {
                 "    ########    ",
                 "      _||_      ",
             ],
-            if is_day {
-                Color::DarkGreen
-            } else {
-                Color::Rgb { r: 0, g: 50, b: 0 }
-            },
+            Scenery::pick(config.scenery.trees, config.is_day),
         )
     }
 
-    fn get_fence(&self, is_day: bool) -> (Vec<&'static str>, Color) {
+    fn get_fence(&self, config: &DecorationRenderConfig) -> (Vec<&'static str>, Color) {
         (
             vec!["|--|--|--|--|", "|  |  |  |  |"],
-            if is_day { Color::White } else { Color::Grey },
+            Scenery::pick(config.scenery.fence, config.is_day),
         )
     }
 
-    fn get_mailbox(&self, is_day: bool) -> (Vec<&'static str>, Color) {
+    fn get_mailbox(&self, config: &DecorationRenderConfig) -> (Vec<&'static str>, Color) {
         (
             vec![" ___ ", "|___|", "  |  "],
-            if is_day { Color::Blue } else { Color::DarkBlue },
+            Scenery::pick(config.scenery.mailbox, config.is_day),
+        )
+    }
+
+    fn get_snowman(&self, config: &DecorationRenderConfig) -> (Vec<&'static str>, Color) {
+        (
+            vec!["  _=_  ", " (o.o) ", "-( : )-", " (___) "],
+            Scenery::pick(config.scenery.fence, config.is_day),
         )
     }
 
-    fn get_pine_tree(&self, is_day: bool) -> (Vec<&'static str>, Color) {
+    fn get_pine_tree(&self, config: &DecorationRenderConfig) -> (Vec<&'static str>, Color) {
         (
             vec![
                 "    *    ",
@@ -153,11 +185,7 @@ impl Decorations {
                 " ******* ",
                 "   |||   ",
             ],
-            if is_day {
-                Color::DarkGreen
-            } else {
-                Color::Rgb { r: 0, g: 50, b: 0 }
-            },
+            Scenery::pick(config.scenery.trees, config.is_day),
         )
     }
 }
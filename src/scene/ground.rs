@@ -1,5 +1,5 @@
 use crate::render::TerminalRenderer;
-use crossterm::style::Color;
+use crate::theme::Scenery;
 use std::io;
 
 #[derive(Default)]
@@ -13,40 +13,22 @@ impl Ground {
         height: u16,
         y_start: u16,
         is_day: bool,
+        scenery: &Scenery,
     ) -> io::Result<()> {
         let width = width as usize;
         let height = height as usize;
 
         let grass_colors = if is_day {
-            [Color::Green, Color::DarkGreen]
+            [scenery.grass[0], scenery.grass[1]]
         } else {
-            [Color::DarkGreen, Color::Rgb { r: 0, g: 50, b: 0 }]
+            [scenery.grass[1], scenery.grass[2]]
         };
-
         let flower_colors = if is_day {
-            vec![Color::Magenta, Color::Red, Color::Cyan, Color::Yellow]
-        } else {
-            vec![
-                Color::DarkMagenta,
-                Color::DarkRed,
-                Color::Blue,
-                Color::DarkYellow,
-            ]
-        };
-
-        let soil_color = if is_day {
-            Color::Rgb {
-                r: 101,
-                g: 67,
-                b: 33,
-            }
+            scenery.flowers.0
         } else {
-            Color::Rgb {
-                r: 60,
-                g: 40,
-                b: 20,
-            }
+            scenery.flowers.1
         };
+        let soil_color = Scenery::pick(scenery.soil, is_day);
 
         // Simple seeded RNG for deterministic decoration positions
         fn pseudo_rand(x: usize, y: usize) -> u32 {
@@ -1,18 +1,8 @@
 use crate::render::TerminalRenderer;
+use crate::theme::Scenery;
 use crossterm::style::Color;
 use std::io;
 
-const WOOD_COLOR: Color = Color::Rgb {
-    r: 210,
-    g: 180,
-    b: 140,
-};
-const DOOR_COLOR: Color = Color::Rgb {
-    r: 139,
-    g: 69,
-    b: 19,
-};
-
 #[derive(Default)]
 pub struct House;
 
@@ -20,6 +10,7 @@ impl House {
     pub const WIDTH: u16 = 64;
     pub const HEIGHT: u16 = 13;
     pub const CHIMNEY_X_OFFSET: u16 = 10;
+    const ROOF_EAVE_ROW: usize = 7;
 
     pub fn height(&self) -> u16 {
         Self::HEIGHT
@@ -47,30 +38,43 @@ impl House {
         ]
     }
 
+    // Topmost roof cell of every column, as offsets from the house origin. Ignores the
+    // chimney smoke drawn above the roof.
+    pub fn roofline(&self) -> Vec<(u16, u16)> {
+        let ascii = self.get_ascii();
+        let mut cells: Vec<(u16, u16)> = Vec::new();
+
+        for (row, line) in ascii
+            .iter()
+            .enumerate()
+            .take(Self::ROOF_EAVE_ROW + 1)
+            .skip(3)
+        {
+            for (col, ch) in line.chars().enumerate() {
+                let col = col as u16;
+                if ch != ' ' && ch != '(' && ch != ')' && !cells.iter().any(|&(c, _)| c == col) {
+                    cells.push((col, row as u16));
+                }
+            }
+        }
+
+        cells.sort_unstable();
+        cells
+    }
+
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
         x: u16,
         y: u16,
         is_day: bool,
+        scenery: &Scenery,
     ) -> io::Result<()> {
         let ascii = self.get_ascii();
 
-        let wood_color = if is_day {
-            WOOD_COLOR
-        } else {
-            Color::Rgb {
-                r: 100,
-                g: 70,
-                b: 50,
-            }
-        };
-        let roof_color = if is_day {
-            Color::DarkRed
-        } else {
-            Color::DarkMagenta
-        };
-        let window_color = if is_day { Color::Cyan } else { Color::Yellow };
+        let wood_color = Scenery::pick(scenery.wood, is_day);
+        let roof_color = Scenery::pick(scenery.roof, is_day);
+        let window_color = Scenery::pick(scenery.window, is_day);
 
         for (i, line) in ascii.iter().enumerate() {
             let row = y + i as u16;
@@ -81,9 +85,9 @@ impl House {
                         if ch != ' ' {
                             let col = x + j as u16;
                             let color = if i < 4 && (ch == '(' || ch == ')' || ch == '_') {
-                                Color::DarkGrey
+                                scenery.stone
                             } else if i < 4 {
-                                Color::Grey
+                                scenery.chimney
                             } else {
                                 roof_color
                             };
@@ -103,9 +107,9 @@ impl House {
                             } else if ch == '|' || ch == '.' || ch == '_' {
                                 wood_color
                             } else if ch == '(' || ch == ')' {
-                                DOOR_COLOR
+                                scenery.door
                             } else if ch == '=' {
-                                Color::DarkGrey
+                                scenery.stone
                             } else {
                                 wood_color
                             };
@@ -118,9 +122,9 @@ impl House {
                         if ch != ' ' {
                             let col = x + j as u16;
                             let color = if ch == '=' || ch == '|' {
-                                Color::DarkGrey
+                                scenery.stone
                             } else if ch == '(' || ch == ')' {
-                                DOOR_COLOR
+                                scenery.door
                             } else {
                                 wood_color
                             };
@@ -133,13 +137,9 @@ impl House {
                         if ch != ' ' {
                             let col = x + j as u16;
                             let color = if ch == '^' {
-                                if is_day {
-                                    Color::Green
-                                } else {
-                                    Color::DarkGreen
-                                }
+                                scenery.grass[if is_day { 0 } else { 1 }]
                             } else if ch == '=' {
-                                Color::DarkGrey
+                                scenery.stone
                             } else {
                                 Color::Reset
                             };
@@ -148,7 +148,7 @@ impl House {
                     }
                 }
                 _ => {
-                    renderer.render_line_colored(x, row, line, Color::Yellow)?;
+                    renderer.render_line_colored(x, row, line, window_color)?;
                 }
             }
         }
@@ -1,55 +1,180 @@
+pub mod art;
+pub mod builtin;
 pub mod decorations;
 pub mod ground;
 pub mod house;
+pub mod sky;
 
+use crate::error::SceneError;
 use crate::render::TerminalRenderer;
+use crate::theme::Scenery;
 use crate::weather::WeatherConditions;
 use std::io;
 
+// Screen positions other systems attach effects to, recomputed whenever the terminal
+// is resized.
+#[derive(Debug, Clone, Default)]
+pub struct SceneAnchors {
+    pub horizon_y: u16,
+    pub house_x: u16,
+    pub house_y: u16,
+    pub chimney: Option<(u16, u16)>,
+    pub roofline: Vec<(u16, u16)>,
+}
+
+// What stands in the middle of the scene
+pub enum Midground {
+    House(house::House),
+    Custom(art::SceneArt),
+}
+
+impl Default for Midground {
+    fn default() -> Self {
+        Midground::House(house::House)
+    }
+}
+
+impl Midground {
+    // Built-in scenes first, then the scenes directory
+    pub fn from_name(name: &str) -> Result<Self, SceneError> {
+        if name == "house" {
+            return Ok(Midground::House(house::House));
+        }
+        match builtin::art(name) {
+            Some((text, color)) => {
+                art::SceneArt::parse(name, text).map(|art| Midground::Custom(art.with_color(color)))
+            }
+            None => art::SceneArt::load(name).map(Midground::Custom),
+        }
+    }
+
+    fn width(&self) -> u16 {
+        match self {
+            Midground::House(house) => house.width(),
+            Midground::Custom(art) => art.width(),
+        }
+    }
+
+    fn height(&self) -> u16 {
+        match self {
+            Midground::House(house) => house.height(),
+            Midground::Custom(art) => art.height(),
+        }
+    }
+
+    fn roofline(&self) -> Vec<(u16, u16)> {
+        match self {
+            Midground::House(house) => house.roofline(),
+            Midground::Custom(art) => art.roofline(),
+        }
+    }
+
+    fn chimney(&self) -> Option<(u16, u16)> {
+        match self {
+            Midground::House(_) => Some((house::House::CHIMNEY_X_OFFSET, 0)),
+            Midground::Custom(art) => art.smoke_anchor(),
+        }
+    }
+
+    fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        is_day: bool,
+        scenery: &Scenery,
+    ) -> io::Result<()> {
+        match self {
+            Midground::House(house) => house.render(renderer, x, y, is_day, scenery),
+            Midground::Custom(art) => art.render(renderer, x, y, is_day, scenery),
+        }
+    }
+}
+
 pub struct WorldScene {
-    house: house::House,
+    midground: Midground,
     ground: ground::Ground,
     decorations: decorations::Decorations,
     width: u16,
     height: u16,
+    anchors: SceneAnchors,
+    scenery: Scenery,
 }
 
 impl WorldScene {
     pub const GROUND_HEIGHT: u16 = 9;
 
-    pub fn new(width: u16, height: u16) -> Self {
-        let house = house::House;
+    pub fn new(width: u16, height: u16, midground: Midground) -> Self {
         let ground = ground::Ground;
         let decorations = decorations::Decorations::new();
 
+        let anchors = Self::compute_anchors(&midground, width, height);
+
         Self {
-            house,
+            midground,
             ground,
             decorations,
             width,
             height,
+            anchors,
+            scenery: Scenery::default(),
         }
     }
 
+    pub fn set_scenery(&mut self, scenery: Scenery) {
+        self.scenery = scenery;
+    }
+
     pub fn update_size(&mut self, width: u16, height: u16) {
+        if width != self.width || height != self.height {
+            self.anchors = Self::compute_anchors(&self.midground, width, height);
+        }
         self.width = width;
         self.height = height;
     }
 
+    pub fn set_midground(&mut self, midground: Midground) {
+        self.anchors = Self::compute_anchors(&midground, self.width, self.height);
+        self.midground = midground;
+    }
+
+    pub fn anchors(&self) -> &SceneAnchors {
+        &self.anchors
+    }
+
+    fn compute_anchors(midground: &Midground, width: u16, height: u16) -> SceneAnchors {
+        let horizon_y = height.saturating_sub(Self::GROUND_HEIGHT);
+        let house_x = (width / 2).saturating_sub(midground.width() / 2);
+        let house_y = horizon_y.saturating_sub(midground.height());
+        let roofline = midground
+            .roofline()
+            .into_iter()
+            .map(|(dx, dy)| (house_x + dx, house_y + dy))
+            .collect();
+
+        SceneAnchors {
+            horizon_y,
+            house_x,
+            house_y,
+            chimney: midground
+                .chimney()
+                .map(|(dx, dy)| (house_x + dx, house_y + dy)),
+            roofline,
+        }
+    }
+
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
         conditions: &WeatherConditions,
     ) -> io::Result<()> {
-        let horizon_y = self.height.saturating_sub(Self::GROUND_HEIGHT);
-
-        // House position
-        let house_width = self.house.width();
-        let house_height = self.house.height();
-        let house_x = (self.width / 2).saturating_sub(house_width / 2);
-        let house_y = horizon_y.saturating_sub(house_height);
-
-        // Door/Path alignment
+        let SceneAnchors {
+            horizon_y,
+            house_x,
+            house_y,
+            ..
+        } = self.anchors;
+        let house_width = self.midground.width();
 
         // Render Ground
         self.ground.render(
@@ -58,11 +183,12 @@ impl WorldScene {
             Self::GROUND_HEIGHT,
             horizon_y,
             conditions.is_day,
+            &self.scenery,
         )?;
 
         // Render House
-        self.house
-            .render(renderer, house_x, house_y, conditions.is_day)?;
+        self.midground
+            .render(renderer, house_x, house_y, conditions.is_day, &self.scenery)?;
 
         // Render Decorations
         self.decorations.render(
@@ -73,6 +199,8 @@ impl WorldScene {
                 house_width,
                 width: self.width,
                 is_day: conditions.is_day,
+                season: conditions.season,
+                scenery: self.scenery,
             },
         )?;
 
@@ -0,0 +1,105 @@
+use crate::render::TerminalRenderer;
+use crate::theme::{Rgb, Theme};
+use crossterm::style::Color;
+use std::io;
+
+// Fraction of the day, at each end, over which sunrise/sunset colors fade out
+const TWILIGHT_SPAN: f64 = 0.15;
+
+fn lerp(a: Rgb, b: Rgb, t: f64) -> Rgb {
+    let mix = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
+
+// Top and horizon colors of the sky. `sun_progress` is 0.0 at sunrise and 1.0 at sunset
+// (see `astronomy::sun_progress`); when it is unknown `is_day` picks midday or night.
+pub fn sky_colors(
+    theme: &Theme,
+    sun_progress: Option<f64>,
+    is_day: bool,
+    overcast: bool,
+) -> (Rgb, Rgb) {
+    let (day, night) = if overcast {
+        (theme.sky_overcast, theme.sky_overcast_night)
+    } else {
+        (theme.sky_day, theme.sky_night)
+    };
+
+    match sun_progress {
+        Some(progress) => {
+            let from_edge = progress.min(1.0 - progress).max(0.0);
+            let twilight = (1.0 - from_edge / TWILIGHT_SPAN).max(0.0);
+            let twilight = if overcast { twilight * 0.3 } else { twilight };
+            (
+                lerp(day.0, theme.sky_twilight.0, twilight),
+                lerp(day.1, theme.sky_twilight.1, twilight),
+            )
+        }
+        None if is_day => day,
+        None => night,
+    }
+}
+
+pub fn render(
+    renderer: &mut TerminalRenderer,
+    (top, bottom): (Rgb, Rgb),
+    horizon_y: u16,
+) -> io::Result<()> {
+    if !renderer.supports_truecolor() || horizon_y == 0 {
+        return Ok(());
+    }
+
+    for y in 0..horizon_y {
+        let t = y as f64 / horizon_y.saturating_sub(1).max(1) as f64;
+        let (r, g, b) = lerp(top, bottom, t);
+        renderer.fill_row_background(y, Color::Rgb { r, g, b })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_midday_is_blue() {
+        let theme = Theme::default();
+        let (top, bottom) = sky_colors(&theme, Some(0.5), true, false);
+        assert_eq!((top, bottom), theme.sky_day);
+        assert!(top.2 > top.0);
+    }
+
+    #[test]
+    fn test_sunrise_and_sunset_are_warm() {
+        let theme = Theme::default();
+        for progress in [0.0, 1.0] {
+            let (_, horizon) = sky_colors(&theme, Some(progress), true, false);
+            assert_eq!(horizon, theme.sky_twilight.1);
+            assert!(horizon.0 > horizon.2);
+        }
+    }
+
+    #[test]
+    fn test_night_without_sun_times() {
+        let theme = Theme::default();
+        assert_eq!(sky_colors(&theme, None, false, false), theme.sky_night);
+        assert_eq!(sky_colors(&theme, None, true, false), theme.sky_day);
+        assert_eq!(
+            sky_colors(&theme, None, false, true),
+            theme.sky_overcast_night
+        );
+    }
+
+    #[test]
+    fn test_high_contrast_sky_stays_black() {
+        let theme = Theme::preset("high-contrast").unwrap();
+        let black = ((0, 0, 0), (0, 0, 0));
+        for overcast in [false, true] {
+            for progress in [Some(0.0), Some(0.05), Some(0.5), None] {
+                for is_day in [true, false] {
+                    assert_eq!(sky_colors(&theme, progress, is_day, overcast), black);
+                }
+            }
+        }
+    }
+}
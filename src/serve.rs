@@ -0,0 +1,374 @@
+use crate::config::Config;
+use crate::metrics::Metrics;
+use crate::output;
+use crate::publish::WeatherPublisher;
+use crate::scene::Midground;
+use crate::simulation::Simulation;
+use crate::theme::Theme;
+use crate::weather::provider::create_providers;
+use crate::weather::{WeatherClient, WeatherData, WeatherLocation, WeatherUnits};
+use crate::widget::WeathrWidget;
+use std::io;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+// Frame size when the request doesn't give one, and the largest it may ask for
+pub const DEFAULT_WIDTH: u16 = 100;
+pub const DEFAULT_HEIGHT: u16 = 30;
+const MAX_WIDTH: u16 = 400;
+const MAX_HEIGHT: u16 = 200;
+// Below this the scene overlaps itself, as in the terminal
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 10;
+
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const INDEX: &str = "weathr\n\n\
+    /weather.json  the current weather\n\
+    /frame.txt     the scene as plain text\n\
+    /frame.ansi    the scene in color, for a terminal\n\n\
+    Frames take ?width=COLUMNS&height=ROWS, e.g.\n\
+    curl \"HOST/frame.ansi?width=$COLUMNS&height=$LINES\"\n";
+
+// A whole HTTP response, sent with the connection closed after it. Also what
+// --metrics-port answers with
+#[derive(Debug, PartialEq)]
+pub(crate) struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    pub(crate) fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body,
+        }
+    }
+
+    pub(crate) fn error(status: &'static str, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: format!("{}\n", message),
+        }
+    }
+
+    fn to_bytes(&self, head_only: bool) -> Vec<u8> {
+        let mut bytes = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )
+        .into_bytes();
+        if !head_only {
+            bytes.extend(self.body.as_bytes());
+        }
+        bytes
+    }
+}
+
+// Reads one request from `stream` and sends what `respond` makes of a GET of its target
+pub(crate) async fn answer(
+    mut stream: TcpStream,
+    respond: impl FnOnce(&str) -> Response,
+) -> io::Result<()> {
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 1024];
+    let read = async {
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 || request.len() + n > MAX_REQUEST_BYTES {
+                break;
+            }
+            request.extend_from_slice(&chunk[..n]);
+        }
+        io::Result::Ok(())
+    };
+    if tokio::time::timeout(REQUEST_TIMEOUT, read).await.is_err() {
+        return Ok(());
+    }
+
+    let line = String::from_utf8_lossy(&request);
+    let mut parts = line.lines().next().unwrap_or_default().split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET" | "HEAD"), Some(target)) => respond(target),
+        (Some(_), Some(_)) => Response::error("405 Method Not Allowed", "Only GET is served"),
+        _ => Response::error("400 Bad Request", "Not an HTTP request"),
+    };
+    let head_only = line.starts_with("HEAD ");
+    stream.write_all(&response.to_bytes(head_only)).await?;
+    stream.shutdown().await
+}
+
+// The frame size asked for in the query string, within MIN_ and MAX_
+fn frame_size(query: &str) -> (u16, u16) {
+    let (mut width, mut height) = (DEFAULT_WIDTH, DEFAULT_HEIGHT);
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match (key, value.parse::<u16>()) {
+            ("width", Ok(value)) => width = value.clamp(MIN_WIDTH, MAX_WIDTH),
+            ("height", Ok(value)) => height = value.clamp(MIN_HEIGHT, MAX_HEIGHT),
+            _ => {}
+        }
+    }
+    (width, height)
+}
+
+struct Latest {
+    weather: Option<WeatherData>,
+    widget: Option<WeathrWidget>,
+    // Handed to the widget once the first weather arrives
+    midground: Option<Midground>,
+}
+
+// What `weathr serve` shows: the latest weather, and the scene for it drawn off screen
+// whenever a frame is asked for
+pub struct FrameServer {
+    latest: Mutex<Latest>,
+    location: WeatherLocation,
+    theme: Theme,
+    units: WeatherUnits,
+}
+
+impl FrameServer {
+    pub fn new(
+        location: WeatherLocation,
+        midground: Midground,
+        theme: Theme,
+        units: WeatherUnits,
+    ) -> Self {
+        Self {
+            latest: Mutex::new(Latest {
+                weather: None,
+                widget: None,
+                midground: Some(midground),
+            }),
+            location,
+            theme,
+            units,
+        }
+    }
+
+    pub fn set_weather(&self, weather: WeatherData) {
+        let mut latest = self.latest.lock().unwrap_or_else(PoisonError::into_inner);
+        match latest.widget {
+            Some(ref mut widget) => widget.set_weather(weather.clone()),
+            None => {
+                let mut widget = WeathrWidget::new(self.location, weather.clone());
+                widget.set_theme(self.theme);
+                if let Some(midground) = latest.midground.take() {
+                    widget.set_midground(midground);
+                }
+                latest.widget = Some(widget);
+            }
+        }
+        latest.weather = Some(weather);
+    }
+
+    // The response for a GET of `target`, a path with an optional query string
+    fn respond(&self, target: &str) -> Response {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        if path == "/" {
+            return Response::ok("text/plain; charset=utf-8", INDEX.to_string());
+        }
+        let mut latest = self.latest.lock().unwrap_or_else(PoisonError::into_inner);
+        let Latest {
+            weather: Some(ref weather),
+            widget: Some(ref mut widget),
+            ..
+        } = *latest
+        else {
+            return Response::error("503 Service Unavailable", "No weather yet, try again soon");
+        };
+
+        let colors = match path {
+            "/weather.json" => {
+                return match output::format_json(weather) {
+                    Ok(json) => Response::ok("application/json", json + "\n"),
+                    Err(e) => Response::error("500 Internal Server Error", &e.to_string()),
+                };
+            }
+            "/frame.txt" => false,
+            "/frame.ansi" => true,
+            _ => return Response::error("404 Not Found", "Not found; see / for what's here"),
+        };
+        let summary = output::format_summary(weather, &self.units);
+        let (width, height) = frame_size(query);
+        if let Err(e) = widget.render(width, height).map(|cells| cells.count()) {
+            return Response::error("500 Internal Server Error", &e.to_string());
+        }
+        let content_type = if colors {
+            "text/x-ansi; charset=utf-8"
+        } else {
+            "text/plain; charset=utf-8"
+        };
+        Response::ok(
+            content_type,
+            format!("{}\n{}", summary, widget.screenshot(colors)),
+        )
+    }
+
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let server = Arc::clone(&self);
+            tokio::spawn(async move {
+                let _ = answer(stream, |target| server.respond(target)).await;
+            });
+        }
+    }
+}
+
+// `weathr serve`: the simulation, or the weather fetched every refresh interval, served
+// on `listener` until the process is stopped. Each fetch is counted in `metrics`, if given
+pub async fn run(
+    config: &Config,
+    simulation: Option<Simulation>,
+    midground: Midground,
+    theme: Theme,
+    listener: TcpListener,
+    metrics: Option<Arc<Metrics>>,
+) -> io::Result<()> {
+    let location = WeatherLocation {
+        latitude: config.location.latitude,
+        longitude: config.location.longitude,
+        elevation: None,
+    };
+    let server = Arc::new(FrameServer::new(location, midground, theme, config.units));
+
+    match simulation {
+        Some(simulation) => {
+            let weather = simulation.weather(&config.units);
+            if let Some(metrics) = metrics {
+                metrics.set_weather(&weather);
+            }
+            server.set_weather(weather);
+        }
+        None => {
+            let client = WeatherClient::new(
+                create_providers(&config.provider),
+                config.refresh_interval(),
+            );
+            let units = config.units;
+            let refresh_interval = config.refresh_interval();
+            let publisher = WeatherPublisher::start(&config.publish);
+            let server = Arc::clone(&server);
+            tokio::spawn(async move {
+                let mut failures = 0;
+                loop {
+                    let retry_in = match client.get_current_weather(&location, &units).await {
+                        Ok(weather) => {
+                            failures = 0;
+                            if let Some(ref metrics) = metrics {
+                                metrics.record_success(&weather);
+                            }
+                            if let Some(ref publisher) = publisher {
+                                publisher.publish(&weather);
+                            }
+                            server.set_weather(weather);
+                            None
+                        }
+                        Err(e) => {
+                            failures += 1;
+                            if let Some(ref metrics) = metrics {
+                                metrics.record_failure();
+                            }
+                            eprintln!("{}", e.user_friendly_message());
+                            Some(client.retry_delay(failures))
+                        }
+                    };
+                    tokio::time::sleep(retry_in.unwrap_or(refresh_interval)).await;
+                }
+            });
+        }
+    }
+
+    server.serve(listener).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn server() -> FrameServer {
+        let location = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        FrameServer::new(
+            location,
+            Midground::default(),
+            Theme::default(),
+            WeatherUnits::metric(),
+        )
+    }
+
+    #[test]
+    fn test_frame_size() {
+        assert_eq!(frame_size(""), (DEFAULT_WIDTH, DEFAULT_HEIGHT));
+        assert_eq!(frame_size("width=80&height=24"), (80, 24));
+        assert_eq!(
+            frame_size("height=9999&width=x"),
+            (DEFAULT_WIDTH, MAX_HEIGHT)
+        );
+        assert_eq!(frame_size("width=1"), (MIN_WIDTH, DEFAULT_HEIGHT));
+    }
+
+    #[test]
+    fn test_routes() {
+        let server = server();
+        assert_eq!(server.respond("/").status, "200 OK");
+        assert_eq!(
+            server.respond("/frame.txt").status,
+            "503 Service Unavailable"
+        );
+
+        let weather =
+            Simulation::new(WeatherCondition::Rain, true).weather(&WeatherUnits::metric());
+        server.set_weather(weather);
+        let json = server.respond("/weather.json");
+        assert_eq!(json.content_type, "application/json");
+        let data: serde_json::Value = serde_json::from_str(&json.body).unwrap();
+        assert_eq!(data["condition"], "rain");
+
+        let text = server.respond("/frame.txt?width=60&height=20").body;
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].starts_with("Rain | 20.0°C"));
+        assert_eq!(lines.len(), 21);
+        assert!(lines[1..].iter().all(|line| line.chars().count() <= 60));
+        assert!(!text.contains('\x1b'));
+        assert!(server.respond("/frame.ansi").body.contains("\x1b["));
+
+        assert_eq!(server.respond("/frame.png").status, "404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_serves_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = Arc::new(server());
+        server.set_weather(
+            Simulation::new(WeatherCondition::Clear, true).weather(&WeatherUnits::metric()),
+        );
+        tokio::spawn(server.serve(listener));
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(b"GET /weather.json HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.contains("\"condition\": \"clear\""));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::{Config, DEFAULT_REFRESH_SECONDS, MIN_REFRESH_SECONDS, toml_value};
+use crate::geography::{self, Place};
+use crate::theme::PRESETS;
+use crate::weather::types::WeatherUnits;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+// What `weathr config init` asks for; everything else keeps its default
+pub struct Answers {
+    // None detects the location from the IP address on each start
+    pub place: Option<Place>,
+    pub units: WeatherUnits,
+    pub theme: String,
+    pub refresh_seconds: u64,
+}
+
+// A config file with a comment on every setting, so it can be edited by hand later
+pub fn render_config(answers: &Answers) -> String {
+    let mut lines = vec![
+        "# Written by `weathr config init`; the README describes every other setting".to_string(),
+        String::new(),
+        "# Seconds between weather updates (at least 60)".to_string(),
+        format!("refresh_seconds = {}", answers.refresh_seconds),
+        String::new(),
+        "[location]".to_string(),
+    ];
+    match answers.place {
+        Some(ref place) => lines.extend([
+            format!("# {}", place.label()),
+            format!("latitude = {}", place.latitude),
+            format!("longitude = {}", place.longitude),
+            "# Detect the location from the IP address instead".to_string(),
+            "auto = false".to_string(),
+        ]),
+        None => lines.extend([
+            "# Detected from the IP address on each start (uses ipinfo.io)".to_string(),
+            "auto = true".to_string(),
+        ]),
+    }
+    lines.extend([
+        "# Hide the location in the status line".to_string(),
+        "hide = false".to_string(),
+        String::new(),
+        "[units]".to_string(),
+        "# \"celsius\" or \"fahrenheit\"".to_string(),
+        format!("temperature = {}", toml_value(answers.units.temperature)),
+        "# \"kmh\", \"ms\", \"mph\" or \"kn\"".to_string(),
+        format!("wind_speed = {}", toml_value(answers.units.wind_speed)),
+        "# \"mm\" or \"inch\"".to_string(),
+        format!(
+            "precipitation = {}",
+            toml_value(answers.units.precipitation)
+        ),
+        "# \"hpa\", \"inhg\" or \"mmhg\"".to_string(),
+        format!("pressure = {}", toml_value(answers.units.pressure)),
+        String::new(),
+        "[theme]".to_string(),
+        format!("# {}", PRESETS.join(", ")),
+        format!("preset = {}", toml_value(&answers.theme)),
+    ]);
+    lines.join("\n") + "\n"
+}
+
+// Shows the question with its default and reads one line; an empty answer is the default
+fn ask(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    default: &str,
+) -> io::Result<String> {
+    if default.is_empty() {
+        write!(output, "{}: ", question)?;
+    } else {
+        write!(output, "{} [{}]: ", question, default)?;
+    }
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "setup cancelled",
+        ));
+    }
+    let answer = line.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+// Asks again, with the reason, until `parse` accepts the answer
+fn ask_until<T>(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> io::Result<T> {
+    loop {
+        let answer = ask(input, output, question, default)?;
+        match parse(&answer) {
+            Ok(value) => return Ok(value),
+            Err(reason) => writeln!(output, "  {}", reason)?,
+        }
+    }
+}
+
+fn parse_units(answer: &str) -> Result<WeatherUnits, String> {
+    match answer.to_lowercase().as_str() {
+        "metric" | "m" => Ok(WeatherUnits::metric()),
+        "imperial" | "i" => Ok(WeatherUnits::imperial()),
+        _ => Err("Answer metric or imperial".to_string()),
+    }
+}
+
+// A preset name or its number in the list
+fn parse_theme(answer: &str) -> Result<String, String> {
+    let by_number = answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| PRESETS.get(i));
+    match by_number.or_else(|| PRESETS.iter().find(|p| p.eq_ignore_ascii_case(answer))) {
+        Some(name) => Ok(name.to_string()),
+        None => Err(format!("Pick one of: {}", PRESETS.join(", "))),
+    }
+}
+
+fn parse_refresh(answer: &str) -> Result<u64, String> {
+    match answer.parse::<u64>() {
+        Ok(seconds) if seconds >= MIN_REFRESH_SECONDS => Ok(seconds),
+        _ => Err(format!(
+            "Enter a number of seconds, at least {}",
+            MIN_REFRESH_SECONDS
+        )),
+    }
+}
+
+// Searches until a place is picked, or returns None for IP detection
+async fn ask_place(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Option<Place>> {
+    loop {
+        let name = ask(
+            input,
+            output,
+            "City (leave empty to detect it from your IP address)",
+            "",
+        )?;
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        let places = match geography::search_places(&name).await {
+            Ok(places) => places,
+            Err(e) => {
+                writeln!(output, "  {}", e.user_friendly_message())?;
+                continue;
+            }
+        };
+        if places.is_empty() {
+            writeln!(output, "  No place called '{}' found", name)?;
+            continue;
+        }
+
+        for (idx, place) in places.iter().enumerate() {
+            writeln!(
+                output,
+                "  {}) {} ({:.2}, {:.2})",
+                idx + 1,
+                place.label(),
+                place.latitude,
+                place.longitude
+            )?;
+        }
+        let count = places.len();
+        let pick = ask_until(
+            input,
+            output,
+            "Which one (0 to search again)",
+            "1",
+            |a| match a.parse::<usize>() {
+                Ok(n) if n <= count => Ok(n),
+                _ => Err(format!("Enter a number from 0 to {}", count)),
+            },
+        )?;
+        if pick > 0 {
+            return Ok(places.into_iter().nth(pick - 1));
+        }
+    }
+}
+
+// Runs `weathr config init` on stdin and stdout and returns where the file was written:
+// `path`, or the default config file
+pub async fn run(path: Option<PathBuf>) -> io::Result<Option<PathBuf>> {
+    let path = match path {
+        Some(path) => path,
+        None => Config::get_config_path().map_err(io::Error::other)?,
+    };
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+
+    writeln!(output, "Setting up {}", path.display())?;
+    if path.exists() {
+        let overwrite = ask_until(
+            &mut input,
+            &mut output,
+            "A config file already exists. Replace it? (y/n)",
+            "n",
+            |a| match a.to_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err("Answer y or n".to_string()),
+            },
+        )?;
+        if !overwrite {
+            return Ok(None);
+        }
+    }
+
+    let place = ask_place(&mut input, &mut output).await?;
+    let units = ask_until(
+        &mut input,
+        &mut output,
+        "Units: metric (°C, km/h, mm) or imperial (°F, mph, inch)",
+        "metric",
+        parse_units,
+    )?;
+    for (idx, name) in PRESETS.iter().enumerate() {
+        writeln!(output, "  {}) {}", idx + 1, name)?;
+    }
+    let theme = ask_until(&mut input, &mut output, "Theme", "default", parse_theme)?;
+    let refresh_seconds = ask_until(
+        &mut input,
+        &mut output,
+        "Seconds between weather updates",
+        &DEFAULT_REFRESH_SECONDS.to_string(),
+        parse_refresh,
+    )?;
+
+    let answers = Answers {
+        place,
+        units,
+        theme,
+        refresh_seconds,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, render_config(&answers))?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::TemperatureUnit;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_render_config_round_trips() {
+        let answers = Answers {
+            place: Some(Place {
+                name: "Portland".to_string(),
+                region: Some("Oregon".to_string()),
+                country: Some("United States".to_string()),
+                latitude: 45.52,
+                longitude: -122.68,
+            }),
+            units: WeatherUnits::imperial(),
+            theme: "nord".to_string(),
+            refresh_seconds: 600,
+        };
+        let content = render_config(&answers);
+        assert!(content.contains("# Portland, Oregon, United States\n"));
+
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(config.location.latitude, 45.52);
+        assert!(!config.location.auto);
+        assert_eq!(config.units.temperature, TemperatureUnit::Fahrenheit);
+        assert_eq!(config.theme.preset.as_deref(), Some("nord"));
+        assert_eq!(config.refresh_seconds, Some(600));
+
+        let auto = render_config(&Answers {
+            place: None,
+            ..answers
+        });
+        let config: Config = toml::from_str(&auto).unwrap();
+        assert!(config.location.auto);
+    }
+
+    #[test]
+    fn test_ask_until_repeats_and_defaults() {
+        let mut input = Cursor::new("abc\n30\n\n");
+        let mut output = Vec::new();
+        let seconds = ask_until(&mut input, &mut output, "Refresh", "300", parse_refresh).unwrap();
+        assert_eq!(seconds, 300);
+        let shown = String::from_utf8(output).unwrap();
+        assert_eq!(shown.matches("at least 60").count(), 2);
+
+        let mut input = Cursor::new("");
+        assert!(ask(&mut input, &mut Vec::new(), "City", "").is_err());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        assert_eq!(parse_theme("2"), Ok(PRESETS[1].to_string()));
+        assert_eq!(parse_theme("Nord"), Ok("nord".to_string()));
+        assert!(parse_theme("0").is_err());
+        assert!(parse_theme("neon").is_err());
+    }
+}
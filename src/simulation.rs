@@ -0,0 +1,336 @@
+use crate::weather::air_quality::AirQuality;
+use crate::weather::alerts::{AlertSeverity, WeatherAlert};
+use crate::weather::units::{normalize_precipitation, normalize_temperature, normalize_wind_speed};
+use crate::weather::{HourlyForecast, WeatherCondition, WeatherData, WeatherUnits, astronomy};
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use std::str::FromStr;
+
+// The simulated day for --time, in local time
+const SUNRISE: (u32, u32) = (6, 0);
+const SUNSET: (u32, u32) = (20, 0);
+
+// Today at `time` on the local clock
+fn today_at(time: NaiveTime) -> DateTime<Utc> {
+    let local = Local::now().date_naive().and_time(time);
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        // Skipped by a daylight saving change; take it as UTC
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
+
+fn hour_minute((hour, minute): (u32, u32)) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).expect("valid time")
+}
+
+fn typical_temperature(condition: WeatherCondition) -> f64 {
+    if condition.is_snowing() || condition == WeatherCondition::FreezingRain {
+        -3.0
+    } else {
+        20.0
+    }
+}
+
+// m/s, like the normalized API data. Snow showers get a gale so they show as a blizzard.
+fn typical_wind_speed(condition: WeatherCondition) -> f64 {
+    match condition {
+        WeatherCondition::SnowShowers => 16.0,
+        _ if condition.is_thunderstorm() => 12.5,
+        _ => 2.8,
+    }
+}
+
+fn typical_cloud_cover(condition: WeatherCondition) -> f64 {
+    match condition {
+        WeatherCondition::Clear => 10.0,
+        WeatherCondition::PartlyCloudy => 40.0,
+        WeatherCondition::Cloudy => 75.0,
+        WeatherCondition::Fog => 60.0,
+        _ => 100.0,
+    }
+}
+
+fn typical_uv_index(condition: WeatherCondition, is_day: bool) -> f64 {
+    match condition {
+        _ if !is_day => 0.0,
+        WeatherCondition::Clear => 6.0,
+        WeatherCondition::PartlyCloudy => 4.0,
+        _ => 1.0,
+    }
+}
+
+// A day's swing of 5° either side of `base`, warmest mid-afternoon
+fn hourly(condition: WeatherCondition, base: f64, now: DateTime<Utc>) -> Vec<HourlyForecast> {
+    use chrono::{Duration as ChronoDuration, Timelike};
+
+    (0..24)
+        .map(|hour| {
+            let time = now + ChronoDuration::hours(hour);
+            let local_hour = time.with_timezone(&chrono::Local).hour() as f64;
+            let swing = ((local_hour - 9.0) / 24.0 * std::f64::consts::TAU).sin();
+            let wet =
+                condition.is_raining() || condition.is_snowing() || condition.is_thunderstorm();
+            HourlyForecast {
+                time,
+                temperature: base + 5.0 * swing,
+                precipitation_probability: Some(if wet {
+                    60.0 + 30.0 * swing.abs()
+                } else {
+                    10.0 * swing.abs()
+                }),
+            }
+        })
+        .collect()
+}
+
+// What --simulate shows: a condition, and any readings that should differ from its
+// typical ones, as in "rain,temp=2,wind=45,night,precip=8"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub condition: WeatherCondition,
+    pub is_day: bool,
+    // In the display units ([units] or --imperial); the rest as in WeatherData
+    pub temperature: Option<f64>,
+    pub wind_speed: Option<f64>,
+    pub precipitation: Option<f64>,
+    pub wind_direction: Option<f64>,
+    pub humidity: Option<f64>,
+    pub cloud_cover: Option<f64>,
+    pub pressure: Option<f64>,
+    pub uv_index: Option<f64>,
+    // --time: the local time the sun, the sky and day or night are shown for
+    pub time: Option<NaiveTime>,
+}
+
+impl Simulation {
+    pub fn new(condition: WeatherCondition, is_day: bool) -> Self {
+        Self {
+            condition,
+            is_day,
+            temperature: None,
+            wind_speed: None,
+            precipitation: None,
+            wind_direction: None,
+            humidity: None,
+            cloud_cover: None,
+            pressure: None,
+            uv_index: None,
+            time: None,
+        }
+    }
+
+    // Stands in for the system clock while a time is set
+    pub fn clock(&self) -> Option<DateTime<Utc>> {
+        self.time.map(today_at)
+    }
+
+    pub fn weather(&self, units: &WeatherUnits) -> WeatherData {
+        let condition = self.condition;
+        let clock = self.clock();
+        let (sunrise, sunset) = match clock {
+            Some(_) => (
+                Some(today_at(hour_minute(SUNRISE))),
+                Some(today_at(hour_minute(SUNSET))),
+            ),
+            None => (None, None),
+        };
+        // A set time decides between day and night
+        let is_day = match (clock, sunrise, sunset) {
+            (Some(now), Some(rise), Some(set)) => rise <= now && now < set,
+            _ => self.is_day,
+        };
+        let temperature = self
+            .temperature
+            .map_or(typical_temperature(condition), |t| {
+                normalize_temperature(t, units.temperature)
+            });
+        WeatherData {
+            condition,
+            temperature,
+            apparent_temperature: temperature - 1.0,
+            humidity: self.humidity.unwrap_or(65.0),
+            precipitation: self
+                .precipitation
+                .map_or(if condition.is_raining() { 2.5 } else { 0.0 }, |p| {
+                    normalize_precipitation(p, units.precipitation)
+                }),
+            wind_speed: self.wind_speed.map_or(typical_wind_speed(condition), |w| {
+                normalize_wind_speed(w, units.wind_speed)
+            }),
+            wind_direction: self.wind_direction.unwrap_or(225.0),
+            cloud_cover: self.cloud_cover.unwrap_or(typical_cloud_cover(condition)),
+            pressure: self.pressure.unwrap_or(1013.0),
+            visibility: Some(10000.0),
+            uv_index: Some(self.uv_index.unwrap_or(typical_uv_index(condition, is_day))),
+            is_day,
+            moon_phase: Some(
+                clock.map_or_else(astronomy::current_moon_phase, astronomy::moon_phase),
+            ),
+            timestamp: "simulated".to_string(),
+            sunrise,
+            sunset,
+            hourly: hourly(condition, temperature, clock.unwrap_or_else(Utc::now)),
+            minutely: Vec::new(),
+            source: None,
+            fetched_at: None,
+            air_quality: Some(AirQuality {
+                pm2_5: Some(14.0),
+                pm10: Some(22.0),
+                ozone: Some(61.0),
+                european_aqi: Some(32.0),
+                us_aqi: Some(55.0),
+                birch_pollen: Some(3.0),
+                grass_pollen: Some(24.0),
+                ragweed_pollen: None,
+            }),
+        }
+    }
+
+    pub fn alerts(&self) -> Vec<WeatherAlert> {
+        if !self.condition.is_thunderstorm() {
+            return Vec::new();
+        }
+        vec![WeatherAlert {
+            event: "Severe Thunderstorm Warning".to_string(),
+            severity: AlertSeverity::Severe,
+            headline: Some("Severe Thunderstorm Warning for the next hour (simulated)".to_string()),
+            description: "Wind gusts up to 60 mph and quarter size hail are possible.".to_string(),
+            instruction: Some("Move indoors and stay away from windows.".to_string()),
+            expires: None,
+        }]
+    }
+}
+
+// Keys after the condition, and the range each value must be in
+const SETTINGS: &[(&str, f64, f64)] = &[
+    ("temp", -100.0, 150.0),
+    ("wind", 0.0, 500.0),
+    ("precip", 0.0, 500.0),
+    ("dir", 0.0, 360.0),
+    ("humidity", 0.0, 100.0),
+    ("clouds", 0.0, 100.0),
+    ("pressure", 800.0, 1100.0),
+    ("uv", 0.0, 20.0),
+];
+
+impl FromStr for Simulation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+        let condition = parts.next().unwrap_or_default().parse()?;
+        let mut simulation = Self::new(condition, true);
+
+        for part in parts {
+            match part.split_once('=') {
+                None if part == "night" => simulation.is_day = false,
+                None if part == "day" => simulation.is_day = true,
+                None => return Err(format!("'{}' isn't night, day or a key=value", part)),
+                Some((key, value)) => {
+                    let Some(&(_, min, max)) = SETTINGS.iter().find(|(k, _, _)| *k == key) else {
+                        let keys: Vec<_> = SETTINGS.iter().map(|(k, _, _)| *k).collect();
+                        return Err(format!(
+                            "unknown setting '{}' (use {})",
+                            key,
+                            keys.join(", ")
+                        ));
+                    };
+                    let value: f64 = value
+                        .parse()
+                        .map_err(|_| format!("{} = '{}' isn't a number", key, value))?;
+                    if !(min..=max).contains(&value) {
+                        return Err(format!("{} must be between {} and {}", key, min, max));
+                    }
+                    let field = match key {
+                        "temp" => &mut simulation.temperature,
+                        "wind" => &mut simulation.wind_speed,
+                        "precip" => &mut simulation.precipitation,
+                        "dir" => &mut simulation.wind_direction,
+                        "humidity" => &mut simulation.humidity,
+                        "clouds" => &mut simulation.cloud_cover,
+                        "pressure" => &mut simulation.pressure,
+                        _ => &mut simulation.uv_index,
+                    };
+                    *field = Some(value);
+                }
+            }
+        }
+        Ok(simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+
+    #[test]
+    fn test_plain_condition() {
+        let simulation: Simulation = "snow-showers".parse().unwrap();
+        assert_eq!(
+            simulation,
+            Simulation::new(WeatherCondition::SnowShowers, true)
+        );
+
+        let weather = simulation.weather(&WeatherUnits::metric());
+        assert_eq!(weather.temperature, -3.0);
+        assert_eq!(weather.wind_speed, 16.0);
+        assert_eq!(weather.precipitation, 0.0);
+        assert!(simulation.alerts().is_empty());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let simulation: Simulation = "rain, temp=2,wind=45,night,precip=8,dir=90"
+            .parse()
+            .unwrap();
+        assert_eq!(simulation.condition, WeatherCondition::Rain);
+        assert!(!simulation.is_day);
+
+        let units = WeatherUnits {
+            temperature: TemperatureUnit::Celsius,
+            wind_speed: WindSpeedUnit::Kmh,
+            precipitation: PrecipitationUnit::Mm,
+            ..WeatherUnits::metric()
+        };
+        let weather = simulation.weather(&units);
+        assert_eq!(weather.temperature, 2.0);
+        assert!((weather.wind_speed - 12.5).abs() < 1e-9);
+        assert_eq!(weather.precipitation, 8.0);
+        assert_eq!(weather.wind_direction, 90.0);
+        assert_eq!(weather.uv_index, Some(0.0));
+        assert!(!weather.is_day);
+
+        let weather = simulation.weather(&WeatherUnits::imperial());
+        assert!((weather.temperature - -16.67).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_time_decides_day_or_night() {
+        let mut simulation: Simulation = "clear,night".parse().unwrap();
+        simulation.time = NaiveTime::from_hms_opt(12, 0, 0);
+        let weather = simulation.weather(&WeatherUnits::metric());
+        assert!(weather.is_day);
+        let progress = astronomy::sun_progress(
+            simulation.clock().unwrap(),
+            weather.sunrise.unwrap(),
+            weather.sunset.unwrap(),
+        );
+        assert!((progress.unwrap() - 3.0 / 7.0).abs() < 0.01);
+
+        simulation.time = NaiveTime::from_hms_opt(21, 30, 0);
+        let weather = simulation.weather(&WeatherUnits::metric());
+        assert!(!weather.is_day);
+        assert_eq!(weather.uv_index, Some(0.0));
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        assert!("sunny".parse::<Simulation>().is_err());
+        assert!("rain,temp".parse::<Simulation>().is_err());
+        assert!("rain,heat=40".parse::<Simulation>().is_err());
+        assert!("rain,temp=warm".parse::<Simulation>().is_err());
+        assert!("rain,humidity=120".parse::<Simulation>().is_err());
+    }
+}
@@ -0,0 +1,756 @@
+use crate::config::ThemeConfig;
+use crate::error::ConfigError;
+use crate::weather::alerts::AlertSeverity;
+use crossterm::style::Color;
+
+pub type Rgb = (u8, u8, u8);
+
+pub const PRESETS: &[&str] = &[
+    "default",
+    "gruvbox",
+    "solarized",
+    "nord",
+    "colorblind-deuteranopia",
+    "colorblind-protanopia",
+    "colorblind-tritanopia",
+    "high-contrast",
+];
+
+// Colors for each part of the scene that has one of its own; everything else keeps
+// the terminal's palette
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    // Top and horizon of the sky gradient (truecolor terminals only)
+    pub sky_day: (Rgb, Rgb),
+    pub sky_night: (Rgb, Rgb),
+    // Blended in around sunrise and sunset
+    pub sky_twilight: (Rgb, Rgb),
+    // Under cloud, by day and by night
+    pub sky_overcast: (Rgb, Rgb),
+    pub sky_overcast_night: (Rgb, Rgb),
+    pub sun: Color,
+    // A weak, low-UV sun
+    pub sun_dim: Color,
+    // Drizzle and heavy rain, then the brighter streaks of light rain and storms
+    pub rain: Color,
+    pub rain_bright: Color,
+    pub snow: Color,
+    pub cloud: Color,
+    // The status line
+    pub text: Color,
+    // Alert ticker and panel, by severity
+    pub alert_extreme: Color,
+    pub alert_severe: Color,
+    pub alert_moderate: Color,
+    pub alert_minor: Color,
+    // The house, the ground and the rest of the scenery
+    pub scenery: Scenery,
+    // Bold text and heavier rain and snow characters
+    pub high_contrast: bool,
+}
+
+// Colors of everything in the scene that isn't weather. Pairs are by day, then by night
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scenery {
+    // Bright stars and shooting stars, then faint stars
+    pub star: Color,
+    pub star_dim: Color,
+    // Dense fog, then thin fog
+    pub fog: Color,
+    pub fog_dim: Color,
+    // Brightest glow first
+    pub fireflies: [Color; 3],
+    pub leaves: [Color; 6],
+    pub blossoms: [Color; 3],
+    pub birds: (Color, Color),
+    // Body, windows, tail, underside and exhaust
+    pub airplane: [Color; 5],
+    pub wood: (Color, Color),
+    pub door: Color,
+    pub roof: (Color, Color),
+    pub window: (Color, Color),
+    pub chimney: Color,
+    // Chimney pots, the foundation and the path
+    pub stone: Color,
+    // Brightest first; the day uses the first two and the night the last two
+    pub grass: [Color; 3],
+    pub trees: (Color, Color),
+    pub flowers: ([Color; 4], [Color; 4]),
+    pub soil: (Color, Color),
+    // The fence and the snowman
+    pub fence: (Color, Color),
+    pub mailbox: (Color, Color),
+    pub water: (Color, Color),
+}
+
+const TWILIGHT: (Rgb, Rgb) = ((75, 80, 150), (245, 145, 105));
+const OVERCAST: (Rgb, Rgb) = ((85, 92, 105), (145, 150, 160));
+const OVERCAST_NIGHT: (Rgb, Rgb) = ((14, 16, 22), (32, 35, 42));
+
+pub const DEFAULT_SCENERY: Scenery = Scenery {
+    star: Color::White,
+    star_dim: Color::DarkGrey,
+    fog: Color::Grey,
+    fog_dim: Color::DarkGrey,
+    fireflies: [Color::Yellow, rgb((200, 255, 100)), rgb((150, 200, 80))],
+    leaves: [
+        rgb((255, 165, 0)),
+        rgb((218, 165, 32)),
+        rgb((184, 134, 11)),
+        rgb((205, 92, 92)),
+        rgb((160, 82, 45)),
+        rgb((139, 69, 19)),
+    ],
+    blossoms: [
+        rgb((255, 183, 197)),
+        rgb((255, 240, 245)),
+        rgb((238, 130, 238)),
+    ],
+    birds: (Color::Yellow, Color::DarkGrey),
+    airplane: [
+        Color::White,
+        Color::Cyan,
+        Color::Blue,
+        Color::DarkGrey,
+        Color::Grey,
+    ],
+    wood: (rgb((210, 180, 140)), rgb((100, 70, 50))),
+    door: rgb((139, 69, 19)),
+    roof: (Color::DarkRed, Color::DarkMagenta),
+    window: (Color::Cyan, Color::Yellow),
+    chimney: Color::Grey,
+    stone: Color::DarkGrey,
+    grass: [Color::Green, Color::DarkGreen, rgb((0, 50, 0))],
+    trees: (Color::DarkGreen, rgb((0, 50, 0))),
+    flowers: (
+        [Color::Magenta, Color::Red, Color::Cyan, Color::Yellow],
+        [
+            Color::DarkMagenta,
+            Color::DarkRed,
+            Color::Blue,
+            Color::DarkYellow,
+        ],
+    ),
+    soil: (rgb((101, 67, 33)), rgb((60, 40, 20))),
+    fence: (Color::White, Color::Grey),
+    mailbox: (Color::Blue, Color::DarkBlue),
+    water: (Color::Blue, Color::DarkBlue),
+};
+
+const GRUVBOX_SCENERY: Scenery = Scenery {
+    star: rgb((235, 219, 178)),
+    star_dim: rgb((124, 111, 100)),
+    fog: rgb((168, 153, 132)),
+    fog_dim: rgb((102, 92, 84)),
+    fireflies: [
+        rgb((250, 189, 47)),
+        rgb((184, 187, 38)),
+        rgb((152, 151, 26)),
+    ],
+    leaves: [
+        rgb((254, 128, 25)),
+        rgb((250, 189, 47)),
+        rgb((215, 153, 33)),
+        rgb((251, 73, 52)),
+        rgb((214, 93, 14)),
+        rgb((175, 58, 3)),
+    ],
+    blossoms: [
+        rgb((211, 134, 155)),
+        rgb((235, 219, 178)),
+        rgb((177, 98, 134)),
+    ],
+    birds: (rgb((250, 189, 47)), rgb((102, 92, 84))),
+    airplane: [
+        rgb((235, 219, 178)),
+        rgb((131, 165, 152)),
+        rgb((69, 133, 136)),
+        rgb((102, 92, 84)),
+        rgb((168, 153, 132)),
+    ],
+    wood: (rgb((213, 196, 161)), rgb((124, 111, 100))),
+    door: rgb((175, 58, 3)),
+    roof: (rgb((204, 36, 29)), rgb((143, 63, 113))),
+    window: (rgb((131, 165, 152)), rgb((250, 189, 47))),
+    chimney: rgb((168, 153, 132)),
+    stone: rgb((102, 92, 84)),
+    grass: [
+        rgb((184, 187, 38)),
+        rgb((152, 151, 26)),
+        rgb((121, 116, 14)),
+    ],
+    trees: (rgb((152, 151, 26)), rgb((121, 116, 14))),
+    flowers: (
+        [
+            rgb((211, 134, 155)),
+            rgb((251, 73, 52)),
+            rgb((142, 192, 124)),
+            rgb((250, 189, 47)),
+        ],
+        [
+            rgb((177, 98, 134)),
+            rgb((204, 36, 29)),
+            rgb((104, 157, 106)),
+            rgb((215, 153, 33)),
+        ],
+    ),
+    soil: (rgb((146, 131, 116)), rgb((80, 73, 69))),
+    fence: (rgb((235, 219, 178)), rgb((168, 153, 132))),
+    mailbox: (rgb((69, 133, 136)), rgb((7, 102, 120))),
+    water: (rgb((69, 133, 136)), rgb((7, 102, 120))),
+};
+
+const SOLARIZED_SCENERY: Scenery = Scenery {
+    star: rgb((238, 232, 213)),
+    star_dim: rgb((88, 110, 117)),
+    fog: rgb((147, 161, 161)),
+    fog_dim: rgb((88, 110, 117)),
+    fireflies: [rgb((181, 137, 0)), rgb((133, 153, 0)), rgb((101, 123, 131))],
+    leaves: [
+        rgb((203, 75, 22)),
+        rgb((181, 137, 0)),
+        rgb((220, 50, 47)),
+        rgb((203, 75, 22)),
+        rgb((181, 137, 0)),
+        rgb((220, 50, 47)),
+    ],
+    blossoms: [
+        rgb((211, 54, 130)),
+        rgb((238, 232, 213)),
+        rgb((108, 113, 196)),
+    ],
+    birds: (rgb((181, 137, 0)), rgb((88, 110, 117))),
+    airplane: [
+        rgb((238, 232, 213)),
+        rgb((42, 161, 152)),
+        rgb((38, 139, 210)),
+        rgb((88, 110, 117)),
+        rgb((147, 161, 161)),
+    ],
+    wood: (rgb((147, 161, 161)), rgb((88, 110, 117))),
+    door: rgb((203, 75, 22)),
+    roof: (rgb((220, 50, 47)), rgb((108, 113, 196))),
+    window: (rgb((42, 161, 152)), rgb((181, 137, 0))),
+    chimney: rgb((147, 161, 161)),
+    stone: rgb((88, 110, 117)),
+    grass: [rgb((133, 153, 0)), rgb((100, 116, 0)), rgb((60, 70, 0))],
+    trees: (rgb((100, 116, 0)), rgb((60, 70, 0))),
+    flowers: (
+        [
+            rgb((211, 54, 130)),
+            rgb((220, 50, 47)),
+            rgb((42, 161, 152)),
+            rgb((181, 137, 0)),
+        ],
+        [
+            rgb((108, 113, 196)),
+            rgb((203, 75, 22)),
+            rgb((38, 139, 210)),
+            rgb((147, 115, 0)),
+        ],
+    ),
+    soil: (rgb((101, 123, 131)), rgb((7, 54, 66))),
+    fence: (rgb((238, 232, 213)), rgb((147, 161, 161))),
+    mailbox: (rgb((38, 139, 210)), rgb((7, 54, 66))),
+    water: (rgb((38, 139, 210)), rgb((7, 54, 66))),
+};
+
+const NORD_SCENERY: Scenery = Scenery {
+    star: rgb((236, 239, 244)),
+    star_dim: rgb((76, 86, 106)),
+    fog: rgb((216, 222, 233)),
+    fog_dim: rgb((76, 86, 106)),
+    fireflies: [
+        rgb((235, 203, 139)),
+        rgb((163, 190, 140)),
+        rgb((110, 130, 95)),
+    ],
+    leaves: [
+        rgb((208, 135, 112)),
+        rgb((235, 203, 139)),
+        rgb((191, 97, 106)),
+        rgb((208, 135, 112)),
+        rgb((235, 203, 139)),
+        rgb((191, 97, 106)),
+    ],
+    blossoms: [
+        rgb((180, 142, 173)),
+        rgb((236, 239, 244)),
+        rgb((229, 233, 240)),
+    ],
+    birds: (rgb((235, 203, 139)), rgb((76, 86, 106))),
+    airplane: [
+        rgb((236, 239, 244)),
+        rgb((136, 192, 208)),
+        rgb((94, 129, 172)),
+        rgb((76, 86, 106)),
+        rgb((216, 222, 233)),
+    ],
+    wood: (rgb((208, 135, 112)), rgb((110, 80, 70))),
+    door: rgb((191, 97, 106)),
+    roof: (rgb((191, 97, 106)), rgb((180, 142, 173))),
+    window: (rgb((136, 192, 208)), rgb((235, 203, 139))),
+    chimney: rgb((216, 222, 233)),
+    stone: rgb((76, 86, 106)),
+    grass: [
+        rgb((163, 190, 140)),
+        rgb((120, 150, 100)),
+        rgb((67, 90, 60)),
+    ],
+    trees: (rgb((120, 150, 100)), rgb((67, 90, 60))),
+    flowers: (
+        [
+            rgb((180, 142, 173)),
+            rgb((191, 97, 106)),
+            rgb((136, 192, 208)),
+            rgb((235, 203, 139)),
+        ],
+        [
+            rgb((130, 100, 125)),
+            rgb((140, 70, 78)),
+            rgb((94, 129, 172)),
+            rgb((170, 145, 100)),
+        ],
+    ),
+    soil: (rgb((110, 85, 70)), rgb((59, 66, 82))),
+    fence: (rgb((236, 239, 244)), rgb((216, 222, 233))),
+    mailbox: (rgb((94, 129, 172)), rgb((76, 86, 106))),
+    water: (rgb((94, 129, 172)), rgb((67, 76, 94))),
+};
+
+// Only bright named colors, which stand out on the black sky
+const HIGH_CONTRAST_SCENERY: Scenery = Scenery {
+    star: Color::White,
+    star_dim: Color::Grey,
+    fog: Color::White,
+    fog_dim: Color::Grey,
+    fireflies: [Color::Yellow, Color::Yellow, Color::Green],
+    leaves: [
+        Color::Yellow,
+        Color::Red,
+        Color::Magenta,
+        Color::Yellow,
+        Color::Red,
+        Color::Magenta,
+    ],
+    blossoms: [Color::Magenta, Color::White, Color::Magenta],
+    birds: (Color::Yellow, Color::White),
+    airplane: [
+        Color::White,
+        Color::Cyan,
+        Color::Blue,
+        Color::Grey,
+        Color::Grey,
+    ],
+    wood: (Color::Yellow, Color::Yellow),
+    door: Color::Red,
+    roof: (Color::Red, Color::Magenta),
+    window: (Color::Cyan, Color::Yellow),
+    chimney: Color::White,
+    stone: Color::Grey,
+    grass: [Color::Green, Color::Green, Color::Green],
+    trees: (Color::Green, Color::Green),
+    flowers: (
+        [Color::Magenta, Color::Red, Color::Cyan, Color::Yellow],
+        [Color::Magenta, Color::Red, Color::Cyan, Color::Yellow],
+    ),
+    soil: (Color::Yellow, Color::Yellow),
+    fence: (Color::White, Color::White),
+    mailbox: (Color::Blue, Color::Blue),
+    water: (Color::Blue, Color::Blue),
+};
+
+impl Default for Scenery {
+    fn default() -> Self {
+        DEFAULT_SCENERY
+    }
+}
+
+impl Scenery {
+    // The by-day or by-night color of a pair
+    pub fn pick(pair: (Color, Color), is_day: bool) -> Color {
+        if is_day { pair.0 } else { pair.1 }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            sky_day: ((38, 105, 195), (135, 185, 235)),
+            sky_night: ((8, 12, 35), (22, 30, 68)),
+            sky_twilight: TWILIGHT,
+            sky_overcast: OVERCAST,
+            sky_overcast_night: OVERCAST_NIGHT,
+            sun: Color::Yellow,
+            sun_dim: Color::DarkYellow,
+            rain: Color::Cyan,
+            rain_bright: Color::White,
+            snow: Color::White,
+            cloud: Color::White,
+            text: Color::Cyan,
+            alert_extreme: Color::Magenta,
+            alert_severe: Color::Red,
+            alert_moderate: Color::DarkYellow,
+            alert_minor: Color::Yellow,
+            scenery: DEFAULT_SCENERY,
+            high_contrast: false,
+        }
+    }
+}
+
+const fn rgb((r, g, b): Rgb) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+impl Theme {
+    pub fn preset(name: &str) -> Option<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "gruvbox" => Self {
+                sky_day: ((69, 133, 136), (131, 165, 152)),
+                sky_night: ((29, 32, 33), (50, 48, 47)),
+                sky_twilight: ((177, 98, 134), (254, 128, 25)),
+                sky_overcast: ((80, 73, 69), (146, 131, 116)),
+                sky_overcast_night: ((29, 32, 33), (50, 48, 47)),
+                sun: rgb((250, 189, 47)),
+                sun_dim: rgb((215, 153, 33)),
+                rain: rgb((131, 165, 152)),
+                rain_bright: rgb((235, 219, 178)),
+                snow: rgb((235, 219, 178)),
+                cloud: rgb((213, 196, 161)),
+                text: rgb((142, 192, 124)),
+                alert_extreme: rgb((211, 134, 155)),
+                alert_severe: rgb((251, 73, 52)),
+                alert_moderate: rgb((254, 128, 25)),
+                alert_minor: rgb((250, 189, 47)),
+                scenery: GRUVBOX_SCENERY,
+                high_contrast: false,
+            },
+            "solarized" => Self {
+                sky_day: ((38, 139, 210), (131, 148, 150)),
+                sky_night: ((0, 43, 54), (7, 54, 66)),
+                sky_twilight: ((108, 113, 196), (203, 75, 22)),
+                sky_overcast: ((88, 110, 117), (147, 161, 161)),
+                sky_overcast_night: ((0, 43, 54), (7, 54, 66)),
+                sun: rgb((181, 137, 0)),
+                sun_dim: rgb((203, 75, 22)),
+                rain: rgb((42, 161, 152)),
+                rain_bright: rgb((147, 161, 161)),
+                snow: rgb((238, 232, 213)),
+                cloud: rgb((147, 161, 161)),
+                text: rgb((42, 161, 152)),
+                alert_extreme: rgb((211, 54, 130)),
+                alert_severe: rgb((220, 50, 47)),
+                alert_moderate: rgb((203, 75, 22)),
+                alert_minor: rgb((181, 137, 0)),
+                scenery: SOLARIZED_SCENERY,
+                high_contrast: false,
+            },
+            "nord" => Self {
+                sky_day: ((94, 129, 172), (136, 192, 208)),
+                sky_night: ((46, 52, 64), (59, 66, 82)),
+                sky_twilight: ((94, 129, 172), (208, 135, 112)),
+                sky_overcast: ((76, 86, 106), (129, 140, 160)),
+                sky_overcast_night: ((46, 52, 64), (59, 66, 82)),
+                sun: rgb((235, 203, 139)),
+                sun_dim: rgb((208, 135, 112)),
+                rain: rgb((129, 161, 193)),
+                rain_bright: rgb((143, 188, 187)),
+                snow: rgb((236, 239, 244)),
+                cloud: rgb((216, 222, 233)),
+                text: rgb((136, 192, 208)),
+                alert_extreme: rgb((180, 142, 173)),
+                alert_severe: rgb((191, 97, 106)),
+                alert_moderate: rgb((208, 135, 112)),
+                alert_minor: rgb((235, 203, 139)),
+                scenery: NORD_SCENERY,
+                high_contrast: false,
+            },
+            // Red-green safe: water in blues, alerts along yellow to vermillion and sorted
+            // by brightness as well as hue (Okabe-Ito colors)
+            "colorblind-deuteranopia" => Self {
+                sky_day: ((0, 90, 160), (120, 170, 220)),
+                sky_night: ((8, 12, 35), (22, 30, 68)),
+                sky_twilight: TWILIGHT,
+                sky_overcast: OVERCAST,
+                sky_overcast_night: OVERCAST_NIGHT,
+                sun: rgb((240, 228, 66)),
+                sun_dim: rgb((230, 159, 0)),
+                rain: rgb((0, 114, 178)),
+                rain_bright: rgb((86, 180, 233)),
+                snow: Color::White,
+                cloud: rgb((200, 200, 200)),
+                text: rgb((86, 180, 233)),
+                alert_extreme: rgb((213, 94, 0)),
+                alert_severe: rgb((230, 159, 0)),
+                alert_moderate: rgb((240, 228, 66)),
+                alert_minor: rgb((255, 245, 170)),
+                scenery: DEFAULT_SCENERY,
+                high_contrast: false,
+            },
+            // As above, but without vermillion, which looks almost black without red cones
+            "colorblind-protanopia" => Self {
+                sky_day: ((0, 90, 160), (120, 170, 220)),
+                sky_night: ((8, 12, 35), (22, 30, 68)),
+                sky_twilight: TWILIGHT,
+                sky_overcast: OVERCAST,
+                sky_overcast_night: OVERCAST_NIGHT,
+                sun: rgb((240, 228, 66)),
+                sun_dim: rgb((230, 159, 0)),
+                rain: rgb((0, 114, 178)),
+                rain_bright: rgb((86, 180, 233)),
+                snow: Color::White,
+                cloud: rgb((200, 200, 200)),
+                text: rgb((86, 180, 233)),
+                alert_extreme: rgb((255, 176, 0)),
+                alert_severe: rgb((230, 159, 0)),
+                alert_moderate: rgb((240, 228, 66)),
+                alert_minor: rgb((255, 245, 170)),
+                scenery: DEFAULT_SCENERY,
+                high_contrast: false,
+            },
+            // Blue-yellow safe: water in teal, alerts and sun along pink to red
+            "colorblind-tritanopia" => Self {
+                sky_day: ((0, 110, 130), (120, 190, 200)),
+                sky_night: ((10, 20, 30), (30, 45, 55)),
+                sky_twilight: TWILIGHT,
+                sky_overcast: OVERCAST,
+                sky_overcast_night: OVERCAST_NIGHT,
+                sun: rgb((255, 150, 130)),
+                sun_dim: rgb((200, 100, 90)),
+                rain: rgb((0, 150, 170)),
+                rain_bright: rgb((150, 220, 230)),
+                snow: Color::White,
+                cloud: rgb((200, 200, 200)),
+                text: rgb((0, 170, 190)),
+                alert_extreme: rgb((220, 20, 60)),
+                alert_severe: rgb((255, 60, 60)),
+                alert_moderate: rgb((255, 130, 150)),
+                alert_minor: rgb((255, 200, 210)),
+                scenery: DEFAULT_SCENERY,
+                high_contrast: false,
+            },
+            // Only the bright named colors, on a black sky
+            "high-contrast" => Self {
+                sky_day: ((0, 0, 0), (0, 0, 0)),
+                sky_night: ((0, 0, 0), (0, 0, 0)),
+                sky_twilight: ((0, 0, 0), (0, 0, 0)),
+                sky_overcast: ((0, 0, 0), (0, 0, 0)),
+                sky_overcast_night: ((0, 0, 0), (0, 0, 0)),
+                sun: Color::Yellow,
+                sun_dim: Color::Yellow,
+                rain: Color::Cyan,
+                rain_bright: Color::White,
+                snow: Color::White,
+                cloud: Color::White,
+                text: Color::White,
+                alert_extreme: Color::Magenta,
+                alert_severe: Color::Red,
+                alert_moderate: Color::Yellow,
+                alert_minor: Color::Green,
+                scenery: HIGH_CONTRAST_SCENERY,
+                high_contrast: true,
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    // A preset (the default one unless named) with any colors from the config on top
+    pub fn from_config(config: &ThemeConfig) -> Result<Self, ConfigError> {
+        let name = config.preset.as_deref().unwrap_or("default");
+        let mut theme =
+            Self::preset(name).ok_or_else(|| ConfigError::UnknownTheme(name.to_string()))?;
+
+        let colors = [
+            ("sun", &config.sun, &mut theme.sun),
+            ("sun_dim", &config.sun_dim, &mut theme.sun_dim),
+            ("rain", &config.rain, &mut theme.rain),
+            ("rain_bright", &config.rain_bright, &mut theme.rain_bright),
+            ("snow", &config.snow, &mut theme.snow),
+            ("cloud", &config.cloud, &mut theme.cloud),
+            ("text", &config.text, &mut theme.text),
+            (
+                "alert_extreme",
+                &config.alert_extreme,
+                &mut theme.alert_extreme,
+            ),
+            (
+                "alert_severe",
+                &config.alert_severe,
+                &mut theme.alert_severe,
+            ),
+            (
+                "alert_moderate",
+                &config.alert_moderate,
+                &mut theme.alert_moderate,
+            ),
+            ("alert_minor", &config.alert_minor, &mut theme.alert_minor),
+        ];
+        for (key, value, slot) in colors {
+            if let Some(value) = value {
+                *slot = parse_color(value).ok_or_else(|| invalid_color(key, value))?;
+            }
+        }
+
+        let sky = [
+            ("sky_top", &config.sky_top, &mut theme.sky_day.0),
+            ("sky_horizon", &config.sky_horizon, &mut theme.sky_day.1),
+        ];
+        for (key, value, slot) in sky {
+            if let Some(value) = value {
+                *slot = parse_hex(value).ok_or_else(|| invalid_color(key, value))?;
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn alert_color(&self, severity: AlertSeverity) -> Color {
+        match severity {
+            AlertSeverity::Extreme => self.alert_extreme,
+            AlertSeverity::Severe => self.alert_severe,
+            AlertSeverity::Moderate => self.alert_moderate,
+            AlertSeverity::Minor | AlertSeverity::Unknown => self.alert_minor,
+        }
+    }
+}
+
+// Closest truecolor value, for drawing a theme color into an image
+pub fn to_rgb(color: Color) -> Rgb {
+    match color {
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::White => (255, 255, 255),
+        Color::Grey => (192, 192, 192),
+        Color::Yellow => (255, 225, 80),
+        Color::DarkYellow => (215, 160, 20),
+        Color::Red => (255, 85, 85),
+        Color::Cyan => (85, 255, 255),
+        _ => (255, 215, 120),
+    }
+}
+
+fn invalid_color(key: &str, value: &str) -> ConfigError {
+    ConfigError::InvalidColor {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn parse_hex(value: &str) -> Option<Rgb> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+// "#rrggbb" or one of the terminal's named colors, e.g. "yellow" or "dark_grey"
+pub fn parse_color(value: &str) -> Option<Color> {
+    match parse_hex(value) {
+        Some(hex) => Some(rgb(hex)),
+        None => Color::try_from(value).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(
+            parse_color("#ff8000"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGrey));
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("chartreuse"), None);
+    }
+
+    #[test]
+    fn test_presets_resolve() {
+        for name in PRESETS {
+            assert!(Theme::preset(name).is_some(), "{}", name);
+        }
+        assert_eq!(Theme::preset("vaporwave"), None);
+    }
+
+    #[test]
+    fn test_colorblind_presets_keep_roles_apart() {
+        for name in PRESETS.iter().filter(|n| n.starts_with("colorblind-")) {
+            let theme = Theme::preset(name).unwrap();
+            let roles = [
+                theme.rain,
+                theme.rain_bright,
+                theme.snow,
+                theme.alert_extreme,
+                theme.alert_severe,
+                theme.alert_moderate,
+                theme.alert_minor,
+            ];
+            for (i, a) in roles.iter().enumerate() {
+                for b in &roles[i + 1..] {
+                    assert_ne!(a, b, "{}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_high_contrast_scenery_is_bright() {
+        let s = Theme::preset("high-contrast").unwrap().scenery;
+        let mut colors = vec![
+            s.star, s.star_dim, s.fog, s.fog_dim, s.door, s.chimney, s.stone,
+        ];
+        colors.extend(s.fireflies.iter().chain(&s.leaves).chain(&s.blossoms));
+        colors.extend(s.airplane.iter().chain(&s.grass));
+        colors.extend(s.flowers.0.iter().chain(&s.flowers.1));
+        for (day, night) in [
+            s.birds, s.wood, s.roof, s.window, s.trees, s.soil, s.fence, s.mailbox, s.water,
+        ] {
+            colors.extend([day, night]);
+        }
+        for color in colors {
+            assert!(
+                !matches!(
+                    color,
+                    Color::Rgb { .. }
+                        | Color::DarkGrey
+                        | Color::DarkRed
+                        | Color::DarkGreen
+                        | Color::DarkYellow
+                        | Color::DarkBlue
+                        | Color::DarkMagenta
+                        | Color::DarkCyan
+                ),
+                "{:?}",
+                color
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_config_overrides_preset() {
+        let config = ThemeConfig {
+            preset: Some("nord".to_string()),
+            sun: Some("red".to_string()),
+            sky_top: Some("#000000".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.sun, Color::Red);
+        assert_eq!(theme.sky_day.0, (0, 0, 0));
+        assert_eq!(theme.rain, Theme::preset("nord").unwrap().rain);
+
+        let bad = ThemeConfig {
+            rain: Some("#12345g".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(Theme::from_config(&bad).unwrap_err().kind(), "InvalidColor");
+    }
+}
@@ -0,0 +1,216 @@
+use super::panel::{PANEL_PADDING, centered_panel, draw_panel};
+use super::text::wrap;
+use crate::render::{TerminalRenderer, display_width};
+use crate::theme::Theme;
+use crate::weather::alerts::WeatherAlert;
+use chrono::{DateTime, Utc};
+use crossterm::style::Color;
+use std::io;
+
+// Frames per column of scrolling
+const SCROLL_FRAMES: u32 = 4;
+const SEPARATOR: &str = "   •   ";
+const PANEL_WIDTH: usize = 70;
+
+// Active alerts scrolling along the top row, with the full text one key away
+#[derive(Default)]
+pub struct AlertTicker {
+    alerts: Vec<WeatherAlert>,
+    text: String,
+    frame: u32,
+    expanded: bool,
+    theme: Theme,
+}
+
+impl AlertTicker {
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn set_alerts(&mut self, alerts: Vec<WeatherAlert>) {
+        self.alerts = alerts;
+        self.drop_expired(Utc::now());
+        self.update_text();
+    }
+
+    // Alerts end on their own between fetches
+    pub fn drop_expired(&mut self, now: DateTime<Utc>) {
+        let count = self.alerts.len();
+        self.alerts
+            .retain(|alert| alert.expires.is_none_or(|expires| expires > now));
+        if self.alerts.len() != count {
+            self.update_text();
+        }
+    }
+
+    fn update_text(&mut self) {
+        self.text = self
+            .alerts
+            .iter()
+            .map(|a| match a.headline {
+                Some(ref headline) => format!("⚠ {}: {}", a.event, headline),
+                None => format!("⚠ {}", a.event),
+            })
+            .collect::<Vec<_>>()
+            .join(SEPARATOR);
+        if self.alerts.is_empty() {
+            self.expanded = false;
+        }
+    }
+
+    pub fn has_alerts(&self) -> bool {
+        !self.alerts.is_empty()
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn toggle_expanded(&mut self) {
+        self.expanded = !self.expanded && self.has_alerts();
+    }
+
+    pub fn hide(&mut self) {
+        self.expanded = false;
+    }
+
+    pub fn update(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let Some(worst) = self.alerts.first() else {
+            return Ok(());
+        };
+
+        let (width, _) = renderer.get_size();
+        let color = self.theme.alert_color(worst.severity);
+        let text_width = display_width(&self.text);
+
+        // Scrolls only when it doesn't fit
+        let line: String = if text_width <= width as usize {
+            self.text.clone()
+        } else {
+            let looped: Vec<char> = format!("{}{}", self.text, SEPARATOR).chars().collect();
+            let offset = (self.frame / SCROLL_FRAMES) as usize % looped.len();
+            looped
+                .iter()
+                .cycle()
+                .skip(offset)
+                .take(width as usize)
+                .collect()
+        };
+        renderer.render_line_colored(0, 0, &line, color)?;
+
+        if self.expanded {
+            self.render_panel(renderer)?;
+        }
+        Ok(())
+    }
+
+    fn render_panel(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let (width, height) = renderer.get_size();
+        let text_width = PANEL_WIDTH.min((width as usize).saturating_sub(PANEL_PADDING * 2 + 2));
+        if text_width < 20 || height < 8 {
+            return Ok(());
+        }
+
+        let mut lines: Vec<(String, Color)> = Vec::new();
+        for alert in &self.alerts {
+            let color = self.theme.alert_color(alert.severity);
+            lines.push((alert.event.clone(), color));
+            if let Some(ref headline) = alert.headline {
+                lines.extend(
+                    wrap(headline, text_width)
+                        .into_iter()
+                        .map(|l| (l, Color::White)),
+                );
+            }
+            lines.push((String::new(), Color::White));
+            lines.extend(
+                wrap(&alert.description, text_width)
+                    .into_iter()
+                    .map(|l| (l, Color::Grey)),
+            );
+            if let Some(ref instruction) = alert.instruction {
+                lines.push((String::new(), Color::White));
+                lines.extend(
+                    wrap(instruction, text_width)
+                        .into_iter()
+                        .map(|l| (l, Color::White)),
+                );
+            }
+            lines.push((String::new(), Color::White));
+        }
+        lines.push(("Press any key to close".to_string(), Color::Yellow));
+
+        // Room left by the title, the border and the space around the text
+        let max_lines = (height as usize).saturating_sub(8);
+        if lines.len() > max_lines {
+            lines.truncate(max_lines.saturating_sub(1));
+            lines.push(("…".to_string(), Color::Grey));
+        }
+
+        let title = "Weather alerts";
+        let rect = centered_panel(renderer, title, text_width, lines.len());
+        let border = self.theme.alert_color(self.alerts[0].severity);
+        let (text_x, text_y) = draw_panel(renderer, rect, title, border)?;
+        for (idx, (line, color)) in lines.iter().enumerate() {
+            renderer.render_line_colored(text_x, text_y + idx as u16, line, *color)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::alerts::AlertSeverity;
+
+    #[test]
+    fn test_ticker_text_and_expand() {
+        let mut ticker = AlertTicker::default();
+        ticker.toggle_expanded();
+        assert!(!ticker.is_expanded());
+
+        ticker.set_alerts(vec![WeatherAlert {
+            event: "Flood Watch".to_string(),
+            severity: AlertSeverity::Moderate,
+            headline: Some("Until Friday".to_string()),
+            description: String::new(),
+            instruction: None,
+            expires: None,
+        }]);
+        assert_eq!(ticker.text, "⚠ Flood Watch: Until Friday");
+        ticker.toggle_expanded();
+        assert!(ticker.is_expanded());
+
+        ticker.set_alerts(Vec::new());
+        assert!(!ticker.has_alerts() && !ticker.is_expanded());
+    }
+
+    #[test]
+    fn test_expired_alerts() {
+        let now = Utc::now();
+        let alert = |event: &str, expires| WeatherAlert {
+            event: event.to_string(),
+            severity: AlertSeverity::Severe,
+            headline: None,
+            description: String::new(),
+            instruction: None,
+            expires,
+        };
+        let mut ticker = AlertTicker::default();
+        ticker.set_alerts(vec![
+            alert("Heat Advisory", Some(now - chrono::Duration::hours(1))),
+            alert("Wind Advisory", Some(now + chrono::Duration::hours(1))),
+            alert("Flood Watch", None),
+        ]);
+        assert_eq!(ticker.text, "⚠ Wind Advisory   •   ⚠ Flood Watch");
+
+        ticker.toggle_expanded();
+        ticker.drop_expired(now + chrono::Duration::hours(2));
+        assert_eq!(ticker.text, "⚠ Flood Watch");
+        assert!(ticker.is_expanded());
+    }
+}
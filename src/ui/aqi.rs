@@ -0,0 +1,66 @@
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::air_quality::{self, AirQuality, AqiScale, PollenLevel};
+use crossterm::style::Color;
+use std::io;
+
+// Cleanest to worst, roughly the colors both scales publish
+const LEVEL_COLORS: [Color; 6] = [
+    Color::Green,
+    Color::Yellow,
+    Color::DarkYellow,
+    Color::Red,
+    Color::Magenta,
+    Color::DarkRed,
+];
+
+fn pollen_color(level: PollenLevel) -> Color {
+    match level {
+        PollenLevel::None | PollenLevel::Low => Color::Green,
+        PollenLevel::Moderate => Color::DarkYellow,
+        PollenLevel::High => Color::Red,
+    }
+}
+
+// Air quality index under the status line, colored by its band, followed by the
+// worst pollen while any is in season
+pub struct AirQualityChip {
+    scale: AqiScale,
+}
+
+impl AirQualityChip {
+    pub fn new(scale: AqiScale) -> Self {
+        Self { scale }
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        air_quality: Option<&AirQuality>,
+    ) -> io::Result<()> {
+        let Some(air_quality) = air_quality else {
+            return Ok(());
+        };
+
+        let mut x = 2;
+        if let Some(index) = air_quality.index(self.scale) {
+            let category = air_quality::category(index, self.scale);
+            let color = LEVEL_COLORS[category.level as usize];
+            let text = format!("AQI {:.0} {}", index, category.label);
+            renderer.render_char(x, 2, '●', color)?;
+            renderer.render_line_colored(x + 2, 2, &text, color)?;
+            x += display_width(&text) as u16 + 5;
+        }
+
+        if let Some((name, level)) = air_quality.peak_pollen() {
+            let color = pollen_color(level);
+            renderer.render_char(x, 2, '✿', color)?;
+            renderer.render_line_colored(
+                x + 2,
+                2,
+                &format!("{} pollen {}", name, level.label().to_lowercase()),
+                color,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::render::TerminalRenderer;
+use crate::weather::format_temperature;
+use crate::weather::types::TemperatureUnit;
+use crossterm::style::Color;
+use std::io;
+
+const GLYPH_HEIGHT: usize = 5;
+const MARGIN: u16 = 2;
+// Just below the status line
+const TOP: u16 = 3;
+
+fn glyph(ch: char) -> [&'static str; GLYPH_HEIGHT] {
+    match ch {
+        '0' => ["███", "█ █", "█ █", "█ █", "███"],
+        '1' => [" ██", "  █", "  █", "  █", "  █"],
+        '2' => ["███", "  █", "███", "█  ", "███"],
+        '3' => ["███", "  █", " ██", "  █", "███"],
+        '4' => ["█ █", "█ █", "███", "  █", "  █"],
+        '5' => ["███", "█  ", "███", "  █", "███"],
+        '6' => ["███", "█  ", "███", "█ █", "███"],
+        '7' => ["███", "  █", "  █", "  █", "  █"],
+        '8' => ["███", "█ █", "███", "█ █", "███"],
+        '9' => ["███", "█ █", "███", "  █", "███"],
+        '-' => ["   ", "   ", "███", "   ", "   "],
+        '°' => ["▄▀▄", "▀▄▀", "   ", "   ", "   "],
+        'C' => ["███", "█  ", "█  ", "█  ", "███"],
+        'F' => ["███", "█  ", "██ ", "█  ", "█  "],
+        _ => ["   ", "   ", "   ", "   ", "   "],
+    }
+}
+
+// Glyphs side by side with a one-column gap
+fn big_text(text: &str) -> Vec<String> {
+    (0..GLYPH_HEIGHT)
+        .map(|row| {
+            text.chars()
+                .map(|ch| glyph(ch)[row])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+pub fn temperature_color(celsius: f64) -> Color {
+    if celsius < 0.0 {
+        Color::Cyan
+    } else if celsius < 15.0 {
+        Color::White
+    } else if celsius < 25.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+// The current temperature in block digits in the top-right corner, readable from
+// across the room
+pub struct BigTemperature {
+    unit: TemperatureUnit,
+    visible: bool,
+}
+
+impl BigTemperature {
+    pub fn new(unit: TemperatureUnit, visible: bool) -> Self {
+        Self { unit, visible }
+    }
+
+    pub fn set_unit(&mut self, unit: TemperatureUnit) {
+        self.unit = unit;
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer, celsius: Option<f64>) -> io::Result<()> {
+        let Some(celsius) = celsius.filter(|_| self.visible) else {
+            return Ok(());
+        };
+
+        let (value, unit) = format_temperature(celsius, self.unit);
+        // Avoid showing "-0"
+        let rounded = value.round() + 0.0;
+        let lines = big_text(&format!("{:.0}{}", rounded, unit));
+
+        let (width, height) = renderer.get_size();
+        let text_width = lines[0].chars().count() as u16;
+        if text_width + MARGIN > width || TOP + GLYPH_HEIGHT as u16 > height {
+            return Ok(());
+        }
+
+        let x = width - text_width - MARGIN;
+        let color = temperature_color(celsius);
+        for (row, line) in lines.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch != ' ' {
+                    renderer.render_char(x + col as u16, TOP + row as u16, ch, color)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_text_layout() {
+        let lines = big_text("-3°C");
+        assert_eq!(lines.len(), GLYPH_HEIGHT);
+        assert!(lines.iter().all(|l| l.chars().count() == 4 * 3 + 3));
+        assert_eq!(lines[2], "███  ██     █  ");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::types::WindSpeedUnit;
+use crate::weather::{compass_direction, format_wind_speed};
+use crossterm::style::Color;
+use std::io;
+
+// Clockwise from north
+const ARROWS: [char; 8] = ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'];
+const ROSE: [&str; 3] = ["  N  ", "W   E", "  S  "];
+const MARGIN: u16 = 2;
+
+// Wind direction is where the wind comes from; the arrow points where it blows
+fn arrow(wind_direction: f64) -> char {
+    let toward = (wind_direction + 180.0).rem_euclid(360.0);
+    ARROWS[((toward + 22.5) / 45.0) as usize % 8]
+}
+
+// A small compass rose in the bottom-left corner, across the screen from the status line
+pub struct WindCompass {
+    unit: WindSpeedUnit,
+    visible: bool,
+}
+
+impl WindCompass {
+    pub fn new(unit: WindSpeedUnit, visible: bool) -> Self {
+        Self { unit, visible }
+    }
+
+    pub fn set_unit(&mut self, unit: WindSpeedUnit) {
+        self.unit = unit;
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        wind: Option<(f64, f64)>,
+    ) -> io::Result<()> {
+        let Some((speed, direction)) = wind.filter(|_| self.visible) else {
+            return Ok(());
+        };
+
+        let (value, unit) = format_wind_speed(speed, self.unit);
+        let label = format!("{} {:.0} {}", compass_direction(direction), value, unit);
+
+        let (width, height) = renderer.get_size();
+        let rows = ROSE.len() as u16 + 1;
+        if width < display_width(&label) as u16 + MARGIN || height < rows + 6 {
+            return Ok(());
+        }
+        // Leave the bottom row to the attribution
+        let y = height - 1 - rows;
+
+        for (row, line) in ROSE.iter().enumerate() {
+            renderer.render_line_colored(MARGIN, y + row as u16, line, Color::DarkGrey)?;
+        }
+        renderer.render_char(MARGIN + 2, y + 1, arrow(direction), Color::White)?;
+        renderer.render_line_colored(MARGIN, y + ROSE.len() as u16, &label, Color::Cyan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_points_downwind() {
+        assert_eq!(arrow(0.0), '↓');
+        assert_eq!(arrow(90.0), '←');
+        assert_eq!(arrow(225.0), '↗');
+        assert_eq!(arrow(350.0), '↓');
+    }
+}
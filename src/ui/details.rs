@@ -0,0 +1,258 @@
+use super::panel::{centered_panel, draw_panel, fits};
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::types::{WeatherData, WeatherUnits, WindSpeedUnit};
+use crate::weather::{
+    compass_direction, format_precipitation, format_pressure, format_temperature, format_wind_speed,
+};
+use chrono::{DateTime, Local, Utc};
+use crossterm::style::Color;
+use std::io;
+
+const LABEL_WIDTH: usize = 16;
+const METERS_PER_MILE: f64 = 1609.344;
+
+fn moon_phase_name(phase: f64) -> &'static str {
+    const NAMES: [&str; 8] = [
+        "New moon",
+        "Waxing crescent",
+        "First quarter",
+        "Waxing gibbous",
+        "Full moon",
+        "Waning gibbous",
+        "Last quarter",
+        "Waning crescent",
+    ];
+    NAMES[((phase.rem_euclid(1.0) * 8.0 + 0.5) as usize) % 8]
+}
+
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%H:%M").to_string()
+}
+
+// Visibility follows the wind unit, since that's what says metric or imperial
+fn format_visibility(meters: f64, unit: WindSpeedUnit) -> String {
+    match unit {
+        WindSpeedUnit::Mph => format!("{:.1} mi", meters / METERS_PER_MILE),
+        _ => format!("{:.1} km", meters / 1000.0),
+    }
+}
+
+// One (label, value) row per field; a blank label starts a new section
+fn detail_rows(weather: &WeatherData, units: WeatherUnits) -> Vec<(&'static str, String)> {
+    let (temp, temp_unit) = format_temperature(weather.temperature, units.temperature);
+    let (feels, _) = format_temperature(weather.apparent_temperature, units.temperature);
+    let (wind, wind_unit) = format_wind_speed(weather.wind_speed, units.wind_speed);
+    let (precip, precip_unit) = format_precipitation(weather.precipitation, units.precipitation);
+    let (pressure, pressure_unit) = format_pressure(weather.pressure, units.pressure);
+
+    let mut rows = vec![
+        ("Condition", weather.condition.description().to_string()),
+        ("Temperature", format!("{:.1}{}", temp, temp_unit)),
+        ("Feels like", format!("{:.1}{}", feels, temp_unit)),
+        ("Humidity", format!("{:.0}%", weather.humidity)),
+        ("Precipitation", format!("{:.1}{}", precip, precip_unit)),
+        (
+            "Wind",
+            format!(
+                "{:.1}{} from {} ({:.0}°)",
+                wind,
+                wind_unit,
+                compass_direction(weather.wind_direction),
+                weather.wind_direction
+            ),
+        ),
+        ("Cloud cover", format!("{:.0}%", weather.cloud_cover)),
+        (
+            "Pressure",
+            format!(
+                "{:.*} {}",
+                units.pressure.decimals(),
+                pressure,
+                pressure_unit
+            ),
+        ),
+    ];
+    if let Some(visibility) = weather.visibility {
+        rows.push((
+            "Visibility",
+            format_visibility(visibility, units.wind_speed),
+        ));
+    }
+    if let Some(uv) = weather.uv_index {
+        rows.push(("UV index", format!("{:.1}", uv)));
+    }
+    if let Some(sunrise) = weather.sunrise {
+        rows.push(("Sunrise", local_time(sunrise)));
+    }
+    if let Some(sunset) = weather.sunset {
+        rows.push(("Sunset", local_time(sunset)));
+    }
+    if let Some(phase) = weather.moon_phase {
+        rows.push(("Moon", moon_phase_name(phase).to_string()));
+    }
+
+    if let Some(ref air) = weather.air_quality {
+        rows.push(("", String::new()));
+        let pollutants = [
+            ("PM2.5", air.pm2_5),
+            ("PM10", air.pm10),
+            ("Ozone", air.ozone),
+        ];
+        for (label, value) in pollutants {
+            if let Some(value) = value {
+                rows.push((label, format!("{:.1} μg/m³", value)));
+            }
+        }
+        if let Some(aqi) = air.european_aqi {
+            rows.push(("European AQI", format!("{:.0}", aqi)));
+        }
+        if let Some(aqi) = air.us_aqi {
+            rows.push(("US AQI", format!("{:.0}", aqi)));
+        }
+        for (name, count, level) in air.pollen() {
+            let label = match name {
+                "Birch" => "Birch pollen",
+                "Grass" => "Grass pollen",
+                _ => "Ragweed pollen",
+            };
+            rows.push((label, format!("{:.0} grains/m³ ({})", count, level.label())));
+        }
+    }
+
+    rows.push(("", String::new()));
+    rows.push(("Updated", weather.timestamp.clone()));
+    rows
+}
+
+// Every field of the current reading in one box, for what the status line leaves out
+pub struct DetailPanel {
+    units: WeatherUnits,
+    visible: bool,
+}
+
+impl DetailPanel {
+    pub fn new(units: WeatherUnits) -> Self {
+        Self {
+            units,
+            visible: false,
+        }
+    }
+
+    pub fn set_units(&mut self, units: WeatherUnits) {
+        self.units = units;
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        weather: Option<&WeatherData>,
+    ) -> io::Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let mut lines: Vec<(String, Color)> = Vec::new();
+        match weather {
+            Some(weather) => {
+                for (label, value) in detail_rows(weather, self.units) {
+                    let line = if label.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{:<LABEL_WIDTH$}{}", label, value)
+                    };
+                    lines.push((line, Color::White));
+                }
+            }
+            None => lines.push(("No weather data yet".to_string(), Color::Grey)),
+        }
+        lines.push((String::new(), Color::White));
+        lines.push(("Press any key to close".to_string(), Color::Yellow));
+
+        let title = "Current conditions";
+        let content_width = lines
+            .iter()
+            .map(|(l, _)| display_width(l))
+            .max()
+            .unwrap_or(0);
+        let rect = centered_panel(renderer, title, content_width, lines.len());
+        if !fits(renderer, rect) {
+            return Ok(());
+        }
+
+        let (text_x, text_y) = draw_panel(renderer, rect, title, Color::Cyan)?;
+        for (idx, (line, color)) in lines.iter().enumerate() {
+            renderer.render_line_colored(text_x, text_y + idx as u16, line, *color)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::air_quality::AirQuality;
+    use crate::weather::types::WeatherCondition;
+
+    fn sample() -> WeatherData {
+        WeatherData {
+            condition: WeatherCondition::Overcast,
+            temperature: 10.0,
+            apparent_temperature: 7.5,
+            humidity: 81.0,
+            precipitation: 0.0,
+            wind_speed: 5.0,
+            wind_direction: 270.0,
+            cloud_cover: 100.0,
+            pressure: 1008.4,
+            visibility: Some(16093.44),
+            uv_index: None,
+            is_day: true,
+            moon_phase: Some(0.5),
+            timestamp: "2024-01-01T12:00".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            source: None,
+            fetched_at: None,
+            air_quality: Some(AirQuality {
+                pm2_5: Some(8.0),
+                grass_pollen: Some(60.0),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_detail_rows() {
+        let rows = detail_rows(&sample(), WeatherUnits::imperial());
+        let value = |label: &str| {
+            rows.iter()
+                .find(|(l, _)| *l == label)
+                .map(|(_, v)| v.as_str())
+        };
+
+        assert_eq!(value("Temperature"), Some("50.0°F"));
+        assert_eq!(value("Feels like"), Some("45.5°F"));
+        assert_eq!(value("Wind"), Some("11.2mph from W (270°)"));
+        assert_eq!(value("Pressure"), Some("29.78 inHg"));
+        assert_eq!(value("Visibility"), Some("10.0 mi"));
+        assert_eq!(value("Moon"), Some("Full moon"));
+        assert_eq!(value("PM2.5"), Some("8.0 μg/m³"));
+        assert_eq!(value("Grass pollen"), Some("60 grains/m³ (High)"));
+        assert_eq!(value("UV index"), None);
+        assert_eq!(value("PM10"), None);
+    }
+}
@@ -0,0 +1,119 @@
+use super::big_temp::temperature_color;
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::format_temperature;
+use crate::weather::types::TemperatureUnit;
+use crossterm::style::Color;
+use std::io;
+
+const BAR_ROWS: usize = 8;
+const MIN_CELSIUS: f64 = -20.0;
+const MAX_CELSIUS: f64 = 40.0;
+const PARTIAL: [char; 8] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+// Each gauge is a boxed bar with its label underneath
+const GAUGE_WIDTH: u16 = 5;
+const MARGIN: u16 = 2;
+
+// Cells of a bar from the bottom up, with the top cell partly filled
+pub fn bar_cells(fraction: f64, rows: usize) -> Vec<char> {
+    let eighths = (fraction.clamp(0.0, 1.0) * (rows * 8) as f64).round() as usize;
+    (0..rows)
+        .map(|row| match eighths.saturating_sub(row * 8) {
+            0 => ' ',
+            n if n >= 8 => '█',
+            n => PARTIAL[n],
+        })
+        .collect()
+}
+
+// A thermometer and a humidity bar standing on the right edge, above the attribution
+pub struct Gauges {
+    unit: TemperatureUnit,
+    visible: bool,
+}
+
+impl Gauges {
+    pub fn new(unit: TemperatureUnit, visible: bool) -> Self {
+        Self { unit, visible }
+    }
+
+    pub fn set_unit(&mut self, unit: TemperatureUnit) {
+        self.unit = unit;
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        reading: Option<(f64, f64)>,
+    ) -> io::Result<()> {
+        let Some((celsius, humidity)) = reading.filter(|_| self.visible) else {
+            return Ok(());
+        };
+
+        let (width, height) = renderer.get_size();
+        // Box, label and attribution line below the bar
+        let needed = BAR_ROWS as u16 + 5;
+        if width < GAUGE_WIDTH * 2 + MARGIN || height < needed + 8 {
+            return Ok(());
+        }
+        let top = height - needed;
+        let humidity_x = width - MARGIN - GAUGE_WIDTH;
+        let thermometer_x = humidity_x - GAUGE_WIDTH;
+
+        let (value, unit) = format_temperature(celsius, self.unit);
+        let degree = unit.trim_end_matches(['C', 'F']);
+        let fraction = (celsius - MIN_CELSIUS) / (MAX_CELSIUS - MIN_CELSIUS);
+        self.render_bar(
+            renderer,
+            thermometer_x,
+            top,
+            fraction,
+            temperature_color(celsius),
+            &format!("{:.0}{}", value.round() + 0.0, degree),
+        )?;
+        self.render_bar(
+            renderer,
+            humidity_x,
+            top,
+            humidity / 100.0,
+            Color::Blue,
+            &format!("{:.0}%", humidity),
+        )
+    }
+
+    fn render_bar(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        top: u16,
+        fraction: f64,
+        color: Color,
+        label: &str,
+    ) -> io::Result<()> {
+        let frame = Color::DarkGrey;
+        renderer.render_line_colored(x, top, "┌─┐", frame)?;
+        for (row, cell) in bar_cells(fraction, BAR_ROWS).into_iter().rev().enumerate() {
+            let y = top + 1 + row as u16;
+            renderer.render_char(x, y, '│', frame)?;
+            renderer.render_char(x + 1, y, cell, color)?;
+            renderer.render_char(x + 2, y, '│', frame)?;
+        }
+        renderer.render_line_colored(x, top + 1 + BAR_ROWS as u16, "└─┘", frame)?;
+
+        let label_x = (x + 1).saturating_sub(display_width(label) as u16 / 2);
+        renderer.render_line_colored(label_x, top + 2 + BAR_ROWS as u16, label, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_cells() {
+        assert_eq!(bar_cells(0.0, 4), vec![' '; 4]);
+        assert_eq!(bar_cells(1.0, 4), vec!['█'; 4]);
+        assert_eq!(bar_cells(0.5, 4), vec!['█', '█', ' ', ' ']);
+        assert_eq!(bar_cells(0.8, 2), vec!['█', '▅']);
+        assert_eq!(bar_cells(-1.0, 2), vec![' ', ' ']);
+    }
+}
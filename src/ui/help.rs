@@ -0,0 +1,147 @@
+use super::panel::{centered_panel, draw_panel};
+use crate::config::Config;
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::{
+    WeatherUnits, format_precipitation, format_pressure, format_temperature, format_wind_speed,
+};
+use crossterm::style::Color;
+use std::io;
+use std::time::Duration;
+
+const KEYBINDINGS: &[(&str, &str)] = &[
+    ("q", "Quit"),
+    ("r", "Refresh weather now"),
+    ("n / p", "Next / previous saved location"),
+    ("s", "Save a screenshot"),
+    ("c", "Next scene"),
+    ("t", "Toggle big temperature"),
+    ("f", "Toggle precipitation forecast"),
+    ("a", "Show weather alerts"),
+    ("d", "Show all weather details"),
+    ("u", "Switch metric / imperial units"),
+    ("Space", "Pause / resume animation"),
+    ("+ / -", "Speed up / slow down animation"),
+    ("?", "Toggle this help"),
+    ("Ctrl+C", "Exit"),
+];
+
+pub struct HelpOverlay {
+    lines: Vec<String>,
+    units_row: usize,
+    visible: bool,
+}
+
+fn units_line(units: &WeatherUnits) -> String {
+    let (_, temp_unit) = format_temperature(0.0, units.temperature);
+    let (_, wind_unit) = format_wind_speed(0.0, units.wind_speed);
+    let (_, precip_unit) = format_precipitation(0.0, units.precipitation);
+    let (_, pressure_unit) = format_pressure(0.0, units.pressure);
+    format!(
+        "  {:<8} {}, {}, {}, {}",
+        "Units", temp_unit, wind_unit, precip_unit, pressure_unit
+    )
+}
+
+impl HelpOverlay {
+    pub fn new(config: &Config, provider_name: &str, refresh_interval: Duration) -> Self {
+        let mut lines = vec!["Keybindings".to_string(), String::new()];
+        for (key, action) in KEYBINDINGS {
+            lines.push(format!("  {:<8} {}", key, action));
+        }
+
+        let location = if config.location.hide {
+            "hidden".to_string()
+        } else {
+            format!(
+                "{:.2}, {:.2}",
+                config.location.latitude, config.location.longitude
+            )
+        };
+
+        lines.push(String::new());
+        lines.push("Configuration".to_string());
+        lines.push(String::new());
+        lines.push(format!("  {:<8} {}", "Location", location));
+        let units_row = lines.len();
+        lines.push(units_line(&config.units));
+        lines.push(format!("  {:<8} {}", "Provider", provider_name));
+        lines.push(format!(
+            "  {:<8} every {}s",
+            "Refresh",
+            refresh_interval.as_secs()
+        ));
+        lines.push(String::new());
+        lines.push("Press any key to close".to_string());
+
+        Self {
+            lines,
+            units_row,
+            visible: false,
+        }
+    }
+
+    pub fn set_units(&mut self, units: &WeatherUnits) {
+        self.lines[self.units_row] = units_line(units);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        // The first line is the title, and the second the blank line under it
+        let (title, body) = (&self.lines[0], &self.lines[2..]);
+        let content_width = body.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        let rect = centered_panel(renderer, title, content_width, body.len());
+        let (text_x, text_y) = draw_panel(renderer, rect, title, Color::Cyan)?;
+        for (idx, line) in body.iter().enumerate() {
+            let color = if line.starts_with(' ') {
+                Color::White
+            } else {
+                Color::Yellow
+            };
+            renderer.render_line_colored(text_x, text_y + idx as u16, line, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_lists_keys_and_config() {
+        let config = Config::default();
+        let help = HelpOverlay::new(&config, "Open-Meteo", Duration::from_secs(300));
+
+        assert!(!help.is_visible());
+        assert!(help.lines.iter().any(|l| l.contains("Refresh weather now")));
+        assert!(help.lines.iter().any(|l| l.contains("52.52, 13.41")));
+        assert!(help.lines.iter().any(|l| l.contains("°C, km/h, mm, hPa")));
+        assert!(help.lines.iter().any(|l| l.contains("every 300s")));
+    }
+
+    #[test]
+    fn test_help_hides_location() {
+        let mut config = Config::default();
+        config.location.hide = true;
+        let help = HelpOverlay::new(&config, "Open-Meteo", Duration::from_secs(300));
+
+        assert!(help.lines.iter().any(|l| l.contains("hidden")));
+        assert!(!help.lines.iter().any(|l| l.contains("52.52")));
+    }
+}
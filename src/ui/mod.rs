@@ -0,0 +1,22 @@
+pub mod alerts;
+pub mod aqi;
+pub mod big_temp;
+pub mod compass;
+pub mod details;
+pub mod gauges;
+pub mod help;
+pub mod panel;
+pub mod precipitation;
+pub mod sparkline;
+pub mod text;
+
+pub use alerts::AlertTicker;
+pub use aqi::AirQualityChip;
+pub use big_temp::BigTemperature;
+pub use compass::WindCompass;
+pub use details::DetailPanel;
+pub use gauges::Gauges;
+pub use help::HelpOverlay;
+pub use panel::draw_panel;
+pub use precipitation::PrecipitationPanel;
+pub use sparkline::TemperatureSparkline;
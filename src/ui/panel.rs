@@ -0,0 +1,103 @@
+use crate::render::{Region, TerminalRenderer, display_width};
+use crossterm::style::Color;
+use std::io;
+
+// Columns between the border and the text
+pub const PANEL_PADDING: usize = 2;
+
+// A panel in the middle of the screen for the title and `rows` lines of text up to `width`
+// columns wide, with a row and PANEL_PADDING columns of space inside the border
+pub fn centered_panel(
+    renderer: &TerminalRenderer,
+    title: &str,
+    width: usize,
+    rows: usize,
+) -> Region {
+    let (term_width, term_height) = renderer.get_size();
+    let inner_width = width.max(display_width(title)) + PANEL_PADDING * 2;
+    let panel_width = (inner_width + 2) as u16;
+    // The title and the blank line under it, and the space and the border above and below
+    let panel_height = (rows + 6) as u16;
+    Region {
+        x: term_width.saturating_sub(panel_width) / 2,
+        y: term_height.saturating_sub(panel_height) / 2,
+        width: panel_width,
+        height: panel_height,
+    }
+}
+
+// Whether the whole of `rect` is on screen
+pub fn fits(renderer: &TerminalRenderer, rect: Region) -> bool {
+    let (width, height) = renderer.get_size();
+    rect.width <= width && rect.height <= height
+}
+
+// The border in `border`, blanks inside it and the title; returns where the first line
+// under the title goes
+pub fn draw_panel(
+    renderer: &mut TerminalRenderer,
+    rect: Region,
+    title: &str,
+    border: Color,
+) -> io::Result<(u16, u16)> {
+    let Region {
+        x,
+        y,
+        width,
+        height,
+    } = rect;
+    let inner_width = width.saturating_sub(2) as usize;
+    renderer.render_line_colored(x, y, &format!("┌{}┐", "─".repeat(inner_width)), border)?;
+    for row in 1..height.saturating_sub(1) {
+        renderer.render_line_colored(
+            x,
+            y + row,
+            &format!("│{}│", " ".repeat(inner_width)),
+            border,
+        )?;
+    }
+    renderer.render_line_colored(
+        x,
+        y + height.saturating_sub(1),
+        &format!("└{}┘", "─".repeat(inner_width)),
+        border,
+    )?;
+
+    let text_x = x + 1 + PANEL_PADDING as u16;
+    renderer.render_line_colored(text_x, y + 2, title, Color::Yellow)?;
+    Ok((text_x, y + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_panel() {
+        let mut renderer = TerminalRenderer::headless(20, 10);
+        let rect = centered_panel(&renderer, "Title", 6, 1);
+        assert_eq!(
+            rect,
+            Region {
+                x: 4,
+                y: 1,
+                width: 12,
+                height: 7,
+            }
+        );
+        assert!(fits(&renderer, rect));
+        assert!(!fits(&renderer, centered_panel(&renderer, "Title", 6, 5)));
+
+        let (x, y) = draw_panel(&mut renderer, rect, "Title", Color::Cyan).unwrap();
+        renderer
+            .render_line_colored(x, y, "body", Color::White)
+            .unwrap();
+        let screen = renderer.screenshot(false);
+        let rows: Vec<&str> = screen.lines().collect();
+        assert_eq!(rows[1].trim(), "┌──────────┐");
+        assert_eq!(rows[2].trim(), "│          │");
+        assert_eq!(rows[3].trim(), "│  Title   │");
+        assert_eq!(rows[5].trim(), "│  body    │");
+        assert_eq!(rows[7].trim(), "└──────────┘");
+    }
+}
@@ -0,0 +1,214 @@
+use super::gauges::bar_cells;
+use super::panel::{centered_panel, draw_panel, fits};
+use crate::render::{TerminalRenderer, display_width};
+use crate::weather::types::{HourlyForecast, MinutelyPrecipitation};
+use chrono::Local;
+use crossterm::style::Color;
+use std::io;
+
+const HOURS: usize = 12;
+const CHART_ROWS: usize = 6;
+const AXIS_WIDTH: usize = 5;
+// Lighter than this is a trace the nowcast doesn't count
+const WET_MM_PER_HOUR: f64 = 0.1;
+// Full-height nowcast bars
+const HEAVY_MM_PER_HOUR: f64 = 10.0;
+const MINUTES_PER_CELL: usize = 2;
+
+// Chart rows top-down with a percentage axis, then the local hour under each bar
+fn chart_lines(hourly: &[HourlyForecast]) -> Vec<String> {
+    let hours = &hourly[..hourly.len().min(HOURS)];
+    let columns: Vec<Vec<char>> = hours
+        .iter()
+        .map(|h| {
+            bar_cells(
+                h.precipitation_probability.unwrap_or(0.0) / 100.0,
+                CHART_ROWS,
+            )
+        })
+        .collect();
+
+    let mut lines = Vec::with_capacity(CHART_ROWS + 1);
+    for row in (0..CHART_ROWS).rev() {
+        let axis = match row {
+            r if r == CHART_ROWS - 1 => "100%",
+            0 => "  0%",
+            _ => "",
+        };
+        let bars: String = columns
+            .iter()
+            .map(|cells| format!("{}{} ", cells[row], cells[row]))
+            .collect();
+        lines.push(format!("{:>4} {}", axis, bars.trim_end()));
+    }
+
+    let labels: String = hours
+        .iter()
+        .map(|h| h.time.with_timezone(&Local).format("%H ").to_string())
+        .collect();
+    lines.push(format!("{:AXIS_WIDTH$}{}", "", labels.trim_end()));
+    lines
+}
+
+// What the next hour holds, e.g. "Precipitation starting in 12 min"
+fn nowcast_summary(minutely: &[MinutelyPrecipitation]) -> Option<String> {
+    let first = minutely.first()?;
+    let wet = |m: &MinutelyPrecipitation| m.intensity >= WET_MM_PER_HOUR;
+    let wet_now = wet(first);
+    let summary = match minutely.iter().find(|m| wet(m) != wet_now) {
+        Some(change) => format!(
+            "Precipitation {} in {} min",
+            if wet_now { "stopping" } else { "starting" },
+            (change.time - first.time).num_minutes()
+        ),
+        None if wet_now => "Precipitation for the next hour".to_string(),
+        None => "No precipitation in the next hour".to_string(),
+    };
+    Some(summary)
+}
+
+// The next hour's intensity, two minutes to a cell, with dots where it's dry
+fn nowcast_bars(minutely: &[MinutelyPrecipitation]) -> String {
+    minutely
+        .chunks(MINUTES_PER_CELL)
+        .map(|chunk| {
+            let peak = chunk.iter().map(|m| m.intensity).fold(0.0, f64::max);
+            match bar_cells(peak / HEAVY_MM_PER_HOUR, 1)[0] {
+                _ if peak < WET_MM_PER_HOUR => '·',
+                ' ' => '▁',
+                bar => bar,
+            }
+        })
+        .collect()
+}
+
+// Chance of precipitation over the next 12 hours, as a bar per hour, under the
+// minute-by-minute nowcast when the provider has one
+#[derive(Default)]
+pub struct PrecipitationPanel {
+    visible: bool,
+}
+
+impl PrecipitationPanel {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        hourly: &[HourlyForecast],
+        minutely: &[MinutelyPrecipitation],
+        // The provider, when it never sends an hourly forecast
+        without_hourly: Option<&str>,
+    ) -> io::Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let mut lines = Vec::new();
+        if let Some(summary) = nowcast_summary(minutely) {
+            lines.push(summary);
+            lines.push(format!("{:>4} {}", "now", nowcast_bars(minutely)));
+            lines.push(String::new());
+        }
+        if hourly.is_empty() {
+            lines.push(match without_hourly {
+                Some(provider) => format!("{} has no hourly forecast", provider),
+                None => "No hourly forecast yet".to_string(),
+            });
+        } else {
+            lines.extend(chart_lines(hourly));
+        }
+
+        let title = "Chance of precipitation";
+        let content_width = lines.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        let rect = centered_panel(renderer, title, content_width, lines.len());
+        if !fits(renderer, rect) {
+            return Ok(());
+        }
+
+        let (text_x, text_y) = draw_panel(renderer, rect, title, Color::Cyan)?;
+        for (idx, line) in lines.iter().enumerate() {
+            let line_y = text_y + idx as u16;
+            for (col, ch) in line.chars().enumerate() {
+                let color = if bar_char(ch) {
+                    Color::Blue
+                } else {
+                    Color::White
+                };
+                renderer.render_char(text_x + col as u16, line_y, ch, color)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn bar_char(ch: char) -> bool {
+    ('▁'..='█').contains(&ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_chart_lines() {
+        let now = Utc::now();
+        let hourly: Vec<HourlyForecast> = (0..20)
+            .map(|h| HourlyForecast {
+                time: now + Duration::hours(h),
+                temperature: 10.0,
+                precipitation_probability: Some(if h == 0 { 100.0 } else { 0.0 }),
+            })
+            .collect();
+        let lines = chart_lines(&hourly);
+
+        assert_eq!(lines.len(), CHART_ROWS + 1);
+        assert_eq!(lines[0], "100% ██");
+        assert_eq!(lines[CHART_ROWS - 1], "  0% ██");
+        // One two-digit label per hour, twelve hours only
+        assert_eq!(lines[CHART_ROWS].split_whitespace().count(), HOURS);
+    }
+
+    #[test]
+    fn test_nowcast() {
+        let now = Utc::now();
+        let minutely = |wet_from: i64, wet_until: i64| -> Vec<MinutelyPrecipitation> {
+            (0..60)
+                .map(|m| MinutelyPrecipitation {
+                    time: now + Duration::minutes(m),
+                    intensity: if (wet_from..wet_until).contains(&m) {
+                        12.0
+                    } else {
+                        0.0
+                    },
+                    probability: None,
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            nowcast_summary(&minutely(12, 60)).unwrap(),
+            "Precipitation starting in 12 min"
+        );
+        assert_eq!(
+            nowcast_summary(&minutely(0, 25)).unwrap(),
+            "Precipitation stopping in 25 min"
+        );
+        assert_eq!(
+            nowcast_summary(&minutely(0, 60)).unwrap(),
+            "Precipitation for the next hour"
+        );
+        assert_eq!(
+            nowcast_summary(&minutely(0, 0)).unwrap(),
+            "No precipitation in the next hour"
+        );
+        assert!(nowcast_summary(&[]).is_none());
+
+        let bars = nowcast_bars(&minutely(12, 60));
+        assert_eq!(bars.chars().count(), 60 / MINUTES_PER_CELL);
+        assert!(bars.starts_with("······█"));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::render::TerminalRenderer;
+use crate::weather::format_temperature;
+use crate::weather::types::{HourlyForecast, TemperatureUnit};
+use crossterm::style::Color;
+use std::io;
+
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const MARGIN: u16 = 2;
+
+// Scaled between the lowest and highest value; a flat series sits in the middle
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|&v| {
+            let level = if range > f64::EPSILON {
+                ((v - min) / range * (LEVELS.len() - 1) as f64).round() as usize
+            } else {
+                LEVELS.len() / 2
+            };
+            LEVELS[level]
+        })
+        .collect()
+}
+
+// The next 24 hours of temperature along the bottom row, with the low and high
+pub struct TemperatureSparkline {
+    unit: TemperatureUnit,
+    visible: bool,
+}
+
+impl TemperatureSparkline {
+    pub fn new(unit: TemperatureUnit, visible: bool) -> Self {
+        Self { unit, visible }
+    }
+
+    pub fn set_unit(&mut self, unit: TemperatureUnit) {
+        self.unit = unit;
+    }
+
+    // `max_width` keeps it clear of whatever else shares the row
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        hourly: &[HourlyForecast],
+        max_width: u16,
+    ) -> io::Result<()> {
+        if !self.visible || hourly.len() < 2 {
+            return Ok(());
+        }
+
+        let temperatures: Vec<f64> = hourly.iter().map(|h| h.temperature).collect();
+        let low = temperatures.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = temperatures
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let (low, unit) = format_temperature(low, self.unit);
+        let (high, _) = format_temperature(high, self.unit);
+
+        let prefix = format!("{}h ", hourly.len());
+        let line = sparkline(&temperatures);
+        let suffix = format!(" min {:.0}{} max {:.0}{}", low, unit, high, unit);
+
+        let width = (prefix.len() + hourly.len() + suffix.chars().count()) as u16;
+        let (_, height) = renderer.get_size();
+        if MARGIN + width > max_width || height == 0 {
+            return Ok(());
+        }
+
+        let y = height - 1;
+        renderer.render_line_colored(MARGIN, y, &prefix, Color::DarkGrey)?;
+        let x = MARGIN + prefix.len() as u16;
+        renderer.render_line_colored(x, y, &line, Color::Yellow)?;
+        renderer.render_line_colored(x + hourly.len() as u16, y, &suffix, Color::Grey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_scales_to_range() {
+        assert_eq!(sparkline(&[0.0, 7.0, 3.5, 1.0]), "▁█▅▂");
+        assert_eq!(sparkline(&[4.0, 4.0]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
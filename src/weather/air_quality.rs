@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+// Pollutants in μg/m³, pollen in grains/m³, indices unitless. Pollen is only
+// modelled for Europe and is missing elsewhere.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AirQuality {
+    pub pm2_5: Option<f64>,
+    pub pm10: Option<f64>,
+    pub ozone: Option<f64>,
+    pub european_aqi: Option<f64>,
+    pub us_aqi: Option<f64>,
+    pub birch_pollen: Option<f64>,
+    pub grass_pollen: Option<f64>,
+    pub ragweed_pollen: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AqiScale {
+    #[default]
+    European,
+    Us,
+}
+
+// 0 is the cleanest band; both scales have six
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AqiCategory {
+    pub level: u8,
+    pub label: &'static str,
+}
+
+const EUROPEAN_BANDS: [(f64, &str); 6] = [
+    (20.0, "Good"),
+    (40.0, "Fair"),
+    (60.0, "Moderate"),
+    (80.0, "Poor"),
+    (100.0, "Very poor"),
+    (f64::INFINITY, "Extremely poor"),
+];
+
+const US_BANDS: [(f64, &str); 6] = [
+    (50.0, "Good"),
+    (100.0, "Moderate"),
+    (150.0, "Unhealthy for sensitive groups"),
+    (200.0, "Unhealthy"),
+    (300.0, "Very unhealthy"),
+    (f64::INFINITY, "Hazardous"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PollenLevel {
+    None,
+    Low,
+    Moderate,
+    High,
+}
+
+impl PollenLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PollenLevel::None => "None",
+            PollenLevel::Low => "Low",
+            PollenLevel::Moderate => "Moderate",
+            PollenLevel::High => "High",
+        }
+    }
+}
+
+// Grains/m³ at which each species becomes moderate and high; rough figures from
+// European pollen information services, since each species provokes symptoms at
+// different counts
+const BIRCH_THRESHOLDS: (f64, f64) = (10.0, 100.0);
+const GRASS_THRESHOLDS: (f64, f64) = (10.0, 50.0);
+const RAGWEED_THRESHOLDS: (f64, f64) = (5.0, 20.0);
+
+fn pollen_level(count: f64, (moderate, high): (f64, f64)) -> PollenLevel {
+    if count >= high {
+        PollenLevel::High
+    } else if count >= moderate {
+        PollenLevel::Moderate
+    } else if count >= 1.0 {
+        PollenLevel::Low
+    } else {
+        PollenLevel::None
+    }
+}
+
+impl AirQuality {
+    pub fn index(&self, scale: AqiScale) -> Option<f64> {
+        match scale {
+            AqiScale::European => self.european_aqi,
+            AqiScale::Us => self.us_aqi,
+        }
+    }
+
+    // Every species with a reading, in a fixed order
+    pub fn pollen(&self) -> Vec<(&'static str, f64, PollenLevel)> {
+        [
+            ("Birch", self.birch_pollen, BIRCH_THRESHOLDS),
+            ("Grass", self.grass_pollen, GRASS_THRESHOLDS),
+            ("Ragweed", self.ragweed_pollen, RAGWEED_THRESHOLDS),
+        ]
+        .into_iter()
+        .filter_map(|(name, count, thresholds)| {
+            count.map(|c| (name, c, pollen_level(c, thresholds)))
+        })
+        .collect()
+    }
+
+    // The species that's worst right now, if any is in season
+    pub fn peak_pollen(&self) -> Option<(&'static str, PollenLevel)> {
+        self.pollen()
+            .into_iter()
+            .filter(|&(_, _, level)| level > PollenLevel::None)
+            .max_by_key(|&(_, _, level)| level)
+            .map(|(name, _, level)| (name, level))
+    }
+}
+
+pub fn category(index: f64, scale: AqiScale) -> AqiCategory {
+    let bands = match scale {
+        AqiScale::European => &EUROPEAN_BANDS,
+        AqiScale::Us => &US_BANDS,
+    };
+    let level = bands
+        .iter()
+        .position(|&(upper, _)| index <= upper)
+        .unwrap_or(bands.len() - 1);
+    AqiCategory {
+        level: level as u8,
+        label: bands[level].1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_bands() {
+        assert_eq!(category(12.0, AqiScale::European).label, "Good");
+        assert_eq!(category(40.0, AqiScale::European).label, "Fair");
+        assert_eq!(category(140.0, AqiScale::European).level, 5);
+
+        assert_eq!(category(50.0, AqiScale::Us).label, "Good");
+        assert_eq!(category(51.0, AqiScale::Us).label, "Moderate");
+        assert_eq!(category(420.0, AqiScale::Us).label, "Hazardous");
+    }
+
+    #[test]
+    fn test_peak_pollen() {
+        let mut air = AirQuality::default();
+        assert!(air.pollen().is_empty());
+        assert_eq!(air.peak_pollen(), None);
+
+        air.birch_pollen = Some(0.2);
+        assert_eq!(air.peak_pollen(), None);
+
+        air.grass_pollen = Some(12.0);
+        air.ragweed_pollen = Some(25.0);
+        assert_eq!(air.pollen().len(), 3);
+        assert_eq!(air.pollen()[1].2, PollenLevel::Moderate);
+        assert_eq!(air.peak_pollen(), Some(("Ragweed", PollenLevel::High)));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::types::WeatherLocation;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const NWS_ALERTS_URL: &str = "https://api.weather.gov/alerts/active";
+// api.weather.gov rejects requests without an identifying User-Agent
+pub(crate) const USER_AGENT: &str = concat!(
+    "weathr/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/veirt/weathr)"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Unknown,
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl AlertSeverity {
+    fn from_cap(severity: &str) -> Self {
+        match severity {
+            "Extreme" => AlertSeverity::Extreme,
+            "Severe" => AlertSeverity::Severe,
+            "Moderate" => AlertSeverity::Moderate,
+            "Minor" => AlertSeverity::Minor,
+            _ => AlertSeverity::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherAlert {
+    pub event: String,
+    pub severity: AlertSeverity,
+    pub headline: Option<String>,
+    pub description: String,
+    pub instruction: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct NwsResponse {
+    #[serde(default)]
+    features: Vec<NwsFeature>,
+}
+
+#[derive(Deserialize)]
+struct NwsFeature {
+    properties: NwsProperties,
+}
+
+#[derive(Deserialize)]
+struct NwsProperties {
+    event: String,
+    #[serde(default)]
+    severity: String,
+    headline: Option<String>,
+    description: Option<String>,
+    instruction: Option<String>,
+    expires: Option<DateTime<Utc>>,
+}
+
+// Most severe first
+fn parse_alerts(response: NwsResponse) -> Vec<WeatherAlert> {
+    let mut alerts: Vec<WeatherAlert> = response
+        .features
+        .into_iter()
+        .map(|f| WeatherAlert {
+            severity: AlertSeverity::from_cap(&f.properties.severity),
+            event: f.properties.event,
+            headline: f.properties.headline,
+            description: f.properties.description.unwrap_or_default(),
+            instruction: f.properties.instruction,
+            expires: f.properties.expires,
+        })
+        .collect();
+    alerts.sort_by_key(|a| std::cmp::Reverse(a.severity));
+    alerts
+}
+
+// Active alerts from the US National Weather Service
+pub struct NwsAlerts {
+    client: reqwest::Client,
+}
+
+impl NwsAlerts {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self { client }
+    }
+
+    // A rough box around the US and its territories; the API answers with an error
+    // for points it doesn't forecast
+    pub fn covers(location: &WeatherLocation) -> bool {
+        (17.0..=72.0).contains(&location.latitude) && (-180.0..=-64.0).contains(&location.longitude)
+    }
+
+    pub async fn fetch(
+        &self,
+        location: &WeatherLocation,
+    ) -> Result<Vec<WeatherAlert>, WeatherError> {
+        let url = format!(
+            "{}?point={:.4},{:.4}",
+            NWS_ALERTS_URL, location.latitude, location.longitude
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/geo+json")
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+
+        let data: NwsResponse = response
+            .json()
+            .await
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+        Ok(parse_alerts(data))
+    }
+}
+
+impl Default for NwsAlerts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nws_alerts() {
+        let json = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "properties": {
+                    "event": "Heat Advisory", "severity": "Moderate",
+                    "headline": "Heat Advisory until 8 PM", "description": "Hot.",
+                    "instruction": null, "expires": "2024-07-01T20:00:00-05:00"
+                } },
+                { "properties": {
+                    "event": "Tornado Warning", "severity": "Extreme",
+                    "headline": null, "description": "Take cover."
+                } }
+            ]
+        }"#;
+        let alerts = parse_alerts(serde_json::from_str(json).unwrap());
+
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].event, "Tornado Warning");
+        assert_eq!(alerts[0].severity, AlertSeverity::Extreme);
+        assert_eq!(
+            alerts[1].expires.unwrap().to_rfc3339(),
+            "2024-07-02T01:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_covers_us_only() {
+        let denver = WeatherLocation {
+            latitude: 39.74,
+            longitude: -104.99,
+            elevation: None,
+        };
+        let berlin = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        assert!(NwsAlerts::covers(&denver));
+        assert!(!NwsAlerts::covers(&berlin));
+    }
+}
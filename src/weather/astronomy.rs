@@ -0,0 +1,135 @@
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
+
+const SYNODIC_MONTH_DAYS: f64 = 29.530588853;
+// New moon of 2000-01-06 18:14 UTC, as a Julian date
+const REFERENCE_NEW_MOON_JD: f64 = 2451550.1;
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+fn julian_date(time: DateTime<Utc>) -> f64 {
+    time.timestamp() as f64 / 86400.0 + UNIX_EPOCH_JD
+}
+
+// Fraction of the lunar cycle: 0.0 = new, 0.25 = first quarter, 0.5 = full, 0.75 = last quarter.
+pub fn moon_phase(time: DateTime<Utc>) -> f64 {
+    let cycles = (julian_date(time) - REFERENCE_NEW_MOON_JD) / SYNODIC_MONTH_DAYS;
+    cycles.rem_euclid(1.0)
+}
+
+pub fn current_moon_phase() -> f64 {
+    moon_phase(Utc::now())
+}
+
+// Between 6 and 18 o'clock local solar time, for sources that don't say whether it's day
+pub fn solar_daytime(time: DateTime<Utc>, longitude: f64) -> bool {
+    let hours = time.hour() as f64 + time.minute() as f64 / 60.0 + longitude / 15.0;
+    (6.0..18.0).contains(&hours.rem_euclid(24.0))
+}
+
+// How far the sun is through its daytime arc: 0.0 at sunrise, 0.5 at solar noon, 1.0 at
+// sunset. None when `time` is outside daylight hours.
+pub fn sun_progress(
+    time: DateTime<Utc>,
+    sunrise: DateTime<Utc>,
+    sunset: DateTime<Utc>,
+) -> Option<f64> {
+    let day_length = (sunset - sunrise).num_seconds();
+    if day_length <= 0 || time < sunrise || time > sunset {
+        return None;
+    }
+    Some((time - sunrise).num_seconds() as f64 / day_length as f64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Season {
+    Spring,
+    #[default]
+    Summer,
+    Autumn,
+    Winter,
+}
+
+// Meteorological seasons (whole months), flipped for the southern hemisphere
+pub fn season(date: NaiveDate, latitude: f64) -> Season {
+    let northern = match date.month() {
+        3..=5 => Season::Spring,
+        6..=8 => Season::Summer,
+        9..=11 => Season::Autumn,
+        _ => Season::Winter,
+    };
+
+    if latitude >= 0.0 {
+        northern
+    } else {
+        match northern {
+            Season::Spring => Season::Autumn,
+            Season::Summer => Season::Winter,
+            Season::Autumn => Season::Spring,
+            Season::Winter => Season::Summer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn distance_to(phase: f64, target: f64) -> f64 {
+        let diff = (phase - target).abs();
+        diff.min(1.0 - diff)
+    }
+
+    #[test]
+    fn test_moon_phase_known_new_moon() {
+        let new_moon = Utc.with_ymd_and_hms(2024, 1, 11, 11, 57, 0).unwrap();
+        assert!(distance_to(moon_phase(new_moon), 0.0) < 0.02);
+    }
+
+    #[test]
+    fn test_moon_phase_known_full_moon() {
+        let full_moon = Utc.with_ymd_and_hms(2024, 1, 25, 17, 54, 0).unwrap();
+        assert!(distance_to(moon_phase(full_moon), 0.5) < 0.02);
+    }
+
+    #[test]
+    fn test_moon_phase_range() {
+        let early = Utc.with_ymd_and_hms(1990, 3, 1, 0, 0, 0).unwrap();
+        let phase = moon_phase(early);
+        assert!((0.0..1.0).contains(&phase));
+    }
+
+    #[test]
+    fn test_solar_daytime() {
+        let noon = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        assert!(solar_daytime(noon, 0.0));
+        // Around midnight on the other side of the world
+        assert!(!solar_daytime(noon, 179.0));
+        assert!(!solar_daytime(noon, -150.0));
+    }
+
+    #[test]
+    fn test_sun_progress() {
+        let sunrise = Utc.with_ymd_and_hms(2024, 6, 21, 4, 0, 0).unwrap();
+        let sunset = Utc.with_ymd_and_hms(2024, 6, 21, 20, 0, 0).unwrap();
+        let noon = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+        let night = Utc.with_ymd_and_hms(2024, 6, 21, 22, 0, 0).unwrap();
+
+        assert_eq!(sun_progress(sunrise, sunrise, sunset), Some(0.0));
+        assert_eq!(sun_progress(noon, sunrise, sunset), Some(0.5));
+        assert_eq!(sun_progress(sunset, sunrise, sunset), Some(1.0));
+        assert_eq!(sun_progress(night, sunrise, sunset), None);
+    }
+
+    #[test]
+    fn test_season_by_hemisphere() {
+        let july = NaiveDate::from_ymd_opt(2024, 7, 15).unwrap();
+        let october = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+        let december = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        assert_eq!(season(july, 52.5), Season::Summer);
+        assert_eq!(season(july, -33.9), Season::Winter);
+        assert_eq!(season(october, 52.5), Season::Autumn);
+        assert_eq!(season(october, -33.9), Season::Spring);
+        assert_eq!(season(december, 0.0), Season::Winter);
+    }
+}
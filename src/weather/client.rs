@@ -1,17 +1,53 @@
 use crate::cache;
 use crate::error::WeatherError;
 use crate::weather::normalizer::WeatherNormalizer;
+use crate::weather::open_meteo::OpenMeteoProvider;
 use crate::weather::provider::WeatherProvider;
 use crate::weather::types::{WeatherData, WeatherLocation, WeatherUnits};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 #[derive(Clone)]
 pub struct WeatherClient {
-    provider: Arc<dyn WeatherProvider>,
-    cache: Arc<RwLock<Option<CachedWeather>>>,
+    // Tried in order until one answers
+    providers: Vec<Arc<dyn WeatherProvider>>,
+    cache: Arc<RwLock<HashMap<String, CachedWeather>>>,
     cache_duration: Duration,
+    retry_policy: RetryPolicy,
+    // Whether fetched weather is saved to and read back from ~/.cache/weathr
+    disk_cache: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Exponential backoff with "equal jitter": half the capped delay plus a random share of
+    // the other half, so clients that failed together don't retry in lockstep.
+    pub fn delay(&self, attempt: u32, rng: &mut impl rand::Rng) -> Duration {
+        use rand::RngExt;
+
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.min(16)));
+        let capped = exponential.min(self.max_delay);
+        let half = capped / 2;
+        half + half.mul_f64(rng.random_range(0.0..=1.0))
+    }
 }
 
 struct CachedWeather {
@@ -20,52 +56,123 @@ struct CachedWeather {
 }
 
 impl WeatherClient {
-    pub fn new(provider: Arc<dyn WeatherProvider>, cache_duration: Duration) -> Self {
+    // Open-Meteo if `providers` is empty
+    pub fn new(mut providers: Vec<Arc<dyn WeatherProvider>>, cache_duration: Duration) -> Self {
+        if providers.is_empty() {
+            providers.push(Arc::new(OpenMeteoProvider::new()));
+        }
         Self {
-            provider,
-            cache: Arc::new(RwLock::new(None)),
+            providers,
+            cache: Arc::new(RwLock::new(HashMap::new())),
             cache_duration,
+            retry_policy: RetryPolicy::default(),
+            disk_cache: true,
         }
     }
 
+    // Off for a client whose weather shouldn't outlive it, like one in a test
+    pub fn set_disk_cache(&mut self, enabled: bool) {
+        self.disk_cache = enabled;
+    }
+
+    // Tried after the providers already there, as the last fallback
+    pub fn add_provider(&mut self, provider: Arc<dyn WeatherProvider>) {
+        self.providers.push(provider);
+    }
+
+    // Delay before retrying after `failures` consecutive failed fetches.
+    pub fn retry_delay(&self, failures: u32) -> Duration {
+        self.retry_policy
+            .delay(failures.saturating_sub(1), &mut rand::rng())
+    }
+
     pub async fn get_current_weather(
         &self,
         location: &WeatherLocation,
         units: &WeatherUnits,
     ) -> Result<WeatherData, WeatherError> {
+        let key = cache::make_location_key(location.latitude, location.longitude);
         {
             let cache = self.cache.read().await;
-            if let Some(cached) = cache.as_ref()
+            if let Some(cached) = cache.get(&key)
                 && cached.fetched_at.elapsed() < self.cache_duration
             {
                 return Ok(cached.data.clone());
             }
         }
 
-        if let Some(cached_data) =
-            cache::load_cached_weather(location.latitude, location.longitude).await
+        if self.disk_cache
+            && let Some(cached_data) =
+                cache::load_cached_weather(location.latitude, location.longitude).await
         {
             let mut cache = self.cache.write().await;
-            *cache = Some(CachedWeather {
-                data: cached_data.clone(),
-                fetched_at: Instant::now(),
-            });
+            cache.insert(
+                key,
+                CachedWeather {
+                    data: cached_data.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
             return Ok(cached_data);
         }
 
-        let response = self.provider.get_current_weather(location, units).await?;
+        self.fetch_from_provider(location, units).await
+    }
 
-        let data = WeatherNormalizer::normalize(response);
+    // Bypasses both the in-memory and disk caches.
+    pub async fn refresh_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherData, WeatherError> {
+        let key = cache::make_location_key(location.latitude, location.longitude);
+        self.cache.write().await.remove(&key);
+        self.fetch_from_provider(location, units).await
+    }
+
+    async fn fetch_from_provider(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherData, WeatherError> {
+        let mut first_error = None;
+        let mut answer = None;
+        for provider in &self.providers {
+            match provider.get_current_weather(location, units).await {
+                Ok(response) => {
+                    answer = Some((provider.name(), response));
+                    break;
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        // When all of them fail, the selected provider's error is the one worth showing
+        let (source, response) = match (answer, first_error) {
+            (Some(answer), _) => answer,
+            (None, Some(e)) => return Err(e),
+            (None, None) => unreachable!("WeatherClient always has a provider"),
+        };
+
+        let mut data = WeatherNormalizer::normalize(response);
+        data.source = Some(source.to_string());
+        data.fetched_at = Some(chrono::Utc::now());
 
         {
             let mut cache = self.cache.write().await;
-            *cache = Some(CachedWeather {
-                data: data.clone(),
-                fetched_at: Instant::now(),
-            });
+            cache.insert(
+                cache::make_location_key(location.latitude, location.longitude),
+                CachedWeather {
+                    data: data.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
         }
 
-        cache::save_weather_cache(&data, location.latitude, location.longitude);
+        if self.disk_cache {
+            cache::save_weather_cache(&data, location.latitude, location.longitude);
+        }
 
         Ok(data)
     }
@@ -73,24 +180,179 @@ impl WeatherClient {
     #[allow(dead_code)]
     pub async fn invalidate_cache(&self) {
         let mut cache = self.cache.write().await;
-        *cache = None;
+        cache.clear();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::weather::open_meteo::OpenMeteoProvider;
+    use crate::weather::provider::WeatherProviderResponse;
     use std::time::Duration;
 
     #[tokio::test]
     async fn test_cache_invalidation() {
         let provider = Arc::new(OpenMeteoProvider::new());
-        let client = WeatherClient::new(provider, Duration::from_secs(60));
+        let client = WeatherClient::new(vec![provider], Duration::from_secs(60));
 
         client.invalidate_cache().await;
 
         let cache = client.cache.read().await;
-        assert!(cache.is_none());
+        assert!(cache.is_empty());
+    }
+
+    // Answers with a fixed temperature, or fails when there is none
+    struct FixedProvider(&'static str, Option<f64>);
+
+    #[async_trait::async_trait]
+    impl WeatherProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn attribution(&self) -> &'static str {
+            ""
+        }
+
+        async fn get_current_weather(
+            &self,
+            _location: &WeatherLocation,
+            _units: &WeatherUnits,
+        ) -> Result<WeatherProviderResponse, WeatherError> {
+            let temperature = self.1.ok_or_else(|| {
+                WeatherError::Network(crate::error::NetworkError::Unavailable {
+                    url: self.0.to_string(),
+                    reason: "down".to_string(),
+                })
+            })?;
+            Ok(WeatherProviderResponse {
+                weather_code: 0,
+                temperature,
+                apparent_temperature: temperature,
+                humidity: 50.0,
+                precipitation: 0.0,
+                wind_speed: 0.0,
+                wind_direction: 0.0,
+                cloud_cover: 0.0,
+                pressure: 1013.0,
+                visibility: None,
+                uv_index: None,
+                is_day: 1,
+                moon_phase: None,
+                timestamp: "2024-01-01T12:00".to_string(),
+                sunrise: None,
+                sunset: None,
+                hourly: Vec::new(),
+                minutely: Vec::new(),
+                air_quality: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_the_next_provider() {
+        let location = WeatherLocation {
+            latitude: -89.99,
+            longitude: 0.01,
+            elevation: None,
+        };
+        let units = WeatherUnits::default();
+        let mut client = WeatherClient::new(
+            vec![
+                Arc::new(FixedProvider("first", None)),
+                Arc::new(FixedProvider("second", Some(7.0))),
+                Arc::new(FixedProvider("third", Some(9.0))),
+            ],
+            Duration::from_secs(60),
+        );
+        client.set_disk_cache(false);
+        let weather = client.refresh_weather(&location, &units).await.unwrap();
+        assert_eq!(weather.temperature, 7.0);
+        assert_eq!(weather.source.as_deref(), Some("second"));
+        // A cached weather keeps the time it was fetched
+        let cached = client.get_current_weather(&location, &units).await.unwrap();
+        assert!(weather.fetched_at.is_some());
+        assert_eq!(cached.fetched_at, weather.fetched_at);
+
+        let mut client = WeatherClient::new(
+            vec![
+                Arc::new(FixedProvider("first", None)),
+                Arc::new(FixedProvider("second", None)),
+            ],
+            Duration::from_secs(60),
+        );
+        client.set_disk_cache(false);
+        let err = client.refresh_weather(&location, &units).await.unwrap_err();
+        assert!(err.to_string().contains("first"), "{}", err);
+    }
+
+    #[test]
+    fn test_retry_delay_grows_and_is_capped() {
+        let policy = RetryPolicy::default();
+        let mut rng = rand::rng();
+
+        for attempt in 0..10 {
+            let delay = policy.delay(attempt, &mut rng);
+            let ceiling = (policy.base_delay * 2u32.pow(attempt)).min(policy.max_delay);
+            assert!(delay >= ceiling / 2, "attempt {attempt}: {delay:?}");
+            assert!(delay <= ceiling, "attempt {attempt}: {delay:?}");
+        }
+
+        assert!(policy.delay(u32::MAX, &mut rng) <= policy.max_delay);
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_per_location() {
+        let provider = Arc::new(OpenMeteoProvider::new());
+        let client = WeatherClient::new(vec![provider], Duration::from_secs(60));
+        let berlin = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        let tokyo = WeatherLocation {
+            latitude: 35.68,
+            longitude: 139.65,
+            elevation: None,
+        };
+
+        for (location, temperature) in [(berlin, 5.0), (tokyo, 25.0)] {
+            let data = WeatherData {
+                condition: crate::weather::WeatherCondition::Clear,
+                temperature,
+                apparent_temperature: temperature,
+                humidity: 50.0,
+                precipitation: 0.0,
+                wind_speed: 0.0,
+                wind_direction: 0.0,
+                cloud_cover: 0.0,
+                pressure: 1013.0,
+                visibility: None,
+                uv_index: None,
+                is_day: true,
+                moon_phase: None,
+                timestamp: "2024-01-01T12:00".to_string(),
+                sunrise: None,
+                sunset: None,
+                hourly: Vec::new(),
+                minutely: Vec::new(),
+                source: None,
+                fetched_at: None,
+                air_quality: None,
+            };
+            client.cache.write().await.insert(
+                cache::make_location_key(location.latitude, location.longitude),
+                CachedWeather {
+                    data,
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+
+        let units = WeatherUnits::default();
+        let berlin_weather = client.get_current_weather(&berlin, &units).await.unwrap();
+        let tokyo_weather = client.get_current_weather(&tokyo, &units).await.unwrap();
+        assert_eq!(berlin_weather.temperature, 5.0);
+        assert_eq!(tokyo_weather.temperature, 25.0);
     }
 }
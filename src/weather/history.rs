@@ -0,0 +1,224 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::astronomy;
+use crate::weather::normalizer::WeatherNormalizer;
+use crate::weather::open_meteo::parse_local_time;
+use crate::weather::provider::WeatherProviderResponse;
+use crate::weather::types::{HourlyForecast, WeatherData, WeatherLocation};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const ARCHIVE_BASE_URL: &str = "https://archive-api.open-meteo.com/v1/archive";
+pub const SOURCE: &str = "Open-Meteo archive";
+
+// The archive starts with ERA5's first year
+pub fn earliest_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1940, 1, 1).expect("valid date")
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveResponse {
+    hourly: ArchiveHourly,
+    #[serde(default)]
+    daily: Option<ArchiveDaily>,
+    #[serde(default)]
+    utc_offset_seconds: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveHourly {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    relative_humidity_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    apparent_temperature: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation: Vec<Option<f64>>,
+    #[serde(default)]
+    weather_code: Vec<Option<i32>>,
+    #[serde(default)]
+    cloud_cover: Vec<Option<f64>>,
+    #[serde(default)]
+    surface_pressure: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_speed_10m: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_direction_10m: Vec<Option<f64>>,
+    #[serde(default)]
+    is_day: Vec<Option<i32>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveDaily {
+    #[serde(default)]
+    sunrise: Vec<String>,
+    #[serde(default)]
+    sunset: Vec<String>,
+}
+
+// One hour of a past day; `time` is when it starts
+#[derive(Debug, Clone)]
+pub struct HistoricalHour {
+    pub time: DateTime<Utc>,
+    pub weather: WeatherData,
+}
+
+fn value<T: Copy>(values: &[Option<T>], i: usize) -> Option<T> {
+    values.get(i).copied().flatten()
+}
+
+// Every hour of the day, each with the whole day as its hourly chart. Hours the archive
+// has no temperature for yet are left out
+fn to_hours(data: &ArchiveResponse) -> Vec<HistoricalHour> {
+    let offset = data.utc_offset_seconds;
+    let hourly = &data.hourly;
+    let sun_time = |times: Option<&Vec<String>>| {
+        times
+            .and_then(|times| times.first())
+            .and_then(|time| parse_local_time(time, offset))
+    };
+    let sunrise = sun_time(data.daily.as_ref().map(|daily| &daily.sunrise));
+    let sunset = sun_time(data.daily.as_ref().map(|daily| &daily.sunset));
+
+    let day: Vec<(usize, DateTime<Utc>, f64)> = hourly
+        .time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, time)| {
+            Some((
+                i,
+                parse_local_time(time, offset)?,
+                value(&hourly.temperature_2m, i)?,
+            ))
+        })
+        .collect();
+    let chart: Vec<HourlyForecast> = day
+        .iter()
+        .map(|&(_, time, temperature)| HourlyForecast {
+            time,
+            temperature,
+            precipitation_probability: None,
+        })
+        .collect();
+
+    day.iter()
+        .map(|&(i, time, temperature)| {
+            let is_day = match value(&hourly.is_day, i) {
+                Some(flag) => flag,
+                None => matches!((sunrise, sunset), (Some(rise), Some(set)) if rise <= time && time < set)
+                    as i32,
+            };
+            let response = WeatherProviderResponse {
+                weather_code: value(&hourly.weather_code, i).unwrap_or(0),
+                temperature,
+                apparent_temperature: value(&hourly.apparent_temperature, i)
+                    .unwrap_or(temperature),
+                humidity: value(&hourly.relative_humidity_2m, i).unwrap_or(0.0),
+                precipitation: value(&hourly.precipitation, i).unwrap_or(0.0),
+                wind_speed: value(&hourly.wind_speed_10m, i).unwrap_or(0.0),
+                wind_direction: value(&hourly.wind_direction_10m, i).unwrap_or(0.0),
+                cloud_cover: value(&hourly.cloud_cover, i).unwrap_or(0.0),
+                pressure: value(&hourly.surface_pressure, i).unwrap_or(0.0),
+                visibility: None,
+                uv_index: None,
+                is_day,
+                moon_phase: Some(astronomy::moon_phase(time)),
+                // Local time, as Open-Meteo's current weather has it
+                timestamp: hourly.time[i].clone(),
+                sunrise,
+                sunset,
+                hourly: chart.clone(),
+                minutely: Vec::new(),
+                air_quality: None,
+            };
+            let mut weather = WeatherNormalizer::normalize(response);
+            weather.source = Some(SOURCE.to_string());
+            HistoricalHour { time, weather }
+        })
+        .collect()
+}
+
+// A past day's weather, hour by hour, from Open-Meteo's historical archive. The last
+// few days aren't in it yet
+pub async fn fetch_day(
+    location: &WeatherLocation,
+    date: NaiveDate,
+) -> Result<Vec<HistoricalHour>, WeatherError> {
+    let url = format!(
+        "{}?latitude={}&longitude={}&start_date={date}&end_date={date}\
+         &hourly=temperature_2m,relative_humidity_2m,apparent_temperature,precipitation,\
+         weather_code,cloud_cover,surface_pressure,wind_speed_10m,wind_direction_10m,is_day\
+         &daily=sunrise,sunset&temperature_unit=celsius&wind_speed_unit=ms\
+         &precipitation_unit=mm&timezone=auto",
+        ARCHIVE_BASE_URL,
+        location.latitude,
+        location.longitude,
+        date = date.format("%Y-%m-%d"),
+    );
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+    let to_error = |e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30));
+    let data: ArchiveResponse = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(to_error)?
+        .json()
+        .await
+        .map_err(to_error)?;
+
+    let hours = to_hours(&data);
+    if hours.is_empty() {
+        return Err(WeatherError::Network(NetworkError::Unavailable {
+            url: url.clone(),
+            reason: format!("the archive has no weather for {} yet", date),
+        }));
+    }
+    Ok(hours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    #[test]
+    fn test_to_hours() {
+        let data: ArchiveResponse = serde_json::from_str(
+            r#"{
+                "utc_offset_seconds": 7200,
+                "hourly": {
+                    "time": ["2021-07-14T00:00", "2021-07-14T14:00", "2021-07-14T15:00"],
+                    "temperature_2m": [14.2, 18.5, null],
+                    "precipitation": [0.0, 12.4, 3.0],
+                    "weather_code": [3, 65, 63],
+                    "wind_speed_10m": [2.0, 6.5, 5.0]
+                },
+                "daily": {
+                    "sunrise": ["2021-07-14T05:21"],
+                    "sunset": ["2021-07-14T21:38"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let hours = to_hours(&data);
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].weather.condition, WeatherCondition::Overcast);
+        assert!(!hours[0].weather.is_day);
+
+        let storm = &hours[1];
+        assert_eq!(storm.time.to_rfc3339(), "2021-07-14T12:00:00+00:00");
+        assert_eq!(storm.weather.timestamp, "2021-07-14T14:00");
+        assert_eq!(storm.weather.condition, WeatherCondition::Rain);
+        assert_eq!(storm.weather.precipitation, 12.4);
+        assert!(storm.weather.is_day);
+        assert_eq!(storm.weather.hourly.len(), 2);
+        assert_eq!(storm.weather.source.as_deref(), Some(SOURCE));
+    }
+}
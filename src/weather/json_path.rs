@@ -0,0 +1,107 @@
+use serde_json::Value;
+
+// The small part of JSONPath that picks out one value: `$.current.temp_c`,
+// `$.data[0].temp`, and `$["key with spaces"]` for keys that don't fit after a dot
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+pub fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let rest = path
+        .trim()
+        .strip_prefix('$')
+        .ok_or_else(|| "a path starts with $".to_string())?;
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err("empty key after '.'".to_string());
+                }
+                segments.push(Segment::Key(key));
+            }
+            '[' => {
+                let mut inner = String::new();
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    inner.push(c);
+                }
+                let quoted = ['"', '\''].into_iter().find_map(|quote| {
+                    inner
+                        .strip_prefix(quote)
+                        .and_then(|key| key.strip_suffix(quote))
+                });
+                let segment = match quoted {
+                    Some(key) => Segment::Key(key.to_string()),
+                    None => match inner.trim().parse() {
+                        Ok(index) => Segment::Index(index),
+                        Err(_) => {
+                            return Err(format!("'[{}]' isn't an index or a quoted key", inner));
+                        }
+                    },
+                };
+                segments.push(segment);
+            }
+            c => return Err(format!("unexpected '{}'", c)),
+        }
+    }
+    Ok(segments)
+}
+
+pub fn lookup<'a>(value: &'a Value, segments: &[Segment]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Key(key) => value.get(key),
+            Segment::Index(index) => value.get(index),
+        })
+}
+
+// Numbers, numbers written as strings, and booleans as 1 or 0
+pub fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_lookup() {
+        let json: Value = serde_json::from_str(
+            r#"{ "current": { "temp_c": 4.5, "wind speed": "12" },
+                 "data": [ { "rain": 0.2 }, { "rain": 1.5 } ], "day": true }"#,
+        )
+        .unwrap();
+        let get = |path: &str| lookup(&json, &parse(path).unwrap()).and_then(number);
+
+        assert_eq!(get("$.current.temp_c"), Some(4.5));
+        assert_eq!(get("$.current[\"wind speed\"]"), Some(12.0));
+        assert_eq!(get("$['data'][1].rain"), Some(1.5));
+        assert_eq!(get("$.day"), Some(1.0));
+        assert_eq!(get("$.data[5].rain"), None);
+        assert_eq!(get("$.current"), None);
+
+        assert!(parse("current.temp_c").is_err());
+        assert!(parse("$.current..temp").is_err());
+        assert!(parse("$.data[first]").is_err());
+    }
+}
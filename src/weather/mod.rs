@@ -1,14 +1,24 @@
+pub mod air_quality;
+pub mod alerts;
+pub mod astronomy;
 pub mod client;
+pub mod history;
+pub mod json_path;
 pub mod normalizer;
 pub mod open_meteo;
+pub mod pressure;
 pub mod provider;
+pub mod providers;
 pub mod types;
 pub mod units;
 
 pub use client::WeatherClient;
 pub use open_meteo::OpenMeteoProvider;
+pub use provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
 pub use types::{
-    FogIntensity, RainIntensity, SnowIntensity, WeatherCondition, WeatherConditions, WeatherData,
-    WeatherLocation, WeatherUnits,
+    FogIntensity, HourlyForecast, RainIntensity, SnowIntensity, WeatherCondition,
+    WeatherConditions, WeatherData, WeatherLocation, WeatherUnits,
+};
+pub use units::{
+    compass_direction, format_precipitation, format_pressure, format_temperature, format_wind_speed,
 };
-pub use units::{format_precipitation, format_temperature, format_wind_speed};
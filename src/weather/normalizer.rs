@@ -18,13 +18,21 @@ impl WeatherNormalizer {
             cloud_cover: response.cloud_cover,
             pressure: response.pressure,
             visibility: response.visibility,
+            uv_index: response.uv_index,
             is_day: response.is_day == 1,
             moon_phase: response.moon_phase,
             timestamp: response.timestamp,
+            sunrise: response.sunrise,
+            sunset: response.sunset,
+            hourly: response.hourly,
+            minutely: response.minutely,
+            source: None,
+            fetched_at: None,
+            air_quality: response.air_quality,
         }
     }
 
-    fn wmo_code_to_condition(code: i32) -> WeatherCondition {
+    pub fn wmo_code_to_condition(code: i32) -> WeatherCondition {
         match code {
             0 => WeatherCondition::Clear,
             1 => WeatherCondition::PartlyCloudy,
@@ -107,9 +115,15 @@ mod tests {
             cloud_cover: 85.0,
             pressure: 1013.0,
             visibility: Some(10000.0),
+            uv_index: None,
             is_day: 1,
             moon_phase: Some(0.5),
             timestamp: "2024-01-01T12:00".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            air_quality: None,
         };
 
         let data = WeatherNormalizer::normalize(response);
@@ -1,23 +1,61 @@
 use crate::error::{NetworkError, WeatherError};
-use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::air_quality::AirQuality;
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
 use crate::weather::types::{
-    PrecipitationUnit, TemperatureUnit, WeatherLocation, WeatherUnits, WindSpeedUnit,
+    HourlyForecast, PrecipitationUnit, TemperatureUnit, WeatherLocation, WeatherUnits,
+    WindSpeedUnit,
 };
 use crate::weather::units::{normalize_precipitation, normalize_temperature, normalize_wind_speed};
 use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
 use serde::Deserialize;
 use std::time::Duration;
 
 const OPEN_METEO_BASE_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const AIR_QUALITY_BASE_URL: &str = "https://air-quality-api.open-meteo.com/v1/air-quality";
+const FORECAST_HOURS: u32 = 24;
+// Air quality is fetched alongside the forecast, so it mustn't hold the weather up for long
+const AIR_QUALITY_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub struct OpenMeteoProvider {
     client: reqwest::Client,
     base_url: String,
+    air_quality_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AirQualityResponse {
+    current: AirQuality,
 }
 
 #[derive(Debug, Deserialize)]
 struct OpenMeteoResponse {
     current: CurrentWeather,
+    #[serde(default)]
+    daily: Option<DailyWeather>,
+    #[serde(default)]
+    hourly: Option<HourlyWeather>,
+    #[serde(default)]
+    utc_offset_seconds: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyWeather {
+    #[serde(default)]
+    time: Vec<String>,
+    #[serde(default)]
+    temperature_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_probability: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyWeather {
+    #[serde(default)]
+    sunrise: Vec<String>,
+    #[serde(default)]
+    sunset: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,6 +73,42 @@ struct CurrentWeather {
     wind_direction_10m: f64,
     #[serde(default)]
     visibility: Option<f64>,
+    #[serde(default)]
+    uv_index: Option<f64>,
+}
+
+// Open-Meteo reports times in the location's timezone (`timezone=auto`) without an offset
+pub fn parse_local_time(time: &str, utc_offset_seconds: i32) -> Option<DateTime<Utc>> {
+    let local = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?;
+    let offset = FixedOffset::east_opt(utc_offset_seconds)?;
+    local
+        .and_local_timezone(offset)
+        .single()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// Hours with no time or temperature are dropped rather than guessed at
+fn hourly_forecast(
+    hourly: &HourlyWeather,
+    utc_offset_seconds: i32,
+    unit: TemperatureUnit,
+) -> Vec<HourlyForecast> {
+    hourly
+        .time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, time)| {
+            Some(HourlyForecast {
+                time: parse_local_time(time, utc_offset_seconds)?,
+                temperature: normalize_temperature((*hourly.temperature_2m.get(i)?)?, unit),
+                precipitation_probability: hourly
+                    .precipitation_probability
+                    .get(i)
+                    .copied()
+                    .flatten(),
+            })
+        })
+        .collect()
 }
 
 impl OpenMeteoProvider {
@@ -52,6 +126,7 @@ impl OpenMeteoProvider {
         Self {
             client,
             base_url: OPEN_METEO_BASE_URL.to_string(),
+            air_quality_url: AIR_QUALITY_BASE_URL.to_string(),
         }
     }
 
@@ -80,15 +155,34 @@ impl OpenMeteoProvider {
 
     fn build_url(&self, location: &WeatherLocation, units: &WeatherUnits) -> String {
         format!(
-            "{}?latitude={}&longitude={}&current=temperature_2m,relative_humidity_2m,apparent_temperature,is_day,precipitation,weather_code,cloud_cover,surface_pressure,wind_speed_10m,wind_direction_10m,visibility&temperature_unit={}&wind_speed_unit={}&precipitation_unit={}&timezone=auto",
+            "{}?latitude={}&longitude={}&current=temperature_2m,relative_humidity_2m,apparent_temperature,is_day,precipitation,weather_code,cloud_cover,surface_pressure,wind_speed_10m,wind_direction_10m,visibility,uv_index&daily=sunrise,sunset&hourly=temperature_2m,precipitation_probability&forecast_hours={}&temperature_unit={}&wind_speed_unit={}&precipitation_unit={}&timezone=auto",
             self.base_url,
             location.latitude,
             location.longitude,
+            FORECAST_HOURS,
             Self::temperature_unit_param(&units.temperature),
             Self::wind_speed_unit_param(&units.wind_speed),
             Self::precipitation_unit_param(&units.precipitation)
         )
     }
+
+    // The air quality API is a separate service; weather still shows if it's down
+    async fn fetch_air_quality(&self, location: &WeatherLocation) -> Option<AirQuality> {
+        let url = format!(
+            "{}?latitude={}&longitude={}&current=pm10,pm2_5,ozone,european_aqi,us_aqi,\
+             birch_pollen,grass_pollen,ragweed_pollen",
+            self.air_quality_url, location.latitude, location.longitude
+        );
+        let response = self
+            .client
+            .get(&url)
+            .timeout(AIR_QUALITY_TIMEOUT)
+            .send()
+            .await
+            .ok()?;
+        let data: AirQualityResponse = response.error_for_status().ok()?.json().await.ok()?;
+        Some(data.current)
+    }
 }
 
 impl Default for OpenMeteoProvider {
@@ -99,25 +193,60 @@ impl Default for OpenMeteoProvider {
 
 #[async_trait]
 impl WeatherProvider for OpenMeteoProvider {
+    fn name(&self) -> &'static str {
+        "Open-Meteo"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data by Open-Meteo.com"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            air_quality: true,
+            ..Capabilities::default()
+        }
+    }
+
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
         units: &WeatherUnits,
     ) -> Result<WeatherProviderResponse, WeatherError> {
         let url = self.build_url(location, units);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+        let (response, air_quality) = tokio::join!(
+            self.client.get(&url).send(),
+            self.fetch_air_quality(location)
+        );
+        let response =
+            response.map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
 
         let data: OpenMeteoResponse = response
             .json()
             .await
             .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
 
-        let moon_phase = Some(0.5);
+        let moon_phase = Some(astronomy::current_moon_phase());
+        let offset = data.utc_offset_seconds;
+        let (sunrise, sunset) = match &data.daily {
+            Some(daily) => (
+                daily
+                    .sunrise
+                    .first()
+                    .and_then(|t| parse_local_time(t, offset)),
+                daily
+                    .sunset
+                    .first()
+                    .and_then(|t| parse_local_time(t, offset)),
+            ),
+            None => (None, None),
+        };
+        let hourly = data
+            .hourly
+            .as_ref()
+            .map(|h| hourly_forecast(h, offset, units.temperature))
+            .unwrap_or_default();
 
         Ok(WeatherProviderResponse {
             weather_code: data.current.weather_code,
@@ -133,9 +262,15 @@ impl WeatherProvider for OpenMeteoProvider {
             cloud_cover: data.current.cloud_cover,
             pressure: data.current.surface_pressure,
             visibility: data.current.visibility,
+            uv_index: data.current.uv_index,
             is_day: data.current.is_day,
             moon_phase,
             timestamp: data.current.time,
+            sunrise,
+            sunset,
+            hourly,
+            minutely: Vec::new(),
+            air_quality,
         })
     }
 }
@@ -144,6 +279,65 @@ impl WeatherProvider for OpenMeteoProvider {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_local_time_applies_offset() {
+        let sunrise = parse_local_time("2024-06-21T04:43", 7200).unwrap();
+        assert_eq!(sunrise.to_rfc3339(), "2024-06-21T02:43:00+00:00");
+
+        assert!(parse_local_time("not a time", 0).is_none());
+    }
+
+    #[test]
+    fn test_deserialize_daily_sun_times() {
+        let json = r#"{
+            "utc_offset_seconds": -18000,
+            "current": {
+                "time": "2024-01-01T12:00", "temperature_2m": 1.0, "relative_humidity_2m": 50.0,
+                "apparent_temperature": 0.0, "is_day": 1, "precipitation": 0.0, "weather_code": 0,
+                "cloud_cover": 0.0, "surface_pressure": 1013.0, "wind_speed_10m": 5.0,
+                "wind_direction_10m": 180.0
+            },
+            "daily": { "sunrise": ["2024-01-01T07:20"], "sunset": ["2024-01-01T16:39"] }
+        }"#;
+        let data: OpenMeteoResponse = serde_json::from_str(json).unwrap();
+        let daily = data.daily.unwrap();
+        let sunset = parse_local_time(&daily.sunset[0], data.utc_offset_seconds).unwrap();
+        assert_eq!(sunset.to_rfc3339(), "2024-01-01T21:39:00+00:00");
+    }
+
+    #[test]
+    fn test_hourly_forecast_normalizes_and_skips_gaps() {
+        let hourly = HourlyWeather {
+            time: vec![
+                "2024-01-01T12:00".to_string(),
+                "2024-01-01T13:00".to_string(),
+                "2024-01-01T14:00".to_string(),
+            ],
+            temperature_2m: vec![Some(50.0), None, Some(32.0)],
+            precipitation_probability: vec![Some(20.0), Some(30.0)],
+        };
+        let forecast = hourly_forecast(&hourly, 0, TemperatureUnit::Fahrenheit);
+
+        assert_eq!(forecast.len(), 2);
+        assert!((forecast[0].temperature - 10.0).abs() < 1e-9);
+        assert_eq!(forecast[0].precipitation_probability, Some(20.0));
+        assert_eq!(forecast[1].time.to_rfc3339(), "2024-01-01T14:00:00+00:00");
+        assert_eq!(forecast[1].precipitation_probability, None);
+    }
+
+    #[test]
+    fn test_deserialize_air_quality() {
+        let json = r#"{
+            "current": { "time": "2024-01-01T12:00", "interval": 3600, "pm10": 18.2,
+                "pm2_5": 11.4, "ozone": 52.0, "european_aqi": 24.0, "us_aqi": null }
+        }"#;
+        let data: AirQualityResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(data.current.pm2_5, Some(11.4));
+        assert_eq!(data.current.european_aqi, Some(24.0));
+        assert_eq!(data.current.us_aqi, None);
+        assert_eq!(data.current.grass_pollen, None);
+    }
+
     #[test]
     fn test_unit_conversion_params() {
         assert_eq!(
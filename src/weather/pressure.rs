@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+// Weather services report the tendency over three hours
+const TREND_WINDOW_SECS: u64 = 3 * 3600;
+// Too short a span makes noise look like a trend
+const MIN_SPAN_SECS: u64 = 1800;
+const HISTORY_SECS: u64 = 6 * 3600;
+// hPa per three hours
+const TREND_THRESHOLD_HPA: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PressureReading {
+    pub timestamp: u64,
+    pub hpa: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureTrend {
+    Rising,
+    Steady,
+    Falling,
+}
+
+impl PressureTrend {
+    pub fn arrow(&self) -> char {
+        match self {
+            PressureTrend::Rising => '↑',
+            PressureTrend::Steady => '→',
+            PressureTrend::Falling => '↓',
+        }
+    }
+}
+
+// Appends the reading and forgets anything older than the history window
+pub fn record(history: &mut Vec<PressureReading>, reading: PressureReading) {
+    history.push(reading);
+    history.retain(|r| reading.timestamp.saturating_sub(r.timestamp) <= HISTORY_SECS);
+}
+
+// Compares the latest reading with the oldest one in the last three hours, scaled to a
+// three-hour change
+pub fn trend(history: &[PressureReading]) -> Option<PressureTrend> {
+    let latest = history.iter().max_by_key(|r| r.timestamp)?;
+    let oldest = history
+        .iter()
+        .filter(|r| latest.timestamp - r.timestamp <= TREND_WINDOW_SECS)
+        .min_by_key(|r| r.timestamp)?;
+
+    let span = latest.timestamp - oldest.timestamp;
+    if span < MIN_SPAN_SECS {
+        return None;
+    }
+
+    let change = (latest.hpa - oldest.hpa) * TREND_WINDOW_SECS as f64 / span as f64;
+    Some(if change >= TREND_THRESHOLD_HPA {
+        PressureTrend::Rising
+    } else if change <= -TREND_THRESHOLD_HPA {
+        PressureTrend::Falling
+    } else {
+        PressureTrend::Steady
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(minutes: u64, hpa: f64) -> PressureReading {
+        PressureReading {
+            timestamp: 1_700_000_000 + minutes * 60,
+            hpa,
+        }
+    }
+
+    #[test]
+    fn test_trend() {
+        assert_eq!(trend(&[]), None);
+        assert_eq!(trend(&[reading(0, 1010.0), reading(10, 1012.0)]), None);
+
+        let rising = [
+            reading(0, 1010.0),
+            reading(60, 1010.5),
+            reading(120, 1011.0),
+        ];
+        assert_eq!(trend(&rising), Some(PressureTrend::Rising));
+
+        let falling = [reading(0, 1012.0), reading(180, 1009.0)];
+        assert_eq!(trend(&falling), Some(PressureTrend::Falling));
+
+        let steady = [reading(0, 1012.0), reading(180, 1011.5)];
+        assert_eq!(trend(&steady), Some(PressureTrend::Steady));
+
+        // A fall older than three hours doesn't count
+        let recovered = [
+            reading(0, 1020.0),
+            reading(200, 1012.0),
+            reading(300, 1012.2),
+        ];
+        assert_eq!(trend(&recovered), Some(PressureTrend::Steady));
+    }
+
+    #[test]
+    fn test_record_forgets_old_readings() {
+        let mut history = vec![reading(0, 1010.0), reading(300, 1011.0)];
+        record(&mut history, reading(400, 1012.0));
+        assert_eq!(history, vec![reading(300, 1011.0), reading(400, 1012.0)]);
+    }
+}
@@ -1,8 +1,40 @@
+use crate::config::ProviderConfig;
 use crate::error::WeatherError;
-use crate::weather::types::{WeatherLocation, WeatherUnits};
-use async_trait::async_trait;
+use crate::weather::air_quality::AirQuality;
+use crate::weather::open_meteo::OpenMeteoProvider;
+use crate::weather::providers::{
+    CustomProvider, MetNoProvider, MetarProvider, MqttSensorProvider, NwsProvider,
+    OpenWeatherMapProvider, PirateWeatherProvider, SensorFeed, WttrProvider,
+};
+use crate::weather::types::{HourlyForecast, MinutelyPrecipitation, WeatherLocation, WeatherUnits};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
+/// Providers outside this crate implement the trait with this, so they needn't depend
+/// on async-trait themselves.
+pub use async_trait::async_trait;
+
+// Names accepted by the `provider` setting and `--provider`
+pub const PROVIDERS: &[&str] = &[
+    "open-meteo",
+    "nws",
+    "met-no",
+    "openweathermap",
+    "pirateweather",
+    "wttr.in",
+    "custom",
+    "metar",
+];
+pub const DEFAULT_PROVIDER: &str = "open-meteo";
+// Tried when the selected provider fails, unless `fallback` is set. wttr.in needs no
+// key and runs on different infrastructure from Open-Meteo
+pub const DEFAULT_FALLBACK: &[&str] = &["wttr.in"];
+
+/// A provider's reading, in the units it was asked for; the client turns it into the
+/// `WeatherData` the scene is drawn from. `weather_code` is a WMO code and `is_day` is
+/// 1 or 0, as Open-Meteo reports them. Percentages run 0-100, pressure is in hPa,
+/// visibility in meters and wind direction in degrees the wind blows from.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherProviderResponse {
     pub weather_code: i32,
@@ -15,16 +47,177 @@ pub struct WeatherProviderResponse {
     pub cloud_cover: f64,
     pub pressure: f64,
     pub visibility: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
     pub is_day: i32,
     pub moon_phase: Option<f64>,
     pub timestamp: String,
+    #[serde(default)]
+    pub sunrise: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sunset: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub hourly: Vec<HourlyForecast>,
+    #[serde(default)]
+    pub minutely: Vec<MinutelyPrecipitation>,
+    #[serde(default)]
+    pub air_quality: Option<AirQuality>,
+}
+
+/// What a provider's responses can hold beyond the current conditions, so the panels can
+/// tell "not fetched yet" from "never comes from this provider".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `hourly`: a forecast for the hours ahead.
+    pub hourly: bool,
+    /// `minutely`: a nowcast for the next hour.
+    pub minutely: bool,
+    /// `air_quality`: pollutants, the AQI and pollen.
+    pub air_quality: bool,
 }
 
+/// A source of weather for `WeatherClient`. Implement it to add a backend from another
+/// crate and pass it to `WeatherClient::new`, or to `add_provider` to have it tried after
+/// the others:
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use weathr::error::{NetworkError, WeatherError};
+/// use weathr::weather::provider::{async_trait, WeatherProvider, WeatherProviderResponse};
+/// use weathr::weather::{WeatherClient, WeatherLocation, WeatherUnits};
+///
+/// struct Station;
+///
+/// #[async_trait]
+/// impl WeatherProvider for Station {
+///     fn name(&self) -> &'static str {
+///         "Station"
+///     }
+///
+///     fn attribution(&self) -> &'static str {
+///         "Readings from the garden"
+///     }
+///
+///     async fn get_current_weather(
+///         &self,
+///         _location: &WeatherLocation,
+///         _units: &WeatherUnits,
+///     ) -> Result<WeatherProviderResponse, WeatherError> {
+///         Err(WeatherError::Network(NetworkError::Unavailable {
+///             url: "station.local".to_string(),
+///             reason: "the station is offline".to_string(),
+///         }))
+///     }
+/// }
+///
+/// let mut client = WeatherClient::new(Vec::new(), Duration::from_secs(300));
+/// client.add_provider(Arc::new(Station));
+/// ```
+///
+/// The client tries its providers in order until one answers, so a provider should fail
+/// rather than return made-up readings: with `NetworkError::from_reqwest` for a failed
+/// request, or `NetworkError::Unavailable` when the service answered without usable data.
+/// The client caches answers, so `get_current_weather` is called at most once per
+/// refresh.
 #[async_trait]
 pub trait WeatherProvider: Send + Sync {
+    /// Shown in the help overlay, and kept with the weather as its source.
+    fn name(&self) -> &'static str;
+
+    /// Credit drawn in the bottom-right corner, as the data's license asks.
+    fn attribution(&self) -> &'static str;
+
+    /// None beyond the current conditions, unless a provider says otherwise.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+
+    /// The current conditions at `location`, in `units`, plus whatever `capabilities`
+    /// promises.
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
         units: &WeatherUnits,
     ) -> Result<WeatherProviderResponse, WeatherError>;
 }
+
+// One of PROVIDERS, with its settings from `settings`
+pub fn create_provider(name: &str, settings: &ProviderConfig) -> Option<Arc<dyn WeatherProvider>> {
+    match name {
+        "open-meteo" => Some(Arc::new(OpenMeteoProvider::new())),
+        "nws" => Some(Arc::new(NwsProvider::new())),
+        "met-no" => Some(Arc::new(MetNoProvider::new())),
+        "openweathermap" => Some(Arc::new(OpenWeatherMapProvider::new(
+            settings.openweathermap.api_key.clone().unwrap_or_default(),
+        ))),
+        "pirateweather" => Some(Arc::new(PirateWeatherProvider::new(
+            settings.pirateweather.api_key.clone().unwrap_or_default(),
+        ))),
+        "wttr.in" => Some(Arc::new(WttrProvider::new())),
+        "custom" => Some(Arc::new(CustomProvider::new(&settings.custom))),
+        "metar" => Some(Arc::new(MetarProvider::new(
+            settings.metar.station.clone().unwrap_or_default(),
+        ))),
+        _ => None,
+    }
+}
+
+// The selected provider and its fallbacks, in the order they're tried, each topped up
+// with readings from [provider.mqtt] when a home weather station is set up
+pub fn create_providers(settings: &ProviderConfig) -> Vec<Arc<dyn WeatherProvider>> {
+    let providers = settings
+        .chain()
+        .into_iter()
+        .filter_map(|name| create_provider(name, settings));
+    match SensorFeed::new(&settings.mqtt) {
+        Some(feed) => providers
+            .map(|provider| {
+                Arc::new(MqttSensorProvider::new(provider, feed.clone())) as Arc<dyn WeatherProvider>
+            })
+            .collect(),
+        None => providers.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_listed_provider_is_registered() {
+        let settings = ProviderConfig::default();
+        for name in PROVIDERS {
+            assert!(
+                create_provider(name, &settings).is_some(),
+                "{} isn't registered",
+                name
+            );
+        }
+        assert!(PROVIDERS.contains(&DEFAULT_PROVIDER));
+        assert!(DEFAULT_FALLBACK.iter().all(|name| PROVIDERS.contains(name)));
+        assert!(create_provider("dark-sky", &settings).is_none());
+    }
+
+    #[test]
+    fn test_providers_follow_the_chain() {
+        let settings = ProviderConfig {
+            name: Some("met-no".to_string()),
+            fallback: Some(vec!["nws".to_string(), "open-meteo".to_string()]),
+            ..ProviderConfig::default()
+        };
+        let names: Vec<_> = create_providers(&settings)
+            .iter()
+            .map(|provider| provider.name())
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(
+            names[0],
+            create_provider("met-no", &settings).unwrap().name()
+        );
+        assert_eq!(
+            names[2],
+            create_provider("open-meteo", &settings).unwrap().name()
+        );
+    }
+}
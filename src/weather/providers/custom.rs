@@ -0,0 +1,258 @@
+use crate::config::CustomProviderConfig;
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::astronomy;
+use crate::weather::json_path::{self, Segment};
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{WeatherCondition, WeatherLocation, WeatherUnits};
+use crate::weather::units::{
+    normalize_precipitation, normalize_pressure, normalize_temperature, normalize_wind_speed,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::time::Duration;
+
+// A guess for endpoints without a condition code: precipitation first, then clouds
+fn condition_from_readings(
+    temperature: f64,
+    precipitation: f64,
+    cloud_cover: f64,
+) -> WeatherCondition {
+    if precipitation > 0.0 {
+        if temperature <= 0.0 {
+            WeatherCondition::Snow
+        } else {
+            WeatherCondition::Rain
+        }
+    } else {
+        match cloud_cover {
+            c if c >= 90.0 => WeatherCondition::Overcast,
+            c if c >= 60.0 => WeatherCondition::Cloudy,
+            c if c >= 20.0 => WeatherCondition::PartlyCloudy,
+            _ => WeatherCondition::Clear,
+        }
+    }
+}
+
+// The JSON paths from [provider.custom.fields], parsed once
+#[derive(Debug, Default)]
+struct Paths {
+    temperature: Vec<Segment>,
+    apparent_temperature: Option<Vec<Segment>>,
+    humidity: Option<Vec<Segment>>,
+    precipitation: Option<Vec<Segment>>,
+    wind_speed: Option<Vec<Segment>>,
+    wind_direction: Option<Vec<Segment>>,
+    cloud_cover: Option<Vec<Segment>>,
+    pressure: Option<Vec<Segment>>,
+    visibility: Option<Vec<Segment>>,
+    uv_index: Option<Vec<Segment>>,
+    weather_code: Option<Vec<Segment>>,
+    is_day: Option<Vec<Segment>>,
+}
+
+impl Paths {
+    // Paths that don't parse are left out; the config check reports them at startup
+    fn new(settings: &CustomProviderConfig) -> Self {
+        let parse = |path: &Option<String>| path.as_deref().and_then(|p| json_path::parse(p).ok());
+        let fields = &settings.fields;
+        Self {
+            temperature: parse(&fields.temperature).unwrap_or_default(),
+            apparent_temperature: parse(&fields.apparent_temperature),
+            humidity: parse(&fields.humidity),
+            precipitation: parse(&fields.precipitation),
+            wind_speed: parse(&fields.wind_speed),
+            wind_direction: parse(&fields.wind_direction),
+            cloud_cover: parse(&fields.cloud_cover),
+            pressure: parse(&fields.pressure),
+            visibility: parse(&fields.visibility),
+            uv_index: parse(&fields.uv_index),
+            weather_code: parse(&fields.weather_code),
+            is_day: parse(&fields.is_day),
+        }
+    }
+}
+
+fn read(data: &Value, path: &Option<Vec<Segment>>) -> Option<f64> {
+    path.as_ref()
+        .and_then(|p| json_path::lookup(data, p))
+        .and_then(json_path::number)
+}
+
+fn to_response(
+    data: &Value,
+    paths: &Paths,
+    units: &WeatherUnits,
+    location: &WeatherLocation,
+    now: DateTime<Utc>,
+) -> Option<WeatherProviderResponse> {
+    let temperature = json_path::lookup(data, &paths.temperature).and_then(json_path::number)?;
+    let temperature = normalize_temperature(temperature, units.temperature);
+    let precipitation = read(data, &paths.precipitation)
+        .map_or(0.0, |p| normalize_precipitation(p, units.precipitation));
+    let cloud_cover = read(data, &paths.cloud_cover).unwrap_or(0.0);
+    let weather_code = match read(data, &paths.weather_code) {
+        Some(code) => code as i32,
+        None => condition_from_readings(temperature, precipitation, cloud_cover).wmo_code(),
+    };
+    let is_day = match read(data, &paths.is_day) {
+        Some(flag) => flag != 0.0,
+        None => astronomy::solar_daytime(now, location.longitude),
+    };
+
+    Some(WeatherProviderResponse {
+        weather_code,
+        temperature,
+        apparent_temperature: read(data, &paths.apparent_temperature)
+            .map_or(temperature, |t| normalize_temperature(t, units.temperature)),
+        humidity: read(data, &paths.humidity).unwrap_or(0.0),
+        precipitation,
+        wind_speed: read(data, &paths.wind_speed)
+            .map_or(0.0, |w| normalize_wind_speed(w, units.wind_speed)),
+        wind_direction: read(data, &paths.wind_direction).unwrap_or(0.0),
+        cloud_cover,
+        pressure: read(data, &paths.pressure)
+            .map_or(0.0, |p| normalize_pressure(p, units.pressure)),
+        visibility: read(data, &paths.visibility),
+        uv_index: read(data, &paths.uv_index),
+        is_day: is_day as i32,
+        moon_phase: Some(astronomy::moon_phase(now)),
+        timestamp: now.to_rfc3339(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
+    })
+}
+
+// Any JSON endpoint, such as a home weather station's, read through the JSON paths in
+// [provider.custom.fields]
+pub struct CustomProvider {
+    client: reqwest::Client,
+    url: String,
+    units: WeatherUnits,
+    paths: Paths,
+}
+
+impl CustomProvider {
+    pub fn new(settings: &CustomProviderConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            url: settings.url.clone().unwrap_or_default(),
+            units: settings.units,
+            paths: Paths::new(settings),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        self.url
+            .replace("{latitude}", &location.latitude.to_string())
+            .replace("{longitude}", &location.longitude.to_string())
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for CustomProvider {
+    fn name(&self) -> &'static str {
+        "Custom"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data from a custom source"
+    }
+
+    // In the units from [provider.custom.units] whatever [units] says
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+        let to_error = |e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30));
+        let data: Value = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(to_error)?
+            .json()
+            .await
+            .map_err(to_error)?;
+
+        to_response(&data, &self.paths, &self.units, location, Utc::now()).ok_or_else(|| {
+            WeatherError::Network(NetworkError::Unavailable {
+                url: url.clone(),
+                reason: "fields.temperature isn't a number in the response".to_string(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomFields;
+    use crate::weather::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+
+    fn location() -> WeatherLocation {
+        WeatherLocation {
+            latitude: 47.37,
+            longitude: 8.54,
+            elevation: None,
+        }
+    }
+
+    #[test]
+    fn test_mapped_fields() {
+        let settings = CustomProviderConfig {
+            url: Some("http://station.local/api?lat={latitude}&lon={longitude}".to_string()),
+            units: WeatherUnits {
+                temperature: TemperatureUnit::Fahrenheit,
+                wind_speed: WindSpeedUnit::Kmh,
+                precipitation: PrecipitationUnit::Mm,
+                ..WeatherUnits::metric()
+            },
+            fields: CustomFields {
+                temperature: Some("$.outdoor.temp_f".to_string()),
+                humidity: Some("$.outdoor.humidity".to_string()),
+                wind_speed: Some("$.wind[0].speed".to_string()),
+                precipitation: Some("$.rain.last_hour".to_string()),
+                pressure: Some("$[\"barometer hpa\"]".to_string()),
+                ..CustomFields::default()
+            },
+        };
+        let provider = CustomProvider::new(&settings);
+        assert_eq!(
+            provider.build_url(&location()),
+            "http://station.local/api?lat=47.37&lon=8.54"
+        );
+
+        let data: Value = serde_json::from_str(
+            r#"{ "outdoor": { "temp_f": 50, "humidity": "81" },
+                 "wind": [ { "speed": 36 } ], "rain": { "last_hour": 1.2 },
+                 "barometer hpa": 1008.4 }"#,
+        )
+        .unwrap();
+        let now = "2024-01-01T12:00:00Z".parse().unwrap();
+        let response =
+            to_response(&data, &provider.paths, &settings.units, &location(), now).unwrap();
+
+        assert!((response.temperature - 10.0).abs() < 1e-9);
+        assert_eq!(response.apparent_temperature, response.temperature);
+        assert_eq!(response.humidity, 81.0);
+        assert!((response.wind_speed - 10.0).abs() < 1e-9);
+        assert_eq!(response.pressure, 1008.4);
+        assert_eq!(response.weather_code, WeatherCondition::Rain.wmo_code());
+        assert_eq!(response.is_day, 1);
+
+        let data: Value = serde_json::from_str(r#"{ "outdoor": {} }"#).unwrap();
+        assert!(to_response(&data, &provider.paths, &settings.units, &location(), now).is_none());
+    }
+}
@@ -0,0 +1,439 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::alerts::USER_AGENT;
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{HourlyForecast, WeatherCondition, WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const LOCATIONFORECAST_URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0/complete";
+const SUNRISE_URL: &str = "https://api.met.no/weatherapi/sunrise/3.0/sun";
+const FORECAST_HOURS: usize = 24;
+
+#[derive(Deserialize)]
+struct ForecastResponse {
+    properties: ForecastProperties,
+}
+
+#[derive(Deserialize)]
+struct ForecastProperties {
+    #[serde(default)]
+    timeseries: Vec<TimeStep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeStep {
+    time: DateTime<Utc>,
+    data: TimeStepData,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeStepData {
+    instant: InstantValues,
+    #[serde(default)]
+    next_1_hours: Option<Period>,
+    #[serde(default)]
+    next_6_hours: Option<Period>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstantValues {
+    details: InstantDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstantDetails {
+    air_temperature: f64,
+    #[serde(default)]
+    air_pressure_at_sea_level: Option<f64>,
+    #[serde(default)]
+    cloud_area_fraction: Option<f64>,
+    #[serde(default)]
+    relative_humidity: Option<f64>,
+    #[serde(default)]
+    wind_from_direction: Option<f64>,
+    #[serde(default)]
+    wind_speed: Option<f64>,
+    #[serde(default)]
+    ultraviolet_index_clear_sky: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Period {
+    #[serde(default)]
+    summary: Option<Summary>,
+    #[serde(default)]
+    details: PeriodDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct Summary {
+    symbol_code: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PeriodDetails {
+    #[serde(default)]
+    precipitation_amount: Option<f64>,
+    #[serde(default)]
+    probability_of_precipitation: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct SunResponse {
+    properties: SunProperties,
+}
+
+// Either is null during polar day and night
+#[derive(Deserialize)]
+struct SunProperties {
+    #[serde(default)]
+    sunrise: Option<SunEvent>,
+    #[serde(default)]
+    sunset: Option<SunEvent>,
+}
+
+#[derive(Deserialize)]
+struct SunEvent {
+    time: String,
+}
+
+// Symbol codes name the weather and, for some, the light: "lightrainshowers_day",
+// "heavysnowandthunder", "partlycloudy_polartwilight"
+fn condition_from_symbol(symbol: &str) -> WeatherCondition {
+    let name = symbol.split('_').next().unwrap_or(symbol);
+    if name.contains("thunder") {
+        WeatherCondition::Thunderstorm
+    } else if name.contains("sleet") {
+        WeatherCondition::FreezingRain
+    } else if name.contains("snowshowers") {
+        WeatherCondition::SnowShowers
+    } else if name.contains("snow") {
+        WeatherCondition::Snow
+    } else if name.contains("rainshowers") {
+        WeatherCondition::RainShowers
+    } else if name.contains("rain") {
+        WeatherCondition::Rain
+    } else {
+        match name {
+            "fog" => WeatherCondition::Fog,
+            "cloudy" => WeatherCondition::Cloudy,
+            "fair" | "partlycloudy" => WeatherCondition::PartlyCloudy,
+            _ => WeatherCondition::Clear,
+        }
+    }
+}
+
+// Times look like "2024-01-01T08:16+01:00", without seconds
+fn parse_sun_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(time, "%Y-%m-%dT%H:%M%:z")
+        .or_else(|_| DateTime::parse_from_rfc3339(time))
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// The symbol says so for clear and partly cloudy skies; otherwise the sun times decide
+fn is_day(
+    symbol: Option<&str>,
+    now: DateTime<Utc>,
+    sunrise: Option<DateTime<Utc>>,
+    sunset: Option<DateTime<Utc>>,
+) -> i32 {
+    match symbol
+        .and_then(|s| s.split_once('_'))
+        .map(|(_, light)| light)
+    {
+        Some("night") => 0,
+        Some(_) => 1,
+        None => match (sunrise, sunset) {
+            (Some(rise), Some(set)) => (rise..=set).contains(&now) as i32,
+            _ => 1,
+        },
+    }
+}
+
+// The step covering now: the last one that has started
+fn current_step(timeseries: &[TimeStep], now: DateTime<Utc>) -> Option<usize> {
+    if timeseries.is_empty() {
+        return None;
+    }
+    Some(
+        timeseries
+            .iter()
+            .rposition(|step| step.time <= now)
+            .unwrap_or(0),
+    )
+}
+
+fn hourly_forecast(steps: &[TimeStep]) -> Vec<HourlyForecast> {
+    steps
+        .iter()
+        .take(FORECAST_HOURS)
+        .map(|step| HourlyForecast {
+            time: step.time,
+            temperature: step.data.instant.details.air_temperature,
+            precipitation_probability: step
+                .data
+                .next_1_hours
+                .as_ref()
+                .and_then(|p| p.details.probability_of_precipitation),
+        })
+        .collect()
+}
+
+fn to_response(
+    timeseries: &[TimeStep],
+    now: DateTime<Utc>,
+    sunrise: Option<DateTime<Utc>>,
+    sunset: Option<DateTime<Utc>>,
+) -> Option<WeatherProviderResponse> {
+    let index = current_step(timeseries, now)?;
+    let step = &timeseries[index];
+    let details = &step.data.instant.details;
+    // Steps far out only have six-hour summaries
+    let period = step
+        .data
+        .next_1_hours
+        .as_ref()
+        .or(step.data.next_6_hours.as_ref());
+    let symbol = period
+        .and_then(|p| p.summary.as_ref())
+        .map(|s| s.symbol_code.as_str());
+    let condition = symbol.map_or(WeatherCondition::Clear, condition_from_symbol);
+
+    Some(WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature: details.air_temperature,
+        apparent_temperature: details.air_temperature,
+        humidity: details.relative_humidity.unwrap_or(0.0),
+        precipitation: step
+            .data
+            .next_1_hours
+            .as_ref()
+            .and_then(|p| p.details.precipitation_amount)
+            .unwrap_or(0.0),
+        wind_speed: details.wind_speed.unwrap_or(0.0),
+        wind_direction: details.wind_from_direction.unwrap_or(0.0),
+        cloud_cover: details.cloud_area_fraction.unwrap_or(0.0),
+        pressure: details.air_pressure_at_sea_level.unwrap_or(0.0),
+        visibility: None,
+        uv_index: details.ultraviolet_index_clear_sky,
+        is_day: is_day(symbol, now, sunrise, sunset),
+        moon_phase: Some(astronomy::moon_phase(now)),
+        timestamp: step.time.to_rfc3339(),
+        sunrise,
+        sunset,
+        hourly: hourly_forecast(&timeseries[index..]),
+        minutely: Vec::new(),
+        air_quality: None,
+    })
+}
+
+// MET Norway's Locationforecast, the data behind Yr. Its terms ask every client to
+// identify itself in the User-Agent, and to keep coordinates to four decimals
+pub struct MetNoProvider {
+    client: reqwest::Client,
+    forecast_url: String,
+    sunrise_url: String,
+}
+
+impl MetNoProvider {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            forecast_url: LOCATIONFORECAST_URL.to_string(),
+            sunrise_url: SUNRISE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        let mut url = format!(
+            "{}?lat={:.4}&lon={:.4}",
+            self.forecast_url, location.latitude, location.longitude
+        );
+        if let Some(elevation) = location.elevation {
+            url.push_str(&format!("&altitude={}", elevation.round() as i64));
+        }
+        url
+    }
+
+    // A separate service; the forecast still shows if it's down
+    async fn fetch_sun_times(
+        &self,
+        location: &WeatherLocation,
+        now: DateTime<Utc>,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let url = format!(
+            "{}?lat={:.4}&lon={:.4}&date={}&offset=+00:00",
+            self.sunrise_url,
+            location.latitude,
+            location.longitude,
+            now.format("%Y-%m-%d")
+        );
+        let response = async {
+            let response = self.client.get(&url).send().await.ok()?;
+            response
+                .error_for_status()
+                .ok()?
+                .json::<SunResponse>()
+                .await
+                .ok()
+        }
+        .await;
+        match response {
+            Some(sun) => (
+                sun.properties.sunrise.and_then(|e| parse_sun_time(&e.time)),
+                sun.properties.sunset.and_then(|e| parse_sun_time(&e.time)),
+            ),
+            None => (None, None),
+        }
+    }
+}
+
+impl Default for MetNoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetNoProvider {
+    fn name(&self) -> &'static str {
+        "MET Norway"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data from MET Norway"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            ..Capabilities::default()
+        }
+    }
+
+    // Always metric, converted for display later
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+        let now = Utc::now();
+        let (response, (sunrise, sunset)) = tokio::join!(
+            self.client.get(&url).send(),
+            self.fetch_sun_times(location, now)
+        );
+        let data: ForecastResponse = response
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?
+            .json()
+            .await
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+
+        to_response(&data.properties.timeseries, now, sunrise, sunset).ok_or_else(|| {
+            WeatherError::Network(NetworkError::Unavailable {
+                url,
+                reason: "the forecast was empty".to_string(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_from_symbol() {
+        let cases = [
+            ("clearsky_day", WeatherCondition::Clear),
+            ("fair_night", WeatherCondition::PartlyCloudy),
+            ("partlycloudy_polartwilight", WeatherCondition::PartlyCloudy),
+            ("cloudy", WeatherCondition::Cloudy),
+            ("fog", WeatherCondition::Fog),
+            ("lightrain", WeatherCondition::Rain),
+            ("heavyrainshowers_day", WeatherCondition::RainShowers),
+            ("sleetshowers_night", WeatherCondition::FreezingRain),
+            ("snow", WeatherCondition::Snow),
+            ("lightsnowshowers_day", WeatherCondition::SnowShowers),
+            ("rainandthunder", WeatherCondition::Thunderstorm),
+            (
+                "heavysnowshowersandthunder_day",
+                WeatherCondition::Thunderstorm,
+            ),
+        ];
+        for (symbol, expected) in cases {
+            assert_eq!(condition_from_symbol(symbol), expected, "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn test_parse_sun_time() {
+        let sunrise = parse_sun_time("2024-01-01T08:16+01:00").unwrap();
+        assert_eq!(sunrise.to_rfc3339(), "2024-01-01T07:16:00+00:00");
+        assert!(parse_sun_time("08:16").is_none());
+    }
+
+    #[test]
+    fn test_is_day_from_symbol_or_sun() {
+        let at = |h| {
+            DateTime::parse_from_rfc3339(&format!("2024-01-01T{:02}:00:00Z", h))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert_eq!(is_day(Some("clearsky_night"), at(12), None, None), 0);
+        assert_eq!(is_day(Some("fair_polartwilight"), at(0), None, None), 1);
+        assert_eq!(is_day(Some("rain"), at(20), Some(at(8)), Some(at(16))), 0);
+        assert_eq!(is_day(Some("rain"), at(12), Some(at(8)), Some(at(16))), 1);
+    }
+
+    #[test]
+    fn test_to_response_uses_step_covering_now() {
+        let json = r#"{ "properties": { "timeseries": [
+            { "time": "2024-01-01T11:00:00Z", "data": {
+                "instant": { "details": { "air_temperature": 1.0 } },
+                "next_1_hours": { "summary": { "symbol_code": "cloudy" },
+                    "details": { "precipitation_amount": 0.0 } } } },
+            { "time": "2024-01-01T12:00:00Z", "data": {
+                "instant": { "details": { "air_temperature": 2.5,
+                    "air_pressure_at_sea_level": 1008.4, "cloud_area_fraction": 96.1,
+                    "relative_humidity": 91.0, "wind_from_direction": 210.0,
+                    "wind_speed": 6.3 } },
+                "next_1_hours": { "summary": { "symbol_code": "lightsnowshowers_day" },
+                    "details": { "precipitation_amount": 0.4,
+                        "probability_of_precipitation": 70.0 } } } },
+            { "time": "2024-01-01T18:00:00Z", "data": {
+                "instant": { "details": { "air_temperature": 0.5 } },
+                "next_6_hours": { "summary": { "symbol_code": "snow" },
+                    "details": { "precipitation_amount": 2.1 } } } }
+        ] } }"#;
+        let data: ForecastResponse = serde_json::from_str(json).unwrap();
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:20:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let response = to_response(&data.properties.timeseries, now, None, None).unwrap();
+
+        assert_eq!(response.temperature, 2.5);
+        assert_eq!(
+            response.weather_code,
+            WeatherCondition::SnowShowers.wmo_code()
+        );
+        assert_eq!(response.precipitation, 0.4);
+        assert_eq!(response.wind_speed, 6.3);
+        assert_eq!(response.is_day, 1);
+        assert_eq!(response.hourly.len(), 2);
+        assert_eq!(response.hourly[0].precipitation_probability, Some(70.0));
+
+        assert!(to_response(&[], now, None, None).is_none());
+    }
+}
@@ -0,0 +1,514 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::alerts::USER_AGENT;
+use crate::weather::astronomy;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{WeatherCondition, WeatherLocation, WeatherUnits};
+use crate::weather::units::{inch_to_mm, kmh_to_ms, kn_to_ms};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Months, NaiveTime, TimeZone, Utc};
+use std::time::Duration;
+
+const METAR_BASE_URL: &str = "https://aviationweather.gov/api/data/metar";
+const METERS_PER_MILE: f64 = 1609.344;
+const HPA_PER_INCH_HG: f64 = 33.8639;
+// Reported as "9999" or "10SM", meaning that or more
+const CLEAR_VISIBILITY_METERS: f64 = 10000.0;
+
+// Weather codes, two letters each, that say what's falling or in the air
+const PHENOMENA: &[&str] = &[
+    "DZ", "RA", "SN", "SG", "IC", "PL", "GR", "GS", "UP", "BR", "FG", "FU", "VA", "DU", "SA", "HZ",
+    "PY", "PO", "SQ", "FC", "SS", "DS",
+];
+const DESCRIPTORS: &[&str] = &["MI", "PR", "BC", "DR", "BL", "SH", "TS", "FZ"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+// One present-weather group such as "-SHRA" or "+TSRAGR"
+#[derive(Debug, Clone, PartialEq)]
+struct WeatherGroup {
+    intensity: Intensity,
+    codes: Vec<String>,
+}
+
+impl WeatherGroup {
+    fn has(&self, code: &str) -> bool {
+        self.codes.iter().any(|c| c == code)
+    }
+
+    fn is_precipitation(&self) -> bool {
+        ["DZ", "RA", "SN", "SG", "IC", "PL", "GR", "GS", "UP"]
+            .iter()
+            .any(|code| self.has(code))
+    }
+}
+
+// The parts of a METAR report weathr shows
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Metar {
+    station: String,
+    // Day of the month, hour and minute in UTC
+    observed: Option<(u32, u32, u32)>,
+    wind_direction: Option<f64>,
+    wind_speed: Option<f64>,
+    visibility: Option<f64>,
+    weather: Vec<WeatherGroup>,
+    // Percent of the sky, from the most covered layer
+    cloud_cover: Option<f64>,
+    temperature: Option<f64>,
+    dew_point: Option<f64>,
+    pressure: Option<f64>,
+    // The last hour's precipitation from the "Pnnnn" remark, in millimeters
+    precipitation: Option<f64>,
+}
+
+fn digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
+}
+
+// "dddffKT", "dddffGggKT", "VRBffKT", also in MPS and KMH. Meters per second
+fn parse_wind(token: &str) -> Option<(Option<f64>, f64)> {
+    let (body, to_ms): (&str, fn(f64) -> f64) = if let Some(body) = token.strip_suffix("KT") {
+        (body, kn_to_ms)
+    } else if let Some(body) = token.strip_suffix("MPS") {
+        (body, |ms| ms)
+    } else if let Some(body) = token.strip_suffix("KMH") {
+        (body, kmh_to_ms)
+    } else {
+        return None;
+    };
+    let direction = body.get(..3)?;
+    let speed = body.get(3..)?.split('G').next()?;
+    if !digits(speed) {
+        return None;
+    }
+    let direction = match direction {
+        "VRB" => None,
+        d if digits(d) => Some(d.parse().ok()?),
+        _ => return None,
+    };
+    Some((direction, to_ms(speed.parse().ok()?)))
+}
+
+// "1/2", "3" or "M1/4" statute miles
+fn parse_miles(text: &str) -> Option<f64> {
+    let text = text.trim_start_matches(['M', 'P']);
+    match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator: f64 = denominator.parse().ok()?;
+            (denominator > 0.0).then_some(numerator.parse::<f64>().ok()? / denominator)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+// "M05" is minus five
+fn parse_celsius(text: &str) -> Option<f64> {
+    let (sign, value) = match text.strip_prefix('M') {
+        Some(value) => (-1.0, value),
+        None => (1.0, text),
+    };
+    digits(value).then(|| sign * value.parse::<f64>().unwrap_or(0.0))
+}
+
+fn parse_weather(token: &str) -> Option<WeatherGroup> {
+    let (intensity, rest) = match token.as_bytes().first()? {
+        b'-' => (Intensity::Light, &token[1..]),
+        b'+' => (Intensity::Heavy, &token[1..]),
+        _ => (Intensity::Moderate, token),
+    };
+    // Weather in the vicinity isn't at the station
+    if rest.starts_with("VC") || rest.is_empty() || rest.len() % 2 != 0 {
+        return None;
+    }
+    let codes: Vec<String> = (0..rest.len())
+        .step_by(2)
+        .map(|i| rest[i..i + 2].to_string())
+        .collect();
+    let known =
+        |code: &String| PHENOMENA.contains(&code.as_str()) || DESCRIPTORS.contains(&code.as_str());
+    codes
+        .iter()
+        .all(known)
+        .then_some(WeatherGroup { intensity, codes })
+}
+
+fn layer_cover(token: &str) -> Option<f64> {
+    let cover = match token.get(..3)? {
+        "FEW" => 25.0,
+        "SCT" => 50.0,
+        "BKN" => 75.0,
+        "OVC" => 100.0,
+        _ if token.starts_with("VV") => return Some(100.0),
+        _ => return None,
+    };
+    let height = token.get(3..6)?;
+    (digits(height) || height == "///").then_some(cover)
+}
+
+fn parse_metar(report: &str) -> Option<Metar> {
+    let mut tokens = report.split_whitespace().peekable();
+    if matches!(tokens.peek(), Some(&"METAR") | Some(&"SPECI")) {
+        tokens.next();
+    }
+    let station = tokens.next().filter(|station| is_icao_code(station))?;
+    let mut metar = Metar {
+        station: station.to_string(),
+        ..Metar::default()
+    };
+
+    let mut whole_miles = None;
+    while let Some(token) = tokens.next() {
+        if token == "RMK" {
+            break;
+        }
+        if let Some(time) = token.strip_suffix('Z')
+            && time.len() == 6
+            && digits(time)
+        {
+            let field = |range: std::ops::Range<usize>| time[range].parse().ok();
+            metar.observed = field(0..2)
+                .zip(field(2..4))
+                .zip(field(4..6))
+                .map(|((d, h), m)| (d, h, m));
+        } else if let Some((direction, speed)) = parse_wind(token) {
+            metar.wind_direction = direction;
+            metar.wind_speed = Some(speed);
+        } else if token == "CAVOK" {
+            metar.visibility = Some(CLEAR_VISIBILITY_METERS);
+            metar.cloud_cover = Some(0.0);
+        } else if token.len() == 4 && digits(token) {
+            metar.visibility = token
+                .parse::<f64>()
+                .ok()
+                .map(|m| m.min(CLEAR_VISIBILITY_METERS));
+        } else if digits(token) && tokens.peek().is_some_and(|next| next.ends_with("SM")) {
+            // "1 1/2SM"
+            whole_miles = token.parse::<f64>().ok();
+        } else if let Some(miles) = token.strip_suffix("SM").and_then(parse_miles) {
+            let miles = miles + whole_miles.take().unwrap_or(0.0);
+            metar.visibility = Some((miles * METERS_PER_MILE).min(CLEAR_VISIBILITY_METERS));
+        } else if matches!(token, "SKC" | "CLR" | "NSC" | "NCD") {
+            metar.cloud_cover = Some(0.0);
+        } else if let Some(cover) = layer_cover(token) {
+            metar.cloud_cover = Some(metar.cloud_cover.unwrap_or(0.0).max(cover));
+        } else if let Some(pressure) = token
+            .strip_prefix('A')
+            .filter(|p| p.len() == 4 && digits(p))
+        {
+            metar.pressure = pressure
+                .parse::<f64>()
+                .ok()
+                .map(|p| p / 100.0 * HPA_PER_INCH_HG);
+        } else if let Some(pressure) = token
+            .strip_prefix('Q')
+            .filter(|p| p.len() == 4 && digits(p))
+        {
+            metar.pressure = pressure.parse().ok();
+        } else if let Some((temperature, dew_point)) = token.split_once('/')
+            && let Some(temperature) = parse_celsius(temperature)
+        {
+            metar.temperature = Some(temperature);
+            metar.dew_point = parse_celsius(dew_point);
+        } else if let Some(group) = parse_weather(token) {
+            metar.weather.push(group);
+        }
+    }
+
+    // Hundredths of an inch over the last hour, in US reports
+    metar.precipitation = tokens
+        .filter_map(|token| token.strip_prefix('P'))
+        .find(|amount| amount.len() == 4 && digits(amount))
+        .and_then(|amount| amount.parse::<f64>().ok())
+        .map(|hundredths| inch_to_mm(hundredths / 100.0));
+
+    Some(metar)
+}
+
+fn condition(metar: &Metar) -> WeatherCondition {
+    for group in &metar.weather {
+        let condition = if group.has("TS") {
+            if group.has("GR") || group.has("GS") {
+                WeatherCondition::ThunderstormHail
+            } else {
+                WeatherCondition::Thunderstorm
+            }
+        } else if group.has("GR") || group.has("GS") {
+            WeatherCondition::ThunderstormHail
+        } else if group.has("PL")
+            || group.has("IC")
+            || (group.has("FZ") && group.is_precipitation())
+        {
+            WeatherCondition::FreezingRain
+        } else if group.has("SG") {
+            WeatherCondition::SnowGrains
+        } else if group.has("SN") {
+            if group.has("SH") {
+                WeatherCondition::SnowShowers
+            } else {
+                WeatherCondition::Snow
+            }
+        } else if group.has("RA") || group.has("UP") {
+            if group.has("SH") {
+                WeatherCondition::RainShowers
+            } else {
+                WeatherCondition::Rain
+            }
+        } else if group.has("DZ") {
+            WeatherCondition::Drizzle
+        } else if group.has("FG") || group.has("BR") || group.has("HZ") || group.has("FU") {
+            WeatherCondition::Fog
+        } else {
+            continue;
+        };
+        return condition;
+    }
+    match metar.cloud_cover.unwrap_or(0.0) {
+        c if c >= 100.0 => WeatherCondition::Overcast,
+        c if c >= 75.0 => WeatherCondition::Cloudy,
+        c if c >= 50.0 => WeatherCondition::PartlyCloudy,
+        _ => WeatherCondition::Clear,
+    }
+}
+
+// Reports carry no amount outside US remarks, so a typical hourly rate for how heavy
+// it's reported to be
+fn precipitation_rate(metar: &Metar) -> f64 {
+    if let Some(mm) = metar.precipitation {
+        return mm;
+    }
+    let heaviest = metar
+        .weather
+        .iter()
+        .filter(|group| group.is_precipitation())
+        .map(|group| group.intensity)
+        .max();
+    match heaviest {
+        Some(Intensity::Light) => 0.5,
+        Some(Intensity::Moderate) => 2.5,
+        Some(Intensity::Heavy) => 8.0,
+        None => 0.0,
+    }
+}
+
+// Magnus formula
+fn relative_humidity(temperature: f64, dew_point: f64) -> f64 {
+    let vapor = |t: f64| (17.625 * t / (243.04 + t)).exp();
+    (100.0 * vapor(dew_point) / vapor(temperature)).clamp(0.0, 100.0)
+}
+
+// Reports only give the day of the month; that day in this month, or the last if it's
+// still to come
+fn observation_time(observed: (u32, u32, u32), now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (day, hour, minute) = observed;
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+    let this_month = now
+        .date_naive()
+        .with_day(day)
+        .map(|d| Utc.from_utc_datetime(&d.and_time(time)));
+    match this_month {
+        Some(t) if t <= now + chrono::TimeDelta::hours(1) => Some(t),
+        _ => {
+            let last_month = now.date_naive().checked_sub_months(Months::new(1))?;
+            Some(Utc.from_utc_datetime(&last_month.with_day(day)?.and_time(time)))
+        }
+    }
+}
+
+fn to_response(
+    metar: &Metar,
+    location: &WeatherLocation,
+    now: DateTime<Utc>,
+) -> Option<WeatherProviderResponse> {
+    let temperature = metar.temperature?;
+    let observed = metar
+        .observed
+        .and_then(|o| observation_time(o, now))
+        .unwrap_or(now);
+    let condition = condition(metar);
+    let cloud_cover = metar.cloud_cover.unwrap_or(match condition {
+        WeatherCondition::Clear => 0.0,
+        _ => 100.0,
+    });
+
+    Some(WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature,
+        apparent_temperature: temperature,
+        humidity: metar
+            .dew_point
+            .map_or(0.0, |dew_point| relative_humidity(temperature, dew_point)),
+        precipitation: precipitation_rate(metar),
+        wind_speed: metar.wind_speed.unwrap_or(0.0),
+        wind_direction: metar.wind_direction.unwrap_or(0.0),
+        cloud_cover,
+        pressure: metar.pressure.unwrap_or(0.0),
+        visibility: metar.visibility,
+        uv_index: None,
+        is_day: astronomy::solar_daytime(observed, location.longitude) as i32,
+        moon_phase: Some(astronomy::moon_phase(observed)),
+        timestamp: observed.to_rfc3339(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
+    })
+}
+
+// Whether `station` looks like an ICAO airport code such as "KSFO" or "EGLL"
+pub fn is_icao_code(station: &str) -> bool {
+    station.len() == 4 && station.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+// The latest METAR from an airport's weather station, via the Aviation Weather Center.
+// The report has no forecast, so the hourly charts stay empty
+pub struct MetarProvider {
+    client: reqwest::Client,
+    base_url: String,
+    station: String,
+}
+
+impl MetarProvider {
+    pub fn new(station: String) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: METAR_BASE_URL.to_string(),
+            station: station.to_uppercase(),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetarProvider {
+    fn name(&self) -> &'static str {
+        "METAR"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "METAR data from aviationweather.gov"
+    }
+
+    // Weather at the station, wherever the location is
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = format!("{}?ids={}&format=raw", self.base_url, self.station);
+        let to_error = |e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30));
+        let text = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(to_error)?
+            .text()
+            .await
+            .map_err(to_error)?;
+
+        let unavailable = |reason: String| {
+            WeatherError::Network(NetworkError::Unavailable {
+                url: url.clone(),
+                reason,
+            })
+        };
+        let metar = text
+            .lines()
+            .find_map(parse_metar)
+            .ok_or_else(|| unavailable(format!("no recent report from {}", self.station)))?;
+        to_response(&metar, location, Utc::now())
+            .ok_or_else(|| unavailable(format!("{}'s report has no temperature", self.station)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location() -> WeatherLocation {
+        WeatherLocation {
+            latitude: 37.62,
+            longitude: -122.37,
+            elevation: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_us_report() {
+        let metar = parse_metar(
+            "KSFO 141856Z 28015G25KT 1 1/2SM -SHRA BR FEW008 BKN015 OVC030 12/09 A2992 \
+             RMK AO2 SLP132 P0012 T01220094",
+        )
+        .unwrap();
+
+        assert_eq!(metar.station, "KSFO");
+        assert_eq!(metar.observed, Some((14, 18, 56)));
+        assert_eq!(metar.wind_direction, Some(280.0));
+        assert!((metar.wind_speed.unwrap() - kn_to_ms(15.0)).abs() < 1e-9);
+        assert!((metar.visibility.unwrap() - 1.5 * METERS_PER_MILE).abs() < 1e-6);
+        assert_eq!(metar.weather.len(), 2);
+        assert_eq!(metar.weather[0].intensity, Intensity::Light);
+        assert_eq!(metar.cloud_cover, Some(100.0));
+        assert_eq!(metar.temperature, Some(12.0));
+        assert_eq!(metar.dew_point, Some(9.0));
+        assert!((metar.pressure.unwrap() - 1013.2).abs() < 0.1);
+        assert!((metar.precipitation.unwrap() - inch_to_mm(0.12)).abs() < 1e-9);
+        assert_eq!(condition(&metar), WeatherCondition::RainShowers);
+    }
+
+    #[test]
+    fn test_parse_international_report() {
+        let metar = parse_metar("METAR EGLL 140920Z VRB03KT CAVOK M02/M05 Q1021 NOSIG").unwrap();
+        assert_eq!(metar.wind_direction, None);
+        assert_eq!(metar.visibility, Some(CLEAR_VISIBILITY_METERS));
+        assert_eq!(metar.cloud_cover, Some(0.0));
+        assert_eq!(metar.temperature, Some(-2.0));
+        assert_eq!(metar.pressure, Some(1021.0));
+        assert_eq!(condition(&metar), WeatherCondition::Clear);
+
+        let metar =
+            parse_metar("EDDM 140920Z 24008KT 0800 +TSGRRA VCSH SCT010CB 18/16 Q1002").unwrap();
+        assert_eq!(metar.visibility, Some(800.0));
+        assert_eq!(metar.weather.len(), 1);
+        assert_eq!(condition(&metar), WeatherCondition::ThunderstormHail);
+        assert_eq!(precipitation_rate(&metar), 8.0);
+
+        let metar = parse_metar("CYYZ 140900Z 02010KT 3SM -FZDZ OVC004 M01/M02 A3001").unwrap();
+        assert_eq!(condition(&metar), WeatherCondition::FreezingRain);
+
+        assert!(parse_metar("No METAR found").is_none());
+    }
+
+    #[test]
+    fn test_to_response() {
+        let metar = parse_metar("KSFO 141856Z 28015KT 10SM FEW200 20/10 A3001").unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 14, 19, 5, 0).unwrap();
+        let response = to_response(&metar, &location(), now).unwrap();
+
+        assert_eq!(response.timestamp, "2024-03-14T18:56:00+00:00");
+        assert_eq!(response.visibility, Some(CLEAR_VISIBILITY_METERS));
+        assert_eq!(response.cloud_cover, 25.0);
+        assert_eq!(response.weather_code, WeatherCondition::Clear.wmo_code());
+        assert!((response.humidity - 52.5).abs() < 1.0);
+        // 10:56 solar time in San Francisco
+        assert_eq!(response.is_day, 1);
+
+        // From the end of last month
+        let now = Utc.with_ymd_and_hms(2024, 4, 1, 0, 30, 0).unwrap();
+        let metar = parse_metar("KSFO 312356Z 00000KT 10SM CLR 20/10 A3001").unwrap();
+        let response = to_response(&metar, &location(), now).unwrap();
+        assert_eq!(response.timestamp, "2024-03-31T23:56:00+00:00");
+    }
+}
@@ -0,0 +1,17 @@
+pub mod custom;
+pub mod met_no;
+pub mod metar;
+pub mod mqtt_sensors;
+pub mod nws;
+pub mod openweathermap;
+pub mod pirate_weather;
+pub mod wttr;
+
+pub use custom::CustomProvider;
+pub use met_no::MetNoProvider;
+pub use metar::MetarProvider;
+pub use mqtt_sensors::{MqttSensorProvider, SensorFeed};
+pub use nws::NwsProvider;
+pub use openweathermap::OpenWeatherMapProvider;
+pub use pirate_weather::PirateWeatherProvider;
+pub use wttr::WttrProvider;
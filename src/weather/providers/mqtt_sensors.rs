@@ -0,0 +1,334 @@
+use crate::config::{MqttSensorConfig, SensorTopics};
+use crate::error::WeatherError;
+use crate::mqtt::{self, ConnectOptions, MqttClient};
+use crate::weather::normalizer::WeatherNormalizer;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{WeatherCondition, WeatherLocation, WeatherUnits};
+use crate::weather::units::{normalize_precipitation, normalize_pressure, normalize_temperature};
+use async_trait::async_trait;
+use std::sync::{Arc, Once, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// Older readings are from a sensor that went quiet, and the provider's are used instead
+const STALE_AFTER: Duration = Duration::from_secs(30 * 60);
+const RECONNECT_DELAY: Duration = Duration::from_secs(30);
+// How long the first fetch waits for retained readings before going without
+const FIRST_READING_WAIT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Readings {
+    temperature: Option<(f64, Instant)>,
+    humidity: Option<(f64, Instant)>,
+    pressure: Option<(f64, Instant)>,
+    rain: Option<(f64, Instant)>,
+}
+
+impl Readings {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn record(&mut self, topics: &SensorTopics, topic: &str, value: f64, now: Instant) {
+        for (subscribed, reading) in [
+            (&topics.temperature, &mut self.temperature),
+            (&topics.humidity, &mut self.humidity),
+            (&topics.pressure, &mut self.pressure),
+            (&topics.rain, &mut self.rain),
+        ] {
+            if subscribed.as_deref() == Some(topic) {
+                *reading = Some((value, now));
+            }
+        }
+    }
+}
+
+// "21.5", or "21.5 °C" from stations that add the unit
+fn parse_reading(payload: &[u8]) -> Option<f64> {
+    let text = std::str::from_utf8(payload).ok()?.trim();
+    let number = text.split_whitespace().next()?;
+    number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+// Local readings in place of the provider's. Rain the provider missed turns the scene
+// rainy; a dry gauge is left alone, since drizzle often doesn't register
+fn apply(
+    response: &mut WeatherProviderResponse,
+    readings: &Readings,
+    units: &WeatherUnits,
+    now: Instant,
+) {
+    let fresh = |reading: Option<(f64, Instant)>| {
+        reading
+            .filter(|(_, at)| now.duration_since(*at) < STALE_AFTER)
+            .map(|(value, _)| value)
+    };
+
+    if let Some(temperature) = fresh(readings.temperature) {
+        let temperature = normalize_temperature(temperature, units.temperature);
+        // Keep the provider's wind chill or heat index on top
+        response.apparent_temperature += temperature - response.temperature;
+        response.temperature = temperature;
+    }
+    if let Some(humidity) = fresh(readings.humidity) {
+        response.humidity = humidity.clamp(0.0, 100.0);
+    }
+    if let Some(pressure) = fresh(readings.pressure) {
+        response.pressure = normalize_pressure(pressure, units.pressure);
+    }
+    if let Some(rain) = fresh(readings.rain) {
+        let rain = normalize_precipitation(rain, units.precipitation).max(0.0);
+        response.precipitation = rain;
+        let condition = WeatherNormalizer::wmo_code_to_condition(response.weather_code);
+        if rain > 0.0 && !condition.is_raining() && !condition.is_snowing() {
+            let falling = if response.temperature <= 0.0 {
+                WeatherCondition::Snow
+            } else {
+                WeatherCondition::Rain
+            };
+            response.weather_code = falling.wmo_code();
+        }
+    }
+}
+
+// One broker connection shared by every provider in the chain, holding the latest
+// reading from each topic
+pub struct SensorFeed {
+    options: ConnectOptions,
+    topics: SensorTopics,
+    units: WeatherUnits,
+    readings: watch::Sender<Readings>,
+    started: Once,
+}
+
+impl SensorFeed {
+    // None while [provider.mqtt] has no broker
+    pub fn new(settings: &MqttSensorConfig) -> Option<Arc<Self>> {
+        let (host, port) = mqtt::parse_broker(settings.broker.as_deref()?).ok()?;
+        Some(Arc::new(Self {
+            options: ConnectOptions {
+                host,
+                port,
+                client_id: format!("weathr-{}", std::process::id()),
+                username: settings.username.clone(),
+                password: settings.password.clone(),
+            },
+            topics: settings.topics.clone(),
+            units: settings.units,
+            readings: watch::Sender::new(Readings::default()),
+            started: Once::new(),
+        }))
+    }
+
+    // Connects on the first fetch, once there's a runtime, and gives retained messages a
+    // moment to arrive
+    async fn start(self: &Arc<Self>) {
+        let mut first = false;
+        self.started.call_once(|| {
+            tokio::spawn(listen(Arc::downgrade(self)));
+            first = true;
+        });
+        if first {
+            let mut readings = self.readings.subscribe();
+            let _ = tokio::time::timeout(
+                FIRST_READING_WAIT,
+                readings.wait_for(|readings| !readings.is_empty()),
+            )
+            .await;
+        }
+    }
+}
+
+// Stays subscribed, reconnecting after the broker goes away, until the providers are gone
+async fn listen(feed: Weak<SensorFeed>) {
+    loop {
+        let Some(options) = feed.upgrade().map(|feed| feed.options.clone()) else {
+            return;
+        };
+        // A broker that's down or refuses us leaves the provider's readings in place
+        if let Ok(client) = MqttClient::connect(&options).await
+            && !receive(client, &feed).await
+        {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+// Records messages as they come. True when the connection dropped, false once nobody
+// is reading them anymore
+async fn receive(mut client: MqttClient, feed: &Weak<SensorFeed>) -> bool {
+    let Some(topics) = feed.upgrade().map(|feed| feed.topics.clone()) else {
+        return false;
+    };
+    if client
+        .subscribe(&topics.all().collect::<Vec<_>>())
+        .await
+        .is_err()
+    {
+        return true;
+    }
+    loop {
+        let Ok(message) = client.next_message().await else {
+            return true;
+        };
+        let Some(feed) = feed.upgrade() else {
+            let _ = client.disconnect().await;
+            return false;
+        };
+        if let Some(value) = parse_reading(&message.payload) {
+            feed.readings.send_modify(|readings| {
+                readings.record(&topics, &message.topic, value, Instant::now())
+            });
+        }
+    }
+}
+
+// Another provider's weather, with the current conditions from a home weather station
+// where it has them
+pub struct MqttSensorProvider {
+    inner: Arc<dyn WeatherProvider>,
+    feed: Arc<SensorFeed>,
+}
+
+impl MqttSensorProvider {
+    pub fn new(inner: Arc<dyn WeatherProvider>, feed: Arc<SensorFeed>) -> Self {
+        Self { inner, feed }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MqttSensorProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn attribution(&self) -> &'static str {
+        self.inner.attribution()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        self.feed.start().await;
+        let mut response = self.inner.get_current_weather(location, units).await?;
+        apply(
+            &mut response,
+            &self.feed.readings.borrow(),
+            &self.feed.units,
+            Instant::now(),
+        );
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::{PrecipitationUnit, TemperatureUnit};
+
+    fn response() -> WeatherProviderResponse {
+        WeatherProviderResponse {
+            weather_code: WeatherCondition::PartlyCloudy.wmo_code(),
+            temperature: 20.0,
+            apparent_temperature: 18.0,
+            humidity: 50.0,
+            precipitation: 0.0,
+            wind_speed: 3.0,
+            wind_direction: 90.0,
+            cloud_cover: 40.0,
+            pressure: 1010.0,
+            visibility: None,
+            uv_index: None,
+            is_day: 1,
+            moon_phase: None,
+            timestamp: "2024-01-01T12:00:00Z".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            air_quality: None,
+        }
+    }
+
+    fn topics() -> SensorTopics {
+        SensorTopics {
+            temperature: Some("home/temp".to_string()),
+            humidity: Some("home/humidity".to_string()),
+            pressure: None,
+            rain: Some("home/rain".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_reading() {
+        assert_eq!(parse_reading(b"21.5"), Some(21.5));
+        assert_eq!(parse_reading(b" -3 \n"), Some(-3.0));
+        assert_eq!(parse_reading("21.5 °C".as_bytes()), Some(21.5));
+        assert_eq!(parse_reading(b"NaN"), None);
+        assert_eq!(parse_reading(b"offline"), None);
+        assert_eq!(parse_reading(b""), None);
+    }
+
+    #[test]
+    fn test_local_readings_win() {
+        let now = Instant::now();
+        let mut readings = Readings::default();
+        readings.record(&topics(), "home/temp", 68.0, now);
+        readings.record(&topics(), "home/humidity", 80.0, now);
+        readings.record(&topics(), "home/rain", 0.1, now);
+        readings.record(&topics(), "elsewhere", 1.0, now);
+
+        let units = WeatherUnits {
+            temperature: TemperatureUnit::Fahrenheit,
+            precipitation: PrecipitationUnit::Inch,
+            ..WeatherUnits::metric()
+        };
+        let mut response = response();
+        apply(&mut response, &readings, &units, now);
+
+        assert!((response.temperature - 20.0).abs() < 1e-9);
+        assert!((response.apparent_temperature - 18.0).abs() < 1e-9);
+        assert_eq!(response.humidity, 80.0);
+        assert_eq!(response.pressure, 1010.0);
+        assert!((response.precipitation - 2.54).abs() < 1e-9);
+        assert_eq!(response.weather_code, WeatherCondition::Rain.wmo_code());
+    }
+
+    #[test]
+    fn test_stale_readings_are_ignored() {
+        let then = Instant::now();
+        let mut readings = Readings::default();
+        readings.record(&topics(), "home/temp", 5.0, then);
+
+        let mut response = response();
+        apply(
+            &mut response,
+            &readings,
+            &WeatherUnits::metric(),
+            then + STALE_AFTER,
+        );
+        assert_eq!(response.temperature, 20.0);
+
+        apply(&mut response, &readings, &WeatherUnits::metric(), then);
+        assert_eq!(response.temperature, 5.0);
+        assert_eq!(response.apparent_temperature, 3.0);
+    }
+
+    #[test]
+    fn test_feed_needs_a_broker() {
+        assert!(SensorFeed::new(&MqttSensorConfig::default()).is_none());
+        let settings = MqttSensorConfig {
+            broker: Some("mqtt://broker.local".to_string()),
+            topics: topics(),
+            ..MqttSensorConfig::default()
+        };
+        assert!(SensorFeed::new(&settings).is_some());
+    }
+}
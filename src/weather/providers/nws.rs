@@ -0,0 +1,566 @@
+use crate::cache;
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::alerts::USER_AGENT;
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{HourlyForecast, WeatherCondition, WeatherLocation, WeatherUnits};
+use crate::weather::units::{fahrenheit_to_celsius, kmh_to_ms};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const NWS_POINTS_URL: &str = "https://api.weather.gov/points";
+// Nearby stations go quiet now and then; the next ones in the list are tried before giving up
+const MAX_STATIONS: usize = 3;
+// A station's recent observations, for readings its latest one left out
+const RECENT_OBSERVATIONS: usize = 6;
+const MAX_FILL_AGE: chrono::TimeDelta = chrono::TimeDelta::hours(3);
+const FORECAST_HOURS: usize = 24;
+
+#[derive(Deserialize)]
+struct PointResponse {
+    properties: PointProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointProperties {
+    observation_stations: String,
+    #[serde(default)]
+    forecast_hourly: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StationsResponse {
+    #[serde(default)]
+    features: Vec<StationFeature>,
+}
+
+#[derive(Deserialize)]
+struct StationFeature {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ObservationResponse {
+    properties: Observation,
+}
+
+// Newest first
+#[derive(Deserialize)]
+struct ObservationsResponse {
+    #[serde(default)]
+    features: Vec<ObservationResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Observation {
+    timestamp: String,
+    #[serde(default)]
+    text_description: Option<String>,
+    #[serde(default)]
+    icon: Option<String>,
+    #[serde(default)]
+    temperature: Measurement,
+    #[serde(default)]
+    wind_direction: Measurement,
+    #[serde(default)]
+    wind_speed: Measurement,
+    #[serde(default)]
+    barometric_pressure: Measurement,
+    #[serde(default)]
+    sea_level_pressure: Measurement,
+    #[serde(default)]
+    visibility: Measurement,
+    #[serde(default)]
+    relative_humidity: Measurement,
+    #[serde(default)]
+    wind_chill: Measurement,
+    #[serde(default)]
+    heat_index: Measurement,
+    #[serde(default)]
+    precipitation_last_hour: Measurement,
+    #[serde(default)]
+    cloud_layers: Vec<CloudLayer>,
+}
+
+// NWS quantities come as `{ "unitCode": "wmoUnit:degC", "value": 21.7 }`, null when the
+// station didn't report them
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Measurement {
+    #[serde(default)]
+    unit_code: String,
+    value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CloudLayer {
+    amount: String,
+}
+
+#[derive(Deserialize)]
+struct HourlyResponse {
+    properties: HourlyProperties,
+}
+
+#[derive(Deserialize)]
+struct HourlyProperties {
+    #[serde(default)]
+    periods: Vec<ForecastPeriod>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForecastPeriod {
+    start_time: DateTime<Utc>,
+    temperature: f64,
+    #[serde(default)]
+    temperature_unit: String,
+    #[serde(default)]
+    probability_of_precipitation: Measurement,
+}
+
+// The observation's plain-English summary, e.g. "Light Rain and Fog/Mist". None for
+// descriptions that don't say anything about clouds or precipitation, like "Haze"
+fn condition_from_text(text: &str) -> Option<WeatherCondition> {
+    let text = text.to_lowercase();
+    let has = |word: &str| text.contains(word);
+
+    let condition = if has("thunder") || has("t-storm") || has("tstorm") {
+        if has("hail") {
+            WeatherCondition::ThunderstormHail
+        } else {
+            WeatherCondition::Thunderstorm
+        }
+    } else if has("freezing") || has("ice pellets") || has("sleet") {
+        WeatherCondition::FreezingRain
+    } else if has("snow grains") {
+        WeatherCondition::SnowGrains
+    } else if has("snow shower") {
+        WeatherCondition::SnowShowers
+    } else if has("snow") || has("blizzard") || has("flurries") {
+        WeatherCondition::Snow
+    } else if has("drizzle") {
+        WeatherCondition::Drizzle
+    } else if has("shower") {
+        WeatherCondition::RainShowers
+    } else if has("rain") {
+        WeatherCondition::Rain
+    } else if has("fog") || has("mist") {
+        WeatherCondition::Fog
+    } else if has("overcast") {
+        WeatherCondition::Overcast
+    } else if has("mostly cloudy") || (has("cloudy") && !has("partly")) {
+        WeatherCondition::Cloudy
+    } else if has("partly") || has("few clouds") || has("mostly sunny") {
+        WeatherCondition::PartlyCloudy
+    } else if has("clear") || has("sunny") || has("fair") {
+        WeatherCondition::Clear
+    } else {
+        return None;
+    };
+    Some(condition)
+}
+
+// METAR sky cover of the most covered layer, in percent
+fn cloud_cover(layers: &[CloudLayer]) -> f64 {
+    layers
+        .iter()
+        .map(|layer| match layer.amount.as_str() {
+            "FEW" => 25.0,
+            "SCT" => 50.0,
+            "BKN" => 75.0,
+            "OVC" | "VV" => 100.0,
+            _ => 0.0,
+        })
+        .fold(0.0, f64::max)
+}
+
+fn condition_from_cover(cover: f64) -> WeatherCondition {
+    match cover {
+        c if c >= 90.0 => WeatherCondition::Overcast,
+        c if c >= 60.0 => WeatherCondition::Cloudy,
+        c if c >= 20.0 => WeatherCondition::PartlyCloudy,
+        _ => WeatherCondition::Clear,
+    }
+}
+
+// Icons live under .../icons/land/day/... or .../icons/land/night/...
+fn is_day(observation: &Observation) -> i32 {
+    match &observation.icon {
+        Some(icon) if icon.contains("/night/") => 0,
+        _ => 1,
+    }
+}
+
+fn wind_speed_ms(speed: &Measurement) -> Option<f64> {
+    let value = speed.value?;
+    Some(if speed.unit_code.ends_with("m_s-1") {
+        value
+    } else {
+        kmh_to_ms(value)
+    })
+}
+
+// Readings that often come back null, taken from a station's earlier observations when
+// its latest one left them out
+fn fill_from_recent(observations: Vec<Observation>) -> Option<Observation> {
+    let mut observations = observations.into_iter();
+    let mut latest = observations.find(|o| o.temperature.value.is_some())?;
+    let taken_at = |o: &Observation| DateTime::parse_from_rfc3339(&o.timestamp).ok();
+    let latest_time = taken_at(&latest)?;
+    for earlier in observations {
+        if taken_at(&earlier).is_none_or(|time| latest_time - time > MAX_FILL_AGE) {
+            break;
+        }
+        if latest.barometric_pressure.value.is_none() {
+            latest.barometric_pressure = earlier.barometric_pressure;
+        }
+        if latest.sea_level_pressure.value.is_none() {
+            latest.sea_level_pressure = earlier.sea_level_pressure;
+        }
+        if latest.relative_humidity.value.is_none() {
+            latest.relative_humidity = earlier.relative_humidity;
+        }
+    }
+    Some(latest)
+}
+
+// None without a temperature, pressure or humidity, rather than a reading of zero
+fn to_response(
+    observation: Observation,
+    hourly: Vec<HourlyForecast>,
+) -> Option<WeatherProviderResponse> {
+    let temperature = observation.temperature.value?;
+    let pressure = observation
+        .barometric_pressure
+        .value
+        .or(observation.sea_level_pressure.value)?;
+    let humidity = observation.relative_humidity.value?;
+    let cloud_cover = cloud_cover(&observation.cloud_layers);
+    let condition = observation
+        .text_description
+        .as_deref()
+        .and_then(condition_from_text)
+        .unwrap_or_else(|| condition_from_cover(cloud_cover));
+    let apparent_temperature = observation
+        .wind_chill
+        .value
+        .or(observation.heat_index.value)
+        .unwrap_or(temperature);
+
+    Some(WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature,
+        apparent_temperature,
+        humidity,
+        precipitation: observation.precipitation_last_hour.value.unwrap_or(0.0),
+        wind_speed: wind_speed_ms(&observation.wind_speed).unwrap_or(0.0),
+        wind_direction: observation.wind_direction.value.unwrap_or(0.0),
+        cloud_cover,
+        // Pascals to hectopascals
+        pressure: pressure / 100.0,
+        visibility: observation.visibility.value,
+        uv_index: None,
+        is_day: is_day(&observation),
+        moon_phase: Some(astronomy::current_moon_phase()),
+        timestamp: observation.timestamp,
+        sunrise: None,
+        sunset: None,
+        hourly,
+        minutely: Vec::new(),
+        air_quality: None,
+    })
+}
+
+fn hourly_forecast(periods: Vec<ForecastPeriod>) -> Vec<HourlyForecast> {
+    periods
+        .into_iter()
+        .take(FORECAST_HOURS)
+        .map(|period| HourlyForecast {
+            time: period.start_time,
+            temperature: if period.temperature_unit == "F" {
+                fahrenheit_to_celsius(period.temperature)
+            } else {
+                period.temperature
+            },
+            precipitation_probability: period.probability_of_precipitation.value,
+        })
+        .collect()
+}
+
+// Latest observations from the US National Weather Service's stations nearest the
+// location, with its gridpoint hourly forecast. Only covers the US and its territories
+pub struct NwsProvider {
+    client: reqwest::Client,
+    base_url: String,
+    // The gridpoint lookup only changes when the location does
+    point: Mutex<Option<(String, PointProperties)>>,
+}
+
+impl NwsProvider {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: NWS_POINTS_URL.to_string(),
+            point: Mutex::new(None),
+        }
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, WeatherError> {
+        let to_error = |e| WeatherError::Network(NetworkError::from_reqwest(e, url, 30));
+        self.client
+            .get(url)
+            .header("Accept", "application/geo+json")
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(to_error)?
+            .json()
+            .await
+            .map_err(to_error)
+    }
+
+    async fn point(&self, location: &WeatherLocation) -> Result<PointProperties, WeatherError> {
+        let key = cache::make_location_key(location.latitude, location.longitude);
+        if let Ok(point) = self.point.lock()
+            && let Some((cached, properties)) = point.as_ref()
+            && *cached == key
+        {
+            return Ok(properties.clone());
+        }
+
+        let url = format!(
+            "{}/{:.4},{:.4}",
+            self.base_url, location.latitude, location.longitude
+        );
+        let properties = match self.get_json::<PointResponse>(&url).await {
+            Ok(response) => response.properties,
+            Err(WeatherError::Network(NetworkError::HttpError { status: 404, .. })) => {
+                return Err(WeatherError::Network(NetworkError::Unavailable {
+                    url,
+                    reason: "the National Weather Service only covers the United States"
+                        .to_string(),
+                }));
+            }
+            Err(e) => return Err(e),
+        };
+        if let Ok(mut point) = self.point.lock() {
+            *point = Some((key, properties.clone()));
+        }
+        Ok(properties)
+    }
+
+    async fn fetch_hourly(&self, url: Option<&str>) -> Vec<HourlyForecast> {
+        let Some(url) = url else {
+            return Vec::new();
+        };
+        match self.get_json::<HourlyResponse>(url).await {
+            Ok(response) => hourly_forecast(response.properties.periods),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    // The latest observation from the nearest station with everything to_response needs
+    async fn fetch_response(
+        &self,
+        stations_url: &str,
+        hourly: Vec<HourlyForecast>,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let stations: StationsResponse = self.get_json(stations_url).await?;
+        for station in stations.features.iter().take(MAX_STATIONS) {
+            let url = format!("{}/observations?limit={}", station.id, RECENT_OBSERVATIONS);
+            let Ok(recent) = self.get_json::<ObservationsResponse>(&url).await else {
+                continue;
+            };
+            let observations = recent.features.into_iter().map(|f| f.properties).collect();
+            if let Some(response) =
+                fill_from_recent(observations).and_then(|o| to_response(o, hourly.clone()))
+            {
+                return Ok(response);
+            }
+        }
+        Err(no_report(stations_url))
+    }
+}
+
+fn no_report(url: &str) -> WeatherError {
+    WeatherError::Network(NetworkError::Unavailable {
+        url: url.to_string(),
+        reason: "no nearby station has reported recently".to_string(),
+    })
+}
+
+impl Default for NwsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for NwsProvider {
+    fn name(&self) -> &'static str {
+        "National Weather Service"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data by the National Weather Service"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            ..Capabilities::default()
+        }
+    }
+
+    // Observations are always metric and converted for display later
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let point = self.point(location).await?;
+        let hourly = self.fetch_hourly(point.forecast_hourly.as_deref()).await;
+        self.fetch_response(&point.observation_stations, hourly)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_from_text() {
+        let cases = [
+            ("Thunderstorms and Rain", WeatherCondition::Thunderstorm),
+            ("Thunderstorm Hail", WeatherCondition::ThunderstormHail),
+            ("Freezing Drizzle", WeatherCondition::FreezingRain),
+            ("Light Snow Showers", WeatherCondition::SnowShowers),
+            ("Heavy Snow and Fog", WeatherCondition::Snow),
+            ("Light Drizzle", WeatherCondition::Drizzle),
+            ("Rain Showers", WeatherCondition::RainShowers),
+            ("Light Rain and Fog/Mist", WeatherCondition::Rain),
+            ("Fog/Mist", WeatherCondition::Fog),
+            ("Overcast", WeatherCondition::Overcast),
+            ("Mostly Cloudy", WeatherCondition::Cloudy),
+            ("Partly Cloudy", WeatherCondition::PartlyCloudy),
+            ("A Few Clouds", WeatherCondition::PartlyCloudy),
+            ("Clear", WeatherCondition::Clear),
+            ("Fair", WeatherCondition::Clear),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(condition_from_text(text), Some(expected), "{}", text);
+        }
+        assert_eq!(condition_from_text("Haze"), None);
+    }
+
+    #[test]
+    fn test_observation_to_response() {
+        let json = r#"{
+            "properties": {
+                "timestamp": "2024-01-01T17:51:00+00:00",
+                "textDescription": "",
+                "icon": "https://api.weather.gov/icons/land/night/bkn?size=medium",
+                "temperature": { "unitCode": "wmoUnit:degC", "value": -2.0 },
+                "windDirection": { "unitCode": "wmoUnit:degree_(angle)", "value": 270 },
+                "windSpeed": { "unitCode": "wmoUnit:km_h-1", "value": 36.0 },
+                "barometricPressure": { "unitCode": "wmoUnit:Pa", "value": 101320 },
+                "visibility": { "unitCode": "wmoUnit:m", "value": 16090 },
+                "relativeHumidity": { "unitCode": "wmoUnit:percent", "value": 64.5 },
+                "windChill": { "unitCode": "wmoUnit:degC", "value": -8.1 },
+                "heatIndex": { "unitCode": "wmoUnit:degC", "value": null },
+                "precipitationLastHour": { "unitCode": "wmoUnit:mm", "value": null },
+                "cloudLayers": [ { "amount": "FEW" }, { "amount": "BKN" } ]
+            }
+        }"#;
+        let observation: ObservationResponse = serde_json::from_str(json).unwrap();
+        let response = to_response(observation.properties, Vec::new()).unwrap();
+
+        assert_eq!(response.temperature, -2.0);
+        assert_eq!(response.apparent_temperature, -8.1);
+        assert!((response.wind_speed - 10.0).abs() < 1e-9);
+        assert!((response.pressure - 1013.2).abs() < 1e-9);
+        assert_eq!(response.cloud_cover, 75.0);
+        // No description, so the sky cover decides
+        assert_eq!(response.weather_code, WeatherCondition::Cloudy.wmo_code());
+        assert_eq!(response.is_day, 0);
+    }
+
+    #[test]
+    fn test_observation_without_temperature_is_skipped() {
+        let json = r#"{ "properties": {
+            "timestamp": "2024-01-01T17:51:00+00:00",
+            "temperature": { "unitCode": "wmoUnit:degC", "value": null }
+        } }"#;
+        let observation: ObservationResponse = serde_json::from_str(json).unwrap();
+        assert!(to_response(observation.properties, Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_missing_readings_come_from_earlier_observations() {
+        let json = r#"{ "features": [
+            { "properties": {
+                "timestamp": "2024-01-01T17:51:00+00:00",
+                "temperature": { "unitCode": "wmoUnit:degC", "value": 4.0 },
+                "barometricPressure": { "unitCode": "wmoUnit:Pa", "value": null },
+                "relativeHumidity": { "unitCode": "wmoUnit:percent", "value": null }
+            } },
+            { "properties": {
+                "timestamp": "2024-01-01T16:51:00+00:00",
+                "temperature": { "unitCode": "wmoUnit:degC", "value": 5.0 },
+                "barometricPressure": { "unitCode": "wmoUnit:Pa", "value": 101500 },
+                "relativeHumidity": { "unitCode": "wmoUnit:percent", "value": 70 }
+            } },
+            { "properties": {
+                "timestamp": "2024-01-01T10:51:00+00:00",
+                "temperature": { "unitCode": "wmoUnit:degC", "value": 2.0 },
+                "barometricPressure": { "unitCode": "wmoUnit:Pa", "value": 99000 },
+                "relativeHumidity": { "unitCode": "wmoUnit:percent", "value": 90 }
+            } }
+        ] }"#;
+        let recent: ObservationsResponse = serde_json::from_str(json).unwrap();
+        let observations: Vec<Observation> =
+            recent.features.into_iter().map(|f| f.properties).collect();
+
+        let filled = fill_from_recent(observations[..2].to_vec()).unwrap();
+        let response = to_response(filled, Vec::new()).unwrap();
+        assert_eq!(response.temperature, 4.0);
+        assert!((response.pressure - 1015.0).abs() < 1e-9);
+        assert_eq!(response.humidity, 70.0);
+
+        // Too old to stand in, so there's no reading rather than a zero
+        let mut stale = observations.clone();
+        stale.remove(1);
+        let filled = fill_from_recent(stale).unwrap();
+        assert!(to_response(filled, Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_hourly_forecast_in_celsius() {
+        let json = r#"{ "properties": { "periods": [
+            { "startTime": "2024-01-01T12:00:00-05:00", "temperature": 50,
+              "temperatureUnit": "F", "probabilityOfPrecipitation": { "value": 20 } },
+            { "startTime": "2024-01-01T13:00:00-05:00", "temperature": 11,
+              "temperatureUnit": "C", "probabilityOfPrecipitation": { "value": null } }
+        ] } }"#;
+        let response: HourlyResponse = serde_json::from_str(json).unwrap();
+        let forecast = hourly_forecast(response.properties.periods);
+
+        assert!((forecast[0].temperature - 10.0).abs() < 1e-9);
+        assert_eq!(forecast[0].precipitation_probability, Some(20.0));
+        assert_eq!(forecast[1].time.to_rfc3339(), "2024-01-01T18:00:00+00:00");
+        assert_eq!(forecast[1].temperature, 11.0);
+    }
+}
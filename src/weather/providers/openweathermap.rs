@@ -0,0 +1,373 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{
+    HourlyForecast, TemperatureUnit, WeatherCondition, WeatherLocation, WeatherUnits, WindSpeedUnit,
+};
+use crate::weather::units::{normalize_temperature, normalize_wind_speed};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const OPENWEATHERMAP_BASE_URL: &str = "https://api.openweathermap.org/data/2.5";
+// The free forecast comes in three-hour steps
+const FORECAST_STEPS: usize = 8;
+
+#[derive(Debug, Deserialize)]
+struct CurrentResponse {
+    #[serde(default)]
+    weather: Vec<Condition>,
+    main: MainValues,
+    #[serde(default)]
+    visibility: Option<f64>,
+    #[serde(default)]
+    wind: Wind,
+    #[serde(default)]
+    clouds: Clouds,
+    #[serde(default)]
+    rain: Option<Precipitation>,
+    #[serde(default)]
+    snow: Option<Precipitation>,
+    dt: i64,
+    #[serde(default)]
+    sys: Sun,
+}
+
+#[derive(Debug, Deserialize)]
+struct Condition {
+    id: i32,
+    #[serde(default)]
+    icon: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MainValues {
+    temp: f64,
+    #[serde(default)]
+    feels_like: Option<f64>,
+    #[serde(default)]
+    pressure: f64,
+    #[serde(default)]
+    humidity: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Wind {
+    #[serde(default)]
+    speed: f64,
+    #[serde(default)]
+    deg: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Clouds {
+    #[serde(default)]
+    all: f64,
+}
+
+// Millimeters over the last hour
+#[derive(Debug, Deserialize)]
+struct Precipitation {
+    #[serde(rename = "1h", default)]
+    one_hour: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Sun {
+    #[serde(default)]
+    sunrise: Option<i64>,
+    #[serde(default)]
+    sunset: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct ForecastResponse {
+    #[serde(default)]
+    list: Vec<ForecastStep>,
+}
+
+#[derive(Deserialize)]
+struct ForecastStep {
+    dt: i64,
+    main: MainValues,
+    // Chance of precipitation from 0 to 1
+    #[serde(default)]
+    pop: Option<f64>,
+}
+
+// OpenWeatherMap's condition codes: 2xx thunderstorm, 3xx drizzle, 5xx rain, 6xx snow,
+// 7xx haze and the like, 800 clear and 80x clouds
+fn condition_from_code(code: i32) -> WeatherCondition {
+    match code {
+        200..=299 => WeatherCondition::Thunderstorm,
+        300..=399 => WeatherCondition::Drizzle,
+        511 => WeatherCondition::FreezingRain,
+        520..=531 => WeatherCondition::RainShowers,
+        500..=599 => WeatherCondition::Rain,
+        611..=616 => WeatherCondition::FreezingRain,
+        620..=622 => WeatherCondition::SnowShowers,
+        600..=699 => WeatherCondition::Snow,
+        771 | 781 => WeatherCondition::Thunderstorm,
+        700..=799 => WeatherCondition::Fog,
+        801 | 802 => WeatherCondition::PartlyCloudy,
+        803 => WeatherCondition::Cloudy,
+        804 => WeatherCondition::Overcast,
+        _ => WeatherCondition::Clear,
+    }
+}
+
+// The `units` parameter and what it gives back. There's no way to ask for km/h or knots,
+// so wind comes in m/s or mph and is converted like the rest
+fn units_param(units: &WeatherUnits) -> (&'static str, TemperatureUnit, WindSpeedUnit) {
+    match units.temperature {
+        TemperatureUnit::Fahrenheit => {
+            ("imperial", TemperatureUnit::Fahrenheit, WindSpeedUnit::Mph)
+        }
+        TemperatureUnit::Celsius => ("metric", TemperatureUnit::Celsius, WindSpeedUnit::Ms),
+    }
+}
+
+fn from_timestamp(seconds: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+}
+
+fn to_response(
+    current: CurrentResponse,
+    hourly: Vec<HourlyForecast>,
+    units: &WeatherUnits,
+) -> WeatherProviderResponse {
+    let (_, temperature_unit, wind_unit) = units_param(units);
+    let condition = current
+        .weather
+        .first()
+        .map_or(WeatherCondition::Clear, |c| condition_from_code(c.id));
+    // Icons end in "d" or "n", e.g. "10n"
+    let is_day = match current.weather.first() {
+        Some(c) if c.icon.ends_with('n') => 0,
+        _ => 1,
+    };
+    let precipitation = current.rain.as_ref().map_or(0.0, |p| p.one_hour)
+        + current.snow.as_ref().map_or(0.0, |p| p.one_hour);
+    let time = from_timestamp(current.dt);
+
+    WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature: normalize_temperature(current.main.temp, temperature_unit),
+        apparent_temperature: normalize_temperature(
+            current.main.feels_like.unwrap_or(current.main.temp),
+            temperature_unit,
+        ),
+        humidity: current.main.humidity,
+        precipitation,
+        wind_speed: normalize_wind_speed(current.wind.speed, wind_unit),
+        wind_direction: current.wind.deg,
+        cloud_cover: current.clouds.all,
+        pressure: current.main.pressure,
+        visibility: current.visibility,
+        uv_index: None,
+        is_day,
+        moon_phase: Some(time.map_or_else(astronomy::current_moon_phase, astronomy::moon_phase)),
+        timestamp: time.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        sunrise: current.sys.sunrise.and_then(from_timestamp),
+        sunset: current.sys.sunset.and_then(from_timestamp),
+        hourly,
+        minutely: Vec::new(),
+        air_quality: None,
+    }
+}
+
+fn hourly_forecast(steps: Vec<ForecastStep>, unit: TemperatureUnit) -> Vec<HourlyForecast> {
+    steps
+        .into_iter()
+        .take(FORECAST_STEPS)
+        .filter_map(|step| {
+            Some(HourlyForecast {
+                time: from_timestamp(step.dt)?,
+                temperature: normalize_temperature(step.main.temp, unit),
+                precipitation_probability: step.pop.map(|p| p * 100.0),
+            })
+        })
+        .collect()
+}
+
+// Current weather and the three-hourly forecast from OpenWeatherMap, which needs a
+// (free) API key from openweathermap.org
+pub struct OpenWeatherMapProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl OpenWeatherMapProvider {
+    pub fn new(api_key: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: OPENWEATHERMAP_BASE_URL.to_string(),
+            api_key,
+        }
+    }
+
+    fn build_url(
+        &self,
+        endpoint: &str,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> String {
+        format!(
+            "{}/{}?lat={}&lon={}&units={}&appid={}",
+            self.base_url,
+            endpoint,
+            location.latitude,
+            location.longitude,
+            units_param(units).0,
+            self.api_key
+        )
+    }
+
+    // Errors name the address without the key in it
+    fn public_url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.base_url, endpoint)
+    }
+
+    async fn fetch_hourly(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Vec<HourlyForecast> {
+        let url = self.build_url("forecast", location, units);
+        let response = async {
+            let response = self.client.get(&url).send().await.ok()?;
+            response
+                .error_for_status()
+                .ok()?
+                .json::<ForecastResponse>()
+                .await
+                .ok()
+        }
+        .await;
+        response
+            .map(|r| hourly_forecast(r.list, units_param(units).1))
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMapProvider {
+    fn name(&self) -> &'static str {
+        "OpenWeatherMap"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data by OpenWeather"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            ..Capabilities::default()
+        }
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url("weather", location, units);
+        let public_url = self.public_url("weather");
+        let (response, hourly) = tokio::join!(
+            self.client.get(&url).send(),
+            self.fetch_hourly(location, units)
+        );
+        let to_error = |e: reqwest::Error| {
+            WeatherError::Network(NetworkError::from_reqwest(e.without_url(), &public_url, 30))
+        };
+        let current: CurrentResponse = response
+            .and_then(|r| r.error_for_status())
+            .map_err(to_error)?
+            .json()
+            .await
+            .map_err(to_error)?;
+        Ok(to_response(current, hourly, units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_from_code() {
+        let cases = [
+            (211, WeatherCondition::Thunderstorm),
+            (301, WeatherCondition::Drizzle),
+            (500, WeatherCondition::Rain),
+            (511, WeatherCondition::FreezingRain),
+            (521, WeatherCondition::RainShowers),
+            (601, WeatherCondition::Snow),
+            (612, WeatherCondition::FreezingRain),
+            (621, WeatherCondition::SnowShowers),
+            (741, WeatherCondition::Fog),
+            (800, WeatherCondition::Clear),
+            (802, WeatherCondition::PartlyCloudy),
+            (803, WeatherCondition::Cloudy),
+            (804, WeatherCondition::Overcast),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(condition_from_code(code), expected, "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_units_param() {
+        assert_eq!(units_param(&WeatherUnits::metric()).0, "metric");
+        assert_eq!(units_param(&WeatherUnits::imperial()).0, "imperial");
+    }
+
+    #[test]
+    fn test_imperial_response_is_normalized() {
+        let json = r#"{
+            "weather": [ { "id": 500, "main": "Rain", "description": "light rain", "icon": "10n" } ],
+            "main": { "temp": 50.0, "feels_like": 41.0, "pressure": 1009, "humidity": 87 },
+            "visibility": 8000,
+            "wind": { "speed": 22.369, "deg": 250 },
+            "clouds": { "all": 90 },
+            "rain": { "1h": 0.6 },
+            "dt": 1704110400,
+            "sys": { "sunrise": 1704093600, "sunset": 1704124800 }
+        }"#;
+        let current: CurrentResponse = serde_json::from_str(json).unwrap();
+        let response = to_response(current, Vec::new(), &WeatherUnits::imperial());
+
+        assert_eq!(response.weather_code, WeatherCondition::Rain.wmo_code());
+        assert!((response.temperature - 10.0).abs() < 1e-9);
+        assert!((response.apparent_temperature - 5.0).abs() < 1e-9);
+        assert!((response.wind_speed - 10.0).abs() < 1e-3);
+        assert_eq!(response.precipitation, 0.6);
+        assert_eq!(response.is_day, 0);
+        assert_eq!(
+            response.sunrise.unwrap().to_rfc3339(),
+            "2024-01-01T07:20:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_hourly_forecast_percent() {
+        let json = r#"{ "list": [
+            { "dt": 1704110400, "main": { "temp": 3.0 }, "pop": 0.35 },
+            { "dt": 1704121200, "main": { "temp": 1.5 } }
+        ] }"#;
+        let forecast: ForecastResponse = serde_json::from_str(json).unwrap();
+        let hourly = hourly_forecast(forecast.list, TemperatureUnit::Celsius);
+
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].precipitation_probability, Some(35.0));
+        assert_eq!(hourly[1].precipitation_probability, None);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{
+    HourlyForecast, MinutelyPrecipitation, WeatherCondition, WeatherLocation, WeatherUnits,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const PIRATE_WEATHER_BASE_URL: &str = "https://api.pirateweather.net/forecast";
+const FORECAST_HOURS: usize = 24;
+
+// Dark Sky's format: `units=si` gives °C, m/s, hPa, mm/h and kilometers of visibility,
+// and fractions from 0 to 1 for humidity, cloud cover and chances
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForecastResponse {
+    currently: DataPoint,
+    #[serde(default)]
+    minutely: Option<DataBlock>,
+    #[serde(default)]
+    hourly: Option<DataBlock>,
+    #[serde(default)]
+    daily: Option<DataBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DataBlock {
+    #[serde(default)]
+    data: Vec<DataPoint>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct DataPoint {
+    time: i64,
+    icon: Option<String>,
+    precip_intensity: Option<f64>,
+    precip_probability: Option<f64>,
+    temperature: Option<f64>,
+    apparent_temperature: Option<f64>,
+    humidity: Option<f64>,
+    pressure: Option<f64>,
+    wind_speed: Option<f64>,
+    wind_bearing: Option<f64>,
+    cloud_cover: Option<f64>,
+    uv_index: Option<f64>,
+    visibility: Option<f64>,
+    sunrise_time: Option<i64>,
+    sunset_time: Option<i64>,
+}
+
+// Icons are Dark Sky's ("rain", "partly-cloudy-night") plus Pirate Weather's extras
+// ("thunderstorm", "hail", "mostly-cloudy-day"). None for ones like "wind", which say
+// nothing about the sky
+fn condition_from_icon(icon: &str) -> Option<WeatherCondition> {
+    let has = |word: &str| icon.contains(word);
+    let condition = if has("thunderstorm") {
+        WeatherCondition::Thunderstorm
+    } else if has("hail") {
+        WeatherCondition::ThunderstormHail
+    } else if has("sleet") || has("mixed") || has("freezing") {
+        WeatherCondition::FreezingRain
+    } else if has("snow") || has("flurries") {
+        WeatherCondition::Snow
+    } else if has("drizzle") {
+        WeatherCondition::Drizzle
+    } else if has("rain") {
+        WeatherCondition::Rain
+    } else if has("fog") || has("mist") || has("haze") || has("smoke") {
+        WeatherCondition::Fog
+    } else if has("mostly-cloudy") {
+        WeatherCondition::Cloudy
+    } else if has("partly-cloudy") || has("mostly-clear") {
+        WeatherCondition::PartlyCloudy
+    } else if has("cloudy") {
+        WeatherCondition::Overcast
+    } else if has("clear") {
+        WeatherCondition::Clear
+    } else {
+        return None;
+    };
+    Some(condition)
+}
+
+fn condition_from_cover(cover: f64) -> WeatherCondition {
+    match cover {
+        c if c >= 0.9 => WeatherCondition::Overcast,
+        c if c >= 0.6 => WeatherCondition::Cloudy,
+        c if c >= 0.2 => WeatherCondition::PartlyCloudy,
+        _ => WeatherCondition::Clear,
+    }
+}
+
+fn from_timestamp(seconds: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+}
+
+fn percent(fraction: Option<f64>) -> Option<f64> {
+    fraction.map(|f| f * 100.0)
+}
+
+fn minutely(points: &[DataPoint]) -> Vec<MinutelyPrecipitation> {
+    points
+        .iter()
+        .filter_map(|point| {
+            Some(MinutelyPrecipitation {
+                time: from_timestamp(point.time)?,
+                intensity: point.precip_intensity.unwrap_or(0.0),
+                probability: percent(point.precip_probability),
+            })
+        })
+        .collect()
+}
+
+fn hourly_forecast(points: &[DataPoint]) -> Vec<HourlyForecast> {
+    points
+        .iter()
+        .take(FORECAST_HOURS)
+        .filter_map(|point| {
+            Some(HourlyForecast {
+                time: from_timestamp(point.time)?,
+                temperature: point.temperature?,
+                precipitation_probability: percent(point.precip_probability),
+            })
+        })
+        .collect()
+}
+
+fn to_response(data: ForecastResponse) -> Option<WeatherProviderResponse> {
+    let current = &data.currently;
+    let temperature = current.temperature?;
+    let cloud_cover = current.cloud_cover.unwrap_or(0.0);
+    let icon = current.icon.as_deref().unwrap_or("");
+    let condition = condition_from_icon(icon).unwrap_or_else(|| condition_from_cover(cloud_cover));
+
+    let today = data.daily.as_ref().and_then(|d| d.data.first());
+    let sunrise = today.and_then(|d| d.sunrise_time).and_then(from_timestamp);
+    let sunset = today.and_then(|d| d.sunset_time).and_then(from_timestamp);
+    let time = from_timestamp(current.time);
+    let is_day = if icon.ends_with("-night") {
+        false
+    } else if icon.ends_with("-day") {
+        true
+    } else {
+        match (time, sunrise, sunset) {
+            (Some(now), Some(rise), Some(set)) => (rise..=set).contains(&now),
+            _ => true,
+        }
+    };
+
+    Some(WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature,
+        apparent_temperature: current.apparent_temperature.unwrap_or(temperature),
+        humidity: percent(current.humidity).unwrap_or(0.0),
+        // Over the current hour, so the rate per hour reads as the amount
+        precipitation: current.precip_intensity.unwrap_or(0.0),
+        wind_speed: current.wind_speed.unwrap_or(0.0),
+        wind_direction: current.wind_bearing.unwrap_or(0.0),
+        cloud_cover: cloud_cover * 100.0,
+        pressure: current.pressure.unwrap_or(0.0),
+        visibility: current.visibility.map(|km| km * 1000.0),
+        uv_index: current.uv_index,
+        is_day: is_day as i32,
+        moon_phase: Some(time.map_or_else(astronomy::current_moon_phase, astronomy::moon_phase)),
+        timestamp: time.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        sunrise,
+        sunset,
+        hourly: data
+            .hourly
+            .as_ref()
+            .map(|h| hourly_forecast(&h.data))
+            .unwrap_or_default(),
+        minutely: data
+            .minutely
+            .as_ref()
+            .map(|m| minutely(&m.data))
+            .unwrap_or_default(),
+        air_quality: None,
+    })
+}
+
+// Pirate Weather, a free Dark Sky-compatible API that needs a key from pirateweather.net.
+// Its minute-by-minute precipitation feeds the nowcast in the precipitation chart
+pub struct PirateWeatherProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl PirateWeatherProvider {
+    pub fn new(api_key: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: PIRATE_WEATHER_BASE_URL.to_string(),
+            api_key,
+        }
+    }
+
+    // The key is part of the path
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}/{}/{},{}?units=si&exclude=alerts",
+            self.base_url, self.api_key, location.latitude, location.longitude
+        )
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for PirateWeatherProvider {
+    fn name(&self) -> &'static str {
+        "Pirate Weather"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data by Pirate Weather"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            minutely: true,
+            ..Capabilities::default()
+        }
+    }
+
+    // Always metric, converted for display later
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+        // Errors name the address without the key in it
+        let public_url = self.base_url.clone();
+        let to_error = |e: reqwest::Error| {
+            WeatherError::Network(NetworkError::from_reqwest(e.without_url(), &public_url, 30))
+        };
+        let data: ForecastResponse = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(to_error)?
+            .json()
+            .await
+            .map_err(to_error)?;
+
+        to_response(data).ok_or_else(|| {
+            WeatherError::Network(NetworkError::Unavailable {
+                url: public_url.clone(),
+                reason: "the response had no current temperature".to_string(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_from_icon() {
+        let cases = [
+            ("clear-day", WeatherCondition::Clear),
+            ("partly-cloudy-night", WeatherCondition::PartlyCloudy),
+            ("mostly-cloudy-day", WeatherCondition::Cloudy),
+            ("cloudy", WeatherCondition::Overcast),
+            ("fog", WeatherCondition::Fog),
+            ("light-rain", WeatherCondition::Rain),
+            ("drizzle", WeatherCondition::Drizzle),
+            ("sleet", WeatherCondition::FreezingRain),
+            ("heavy-snow", WeatherCondition::Snow),
+            ("hail", WeatherCondition::ThunderstormHail),
+            ("thunderstorm", WeatherCondition::Thunderstorm),
+        ];
+        for (icon, expected) in cases {
+            assert_eq!(condition_from_icon(icon), Some(expected), "{}", icon);
+        }
+        assert_eq!(condition_from_icon("wind"), None);
+    }
+
+    #[test]
+    fn test_response_with_minutely() {
+        let json = r#"{
+            "currently": {
+                "time": 1704110400, "icon": "wind", "precipIntensity": 0.0,
+                "temperature": 4.5, "apparentTemperature": 0.2, "humidity": 0.81,
+                "pressure": 1002.3, "windSpeed": 11.2, "windBearing": 240,
+                "cloudCover": 0.72, "uvIndex": 0.4, "visibility": 14.5
+            },
+            "minutely": { "data": [
+                { "time": 1704110400, "precipIntensity": 0.0, "precipProbability": 0.1 },
+                { "time": 1704110460, "precipIntensity": 0.8, "precipProbability": 0.7 }
+            ] },
+            "hourly": { "data": [
+                { "time": 1704110400, "temperature": 4.5, "precipProbability": 0.35 }
+            ] },
+            "daily": { "data": [
+                { "time": 1704067200, "sunriseTime": 1704093600, "sunsetTime": 1704124800 }
+            ] }
+        }"#;
+        let data: ForecastResponse = serde_json::from_str(json).unwrap();
+        let response = to_response(data).unwrap();
+
+        assert_eq!(response.weather_code, WeatherCondition::Cloudy.wmo_code());
+        assert!((response.humidity - 81.0).abs() < 1e-9);
+        assert!((response.cloud_cover - 72.0).abs() < 1e-9);
+        assert_eq!(response.visibility, Some(14500.0));
+        // Between sunrise and sunset
+        assert_eq!(response.is_day, 1);
+        assert_eq!(response.hourly[0].precipitation_probability, Some(35.0));
+        assert_eq!(response.minutely.len(), 2);
+        assert_eq!(response.minutely[1].intensity, 0.8);
+        assert_eq!(
+            response.minutely[1].probability.map(|p| p.round()),
+            Some(70.0)
+        );
+    }
+}
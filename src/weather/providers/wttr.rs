@@ -0,0 +1,363 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::astronomy;
+use crate::weather::provider::{Capabilities, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{HourlyForecast, WeatherCondition, WeatherLocation, WeatherUnits};
+use crate::weather::units::kmh_to_ms;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+const WTTR_BASE_URL: &str = "https://wttr.in";
+const FORECAST_HOURS: i64 = 24;
+
+// Every number in wttr.in's JSON is a string, e.g. "temp_C": "5"
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    let text = Option::<String>::deserialize(deserializer)?;
+    Ok(text.and_then(|t| t.trim().parse().ok()))
+}
+
+#[derive(Debug, Deserialize)]
+struct WttrResponse {
+    #[serde(default)]
+    current_condition: Vec<CurrentCondition>,
+    #[serde(default)]
+    weather: Vec<Day>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrentCondition {
+    #[serde(rename = "temp_C", deserialize_with = "number", default)]
+    temp_c: Option<f64>,
+    #[serde(rename = "FeelsLikeC", deserialize_with = "number", default)]
+    feels_like_c: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    humidity: Option<f64>,
+    #[serde(rename = "precipMM", deserialize_with = "number", default)]
+    precip_mm: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    windspeed_kmph: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    winddir_degree: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    cloudcover: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    pressure: Option<f64>,
+    // Kilometers
+    #[serde(deserialize_with = "number", default)]
+    visibility: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    uv_index: Option<f64>,
+    #[serde(deserialize_with = "number", default)]
+    weather_code: Option<f64>,
+    // "2024-01-01 12:20 PM" at the location, and "11:20 AM" in UTC
+    #[serde(default)]
+    local_obs_date_time: String,
+    #[serde(rename = "observation_time", default)]
+    observation_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Day {
+    date: String,
+    #[serde(default)]
+    astronomy: Vec<Astronomy>,
+    #[serde(default)]
+    hourly: Vec<Hour>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Astronomy {
+    #[serde(default)]
+    sunrise: String,
+    #[serde(default)]
+    sunset: String,
+}
+
+// Three-hourly, "time" in local hundreds: "0", "300", ... "2100"
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Hour {
+    #[serde(deserialize_with = "number", default)]
+    time: Option<f64>,
+    #[serde(rename = "tempC", deserialize_with = "number", default)]
+    temp_c: Option<f64>,
+    #[serde(rename = "chanceofrain", deserialize_with = "number", default)]
+    chance_of_rain: Option<f64>,
+    #[serde(rename = "chanceofsnow", deserialize_with = "number", default)]
+    chance_of_snow: Option<f64>,
+}
+
+// World Weather Online's condition codes, which wttr.in passes on
+fn condition_from_code(code: i32) -> WeatherCondition {
+    match code {
+        113 => WeatherCondition::Clear,
+        116 => WeatherCondition::PartlyCloudy,
+        119 => WeatherCondition::Cloudy,
+        122 => WeatherCondition::Overcast,
+        143 | 248 | 260 => WeatherCondition::Fog,
+        263 | 266 => WeatherCondition::Drizzle,
+        176 | 293 | 296 | 299 | 302 | 305 | 308 => WeatherCondition::Rain,
+        182 | 185 | 281 | 284 | 311 | 314 | 317 | 320 | 350 | 362 | 365 | 374 | 377 => {
+            WeatherCondition::FreezingRain
+        }
+        179 | 227 | 230 | 323 | 326 | 329 | 332 | 335 | 338 => WeatherCondition::Snow,
+        353 | 356 | 359 => WeatherCondition::RainShowers,
+        368 | 371 => WeatherCondition::SnowShowers,
+        200 | 386 | 389 | 392 | 395 => WeatherCondition::Thunderstorm,
+        _ => WeatherCondition::Clear,
+    }
+}
+
+// The location's UTC offset, from the same observation in local time and in UTC. Only
+// the clock time is given in UTC, so the day is picked to land in the -12h to +14h that
+// time zones span
+fn utc_offset(current: &CurrentCondition) -> Option<TimeDelta> {
+    let local =
+        NaiveDateTime::parse_from_str(&current.local_obs_date_time, "%Y-%m-%d %I:%M %p").ok()?;
+    let utc = NaiveTime::parse_from_str(&current.observation_time, "%I:%M %p").ok()?;
+    let mut offset = local.time() - utc;
+    if offset > TimeDelta::hours(14) {
+        offset -= TimeDelta::days(1);
+    } else if offset < TimeDelta::hours(-12) {
+        offset += TimeDelta::days(1);
+    }
+    Some(offset)
+}
+
+fn to_utc(date: NaiveDate, time: NaiveTime, offset: TimeDelta) -> DateTime<Utc> {
+    (date.and_time(time) - offset).and_utc()
+}
+
+fn sun_time(date: NaiveDate, time: &str, offset: TimeDelta) -> Option<DateTime<Utc>> {
+    let time = NaiveTime::parse_from_str(time, "%I:%M %p").ok()?;
+    Some(to_utc(date, time, offset))
+}
+
+fn hourly_forecast(days: &[Day], offset: TimeDelta, now: DateTime<Utc>) -> Vec<HourlyForecast> {
+    days.iter()
+        .filter_map(|day| {
+            let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+            Some(day.hourly.iter().filter_map(move |hour| {
+                let hundreds = hour.time? as u32;
+                let time = NaiveTime::from_hms_opt(hundreds / 100, hundreds % 100, 0)?;
+                Some(HourlyForecast {
+                    time: to_utc(date, time, offset),
+                    temperature: hour.temp_c?,
+                    precipitation_probability: match (hour.chance_of_rain, hour.chance_of_snow) {
+                        (None, None) => None,
+                        (rain, snow) => Some(rain.unwrap_or(0.0).max(snow.unwrap_or(0.0))),
+                    },
+                })
+            }))
+        })
+        .flatten()
+        // Keep the step under way now, and a day from there
+        .filter(|h| {
+            h.time > now - TimeDelta::hours(3) && h.time <= now + TimeDelta::hours(FORECAST_HOURS)
+        })
+        .collect()
+}
+
+fn to_response(data: WttrResponse, now: DateTime<Utc>) -> Option<WeatherProviderResponse> {
+    let current = data.current_condition.first()?;
+    let temperature = current.temp_c?;
+    let condition = condition_from_code(current.weather_code.unwrap_or(113.0) as i32);
+    let offset = utc_offset(current).unwrap_or_default();
+
+    let today = data.weather.first();
+    let today_date = today.and_then(|d| NaiveDate::parse_from_str(&d.date, "%Y-%m-%d").ok());
+    let astronomy = today.and_then(|d| d.astronomy.first());
+    let (sunrise, sunset) = match (today_date, astronomy) {
+        (Some(date), Some(a)) => (
+            sun_time(date, &a.sunrise, offset),
+            sun_time(date, &a.sunset, offset),
+        ),
+        _ => (None, None),
+    };
+    let is_day = match (sunrise, sunset) {
+        (Some(rise), Some(set)) => (rise..=set).contains(&now),
+        _ => true,
+    };
+
+    Some(WeatherProviderResponse {
+        weather_code: condition.wmo_code(),
+        temperature,
+        apparent_temperature: current.feels_like_c.unwrap_or(temperature),
+        humidity: current.humidity.unwrap_or(0.0),
+        precipitation: current.precip_mm.unwrap_or(0.0),
+        wind_speed: kmh_to_ms(current.windspeed_kmph.unwrap_or(0.0)),
+        wind_direction: current.winddir_degree.unwrap_or(0.0),
+        cloud_cover: current.cloudcover.unwrap_or(0.0),
+        pressure: current.pressure.unwrap_or(0.0),
+        visibility: current.visibility.map(|km| km * 1000.0),
+        uv_index: current.uv_index,
+        is_day: is_day as i32,
+        moon_phase: Some(astronomy::moon_phase(now)),
+        timestamp: current.local_obs_date_time.clone(),
+        sunrise,
+        sunset,
+        hourly: hourly_forecast(&data.weather, offset, now),
+        minutely: Vec::new(),
+        air_quality: None,
+    })
+}
+
+// wttr.in's JSON format, which needs no key or setup at all. Lighter on detail than the
+// others, so it's mostly there to fall back on when they can't be reached
+pub struct WttrProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl WttrProvider {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: WTTR_BASE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}/{:.4},{:.4}?format=j1",
+            self.base_url, location.latitude, location.longitude
+        )
+    }
+}
+
+impl Default for WttrProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for WttrProvider {
+    fn name(&self) -> &'static str {
+        "wttr.in"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Weather data by wttr.in"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            ..Capabilities::default()
+        }
+    }
+
+    // Always metric, converted for display later
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+        let data: WttrResponse = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?
+            .json()
+            .await
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+
+        to_response(data, Utc::now()).ok_or_else(|| {
+            WeatherError::Network(NetworkError::Unavailable {
+                url,
+                reason: "the response had no current conditions".to_string(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "current_condition": [ {
+            "FeelsLikeC": "2", "cloudcover": "75", "humidity": "87",
+            "localObsDateTime": "2024-01-01 12:20 PM", "observation_time": "11:20 AM",
+            "precipMM": "0.1", "pressure": "1012", "temp_C": "5", "uvIndex": "1",
+            "visibility": "10", "weatherCode": "296", "winddirDegree": "230",
+            "windspeedKmph": "18"
+        } ],
+        "weather": [ {
+            "date": "2024-01-01",
+            "astronomy": [ { "sunrise": "08:17 AM", "sunset": "04:02 PM" } ],
+            "hourly": [
+                { "time": "0", "tempC": "3", "chanceofrain": "10", "chanceofsnow": "0" },
+                { "time": "1200", "tempC": "5", "chanceofrain": "80", "chanceofsnow": "20" },
+                { "time": "2100", "tempC": "2", "chanceofrain": "30", "chanceofsnow": "40" }
+            ]
+        } ]
+    }"#;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_condition_from_code() {
+        assert_eq!(condition_from_code(113), WeatherCondition::Clear);
+        assert_eq!(condition_from_code(122), WeatherCondition::Overcast);
+        assert_eq!(condition_from_code(248), WeatherCondition::Fog);
+        assert_eq!(condition_from_code(266), WeatherCondition::Drizzle);
+        assert_eq!(condition_from_code(302), WeatherCondition::Rain);
+        assert_eq!(condition_from_code(311), WeatherCondition::FreezingRain);
+        assert_eq!(condition_from_code(338), WeatherCondition::Snow);
+        assert_eq!(condition_from_code(356), WeatherCondition::RainShowers);
+        assert_eq!(condition_from_code(371), WeatherCondition::SnowShowers);
+        assert_eq!(condition_from_code(389), WeatherCondition::Thunderstorm);
+    }
+
+    #[test]
+    fn test_utc_offset_across_midnight() {
+        let data: WttrResponse = serde_json::from_str(SAMPLE).unwrap();
+        let mut current = data.current_condition.into_iter().next().unwrap();
+        assert_eq!(utc_offset(&current), Some(TimeDelta::hours(1)));
+
+        // 01:00 local on the 2nd is 18:00 UTC on the 1st in Chicago
+        current.local_obs_date_time = "2024-01-02 01:00 AM".to_string();
+        current.observation_time = "07:00 AM".to_string();
+        assert_eq!(utc_offset(&current), Some(TimeDelta::hours(-6)));
+    }
+
+    #[test]
+    fn test_to_response() {
+        let data: WttrResponse = serde_json::from_str(SAMPLE).unwrap();
+        let response = to_response(data, at("2024-01-01T11:30:00Z")).unwrap();
+
+        assert_eq!(response.weather_code, WeatherCondition::Rain.wmo_code());
+        assert_eq!(response.temperature, 5.0);
+        assert_eq!(response.apparent_temperature, 2.0);
+        assert!((response.wind_speed - 5.0).abs() < 1e-9);
+        assert_eq!(response.visibility, Some(10000.0));
+        assert_eq!(
+            response.sunrise.unwrap().to_rfc3339(),
+            "2024-01-01T07:17:00+00:00"
+        );
+        assert_eq!(response.is_day, 1);
+        // Midnight is long past; noon and 21:00 local are left
+        assert_eq!(response.hourly.len(), 2);
+        assert_eq!(
+            response.hourly[0].time.to_rfc3339(),
+            "2024-01-01T11:00:00+00:00"
+        );
+        assert_eq!(response.hourly[1].precipitation_probability, Some(40.0));
+    }
+}
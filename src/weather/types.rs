@@ -1,3 +1,6 @@
+use super::air_quality::AirQuality;
+use super::astronomy::Season;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -42,6 +45,75 @@ pub enum FogIntensity {
 }
 
 impl WeatherCondition {
+    pub const ALL: [WeatherCondition; 14] = [
+        Self::Clear,
+        Self::PartlyCloudy,
+        Self::Cloudy,
+        Self::Overcast,
+        Self::Fog,
+        Self::Drizzle,
+        Self::Rain,
+        Self::FreezingRain,
+        Self::Snow,
+        Self::SnowGrains,
+        Self::RainShowers,
+        Self::SnowShowers,
+        Self::Thunderstorm,
+        Self::ThunderstormHail,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Clear => "Clear",
+            Self::Cloudy => "Cloudy",
+            Self::PartlyCloudy => "Partly Cloudy",
+            Self::Overcast => "Overcast",
+            Self::Fog => "Fog",
+            Self::Drizzle => "Drizzle",
+            Self::FreezingRain => "Freezing Rain",
+            Self::Rain => "Rain",
+            Self::Snow => "Snow",
+            Self::SnowGrains => "Snow Grains",
+            Self::RainShowers => "Rain Showers",
+            Self::SnowShowers => "Snow Showers",
+            Self::Thunderstorm => "Thunderstorm",
+            Self::ThunderstormHail => "Thunderstorm with Hail",
+        }
+    }
+
+    // The WMO code a provider reports for this condition, for backends that describe the
+    // weather some other way. Cloudy has no code of its own and shares overcast's
+    pub fn wmo_code(&self) -> i32 {
+        match self {
+            Self::Clear => 0,
+            Self::PartlyCloudy => 2,
+            Self::Cloudy | Self::Overcast => 3,
+            Self::Fog => 45,
+            Self::Drizzle => 53,
+            Self::FreezingRain => 66,
+            Self::Rain => 63,
+            Self::Snow => 73,
+            Self::SnowGrains => 77,
+            Self::RainShowers => 81,
+            Self::SnowShowers => 85,
+            Self::Thunderstorm => 95,
+            Self::ThunderstormHail => 96,
+        }
+    }
+
+    pub fn icon(&self, is_day: bool) -> &'static str {
+        match self {
+            Self::Clear if is_day => "☀",
+            Self::Clear => "☾",
+            Self::PartlyCloudy => "⛅",
+            Self::Cloudy | Self::Overcast => "☁",
+            Self::Fog => "🌫",
+            Self::Drizzle | Self::Rain | Self::FreezingRain | Self::RainShowers => "☔",
+            Self::Snow | Self::SnowGrains | Self::SnowShowers => "❄",
+            Self::Thunderstorm | Self::ThunderstormHail => "⚡",
+        }
+    }
+
     pub fn rain_intensity(&self) -> RainIntensity {
         match self {
             Self::Drizzle => RainIntensity::Drizzle,
@@ -148,6 +220,42 @@ pub enum PrecipitationUnit {
     Inch,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureUnit {
+    #[default]
+    Hpa,
+    Inhg,
+    Mmhg,
+}
+
+impl PressureUnit {
+    // Inches of mercury need two to show a change of a hectopascal or so
+    pub fn decimals(self) -> usize {
+        match self {
+            Self::Inhg => 2,
+            Self::Hpa | Self::Mmhg => 0,
+        }
+    }
+}
+
+// One hour of forecast; temperature normalized to Celsius like the current reading
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HourlyForecast {
+    pub time: DateTime<Utc>,
+    pub temperature: f64,
+    pub precipitation_probability: Option<f64>,
+}
+
+// One minute of the next hour's precipitation, from providers with a nowcast
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MinutelyPrecipitation {
+    pub time: DateTime<Utc>,
+    // Millimeters per hour
+    pub intensity: f64,
+    pub probability: Option<f64>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[allow(dead_code)]
 pub struct WeatherData {
@@ -161,17 +269,36 @@ pub struct WeatherData {
     pub cloud_cover: f64,
     pub pressure: f64,
     pub visibility: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
     pub is_day: bool,
     pub moon_phase: Option<f64>,
     pub timestamp: String,
+    #[serde(default)]
+    pub sunrise: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sunset: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub hourly: Vec<HourlyForecast>,
+    #[serde(default)]
+    pub minutely: Vec<MinutelyPrecipitation>,
+    #[serde(default)]
+    pub air_quality: Option<AirQuality>,
+    // Name of the provider that answered; None for simulated weather
+    #[serde(default)]
+    pub source: Option<String>,
+    // When the provider answered, kept through the caches; None for simulated weather
+    #[serde(default)]
+    pub fetched_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct WeatherUnits {
     pub temperature: TemperatureUnit,
     pub wind_speed: WindSpeedUnit,
     pub precipitation: PrecipitationUnit,
+    pub pressure: PressureUnit,
 }
 
 impl WeatherUnits {
@@ -180,6 +307,7 @@ impl WeatherUnits {
             temperature: TemperatureUnit::Fahrenheit,
             wind_speed: WindSpeedUnit::Mph,
             precipitation: PrecipitationUnit::Inch,
+            pressure: PressureUnit::Inhg,
         }
     }
 
@@ -194,11 +322,12 @@ impl Default for WeatherUnits {
             temperature: TemperatureUnit::Celsius,
             wind_speed: WindSpeedUnit::Kmh,
             precipitation: PrecipitationUnit::Mm,
+            pressure: PressureUnit::Hpa,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub struct WeatherLocation {
     pub latitude: f64,
@@ -211,9 +340,13 @@ pub struct WeatherConditions {
     pub is_raining: bool,
     pub is_snowing: bool,
     pub is_thunderstorm: bool,
+    pub is_hailing: bool,
+    pub is_freezing_rain: bool,
+    pub is_blizzard: bool,
     pub is_cloudy: bool,
     pub is_foggy: bool,
     pub is_day: bool,
+    pub season: Season,
 }
 
 impl Default for WeatherConditions {
@@ -222,9 +355,13 @@ impl Default for WeatherConditions {
             is_raining: false,
             is_snowing: false,
             is_thunderstorm: false,
+            is_hailing: false,
+            is_freezing_rain: false,
+            is_blizzard: false,
             is_cloudy: false,
             is_foggy: false,
             is_day: true,
+            season: Season::default(),
         }
     }
 }
@@ -1,4 +1,7 @@
-use super::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+use super::types::{PrecipitationUnit, PressureUnit, TemperatureUnit, WindSpeedUnit};
+
+const HPA_PER_INHG: f64 = 33.863_886;
+const HPA_PER_MMHG: f64 = 1.333_224;
 
 pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
     celsius * 9.0 / 5.0 + 32.0
@@ -63,6 +66,20 @@ pub fn format_precipitation(mm: f64, unit: PrecipitationUnit) -> (f64, &'static
     }
 }
 
+pub fn format_pressure(hpa: f64, unit: PressureUnit) -> (f64, &'static str) {
+    match unit {
+        PressureUnit::Hpa => (hpa, "hPa"),
+        PressureUnit::Inhg => (hpa / HPA_PER_INHG, "inHg"),
+        PressureUnit::Mmhg => (hpa / HPA_PER_MMHG, "mmHg"),
+    }
+}
+
+pub fn compass_direction(degrees: f64) -> &'static str {
+    const DIRECTIONS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    let index = ((degrees.rem_euclid(360.0) + 22.5) / 45.0) as usize % 8;
+    DIRECTIONS[index]
+}
+
 pub fn normalize_temperature(value: f64, unit: TemperatureUnit) -> f64 {
     match unit {
         TemperatureUnit::Celsius => value,
@@ -85,3 +102,11 @@ pub fn normalize_precipitation(value: f64, unit: PrecipitationUnit) -> f64 {
         PrecipitationUnit::Inch => inch_to_mm(value),
     }
 }
+
+pub fn normalize_pressure(value: f64, unit: PressureUnit) -> f64 {
+    match unit {
+        PressureUnit::Hpa => value,
+        PressureUnit::Inhg => value * HPA_PER_INHG,
+        PressureUnit::Mmhg => value * HPA_PER_MMHG,
+    }
+}
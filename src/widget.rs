@@ -0,0 +1,172 @@
+use crate::animation_manager::AnimationManager;
+use crate::app_state::AppState;
+use crate::render::{Layer, TerminalRenderer};
+use crate::scene::{Midground, WorldScene};
+use crate::theme::Theme;
+use crate::weather::{WeatherData, WeatherLocation, WeatherUnits};
+use crossterm::style::Color;
+use std::io;
+use std::time::{Duration, Instant};
+
+// The animated scene without the HUD or the terminal, for another program to draw in a
+// pane of its own: render composes the next frame at the pane's size, and the caller
+// copies the cells into its own screen. weathr doesn't implement ratatui's traits; the
+// README has a StatefulWidget wrapper for an app to keep in its own crate
+pub struct WeathrWidget {
+    renderer: TerminalRenderer,
+    animations: AnimationManager,
+    scene: WorldScene,
+    state: AppState,
+    last_frame: Option<Instant>,
+}
+
+impl WeathrWidget {
+    // `location` places the sun, the season and the aurora
+    pub fn new(location: WeatherLocation, weather: WeatherData) -> Self {
+        let (width, height) = (0, 0);
+        let mut state = AppState::new(location, true, WeatherUnits::default());
+        let mut animations = AnimationManager::new(width, height, false);
+        animations.update_weather(&weather);
+        // The first frame shows the sky filled in rather than filling up
+        animations.fast_forward();
+        state.update_weather(weather);
+        Self {
+            renderer: TerminalRenderer::headless(width, height),
+            animations,
+            scene: WorldScene::new(width, height, Midground::default()),
+            state,
+            last_frame: None,
+        }
+    }
+
+    pub fn set_weather(&mut self, weather: WeatherData) {
+        self.animations.settle();
+        self.animations.update_weather(&weather);
+        self.state.update_weather(weather);
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.animations.set_theme(theme);
+        self.scene.set_scenery(theme.scenery);
+    }
+
+    pub fn set_midground(&mut self, midground: Midground) {
+        self.scene.set_midground(midground);
+        self.animations.settle();
+    }
+
+    // The next frame at `width` x `height`, moved on by the time since the last one, as
+    // (x, y, character, foreground, background) from the top left of the pane
+    pub fn render(
+        &mut self,
+        width: u16,
+        height: u16,
+    ) -> io::Result<impl Iterator<Item = (u16, u16, char, Color, Color)> + '_> {
+        if self.renderer.get_size() != (width, height) {
+            self.renderer = TerminalRenderer::headless(width, height);
+            self.animations.resize(width, height);
+            self.scene.update_size(width, height);
+            self.animations.fast_forward();
+        }
+        let now = Instant::now();
+        let dt = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+        self.last_frame = Some(now);
+        self.animations.tick(dt);
+
+        let mut rng = rand::rng();
+        let renderer = &mut self.renderer;
+        let conditions = &self.state.weather_conditions;
+        renderer.clear()?;
+
+        renderer.set_layer(Layer::Sky);
+        self.animations.render_background(
+            renderer,
+            conditions,
+            &self.state,
+            width,
+            height,
+            &mut rng,
+        )?;
+
+        renderer.set_layer(Layer::Scene);
+        self.scene.render(renderer, conditions)?;
+        let anchors = self.scene.anchors();
+        self.animations
+            .render_surface_effects(renderer, conditions, anchors, width, height, &mut rng)?;
+        self.animations
+            .render_chimney_smoke(renderer, conditions, anchors, width, height, &mut rng)?;
+
+        renderer.set_layer(Layer::Weather);
+        self.animations
+            .render_foreground(renderer, conditions, width, height, &mut rng)?;
+
+        self.animations.update_sunny_animation(conditions);
+        Ok(self.renderer.cells())
+    }
+
+    // The last rendered frame as text, with or without the colors as ANSI escapes
+    pub fn screenshot(&self, colors: bool) -> String {
+        self.renderer.screenshot(colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+    use crate::weather::WeatherCondition;
+
+    fn widget(condition: WeatherCondition) -> WeathrWidget {
+        let location = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        let weather = Simulation::new(condition, true).weather(&WeatherUnits::default());
+        WeathrWidget::new(location, weather)
+    }
+
+    #[test]
+    fn test_renders_at_the_pane_size() {
+        let mut widget = widget(WeatherCondition::Rain);
+        let cells: Vec<_> = widget.render(80, 24).unwrap().collect();
+        assert!(cells.iter().all(|&(x, y, ..)| x < 80 && y < 24));
+        assert!(cells.iter().any(|&(_, _, character, ..)| character != ' '));
+
+        // Resized along with the pane
+        let cells: Vec<_> = widget.render(100, 30).unwrap().collect();
+        assert_eq!(cells.last().map(|&(_, y, ..)| y), Some(29));
+    }
+
+    #[test]
+    fn test_renders_tiny_panes() {
+        for condition in WeatherCondition::ALL {
+            for is_day in [true, false] {
+                let location = WeatherLocation {
+                    latitude: 52.52,
+                    longitude: 13.41,
+                    elevation: None,
+                };
+                let weather = Simulation::new(condition, is_day).weather(&WeatherUnits::default());
+                let mut widget = WeathrWidget::new(location, weather);
+                for (width, height) in [(0, 0), (1, 1), (5, 3), (3, 5), (12, 6)] {
+                    for _ in 0..50 {
+                        let cells = widget.render(width, height).unwrap();
+                        assert!(cells.count() <= width as usize * height as usize);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_weather_changes_the_scene() {
+        let mut widget = widget(WeatherCondition::Clear);
+        let clear: String = widget.render(80, 24).unwrap().map(|cell| cell.2).collect();
+        widget.set_weather(
+            Simulation::new(WeatherCondition::Snow, true).weather(&WeatherUnits::default()),
+        );
+        let snowy: String = widget.render(80, 24).unwrap().map(|cell| cell.2).collect();
+        assert_ne!(clear, snowy);
+    }
+}
@@ -1,11 +1,15 @@
 use std::sync::Arc;
 use std::time::Duration;
-use weathr::weather::{OpenMeteoProvider, WeatherClient, WeatherLocation, WeatherUnits};
+use weathr::error::{NetworkError, WeatherError};
+use weathr::weather::{
+    Capabilities, OpenMeteoProvider, WeatherClient, WeatherCondition, WeatherLocation,
+    WeatherProvider, WeatherProviderResponse, WeatherUnits,
+};
 
 #[tokio::test]
 async fn test_weather_client_integration_cache_behavior() {
     let provider = Arc::new(OpenMeteoProvider::new());
-    let client = WeatherClient::new(provider, Duration::from_secs(60));
+    let client = WeatherClient::new(vec![provider], Duration::from_secs(60));
 
     let location = WeatherLocation {
         latitude: 52.52,
@@ -34,7 +38,7 @@ async fn test_weather_client_integration_cache_behavior() {
 #[tokio::test]
 async fn test_weather_client_integration_cache_invalidation() {
     let provider = Arc::new(OpenMeteoProvider::new());
-    let client = WeatherClient::new(provider, Duration::from_secs(60));
+    let client = WeatherClient::new(vec![provider], Duration::from_secs(60));
 
     let location = WeatherLocation {
         latitude: 52.52,
@@ -65,7 +69,7 @@ async fn test_weather_client_integration_cache_invalidation() {
 #[tokio::test]
 async fn test_weather_client_integration_realistic_weather_ranges() {
     let provider = Arc::new(OpenMeteoProvider::new());
-    let client = WeatherClient::new(provider, Duration::from_secs(60));
+    let client = WeatherClient::new(vec![provider], Duration::from_secs(60));
 
     let location = WeatherLocation {
         latitude: 0.0,
@@ -109,3 +113,99 @@ async fn test_weather_client_integration_realistic_weather_ranges() {
         "Precipitation should be non-negative"
     );
 }
+
+// A backend from outside the crate, built on the public trait alone
+struct Station;
+
+#[weathr::weather::provider::async_trait]
+impl WeatherProvider for Station {
+    fn name(&self) -> &'static str {
+        "Station"
+    }
+
+    fn attribution(&self) -> &'static str {
+        "Backyard station"
+    }
+
+    async fn get_current_weather(
+        &self,
+        _location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        Ok(WeatherProviderResponse {
+            weather_code: 61,
+            temperature: 11.5,
+            apparent_temperature: 10.0,
+            humidity: 90.0,
+            precipitation: 1.2,
+            wind_speed: 3.0,
+            wind_direction: 180.0,
+            cloud_cover: 100.0,
+            pressure: 1004.0,
+            visibility: None,
+            uv_index: None,
+            is_day: 1,
+            moon_phase: None,
+            timestamp: "2024-01-01T12:00".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            air_quality: None,
+        })
+    }
+}
+
+// Fails the way a provider whose service is down should
+struct Offline;
+
+#[weathr::weather::provider::async_trait]
+impl WeatherProvider for Offline {
+    fn name(&self) -> &'static str {
+        "Offline"
+    }
+
+    fn attribution(&self) -> &'static str {
+        ""
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            hourly: true,
+            ..Capabilities::default()
+        }
+    }
+
+    async fn get_current_weather(
+        &self,
+        _location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        Err(NetworkError::Unavailable {
+            url: "offline".to_string(),
+            reason: "down".to_string(),
+        }
+        .into())
+    }
+}
+
+#[tokio::test]
+async fn test_third_party_provider() {
+    let mut client = WeatherClient::new(vec![Arc::new(Offline)], Duration::from_secs(60));
+    client.add_provider(Arc::new(Station));
+    client.set_disk_cache(false);
+    assert_eq!(Station.capabilities(), Capabilities::default());
+
+    let location = WeatherLocation {
+        latitude: 89.99,
+        longitude: -179.99,
+        elevation: None,
+    };
+    let weather = client
+        .refresh_weather(&location, &WeatherUnits::default())
+        .await
+        .expect("the station should answer");
+    assert_eq!(weather.source.as_deref(), Some("Station"));
+    assert_eq!(weather.condition, WeatherCondition::Rain);
+    assert_eq!(weather.temperature, 11.5);
+}
@@ -47,9 +47,15 @@ fn test_weather_normalizer_integration_all_wmo_codes() {
             cloud_cover: 50.0,
             pressure: 1013.0,
             visibility: Some(10000.0),
+            uv_index: None,
             is_day: 1,
             moon_phase: None,
             timestamp: "2024-01-01T12:00".to_string(),
+            sunrise: None,
+            sunset: None,
+            hourly: Vec::new(),
+            minutely: Vec::new(),
+            air_quality: None,
         };
 
         let weather = WeatherNormalizer::normalize(response);
@@ -74,9 +80,15 @@ fn test_weather_normalizer_integration_day_night() {
         cloud_cover: 0.0,
         pressure: 1013.0,
         visibility: Some(10000.0),
+        uv_index: None,
         is_day: 1,
         moon_phase: None,
         timestamp: "2024-01-01T12:00".to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
     };
 
     let response_night = WeatherProviderResponse {
@@ -90,9 +102,15 @@ fn test_weather_normalizer_integration_day_night() {
         cloud_cover: 0.0,
         pressure: 1013.0,
         visibility: Some(10000.0),
+        uv_index: None,
         is_day: 0,
         moon_phase: None,
         timestamp: "2024-01-01T00:00".to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
     };
 
     let weather_day = WeatherNormalizer::normalize(response_day);
@@ -115,9 +133,15 @@ fn test_weather_normalizer_integration_clear_conditions() {
         cloud_cover: 10.0,
         pressure: 1015.0,
         visibility: Some(15000.0),
+        uv_index: None,
         is_day: 1,
         moon_phase: None,
         timestamp: "2024-06-15T14:00".to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
     };
 
     let weather = WeatherNormalizer::normalize(response);
@@ -143,9 +167,15 @@ fn test_weather_normalizer_integration_rainy_conditions() {
         cloud_cover: 95.0,
         pressure: 1005.0,
         visibility: Some(3000.0),
+        uv_index: None,
         is_day: 1,
         moon_phase: None,
         timestamp: "2024-03-20T10:00".to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
     };
 
     let weather = WeatherNormalizer::normalize(response);
@@ -168,9 +198,15 @@ fn test_weather_normalizer_integration_snowy_conditions() {
         cloud_cover: 100.0,
         pressure: 1010.0,
         visibility: Some(1000.0),
+        uv_index: None,
         is_day: 0,
         moon_phase: None,
         timestamp: "2024-01-10T22:00".to_string(),
+        sunrise: None,
+        sunset: None,
+        hourly: Vec::new(),
+        minutely: Vec::new(),
+        air_quality: None,
     };
 
     let weather = WeatherNormalizer::normalize(response);